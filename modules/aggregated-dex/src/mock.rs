@@ -99,6 +99,7 @@ ord_parameter_types! {
 parameter_types! {
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
+	pub const ProtocolFeeReceiver: AccountId = BOB;
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![];
 }
 
@@ -114,6 +115,8 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<Admin, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type RuntimeCall = RuntimeCall;
 }
 
 pub struct EnsurePoolAssetId;
@@ -0,0 +1,393 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Airdrop Module
+//!
+//! ## Overview
+//!
+//! Governance creates airdrops by publishing the merkle root of the `(account, amount)` claims
+//! and funding the drop from the treasury. Users claim their amount by providing the merkle proof
+//! of their leaf. Multiple airdrops of different currencies can run at the same time. Once an
+//! airdrop expires, anyone can close it and the unclaimed amount is returned to the treasury.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, traits::EnsureOrigin, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId};
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	ArithmeticError, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type AirdropId = u32;
+
+/// The cursor to continue clearing the claimed records of a closed airdrop.
+pub type ClearCursor = BoundedVec<u8, ConstU32<128>>;
+
+/// Airdrop published by governance.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct AirdropInfo<BlockNumber> {
+	/// The currency to airdrop.
+	pub currency_id: CurrencyId,
+	/// The merkle root of the `(account, amount)` claims.
+	pub merkle_root: H256,
+	/// The total amount funded to the airdrop.
+	pub total_amount: Balance,
+	/// The amount which has been claimed.
+	pub claimed_amount: Balance,
+	/// The airdrop can not be claimed since this block.
+	pub expire_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The airdrop module id, keep airdrop funds until claimed or returned.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The treasury account which funds airdrops and receives the unclaimed amount.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The max length of the merkle proof.
+		#[pallet::constant]
+		type MaxProofLength: Get<u32>;
+
+		/// The origin which may create airdrops.
+		type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The total amount of the airdrop is zero
+		InvalidAmount,
+		/// The expiry block is not in the future
+		InvalidExpiry,
+		/// The airdrop does not exist
+		AirdropNotFound,
+		/// The airdrop has expired
+		AirdropExpired,
+		/// The airdrop has not expired yet
+		AirdropNotExpired,
+		/// The account has claimed the airdrop
+		AlreadyClaimed,
+		/// The merkle proof is invalid
+		InvalidProof,
+		/// The remaining amount of the airdrop is not enough for the claim
+		AirdropExhausted,
+		/// The airdrop is not closed or its claimed records have been cleared
+		AirdropNotClosed,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An airdrop was created.
+		AirdropCreated {
+			airdrop_id: AirdropId,
+			currency_id: CurrencyId,
+			merkle_root: H256,
+			total_amount: Balance,
+			expire_at: BlockNumberFor<T>,
+		},
+		/// An account claimed the airdrop.
+		Claimed {
+			airdrop_id: AirdropId,
+			who: T::AccountId,
+			amount: Balance,
+		},
+		/// An expired airdrop was closed and the unclaimed amount was returned to the treasury.
+		AirdropClosed {
+			airdrop_id: AirdropId,
+			unclaimed_amount: Balance,
+		},
+		/// All the claimed records of a closed airdrop were cleared.
+		ClaimedCleared { airdrop_id: AirdropId },
+	}
+
+	/// The next airdrop id.
+	///
+	/// NextAirdropId: AirdropId
+	#[pallet::storage]
+	#[pallet::getter(fn next_airdrop_id)]
+	pub type NextAirdropId<T: Config> = StorageValue<_, AirdropId, ValueQuery>;
+
+	/// The airdrops which have not been closed.
+	///
+	/// Airdrops: map AirdropId => Option<AirdropInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn airdrops)]
+	pub type Airdrops<T: Config> = StorageMap<_, Twox64Concat, AirdropId, AirdropInfo<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The accounts which have claimed the airdrop.
+	///
+	/// Claimed: double_map AirdropId, AccountId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn claimed)]
+	pub type Claimed<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, AirdropId, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+	/// The closed airdrops whose claimed records have not been cleared, with the cursor to
+	/// continue clearing. The cursor is empty if the clearing has not started.
+	///
+	/// ClosedAirdrops: map AirdropId => Option<ClearCursor>
+	#[pallet::storage]
+	#[pallet::getter(fn closed_airdrops)]
+	pub type ClosedAirdrops<T: Config> = StorageMap<_, Twox64Concat, AirdropId, ClearCursor, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create an airdrop funded by the treasury.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		///
+		/// - `currency_id`: the currency to airdrop.
+		/// - `merkle_root`: the merkle root of the `(account, amount)` claims.
+		/// - `total_amount`: the amount transferred from the treasury to fund the airdrop.
+		/// - `expire_at`: the airdrop can not be claimed since this block.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::create_airdrop())]
+		pub fn create_airdrop(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			merkle_root: H256,
+			#[pallet::compact] total_amount: Balance,
+			expire_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			Self::do_create_airdrop(currency_id, merkle_root, total_amount, expire_at)?;
+			Ok(())
+		}
+
+		/// Claim the airdrop with the merkle proof of `(who, amount)`.
+		///
+		/// - `airdrop_id`: the airdrop to claim.
+		/// - `amount`: the amount of the claim.
+		/// - `proof`: the merkle proof of the claim.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim())]
+		pub fn claim(
+			origin: OriginFor<T>,
+			airdrop_id: AirdropId,
+			#[pallet::compact] amount: Balance,
+			proof: BoundedVec<H256, T::MaxProofLength>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_claim(&who, airdrop_id, amount, &proof)
+		}
+
+		/// Close an expired airdrop and return the unclaimed amount to the treasury. Anyone can
+		/// close expired airdrops. The claimed records are cleared by `clear_claimed`.
+		///
+		/// - `airdrop_id`: the airdrop to close.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::close_airdrop())]
+		pub fn close_airdrop(origin: OriginFor<T>, airdrop_id: AirdropId) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_close_airdrop(airdrop_id)
+		}
+
+		/// Clear at most `limit` claimed records of a closed airdrop. Anyone can clear the
+		/// closed airdrops.
+		///
+		/// - `airdrop_id`: the closed airdrop to clear.
+		/// - `limit`: the max number of claimed records to clear.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::clear_claimed(*limit))]
+		pub fn clear_claimed(origin: OriginFor<T>, airdrop_id: AirdropId, limit: u32) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let cleared = Self::do_clear_claimed(airdrop_id, limit)?;
+			Ok(Some(<T as Config>::WeightInfo::clear_claimed(cleared)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// The merkle leaf of the claim of `amount` by `who`.
+	pub fn leaf_hash(who: &T::AccountId, amount: Balance) -> H256 {
+		H256(blake2_256(&(who, amount).encode()))
+	}
+
+	fn do_create_airdrop(
+		currency_id: CurrencyId,
+		merkle_root: H256,
+		total_amount: Balance,
+		expire_at: BlockNumberFor<T>,
+	) -> Result<AirdropId, DispatchError> {
+		ensure!(!total_amount.is_zero(), Error::<T>::InvalidAmount);
+		ensure!(
+			expire_at > frame_system::Pallet::<T>::block_number(),
+			Error::<T>::InvalidExpiry
+		);
+
+		let airdrop_id = NextAirdropId::<T>::try_mutate(|next_airdrop_id| -> Result<AirdropId, DispatchError> {
+			let current_id = *next_airdrop_id;
+			*next_airdrop_id = next_airdrop_id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			Ok(current_id)
+		})?;
+
+		T::Currency::transfer(
+			currency_id,
+			&T::TreasuryAccount::get(),
+			&Self::account_id(),
+			total_amount,
+		)?;
+		Airdrops::<T>::insert(
+			airdrop_id,
+			AirdropInfo {
+				currency_id,
+				merkle_root,
+				total_amount,
+				claimed_amount: Zero::zero(),
+				expire_at,
+			},
+		);
+
+		Self::deposit_event(Event::AirdropCreated {
+			airdrop_id,
+			currency_id,
+			merkle_root,
+			total_amount,
+			expire_at,
+		});
+		Ok(airdrop_id)
+	}
+
+	fn do_claim(who: &T::AccountId, airdrop_id: AirdropId, amount: Balance, proof: &[H256]) -> DispatchResult {
+		Airdrops::<T>::try_mutate(airdrop_id, |maybe_airdrop| -> DispatchResult {
+			let airdrop = maybe_airdrop.as_mut().ok_or(Error::<T>::AirdropNotFound)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < airdrop.expire_at,
+				Error::<T>::AirdropExpired
+			);
+			ensure!(!Self::claimed(airdrop_id, who), Error::<T>::AlreadyClaimed);
+			ensure!(
+				Self::verify_proof(airdrop.merkle_root, Self::leaf_hash(who, amount), proof),
+				Error::<T>::InvalidProof
+			);
+
+			let claimed_amount = airdrop
+				.claimed_amount
+				.checked_add(amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(claimed_amount <= airdrop.total_amount, Error::<T>::AirdropExhausted);
+
+			T::Currency::transfer(airdrop.currency_id, &Self::account_id(), who, amount)?;
+			airdrop.claimed_amount = claimed_amount;
+			Claimed::<T>::insert(airdrop_id, who, true);
+
+			Self::deposit_event(Event::Claimed {
+				airdrop_id,
+				who: who.clone(),
+				amount,
+			});
+			Ok(())
+		})
+	}
+
+	fn do_close_airdrop(airdrop_id: AirdropId) -> DispatchResult {
+		let airdrop = Self::airdrops(airdrop_id).ok_or(Error::<T>::AirdropNotFound)?;
+		ensure!(
+			frame_system::Pallet::<T>::block_number() >= airdrop.expire_at,
+			Error::<T>::AirdropNotExpired
+		);
+
+		let unclaimed_amount = airdrop.total_amount.saturating_sub(airdrop.claimed_amount);
+		T::Currency::transfer(
+			airdrop.currency_id,
+			&Self::account_id(),
+			&T::TreasuryAccount::get(),
+			unclaimed_amount,
+		)?;
+		Airdrops::<T>::remove(airdrop_id);
+		ClosedAirdrops::<T>::insert(airdrop_id, ClearCursor::default());
+
+		Self::deposit_event(Event::AirdropClosed {
+			airdrop_id,
+			unclaimed_amount,
+		});
+		Ok(())
+	}
+
+	/// Clear at most `limit` claimed records of the closed airdrop, return the number of the
+	/// cleared records.
+	fn do_clear_claimed(airdrop_id: AirdropId, limit: u32) -> Result<u32, DispatchError> {
+		let cursor = Self::closed_airdrops(airdrop_id).ok_or(Error::<T>::AirdropNotClosed)?;
+		let maybe_cursor = (!cursor.is_empty()).then_some(cursor.as_slice());
+		let result = Claimed::<T>::clear_prefix(airdrop_id, limit, maybe_cursor);
+
+		match result.maybe_cursor {
+			Some(cursor) => {
+				// restart from the beginning if the cursor is too long to keep, the cleared
+				// records are not iterated again.
+				ClosedAirdrops::<T>::insert(airdrop_id, ClearCursor::try_from(cursor).unwrap_or_default());
+			}
+			None => {
+				ClosedAirdrops::<T>::remove(airdrop_id);
+				Self::deposit_event(Event::ClaimedCleared { airdrop_id });
+			}
+		}
+		Ok(result.loops)
+	}
+
+	/// Verify the merkle proof of `leaf` against `root`. The nodes are hashed in sorted pairs, so
+	/// the proof does not need to specify the position of the nodes.
+	fn verify_proof(root: H256, leaf: H256, proof: &[H256]) -> bool {
+		proof.iter().fold(leaf, |node, sibling| hash_pair(node, *sibling)) == root
+	}
+}
+
+/// Hash the sorted pair of merkle nodes.
+pub fn hash_pair(a: H256, b: H256) -> H256 {
+	let (first, second) = if a <= b { (a, b) } else { (b, a) };
+	H256(blake2_256(&[first.as_bytes(), second.as_bytes()].concat()))
+}
@@ -0,0 +1,272 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the airdrop module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use sp_runtime::traits::BadOrigin;
+
+/// Merkle tree of claims ALICE: 100, BOB: 200, CHARLIE: 300, returns the root and the proofs.
+fn merkle_tree() -> (H256, Vec<(AccountId, Balance, Vec<H256>)>) {
+	let alice = AirdropModule::leaf_hash(&ALICE, 100);
+	let bob = AirdropModule::leaf_hash(&BOB, 200);
+	let charlie = AirdropModule::leaf_hash(&CHARLIE, 300);
+	let alice_bob = hash_pair(alice, bob);
+	(
+		hash_pair(alice_bob, charlie),
+		vec![
+			(ALICE, 100, vec![bob, charlie]),
+			(BOB, 200, vec![alice, charlie]),
+			(CHARLIE, 300, vec![alice_bob]),
+		],
+	)
+}
+
+fn bounded(proof: Vec<H256>) -> BoundedVec<H256, <Runtime as Config>::MaxProofLength> {
+	proof.try_into().unwrap()
+}
+
+#[test]
+fn create_airdrop_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (root, _) = merkle_tree();
+		assert_noop!(
+			AirdropModule::create_airdrop(RuntimeOrigin::signed(ALICE), DOT, root, 600, 100),
+			BadOrigin
+		);
+		assert_noop!(
+			AirdropModule::create_airdrop(RuntimeOrigin::signed(Admin::get()), DOT, root, 0, 100),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			AirdropModule::create_airdrop(RuntimeOrigin::signed(Admin::get()), DOT, root, 600, 1),
+			Error::<Runtime>::InvalidExpiry
+		);
+
+		assert_ok!(AirdropModule::create_airdrop(
+			RuntimeOrigin::signed(Admin::get()),
+			DOT,
+			root,
+			600,
+			100
+		));
+		System::assert_last_event(RuntimeEvent::AirdropModule(crate::Event::AirdropCreated {
+			airdrop_id: 0,
+			currency_id: DOT,
+			merkle_root: root,
+			total_amount: 600,
+			expire_at: 100,
+		}));
+		assert_eq!(AirdropModule::next_airdrop_id(), 1);
+		assert_eq!(
+			AirdropModule::airdrops(0),
+			Some(AirdropInfo {
+				currency_id: DOT,
+				merkle_root: root,
+				total_amount: 600,
+				claimed_amount: 0,
+				expire_at: 100,
+			})
+		);
+		assert_eq!(Tokens::free_balance(DOT, &TREASURY), 1_000_000 - 600);
+		assert_eq!(Tokens::free_balance(DOT, &AirdropModule::account_id()), 600);
+	});
+}
+
+#[test]
+fn claim_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (root, claims) = merkle_tree();
+		assert_noop!(
+			AirdropModule::claim(RuntimeOrigin::signed(ALICE), 0, 100, bounded(claims[0].2.clone())),
+			Error::<Runtime>::AirdropNotFound
+		);
+
+		// concurrent airdrops of different currencies
+		assert_ok!(AirdropModule::create_airdrop(
+			RuntimeOrigin::signed(Admin::get()),
+			DOT,
+			root,
+			600,
+			100
+		));
+		assert_ok!(AirdropModule::create_airdrop(
+			RuntimeOrigin::signed(Admin::get()),
+			AUSD,
+			root,
+			500,
+			100
+		));
+
+		assert_noop!(
+			AirdropModule::claim(RuntimeOrigin::signed(ALICE), 0, 200, bounded(claims[0].2.clone())),
+			Error::<Runtime>::InvalidProof
+		);
+		assert_noop!(
+			AirdropModule::claim(RuntimeOrigin::signed(BOB), 0, 100, bounded(claims[0].2.clone())),
+			Error::<Runtime>::InvalidProof
+		);
+
+		for (who, amount, proof) in claims.iter() {
+			assert_ok!(AirdropModule::claim(
+				RuntimeOrigin::signed(*who),
+				0,
+				*amount,
+				bounded(proof.clone())
+			));
+			System::assert_last_event(RuntimeEvent::AirdropModule(crate::Event::Claimed {
+				airdrop_id: 0,
+				who: *who,
+				amount: *amount,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, who), *amount);
+			assert!(AirdropModule::claimed(0, who));
+		}
+		assert_eq!(AirdropModule::airdrops(0).unwrap().claimed_amount, 600);
+		assert_noop!(
+			AirdropModule::claim(RuntimeOrigin::signed(ALICE), 0, 100, bounded(claims[0].2.clone())),
+			Error::<Runtime>::AlreadyClaimed
+		);
+
+		// the AUSD airdrop is underfunded for all the claims
+		assert_ok!(AirdropModule::claim(
+			RuntimeOrigin::signed(CHARLIE),
+			1,
+			300,
+			bounded(claims[2].2.clone())
+		));
+		assert_noop!(
+			AirdropModule::claim(RuntimeOrigin::signed(BOB), 1, 200, bounded(claims[1].2.clone())),
+			Error::<Runtime>::AirdropExhausted
+		);
+		assert_ok!(AirdropModule::claim(
+			RuntimeOrigin::signed(ALICE),
+			1,
+			100,
+			bounded(claims[0].2.clone())
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 100);
+
+		System::set_block_number(100);
+		assert_noop!(
+			AirdropModule::claim(RuntimeOrigin::signed(BOB), 1, 200, bounded(claims[1].2.clone())),
+			Error::<Runtime>::AirdropExpired
+		);
+	});
+}
+
+#[test]
+fn close_airdrop_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (root, claims) = merkle_tree();
+		assert_ok!(AirdropModule::create_airdrop(
+			RuntimeOrigin::signed(Admin::get()),
+			DOT,
+			root,
+			600,
+			100
+		));
+		assert_ok!(AirdropModule::claim(
+			RuntimeOrigin::signed(BOB),
+			0,
+			200,
+			bounded(claims[1].2.clone())
+		));
+
+		assert_noop!(
+			AirdropModule::close_airdrop(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Runtime>::AirdropNotExpired
+		);
+
+		System::set_block_number(100);
+		assert_ok!(AirdropModule::close_airdrop(RuntimeOrigin::signed(ALICE), 0));
+		System::assert_last_event(RuntimeEvent::AirdropModule(crate::Event::AirdropClosed {
+			airdrop_id: 0,
+			unclaimed_amount: 400,
+		}));
+		assert_eq!(AirdropModule::airdrops(0), None);
+		// the claimed records are cleared by `clear_claimed`
+		assert!(AirdropModule::claimed(0, BOB));
+		assert_eq!(AirdropModule::closed_airdrops(0), Some(ClearCursor::default()));
+		assert_eq!(Tokens::free_balance(DOT, &TREASURY), 1_000_000 - 200);
+		assert_eq!(Tokens::free_balance(DOT, &AirdropModule::account_id()), 0);
+
+		assert_noop!(
+			AirdropModule::close_airdrop(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Runtime>::AirdropNotFound
+		);
+	});
+}
+
+#[test]
+fn clear_claimed_works() {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| {
+		let (root, claims) = merkle_tree();
+		assert_ok!(AirdropModule::create_airdrop(
+			RuntimeOrigin::signed(Admin::get()),
+			DOT,
+			root,
+			600,
+			100
+		));
+		for (who, amount, proof) in claims.iter().skip(1) {
+			assert_ok!(AirdropModule::claim(
+				RuntimeOrigin::signed(*who),
+				0,
+				*amount,
+				bounded(proof.clone())
+			));
+		}
+
+		// the claimed records of an open airdrop can not be cleared
+		assert_noop!(
+			AirdropModule::clear_claimed(RuntimeOrigin::signed(ALICE), 0, 10),
+			Error::<Runtime>::AirdropNotClosed
+		);
+
+		System::set_block_number(100);
+		assert_ok!(AirdropModule::close_airdrop(RuntimeOrigin::signed(ALICE), 0));
+	});
+	// commit the claimed records to the backend so that the clearing is limited
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		System::set_block_number(101);
+		assert_ok!(AirdropModule::clear_claimed(RuntimeOrigin::signed(ALICE), 0, 1));
+		assert!(!AirdropModule::closed_airdrops(0).unwrap().is_empty());
+		assert_eq!(Claimed::<Runtime>::iter_prefix(0).count(), 1);
+
+		assert_ok!(AirdropModule::clear_claimed(RuntimeOrigin::signed(ALICE), 0, 1));
+		System::assert_last_event(RuntimeEvent::AirdropModule(crate::Event::ClaimedCleared {
+			airdrop_id: 0,
+		}));
+		assert_eq!(AirdropModule::closed_airdrops(0), None);
+		assert!(!AirdropModule::claimed(0, BOB));
+		assert!(!AirdropModule::claimed(0, CHARLIE));
+
+		assert_noop!(
+			AirdropModule::clear_claimed(RuntimeOrigin::signed(ALICE), 0, 1),
+			Error::<Runtime>::AirdropNotClosed
+		);
+	});
+}
@@ -0,0 +1,48 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use sp_runtime::codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionManagerApi<AccountId, AuctionId, AuctionDetail, Balance> where
+		AccountId: Codec,
+		AuctionId: Codec,
+		AuctionDetail: Codec,
+		Balance: Codec,
+	{
+		/// Get the details of active collateral auction.
+		fn get_collateral_auction(auction_id: AuctionId) -> Option<AuctionDetail>;
+
+		/// Get the details of all active collateral auctions.
+		fn get_active_collateral_auctions() -> Vec<AuctionDetail>;
+
+		/// Get the details of active collateral auctions which the account is the last bidder or
+		/// the refund recipient of.
+		fn get_collateral_auctions_of(who: AccountId) -> Vec<AuctionDetail>;
+
+		/// Get the bidder and the payment locked in the escrow account of collateral auction.
+		fn get_escrowed_bid(auction_id: AuctionId) -> Option<(AccountId, Balance)>;
+
+		/// Get the total bid payment locked in the escrow account.
+		fn get_total_escrowed() -> Balance;
+	}
+}
@@ -24,36 +24,43 @@
 //! business. Auction types include:
 //!   - `collateral auction`: sell collateral assets for getting stable currency to eliminate the
 //!     system's bad debit by auction
+//!
+//! Collateral auctions are English auctions by default. If governance sets Dutch auction
+//! params for a collateral type, its new collateral auctions start at a price above the oracle
+//! price which decays per block, and the whole lot is sold to the first taker.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 #![allow(clippy::unnecessary_unwrap)]
 
-use frame_support::{pallet_prelude::*, transactional};
+use frame_support::{pallet_prelude::*, transactional, PalletId};
 use frame_system::{
 	offchain::{SendTransactionTypes, SubmitTransaction},
 	pallet_prelude::*,
 };
 use module_support::{
-	AuctionManager, CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, PriceProvider, Rate, SwapLimit,
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, KeeperAction, KeeperRewards, Price,
+	PriceProvider, Rate, Ratio, Swap, SwapLimit,
 };
-use orml_traits::{Auction, AuctionHandler, Change, MultiCurrency, OnNewBidResult};
+use orml_traits::{Auction, AuctionHandler, AuctionInfo, Change, MultiCurrency, OnNewBidResult};
 use orml_utilities::OffchainErr;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::{AuctionId, Balance, CurrencyId};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
 	offchain::{
 		storage::StorageValueRef,
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{CheckedDiv, Saturating, Zero},
+	traits::{AccountIdConversion, CheckedDiv, One, Saturating, UniqueSaturatedInto, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
+	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, TransactionOutcome,
 };
 use sp_std::prelude::*;
 
@@ -128,6 +135,114 @@ impl<AccountId, BlockNumber> CollateralAuctionItem<AccountId, BlockNumber> {
 	}
 }
 
+/// Parameters of Dutch collateral auction for specific collateral type
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct DutchAuctionParams {
+	/// The ratio of start price to oracle price, must not be less than one
+	pub start_premium: Ratio,
+	/// The proportion of start price decayed per block, must be in (0, 1]
+	pub decay_per_block: Rate,
+	/// The ratio of floor price to oracle price
+	pub floor_ratio: Ratio,
+}
+
+/// Details of an active collateral auction, used by runtime api
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CollateralAuctionDetail<AccountId, BlockNumber> {
+	/// The auction id
+	pub auction_id: AuctionId,
+	/// Collateral type for sale
+	pub currency_id: CurrencyId,
+	/// Initial collateral amount for sale
+	pub initial_amount: Balance,
+	/// Current collateral amount for sale
+	pub amount: Balance,
+	/// Target sales amount of this auction
+	pub target: Balance,
+	/// Refund recipient for may receive refund
+	pub refund_recipient: AccountId,
+	/// Auction start time
+	pub start_time: BlockNumber,
+	/// Auction end time
+	pub end_time: Option<BlockNumber>,
+	/// The last bidder and bid price of English auction
+	pub bid: Option<(AccountId, Balance)>,
+	/// The current price of Dutch auction, `None` for English auction
+	pub dutch_auction_price: Option<Price>,
+}
+
+/// The handling of collateral auction ended without bid
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum AbortedAuctionPolicy {
+	/// Re-create the collateral auction with the target lowered by `target_discount`
+	Restart { target_discount: Ratio },
+	/// Swap the collateral to stable currency via DEX by CDP treasury
+	SwapByTreasury,
+}
+
+/// Outcome of a collateral auction recorded in its settlement receipt
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum CollateralAuctionOutcome {
+	/// Dealt by the winning bidder or the final taker of Dutch auction
+	Dealt,
+	/// Dutch auction is partially taken and still active
+	PartiallyTaken,
+	/// The collateral is swapped to stable currency via DEX
+	DEXTaken,
+	/// Ended without winning bid, the collateral stays in CDP treasury
+	Aborted,
+	/// Cancelled by emergency shutdown or governance
+	Cancelled,
+}
+
+/// Settlement receipt of a collateral auction
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct CollateralAuctionReceipt<AccountId, BlockNumber> {
+	/// Outcome of the auction
+	pub outcome: CollateralAuctionOutcome,
+	/// Collateral type for sale
+	pub currency_id: CurrencyId,
+	/// The original refund recipient of the auction
+	pub refund_recipient: AccountId,
+	/// The winning bidder, or the last taker of Dutch auction
+	pub winner: Option<AccountId>,
+	/// The collateral amount sold
+	#[codec(compact)]
+	pub collateral_amount: Balance,
+	/// The stable currency amount paid for the collateral
+	#[codec(compact)]
+	pub payment_amount: Balance,
+	/// The average price of sold collateral, `None` if nothing is sold
+	pub final_price: Option<Price>,
+	/// The block number of the last settlement
+	pub settled_at: BlockNumber,
+}
+
+/// Price curve of a Dutch collateral auction, the price is the stable
+/// currency amount per collateral
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct DutchAuctionPrice {
+	/// Price at the start of auction
+	pub start_price: Price,
+	/// Price decayed per block
+	pub decay: Price,
+	/// The price will not decay below it
+	pub floor_price: Price,
+}
+
+impl DutchAuctionPrice {
+	/// Return the price after `elapsed_blocks` since the auction started
+	pub fn price_at(&self, elapsed_blocks: u128) -> Price {
+		self.start_price
+			.saturating_sub(
+				self.decay
+					.saturating_mul(Price::saturating_from_integer(elapsed_blocks)),
+			)
+			.max(self.floor_price)
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -149,10 +264,6 @@ pub mod module {
 		#[pallet::constant]
 		type AuctionDurationSoftCap: Get<BlockNumberFor<Self>>;
 
-		/// The stable currency id
-		#[pallet::constant]
-		type GetStableCurrencyId: Get<CurrencyId>;
-
 		/// Currency to transfer assets
 		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
@@ -175,6 +286,21 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// The origin which may update the auction params.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Swap to get stable currency for bidding
+		type Swap: Swap<Self::AccountId, Balance, CurrencyId>;
+
+		/// The keeper reward program, rewards the account who settles the
+		/// Dutch auction.
+		type KeeperRewards: KeeperRewards<Self::AccountId>;
+
+		/// The auction manager module id, its sub-account escrows the payment
+		/// of the last bid of collateral auctions.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -193,6 +319,22 @@ pub mod module {
 		InvalidBidPrice,
 		/// Invalid input amount
 		InvalidAmount,
+		/// The Dutch auction params are invalid
+		InvalidDutchAuctionParams,
+		/// The operation is not supported by the kind of the auction
+		InvalidAuctionKind,
+		/// The payment exceeds the max payment
+		ExceedMaxPayment,
+		/// The minimum increment size is invalid
+		InvalidMinimumIncrementSize,
+		/// The aborted auction policy is invalid
+		InvalidAbortedAuctionPolicy,
+		/// The reserve price discount is invalid
+		InvalidReservePriceDiscount,
+		/// The collateral price implied by the bid is below the reserve price
+		BidBelowReservePrice,
+		/// The auction has not started yet
+		AuctionNotStarted,
 	}
 
 	#[pallet::event]
@@ -231,6 +373,80 @@ pub mod module {
 			target_stable_amount: Balance,
 			refund_recipient: T::AccountId,
 		},
+		/// The Dutch auction params of collateral type updated.
+		DutchAuctionParamsUpdated {
+			collateral_type: CurrencyId,
+			params: Option<DutchAuctionParams>,
+		},
+		/// A portion of Dutch collateral auction lot taken.
+		CollateralAuctionPartiallyTaken {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			taker: T::AccountId,
+			payment_amount: Balance,
+		},
+		/// The end of auction extended by the bid placed within the bid extension period.
+		AuctionExtended {
+			auction_id: AuctionId,
+			end: BlockNumberFor<T>,
+		},
+		/// The bid extension period updated.
+		BidExtensionPeriodUpdated { period: BlockNumberFor<T> },
+		/// The minimum increment size of collateral auction bid updated.
+		CollateralAuctionMinimumIncrementSizeUpdated { size: Option<Rate> },
+		/// The aborted auction policy of collateral type updated.
+		AbortedAuctionPolicyUpdated {
+			collateral_type: CurrencyId,
+			policy: Option<AbortedAuctionPolicy>,
+		},
+		/// The reserve price discount of collateral type updated.
+		ReservePriceDiscountUpdated {
+			collateral_type: CurrencyId,
+			discount: Option<Ratio>,
+		},
+		/// Bid on collateral auction with the stable currency swapped from other currency.
+		SwappedAndBid {
+			auction_id: AuctionId,
+			bidder: T::AccountId,
+			supply_currency_id: CurrencyId,
+			supply_amount: Balance,
+			bid_price: Balance,
+		},
+		/// Collateral auction ended without bid is restarted.
+		CollateralAuctionRestarted {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			target_stable_amount: Balance,
+		},
+		/// The collateral of auction ended without bid is swapped by CDP treasury.
+		AbortedCollateralSwapped {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			supply_collateral_amount: Balance,
+			target_stable_amount: Balance,
+		},
+		/// Collateral auction is started as Dutch auction.
+		NewDutchCollateralAuction {
+			auction_id: AuctionId,
+			start_price: Price,
+			floor_price: Price,
+		},
+		/// The bid payment is locked in the escrow account.
+		BidFundsLocked {
+			auction_id: AuctionId,
+			bidder: T::AccountId,
+			amount: Balance,
+		},
+		/// The escrowed bid payment is refunded to the bidder.
+		BidFundsRefunded {
+			auction_id: AuctionId,
+			bidder: T::AccountId,
+			amount: Balance,
+		},
+		/// The escrowed bid payment of the dealt auction is settled to CDP treasury.
+		BidFundsSettled { auction_id: AuctionId, amount: Balance },
 	}
 
 	/// Mapping from auction id to collateral auction info
@@ -256,6 +472,93 @@ pub mod module {
 	#[pallet::getter(fn total_target_in_auction)]
 	pub type TotalTargetInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// Mapping from collateral type to its Dutch auction params, the
+	/// collateral auctions are English auctions if it's not set.
+	///
+	/// DutchAuctionParamsOf: map CurrencyId => Option<DutchAuctionParams>
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_auction_params)]
+	pub type DutchAuctionParamsOf<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, DutchAuctionParams, OptionQuery>;
+
+	/// Mapping from auction id to the price curve of Dutch collateral auction
+	///
+	/// DutchAuctions: map AuctionId => Option<DutchAuctionPrice>
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_auctions)]
+	pub type DutchAuctions<T: Config> = StorageMap<_, Twox64Concat, AuctionId, DutchAuctionPrice, OptionQuery>;
+
+	/// The bid placed within the last blocks of this period before the auction
+	/// ends extends the end by this period, zero means disabled.
+	///
+	/// BidExtensionPeriod: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn bid_extension_period)]
+	pub type BidExtensionPeriod<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// The minimum increment size of collateral auction bid, overrides
+	/// `MinimumIncrementSize` if it's set.
+	///
+	/// CollateralAuctionMinimumIncrementSize: Option<Rate>
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_auction_minimum_increment_size)]
+	pub type CollateralAuctionMinimumIncrementSize<T: Config> = StorageValue<_, Rate, OptionQuery>;
+
+	/// Mapping from collateral type to the handling of its collateral auction
+	/// ended without bid, the collateral stays in CDP treasury if it's not
+	/// set.
+	///
+	/// AbortedAuctionPolicies: map CurrencyId => Option<AbortedAuctionPolicy>
+	#[pallet::storage]
+	#[pallet::getter(fn aborted_auction_policies)]
+	pub type AbortedAuctionPolicies<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, AbortedAuctionPolicy, OptionQuery>;
+
+	/// Mapping from collateral type to the discount of reserve price to oracle
+	/// price, the bid implying a collateral price below the reserve price is
+	/// rejected.
+	///
+	/// ReservePriceDiscount: map CurrencyId => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_price_discount)]
+	pub type ReservePriceDiscount<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Ratio, OptionQuery>;
+
+	/// Mapping from collateral auction id to its settlement receipt
+	///
+	/// SettlementReceipts: map AuctionId => Option<CollateralAuctionReceipt>
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_receipts)]
+	pub type SettlementReceipts<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, CollateralAuctionReceipt<T::AccountId, BlockNumberFor<T>>, OptionQuery>;
+
+	/// Index of collateral auctions won or taken by the bidder
+	///
+	/// AuctionsByBidder: double_map AccountId, AuctionId => ()
+	#[pallet::storage]
+	pub type AuctionsByBidder<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, AuctionId, (), ValueQuery>;
+
+	/// Index of collateral auctions by the original refund recipient
+	///
+	/// AuctionsByRefundRecipient: double_map AccountId, AuctionId => ()
+	#[pallet::storage]
+	pub type AuctionsByRefundRecipient<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, AuctionId, (), ValueQuery>;
+
+	/// Mapping from collateral auction id to the bidder and the payment
+	/// locked in the escrow account
+	///
+	/// EscrowedBids: map AuctionId => Option<(AccountId, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn escrowed_bids)]
+	pub type EscrowedBids<T: Config> = StorageMap<_, Twox64Concat, AuctionId, (T::AccountId, Balance), OptionQuery>;
+
+	/// Total bid payment locked in the escrow account
+	///
+	/// TotalEscrowed: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_escrowed)]
+	pub type TotalEscrowed<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -296,6 +599,265 @@ pub mod module {
 			Self::deposit_event(Event::CancelAuction { auction_id: id });
 			Ok(())
 		}
+
+		/// Update the Dutch auction params of specific collateral type, it
+		/// only affects the new collateral auctions.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `params`: the Dutch auction params, `None` means use English auction.
+		#[pallet::call_index(1)]
+		#[pallet::weight((T::WeightInfo::set_dutch_auction_params(), DispatchClass::Operational))]
+		pub fn set_dutch_auction_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: Option<DutchAuctionParams>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(p) = params {
+				ensure!(
+					p.start_premium >= Ratio::one()
+						&& !p.decay_per_block.is_zero()
+						&& p.decay_per_block <= Rate::one()
+						&& p.floor_ratio <= p.start_premium,
+					Error::<T>::InvalidDutchAuctionParams
+				);
+			}
+			DutchAuctionParamsOf::<T>::set(currency_id, params);
+			Self::deposit_event(Event::DutchAuctionParamsUpdated {
+				collateral_type: currency_id,
+				params,
+			});
+			Ok(())
+		}
+
+		/// Take the Dutch collateral auction at the current price.
+		///
+		/// If the lot is worth more than the target of auction, only the
+		/// collateral worth of the target is sold and the rest is refunded.
+		///
+		/// The dispatch origin of this call must be `Signed` by the transactor.
+		///
+		/// - `id`: the auction id.
+		/// - `max_payment`: the max stable currency amount to pay.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::take_dutch_auction())]
+		#[transactional]
+		pub fn take_dutch_auction(
+			origin: OriginFor<T>,
+			id: AuctionId,
+			#[pallet::compact] max_payment: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_take_dutch_auction(who, id, Balance::max_value(), max_payment)
+		}
+
+		/// Take a portion of the Dutch collateral auction lot at the current
+		/// price, the remaining collateral stays in auction.
+		///
+		/// The dispatch origin of this call must be `Signed` by the transactor.
+		///
+		/// - `id`: the auction id.
+		/// - `collateral_amount`: the max collateral amount to take.
+		/// - `max_payment`: the max stable currency amount to pay.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::take_dutch_auction())]
+		#[transactional]
+		pub fn partial_take_dutch_auction(
+			origin: OriginFor<T>,
+			id: AuctionId,
+			#[pallet::compact] collateral_amount: Balance,
+			#[pallet::compact] max_payment: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_take_dutch_auction(who, id, collateral_amount, max_payment)
+		}
+
+		/// Update the bid extension period against last-block sniping.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `period`: the bid placed within the last `period` blocks before the auction ends
+		///   extends the end by `period`, zero means disabled.
+		#[pallet::call_index(4)]
+		#[pallet::weight((T::WeightInfo::set_dutch_auction_params(), DispatchClass::Operational))]
+		pub fn set_bid_extension_period(origin: OriginFor<T>, period: BlockNumberFor<T>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			BidExtensionPeriod::<T>::put(period);
+			Self::deposit_event(Event::BidExtensionPeriodUpdated { period });
+			Ok(())
+		}
+
+		/// Update the minimum increment size of collateral auction bid.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `size`: the minimum increment ratio compared to the previous bid, `None` means use
+		///   `MinimumIncrementSize`.
+		#[pallet::call_index(5)]
+		#[pallet::weight((T::WeightInfo::set_dutch_auction_params(), DispatchClass::Operational))]
+		pub fn set_collateral_auction_minimum_increment_size(
+			origin: OriginFor<T>,
+			size: Option<Rate>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(rate) = size {
+				ensure!(
+					!rate.is_zero() && rate <= Rate::one(),
+					Error::<T>::InvalidMinimumIncrementSize
+				);
+			}
+			CollateralAuctionMinimumIncrementSize::<T>::set(size);
+			Self::deposit_event(Event::CollateralAuctionMinimumIncrementSizeUpdated { size });
+			Ok(())
+		}
+
+		/// Update the handling of collateral auction ended without bid for
+		/// specific collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `policy`: the aborted auction policy, `None` means the collateral stays in CDP
+		///   treasury.
+		#[pallet::call_index(6)]
+		#[pallet::weight((T::WeightInfo::set_dutch_auction_params(), DispatchClass::Operational))]
+		pub fn set_aborted_auction_policy(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			policy: Option<AbortedAuctionPolicy>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(AbortedAuctionPolicy::Restart { target_discount }) = policy {
+				ensure!(target_discount < Ratio::one(), Error::<T>::InvalidAbortedAuctionPolicy);
+			}
+			AbortedAuctionPolicies::<T>::set(currency_id, policy);
+			Self::deposit_event(Event::AbortedAuctionPolicyUpdated {
+				collateral_type: currency_id,
+				policy,
+			});
+			Ok(())
+		}
+
+		/// Cancel the collateral auction, the payment of the last bidder is
+		/// fully refunded from the escrow account and the collateral is returned
+		/// to CDP treasury.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `id`: the auction id.
+		#[pallet::call_index(7)]
+		#[pallet::weight((T::WeightInfo::force_cancel(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn force_cancel(origin: OriginFor<T>, id: AuctionId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let collateral_auction = <CollateralAuctions<T>>::take(id).ok_or(Error::<T>::AuctionNotExists)?;
+			DutchAuctions::<T>::remove(id);
+
+			if let Some((bidder, _)) = Self::get_last_bid(id) {
+				// refund the payment to the bidder
+				Self::refund_bid_funds(
+					T::CDPTreasury::get_stable_currency_id(collateral_auction.currency_id),
+					id,
+				)?;
+
+				// decrease account ref of bidder
+				frame_system::Pallet::<T>::dec_consumers(&bidder);
+			}
+
+			// decrease account ref of refund recipient
+			frame_system::Pallet::<T>::dec_consumers(&collateral_auction.refund_recipient);
+
+			// the collateral is no longer in auction, it can be reprocessed by cdp treasury
+			TotalCollateralInAuction::<T>::mutate(collateral_auction.currency_id, |balance| {
+				*balance = balance.saturating_sub(collateral_auction.amount)
+			});
+			TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
+
+			T::Auction::remove_auction(id);
+			Self::record_settlement(id, &collateral_auction, CollateralAuctionOutcome::Cancelled, None, 0, 0);
+			Self::deposit_event(Event::CancelAuction { auction_id: id });
+			Ok(())
+		}
+
+		/// Update the discount of reserve price to oracle price for specific
+		/// collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `discount`: the discount of reserve price, `None` means no reserve price.
+		#[pallet::call_index(8)]
+		#[pallet::weight((T::WeightInfo::set_dutch_auction_params(), DispatchClass::Operational))]
+		pub fn set_reserve_price_discount(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			discount: Option<Ratio>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(d) = discount {
+				ensure!(d <= Ratio::one(), Error::<T>::InvalidReservePriceDiscount);
+			}
+			ReservePriceDiscount::<T>::set(currency_id, discount);
+			Self::deposit_event(Event::ReservePriceDiscountUpdated {
+				collateral_type: currency_id,
+				discount,
+			});
+			Ok(())
+		}
+
+		/// Swap the supply currency to stable currency via DEX and bid on
+		/// the collateral auction by it atomically.
+		///
+		/// The dispatch origin of this call must be `Signed` by the transactor.
+		///
+		/// - `id`: the auction id.
+		/// - `supply_currency_id`: the currency to swap to stable currency.
+		/// - `max_supply_amount`: the max amount of supply currency to swap.
+		/// - `bid_price`: the bid price, the stable currency swapped is the payment of it.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::swap_and_bid())]
+		#[transactional]
+		pub fn swap_and_bid(
+			origin: OriginFor<T>,
+			id: AuctionId,
+			supply_currency_id: CurrencyId,
+			#[pallet::compact] max_supply_amount: Balance,
+			#[pallet::compact] bid_price: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let collateral_auction = Self::collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+			let auction_info = T::Auction::auction_info(id).ok_or(Error::<T>::AuctionNotExists)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= auction_info.start, Error::<T>::AuctionNotStarted);
+
+			let (supply_amount, _) = T::Swap::swap(
+				&who,
+				supply_currency_id,
+				T::CDPTreasury::get_stable_currency_id(collateral_auction.currency_id),
+				SwapLimit::ExactTarget(max_supply_amount, collateral_auction.payment_amount(bid_price)),
+			)?;
+
+			let new_end = Self::collateral_auction_bid_handler(now, id, (who.clone(), bid_price), auction_info.bid)?;
+			T::Auction::update_auction(
+				id,
+				AuctionInfo {
+					bid: Some((who.clone(), bid_price)),
+					start: auction_info.start,
+					end: Some(new_end),
+				},
+			)?;
+
+			Self::deposit_event(Event::SwappedAndBid {
+				auction_id: id,
+				bidder: who,
+				supply_currency_id,
+				supply_amount,
+				bid_price,
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -332,10 +894,267 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Get the details of active collateral auction, used by runtime api.
+	pub fn get_collateral_auction_detail(
+		auction_id: AuctionId,
+	) -> Option<CollateralAuctionDetail<T::AccountId, BlockNumberFor<T>>> {
+		let collateral_auction = Self::collateral_auctions(auction_id)?;
+		let auction_info = T::Auction::auction_info(auction_id);
+		let dutch_auction_price = Self::dutch_auctions(auction_id).map(|price_curve| {
+			price_curve.price_at(
+				<frame_system::Pallet<T>>::block_number()
+					.saturating_sub(collateral_auction.start_time)
+					.unique_saturated_into(),
+			)
+		});
+
+		Some(CollateralAuctionDetail {
+			auction_id,
+			currency_id: collateral_auction.currency_id,
+			initial_amount: collateral_auction.initial_amount,
+			amount: collateral_auction.amount,
+			target: collateral_auction.target,
+			refund_recipient: collateral_auction.refund_recipient,
+			start_time: collateral_auction.start_time,
+			end_time: auction_info.as_ref().and_then(|info| info.end),
+			bid: auction_info.and_then(|info| info.bid),
+			dutch_auction_price,
+		})
+	}
+
+	/// Get the details of all active collateral auctions, used by runtime api.
+	pub fn get_active_collateral_auctions() -> Vec<CollateralAuctionDetail<T::AccountId, BlockNumberFor<T>>> {
+		<CollateralAuctions<T>>::iter_keys()
+			.filter_map(Self::get_collateral_auction_detail)
+			.collect()
+	}
+
+	/// Get the details of active collateral auctions which `who` is the last
+	/// bidder or the refund recipient of, used by runtime api.
+	pub fn get_collateral_auctions_of(
+		who: &T::AccountId,
+	) -> Vec<CollateralAuctionDetail<T::AccountId, BlockNumberFor<T>>> {
+		Self::get_active_collateral_auctions()
+			.into_iter()
+			.filter(|detail| {
+				&detail.refund_recipient == who || detail.bid.as_ref().map(|(bidder, _)| bidder) == Some(who)
+			})
+			.collect()
+	}
+
 	fn get_last_bid(auction_id: AuctionId) -> Option<(T::AccountId, Balance)> {
 		T::Auction::auction_info(auction_id).and_then(|auction_info| auction_info.bid)
 	}
 
+	/// Get the account which escrows the bid payment of collateral auctions.
+	pub fn escrow_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"escrow")
+	}
+
+	/// Transfer the bid payment in `stable_currency_id` from `bidder` to the
+	/// escrow account.
+	fn lock_bid_funds(
+		stable_currency_id: CurrencyId,
+		auction_id: AuctionId,
+		bidder: &T::AccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		T::Currency::transfer(stable_currency_id, bidder, &Self::escrow_account_id(), amount)?;
+		EscrowedBids::<T>::insert(auction_id, (bidder.clone(), amount));
+		TotalEscrowed::<T>::mutate(|total| *total = total.saturating_add(amount));
+
+		Self::deposit_event(Event::BidFundsLocked {
+			auction_id,
+			bidder: bidder.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Refund the escrowed bid payment of the auction to its bidder, no-op if
+	/// there's no escrowed bid.
+	fn refund_bid_funds(stable_currency_id: CurrencyId, auction_id: AuctionId) -> DispatchResult {
+		if let Some((bidder, amount)) = EscrowedBids::<T>::take(auction_id) {
+			T::Currency::transfer(stable_currency_id, &Self::escrow_account_id(), &bidder, amount)?;
+			TotalEscrowed::<T>::mutate(|total| *total = total.saturating_sub(amount));
+
+			Self::deposit_event(Event::BidFundsRefunded {
+				auction_id,
+				bidder,
+				amount,
+			});
+		}
+		Ok(())
+	}
+
+	/// Settle the escrowed bid payment of the auction to CDP treasury, no-op
+	/// if there's no escrowed bid.
+	fn settle_bid_funds(stable_currency_id: CurrencyId, auction_id: AuctionId) -> DispatchResult {
+		if let Some((_, amount)) = EscrowedBids::<T>::take(auction_id) {
+			T::CDPTreasury::deposit_surplus(stable_currency_id, &Self::escrow_account_id(), amount)?;
+			TotalEscrowed::<T>::mutate(|total| *total = total.saturating_sub(amount));
+
+			Self::deposit_event(Event::BidFundsSettled { auction_id, amount });
+		}
+		Ok(())
+	}
+
+	/// Take at most `max_collateral_amount` collateral of the Dutch auction at
+	/// the current price, the auction is closed once the whole lot is sold or
+	/// the target is reached.
+	fn do_take_dutch_auction(
+		who: T::AccountId,
+		id: AuctionId,
+		max_collateral_amount: Balance,
+		max_payment: Balance,
+	) -> DispatchResult {
+		let price_curve = Self::dutch_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let mut collateral_auction = Self::collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let always_forward = collateral_auction.always_forward();
+
+		let elapsed_blocks = <frame_system::Pallet<T>>::block_number()
+			.saturating_sub(collateral_auction.start_time)
+			.unique_saturated_into();
+		let price = price_curve.price_at(elapsed_blocks);
+		let lot_value = price.saturating_mul_int(collateral_auction.amount);
+
+		// the collateral can be sold and its payment
+		let (sellable_amount, sellable_payment) = if !always_forward && lot_value > collateral_auction.target {
+			// only sell the collateral worth of target
+			let collateral_amount = price
+				.reciprocal()
+				.and_then(|p| p.checked_mul_int(collateral_auction.target))
+				.map_or(collateral_auction.amount, |amount| {
+					sp_std::cmp::min(amount, collateral_auction.amount)
+				});
+			(collateral_amount, collateral_auction.target)
+		} else {
+			(collateral_auction.amount, lot_value)
+		};
+		let (collateral_amount, payment_amount) = if max_collateral_amount >= sellable_amount {
+			(sellable_amount, sellable_payment)
+		} else {
+			(max_collateral_amount, price.saturating_mul_int(max_collateral_amount))
+		};
+		ensure!(!collateral_amount.is_zero(), Error::<T>::InvalidAmount);
+		ensure!(!payment_amount.is_zero(), Error::<T>::InvalidBidPrice);
+		ensure!(payment_amount <= max_payment, Error::<T>::ExceedMaxPayment);
+
+		T::CDPTreasury::deposit_surplus(
+			T::CDPTreasury::get_stable_currency_id(collateral_auction.currency_id),
+			&who,
+			payment_amount,
+		)?;
+		T::CDPTreasury::withdraw_collateral(&who, collateral_auction.currency_id, collateral_amount)?;
+
+		// update the lot and target after take
+		let target_reduction = sp_std::cmp::min(payment_amount, collateral_auction.target);
+		collateral_auction.amount = collateral_auction.amount.saturating_sub(collateral_amount);
+		collateral_auction.target = collateral_auction.target.saturating_sub(target_reduction);
+		TotalCollateralInAuction::<T>::mutate(collateral_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(collateral_amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(target_reduction));
+
+		if collateral_auction.amount.is_zero() || (!always_forward && collateral_auction.target.is_zero()) {
+			Self::record_settlement(
+				id,
+				&collateral_auction,
+				CollateralAuctionOutcome::Dealt,
+				Some(who.clone()),
+				collateral_amount,
+				payment_amount,
+			);
+
+			// refund the remaining collateral to refund recipient
+			if !collateral_auction.amount.is_zero() {
+				T::CDPTreasury::withdraw_collateral(
+					&collateral_auction.refund_recipient,
+					collateral_auction.currency_id,
+					collateral_auction.amount,
+				)?;
+			}
+
+			<CollateralAuctions<T>>::remove(id);
+			DutchAuctions::<T>::remove(id);
+			T::Auction::remove_auction(id);
+
+			// decrement recipient account reference
+			frame_system::Pallet::<T>::dec_consumers(&collateral_auction.refund_recipient);
+
+			// update auction records
+			TotalCollateralInAuction::<T>::mutate(collateral_auction.currency_id, |balance| {
+				*balance = balance.saturating_sub(collateral_auction.amount)
+			});
+			TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
+
+			T::KeeperRewards::reward_keeper(&who, KeeperAction::AuctionSettlement);
+			Self::deposit_event(Event::CollateralAuctionDealt {
+				auction_id: id,
+				collateral_type: collateral_auction.currency_id,
+				collateral_amount,
+				winner: who,
+				payment_amount,
+			});
+		} else {
+			let collateral_type = collateral_auction.currency_id;
+			Self::record_settlement(
+				id,
+				&collateral_auction,
+				CollateralAuctionOutcome::PartiallyTaken,
+				Some(who.clone()),
+				collateral_amount,
+				payment_amount,
+			);
+			<CollateralAuctions<T>>::insert(id, collateral_auction);
+
+			Self::deposit_event(Event::CollateralAuctionPartiallyTaken {
+				auction_id: id,
+				collateral_type,
+				collateral_amount,
+				taker: who,
+				payment_amount,
+			});
+		}
+		Ok(())
+	}
+
+	/// Record the settlement receipt of collateral auction, index the auction
+	/// by the winner. The amounts of Dutch auction are accumulated by every
+	/// take.
+	fn record_settlement(
+		auction_id: AuctionId,
+		collateral_auction: &CollateralAuctionItem<T::AccountId, BlockNumberFor<T>>,
+		outcome: CollateralAuctionOutcome,
+		winner: Option<T::AccountId>,
+		collateral_amount: Balance,
+		payment_amount: Balance,
+	) {
+		if let Some(winner) = &winner {
+			AuctionsByBidder::<T>::insert(winner, auction_id, ());
+		}
+
+		SettlementReceipts::<T>::mutate(auction_id, |maybe_receipt| {
+			let (collateral_amount, payment_amount) = match maybe_receipt {
+				Some(receipt) => (
+					receipt.collateral_amount.saturating_add(collateral_amount),
+					receipt.payment_amount.saturating_add(payment_amount),
+				),
+				None => (collateral_amount, payment_amount),
+			};
+			*maybe_receipt = Some(CollateralAuctionReceipt {
+				outcome,
+				currency_id: collateral_auction.currency_id,
+				refund_recipient: collateral_auction.refund_recipient.clone(),
+				winner,
+				collateral_amount,
+				payment_amount,
+				final_price: Price::checked_from_rational(payment_amount, collateral_amount),
+				settled_at: <frame_system::Pallet<T>>::block_number(),
+			});
+		});
+	}
+
 	fn submit_cancel_auction_tx(auction_id: AuctionId) {
 		let call = Call::<T>::cancel { id: auction_id };
 		if let Err(err) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
@@ -434,9 +1253,9 @@ impl<T: Config> Pallet<T> {
 		}
 
 		// calculate how much collateral to offset target in settle price
-		let settle_price =
-			T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), collateral_auction.currency_id)
-				.ok_or(Error::<T>::InvalidFeedPrice)?;
+		let stable_currency_id = T::CDPTreasury::get_stable_currency_id(collateral_auction.currency_id);
+		let settle_price = T::PriceSource::get_relative_price(stable_currency_id, collateral_auction.currency_id)
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
 		let confiscate_collateral_amount = if collateral_auction.always_forward() {
 			collateral_auction.amount
 		} else {
@@ -455,9 +1274,9 @@ impl<T: Config> Pallet<T> {
 		)?;
 
 		// if there's bid
-		if let Some((bidder, bid_price)) = last_bid {
+		if let Some((bidder, _)) = last_bid {
 			// refund stable token to the bidder
-			T::CDPTreasury::issue_debit(&bidder, bid_price, false)?;
+			Self::refund_bid_funds(stable_currency_id, id)?;
 
 			// decrease account ref of bidder
 			frame_system::Pallet::<T>::dec_consumers(&bidder);
@@ -497,11 +1316,13 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn get_minimum_increment_size(now: BlockNumberFor<T>, start_block: BlockNumberFor<T>) -> Rate {
+		let minimum_increment_size =
+			Self::collateral_auction_minimum_increment_size().unwrap_or_else(T::MinimumIncrementSize::get);
 		if now >= start_block + T::AuctionDurationSoftCap::get() {
 			// double the minimum increment size when reach soft cap
-			T::MinimumIncrementSize::get().saturating_mul(Rate::saturating_from_integer(2))
+			minimum_increment_size.saturating_mul(Rate::saturating_from_integer(2))
 		} else {
-			T::MinimumIncrementSize::get()
+			minimum_increment_size
 		}
 	}
 
@@ -516,6 +1337,61 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Get the reserve price of collateral, which is the oracle price with
+	/// `ReservePriceDiscount` off.
+	fn get_reserve_price(currency_id: CurrencyId) -> Result<Price, DispatchError> {
+		let oracle_price =
+			T::PriceSource::get_relative_price(currency_id, T::CDPTreasury::get_stable_currency_id(currency_id))
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
+		let discount = Self::reserve_price_discount(currency_id).unwrap_or_default();
+		Ok(oracle_price.saturating_mul(Ratio::one().saturating_sub(discount)))
+	}
+
+	/// Ensure the collateral price implied by the bid is not below the
+	/// reserve price derived from oracle price.
+	fn check_reserve_price(
+		collateral_auction: &CollateralAuctionItem<T::AccountId, BlockNumberFor<T>>,
+		last_bid_price: Balance,
+		new_bid_price: Balance,
+	) -> DispatchResult {
+		if Self::reserve_price_discount(collateral_auction.currency_id).is_some() {
+			let reserve_price = Self::get_reserve_price(collateral_auction.currency_id)?;
+			let bid_price = Price::checked_from_rational(
+				collateral_auction.payment_amount(new_bid_price),
+				collateral_auction.collateral_amount(last_bid_price, new_bid_price),
+			)
+			.unwrap_or_default();
+			ensure!(bid_price >= reserve_price, Error::<T>::BidBelowReservePrice);
+		}
+		Ok(())
+	}
+
+	/// Return the end of auction after a bid at `now`, the end is extended by
+	/// `BidExtensionPeriod` if the bid is placed within it before the auction
+	/// ends.
+	fn get_extended_auction_end(
+		now: BlockNumberFor<T>,
+		id: AuctionId,
+		new_end: BlockNumberFor<T>,
+	) -> BlockNumberFor<T> {
+		let period = Self::bid_extension_period();
+		if period.is_zero() {
+			return new_end;
+		}
+
+		if let Some(end) = T::Auction::auction_info(id).and_then(|auction_info| auction_info.end) {
+			let extended_end = end.saturating_add(period);
+			if end.saturating_sub(now) <= period && extended_end > new_end {
+				Self::deposit_event(Event::AuctionExtended {
+					auction_id: id,
+					end: extended_end,
+				});
+				return extended_end;
+			}
+		}
+		new_end
+	}
+
 	/// Handles collateral auction new bid. Returns
 	/// `Ok(new_auction_end_time)` if bid accepted.
 	///
@@ -529,8 +1405,10 @@ impl<T: Config> Pallet<T> {
 	) -> sp_std::result::Result<BlockNumberFor<T>, DispatchError> {
 		let (new_bidder, new_bid_price) = new_bid;
 		ensure!(!new_bid_price.is_zero(), Error::<T>::InvalidBidPrice);
+		// Dutch auction can only be taken
+		ensure!(!DutchAuctions::<T>::contains_key(id), Error::<T>::InvalidAuctionKind);
 
-		<CollateralAuctions<T>>::try_mutate_exists(
+		let new_end = <CollateralAuctions<T>>::try_mutate_exists(
 			id,
 			|collateral_auction| -> sp_std::result::Result<BlockNumberFor<T>, DispatchError> {
 				let collateral_auction = collateral_auction.as_mut().ok_or(Error::<T>::AuctionNotExists)?;
@@ -546,25 +1424,21 @@ impl<T: Config> Pallet<T> {
 					),
 					Error::<T>::InvalidBidPrice
 				);
+				Self::check_reserve_price(collateral_auction, last_bid_price, new_bid_price)?;
 
 				let last_bidder = last_bid.as_ref().map(|(who, _)| who);
 
-				let mut payment = collateral_auction.payment_amount(new_bid_price);
+				// if there's bid before, return the escrowed stablecoin to last bidder
+				let stable_currency_id = T::CDPTreasury::get_stable_currency_id(collateral_auction.currency_id);
+				Self::refund_bid_funds(stable_currency_id, id)?;
 
-				// if there's bid before, return stablecoin from new bidder to last bidder
-				if let Some(last_bidder) = last_bidder {
-					let refund = collateral_auction.payment_amount(last_bid_price);
-					T::Currency::transfer(T::GetStableCurrencyId::get(), &new_bidder, last_bidder, refund)?;
-
-					payment = payment
-						.checked_sub(refund)
-						// This should never fail because new bid payment are always greater or equal to last bid
-						// payment.
-						.ok_or(Error::<T>::InvalidBidPrice)?;
-				}
-
-				// transfer remain payment from new bidder to CDP treasury
-				T::CDPTreasury::deposit_surplus(&new_bidder, payment)?;
+				// lock the payment of new bidder in the escrow account
+				Self::lock_bid_funds(
+					stable_currency_id,
+					id,
+					&new_bidder,
+					collateral_auction.payment_amount(new_bid_price),
+				)?;
 
 				// if collateral auction will be in reverse stage, refund collateral to it's
 				// origin from auction CDP treasury
@@ -591,7 +1465,9 @@ impl<T: Config> Pallet<T> {
 
 				Ok(now + Self::get_auction_time_to_close(now, collateral_auction.start_time))
 			},
-		)
+		)?;
+
+		Ok(Self::get_extended_auction_end(now, id, new_end))
 	}
 
 	fn collateral_auction_end_handler(
@@ -604,6 +1480,8 @@ impl<T: Config> Pallet<T> {
 		} else {
 			(None, Zero::zero())
 		};
+		let mut aborted_without_bid = false;
+		let stable_currency_id = T::CDPTreasury::get_stable_currency_id(collateral_auction.currency_id);
 
 		let swap_limit = if collateral_auction.always_forward() {
 			SwapLimit::ExactSupply(collateral_auction.amount, bid_price)
@@ -620,7 +1498,7 @@ impl<T: Config> Pallet<T> {
 				&collateral_auction.refund_recipient,
 				collateral_auction.amount.saturating_sub(actual_supply_amount),
 			);
-			Self::try_refund_bid(&collateral_auction, last_bid);
+			Self::try_refund_bid(stable_currency_id, auction_id);
 
 			// Note: for StableAsset, the swap of cdp treasury is always on `ExactSupply`
 			// regardless of this swap_limit params. There will be excess stablecoins that
@@ -628,12 +1506,21 @@ impl<T: Config> Pallet<T> {
 			if let SwapLimit::ExactTarget(_, target_limit) = swap_limit {
 				if actual_target_amount > target_limit {
 					let _ = T::CDPTreasury::withdraw_surplus(
+						stable_currency_id,
 						&collateral_auction.refund_recipient,
 						actual_target_amount.saturating_sub(target_limit),
 					);
 				}
 			}
 
+			Self::record_settlement(
+				auction_id,
+				&collateral_auction,
+				CollateralAuctionOutcome::DEXTaken,
+				None,
+				actual_supply_amount,
+				actual_target_amount,
+			);
 			Self::deposit_event(Event::DEXTakeCollateralAuction {
 				auction_id,
 				collateral_type: collateral_auction.currency_id,
@@ -646,7 +1533,16 @@ impl<T: Config> Pallet<T> {
 			let winner = last_bidder.expect("ensured last bidder not empty; qed");
 
 			Self::try_refund_collateral(collateral_auction.currency_id, &winner, collateral_auction.amount);
+			Self::try_settle_bid(stable_currency_id, auction_id);
 			let payment_amount = collateral_auction.payment_amount(bid_price);
+			Self::record_settlement(
+				auction_id,
+				&collateral_auction,
+				CollateralAuctionOutcome::Dealt,
+				Some(winner.clone()),
+				collateral_auction.amount,
+				payment_amount,
+			);
 
 			Self::deposit_event(Event::CollateralAuctionDealt {
 				auction_id,
@@ -657,7 +1553,16 @@ impl<T: Config> Pallet<T> {
 			});
 		} else {
 			// abort this collateral auction, these collateral can be reprocessed by cdp treausry.
-			Self::try_refund_bid(&collateral_auction, last_bid);
+			aborted_without_bid = last_bid.is_none();
+			Self::try_refund_bid(stable_currency_id, auction_id);
+			Self::record_settlement(
+				auction_id,
+				&collateral_auction,
+				CollateralAuctionOutcome::Aborted,
+				None,
+				0,
+				0,
+			);
 
 			Self::deposit_event(Event::CollateralAuctionAborted {
 				auction_id,
@@ -676,25 +1581,105 @@ impl<T: Config> Pallet<T> {
 			*balance = balance.saturating_sub(collateral_auction.amount)
 		});
 		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
+
+		if aborted_without_bid {
+			Self::handle_aborted_auction(auction_id, &collateral_auction);
+		}
 	}
 
-	// Refund stable to the last_bidder.
-	fn try_refund_bid(
+	/// Reprocess the collateral of auction ended without bid by the aborted
+	/// auction policy of its collateral type.
+	fn handle_aborted_auction(
+		auction_id: AuctionId,
 		collateral_auction: &CollateralAuctionItem<T::AccountId, BlockNumberFor<T>>,
-		last_bid: Option<(T::AccountId, Balance)>,
 	) {
-		if let Some((bidder, bid_price)) = last_bid {
-			// If failed, just the bid did not get the stable. It can be fixed by treasury council.
-			let res = T::CDPTreasury::issue_debit(&bidder, collateral_auction.payment_amount(bid_price), false);
-			if let Err(e) = res {
-				log::warn!(
-					target: "auction-manager",
-					"issue_debit: failed to issue stable {:?} to {:?}: {:?}. \
-					This is unexpected but should be safe",
-					collateral_auction.payment_amount(bid_price), bidder, e
-				);
-				debug_assert!(false);
+		let collateral_type = collateral_auction.currency_id;
+		match Self::aborted_auction_policies(collateral_type) {
+			Some(AbortedAuctionPolicy::Restart { target_discount }) => {
+				let target = collateral_auction
+					.target
+					.saturating_sub(target_discount.saturating_mul_int(collateral_auction.target));
+				let res = frame_support::storage::with_transaction(|| {
+					match <Self as AuctionManager<T::AccountId>>::new_collateral_auction(
+						&collateral_auction.refund_recipient,
+						collateral_type,
+						collateral_auction.amount,
+						target,
+					) {
+						Ok(_) => TransactionOutcome::Commit(Ok(())),
+						Err(e) => TransactionOutcome::Rollback(Err(e)),
+					}
+				});
+				match res {
+					Ok(_) => Self::deposit_event(Event::CollateralAuctionRestarted {
+						auction_id,
+						collateral_type,
+						collateral_amount: collateral_auction.amount,
+						target_stable_amount: target,
+					}),
+					Err(e) => log::warn!(
+						target: "auction-manager",
+						"new_collateral_auction: failed to restart auction {:?}: {:?}, remain the collateral in CDP treasury",
+						auction_id, e
+					),
+				}
+			}
+			Some(AbortedAuctionPolicy::SwapByTreasury) => {
+				// the swap price must not be below the reserve price
+				match Self::get_reserve_price(collateral_type).and_then(|reserve_price| {
+					T::CDPTreasury::swap_collateral_to_stable(
+						collateral_type,
+						SwapLimit::ExactSupply(
+							collateral_auction.amount,
+							reserve_price.saturating_mul_int(collateral_auction.amount),
+						),
+						false,
+					)
+				}) {
+					Ok((supply_collateral_amount, target_stable_amount)) => {
+						Self::deposit_event(Event::AbortedCollateralSwapped {
+							auction_id,
+							collateral_type,
+							supply_collateral_amount,
+							target_stable_amount,
+						})
+					}
+					Err(e) => log::warn!(
+						target: "auction-manager",
+						"swap_collateral_to_stable: failed to swap collateral of auction {:?}: {:?}, remain the collateral in CDP treasury",
+						auction_id, e
+					),
+				}
 			}
+			None => {}
+		}
+	}
+
+	// Refund the escrowed stable to the last_bidder.
+	fn try_refund_bid(stable_currency_id: CurrencyId, auction_id: AuctionId) {
+		// If failed, just the bid did not get the stable. It can be fixed by treasury council.
+		if let Err(e) = Self::refund_bid_funds(stable_currency_id, auction_id) {
+			log::warn!(
+				target: "auction-manager",
+				"refund_bid_funds: failed to refund the escrowed bid of auction {:?}: {:?}. \
+				This is unexpected but should be safe",
+				auction_id, e
+			);
+			debug_assert!(false);
+		}
+	}
+
+	// Settle the escrowed stable of the winner to CDP treasury.
+	fn try_settle_bid(stable_currency_id: CurrencyId, auction_id: AuctionId) {
+		// If failed, the stable remains in the escrow account. It can be fixed by treasury council.
+		if let Err(e) = Self::settle_bid_funds(stable_currency_id, auction_id) {
+			log::warn!(
+				target: "auction-manager",
+				"settle_bid_funds: failed to settle the escrowed bid of auction {:?}: {:?}. \
+				This is unexpected but should be safe",
+				auction_id, e
+			);
+			debug_assert!(false);
 		}
 	}
 
@@ -759,6 +1744,8 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, BlockNumberFor<T>, Auction
 	}
 
 	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
+		// the Dutch auction not taken before the end is handled like an English auction without bid
+		DutchAuctions::<T>::remove(id);
 		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
 			Self::collateral_auction_end_handler(id, collateral_auction, winner.clone());
 		}
@@ -811,6 +1798,7 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 				start_time,
 			},
 		);
+		AuctionsByRefundRecipient::<T>::insert(refund_recipient, auction_id, ());
 
 		// increment recipient account reference
 		if frame_system::Pallet::<T>::inc_consumers(refund_recipient).is_err() {
@@ -831,12 +1819,34 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 			collateral_amount: amount,
 			target_bid_price: target,
 		});
+
+		if let Some(params) = Self::dutch_auction_params(currency_id) {
+			// fallback to English auction if the oracle price is not available
+			if let Some(oracle_price) =
+				T::PriceSource::get_relative_price(currency_id, T::CDPTreasury::get_stable_currency_id(currency_id))
+			{
+				let start_price = oracle_price.saturating_mul(params.start_premium);
+				let price_curve = DutchAuctionPrice {
+					start_price,
+					decay: start_price.saturating_mul(params.decay_per_block),
+					floor_price: oracle_price.saturating_mul(params.floor_ratio),
+				};
+				DutchAuctions::<T>::insert(auction_id, price_curve);
+				Self::deposit_event(Event::NewDutchCollateralAuction {
+					auction_id,
+					start_price,
+					floor_price: price_curve.floor_price,
+				});
+			}
+		}
 		Ok(())
 	}
 
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult {
 		let collateral_auction = <CollateralAuctions<T>>::take(id).ok_or(Error::<T>::AuctionNotExists)?;
+		Self::record_settlement(id, &collateral_auction, CollateralAuctionOutcome::Cancelled, None, 0, 0);
 		Self::cancel_collateral_auction(id, collateral_auction)?;
+		DutchAuctions::<T>::remove(id);
 		T::Auction::remove_auction(id);
 		Ok(())
 	}
@@ -849,3 +1859,39 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 		Self::total_target_in_auction()
 	}
 }
+
+/// Move the payment of the last bid of active collateral auctions, which was
+/// deposited to the surplus pool of CDP treasury, to the escrow account.
+pub struct MigrateBidsToEscrow<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> frame_support::traits::OnRuntimeUpgrade for MigrateBidsToEscrow<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut reads: u64 = 0;
+		let mut writes: u64 = 0;
+		let escrow = Pallet::<T>::escrow_account_id();
+
+		for (auction_id, collateral_auction) in CollateralAuctions::<T>::iter() {
+			reads += 2;
+			if EscrowedBids::<T>::contains_key(auction_id) {
+				continue;
+			}
+			if let Some((bidder, bid_price)) = Pallet::<T>::get_last_bid(auction_id) {
+				let amount = collateral_auction.payment_amount(bid_price);
+				let stable_currency_id = T::CDPTreasury::get_stable_currency_id(collateral_auction.currency_id);
+				match T::CDPTreasury::withdraw_surplus(stable_currency_id, &escrow, amount) {
+					Ok(_) => {
+						EscrowedBids::<T>::insert(auction_id, (bidder, amount));
+						TotalEscrowed::<T>::mutate(|total| *total = total.saturating_add(amount));
+						writes += 4;
+					}
+					Err(e) => log::warn!(
+						target: "auction-manager",
+						"withdraw_surplus: failed to move the bid of auction {:?} to escrow: {:?}",
+						auction_id, e
+					),
+				}
+			}
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
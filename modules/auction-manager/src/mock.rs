@@ -47,6 +47,7 @@ pub type Amount = i64;
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const CAROL: AccountId = 3;
+pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const BTC: CurrencyId = CurrencyId::ForeignAsset(255);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
@@ -114,7 +115,10 @@ ord_parameter_types! {
 }
 
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(20, 100);
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD];
+	pub const GetNativeCurrencyId: CurrencyId = ACA;
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account_truncating();
@@ -125,15 +129,28 @@ parameter_types! {
 
 impl module_cdp_treasury::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Tokens;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = AuctionManagerModule;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
 	type Swap = SpecificJointsSwap<DEXModule, AlternativeSwapPathJointList>;
+	type PriceSource = MockPriceSource;
+	type TradingPathLimit = ConstU32<4>;
 	type MaxAuctionsCount = MaxAuctionsCount;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = module_support::DefaultRiskManager<GetStableCurrencyId>;
+	type MaxCollateralTypes = ConstU32<10>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = ();
 	type TreasuryAccount = TreasuryAccount;
+	type IncentivesRewardsSource = TreasuryAccount;
+	type MetricsEraLength = ConstU64<100>;
+	type MaxMetricsEras = ConstU32<10>;
+	type ParamsUpdateDelay = ConstU64<0>;
 	type WeightInfo = ();
 	type StableAsset = MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber>;
 }
@@ -180,6 +197,8 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = TreasuryAccount;
+	type RuntimeCall = RuntimeCall;
 }
 
 thread_local! {
@@ -199,6 +218,7 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 
 parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(1, 20);
+	pub const AuctionManagerPalletId: PalletId = PalletId(*b"aca/aumg");
 }
 
 impl Config for Runtime {
@@ -208,11 +228,14 @@ impl Config for Runtime {
 	type MinimumIncrementSize = MinimumIncrementSize;
 	type AuctionTimeToClose = ConstU64<100>;
 	type AuctionDurationSoftCap = ConstU64<2000>;
-	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
 	type PriceSource = MockPriceSource;
 	type UnsignedPriority = ConstU64<1048576>; // 1 << 20
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type Swap = SpecificJointsSwap<DEXModule, AlternativeSwapPathJointList>;
+	type KeeperRewards = ();
+	type PalletId = AuctionManagerPalletId;
 	type WeightInfo = ();
 }
 
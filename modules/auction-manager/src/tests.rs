@@ -26,7 +26,7 @@ use mock::{RuntimeCall as MockCall, RuntimeEvent, *};
 use module_support::DEXManager;
 use sp_core::offchain::{testing, DbExternalities, OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt};
 use sp_io::offchain;
-use sp_runtime::traits::One;
+use sp_runtime::traits::{BadOrigin, One};
 
 fn run_to_block_offchain(n: u64) {
 	while System::block_number() < n {
@@ -134,7 +134,7 @@ fn collateral_auction_bid_handler_work() {
 			(BOB, 5),
 			None
 		));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 5);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 995);
 
 		let bob_ref_count_1 = System::consumers(&BOB);
@@ -147,7 +147,7 @@ fn collateral_auction_bid_handler_work() {
 			(CAROL, 10),
 			Some((BOB, 5))
 		));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 10);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
 		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 990);
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).unwrap().amount, 10);
@@ -163,7 +163,7 @@ fn collateral_auction_bid_handler_work() {
 			(BOB, 200),
 			Some((CAROL, 10))
 		));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 900);
 		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 1000);
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).unwrap().amount, 5);
@@ -292,7 +292,7 @@ fn always_forward_collateral_auction_dealt() {
 		));
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(Tokens::free_balance(BTC, &BOB), 1000);
 		let ref_count_0 = System::consumers(&CDPTreasuryModule::account_id());
 		let bob_ref_count_0 = System::consumers(&BOB);
@@ -348,7 +348,7 @@ fn always_forward_collateral_auction_with_bid_taked_by_dex() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
 		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (100, 1000));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 500);
 		let ref_count_0 = System::consumers(&CDPTreasuryModule::account_id());
@@ -368,8 +368,8 @@ fn always_forward_collateral_auction_with_bid_taked_by_dex() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
 		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (200, 500));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
-		assert_eq!(CDPTreasuryModule::debit_pool(), 500);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
 		let ref_count_1 = System::consumers(&CDPTreasuryModule::account_id());
 		let bob_ref_count_1 = System::consumers(&BOB);
@@ -403,7 +403,7 @@ fn reverse_collateral_auction_with_bid_taked_by_dex() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
 		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (100, 1000));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 800);
 		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1000);
@@ -423,8 +423,8 @@ fn reverse_collateral_auction_with_bid_taked_by_dex() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
 		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (126, 800));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 400);
-		assert_eq!(CDPTreasuryModule::debit_pool(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
 		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1074);
 		let bob_ref_count_1 = System::consumers(&BOB);
@@ -446,7 +446,7 @@ fn reverse_collateral_auction_with_bid_dealt() {
 		));
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 80);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 80);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(BTC, &BOB), 1000);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 800);
@@ -500,7 +500,7 @@ fn collateral_auction_with_bid_aborted() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
 		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (500, 1000));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 180);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 820);
 		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1000);
@@ -520,8 +520,8 @@ fn collateral_auction_with_bid_aborted() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
 		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (500, 1000));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 180);
-		assert_eq!(CDPTreasuryModule::debit_pool(), 180);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
 		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1000);
 		let alice_ref_count_1 = System::consumers(&ALICE);
@@ -594,7 +594,7 @@ fn cancel_collateral_auction_work() {
 		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 80));
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 920);
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 10);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 80);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(AUSD, &BOB), 920);
 
@@ -611,8 +611,8 @@ fn cancel_collateral_auction_work() {
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
 		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 10);
-		assert_eq!(CDPTreasuryModule::debit_pool(), 80);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 80);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert!(!AuctionManagerModule::collateral_auctions(0).is_some());
 		assert!(!AuctionModule::auction_info(0).is_some());
 
@@ -734,3 +734,891 @@ fn offchain_default_max_iterator_works() {
 		assert_eq!(pool_state.write().transactions.len(), 1001);
 	});
 }
+
+#[test]
+fn set_dutch_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = DutchAuctionParams {
+			start_premium: Ratio::saturating_from_rational(3, 2),
+			decay_per_block: Rate::saturating_from_rational(1, 10),
+			floor_ratio: Ratio::saturating_from_rational(1, 2),
+		};
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(RuntimeOrigin::signed(BOB), BTC, Some(params)),
+			BadOrigin
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					floor_ratio: Ratio::saturating_from_rational(2, 1),
+					..params
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					decay_per_block: Rate::zero(),
+					..params
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					decay_per_block: Rate::saturating_from_rational(11, 10),
+					..params
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					start_premium: Ratio::saturating_from_rational(9, 10),
+					..params
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(params)
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::DutchAuctionParamsUpdated {
+				collateral_type: BTC,
+				params: Some(params),
+			},
+		));
+		assert_eq!(AuctionManagerModule::dutch_auction_params(BTC), Some(params));
+
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			None
+		));
+		assert_eq!(AuctionManagerModule::dutch_auction_params(BTC), None);
+	});
+}
+
+#[test]
+fn dutch_auction_price_work() {
+	let price_curve = DutchAuctionPrice {
+		start_price: Price::saturating_from_rational(3, 2),
+		decay: Price::saturating_from_rational(15, 100),
+		floor_price: Price::saturating_from_rational(1, 2),
+	};
+	assert_eq!(price_curve.price_at(0), Price::saturating_from_rational(3, 2));
+	assert_eq!(price_curve.price_at(2), Price::saturating_from_rational(12, 10));
+	assert_eq!(price_curve.price_at(20), Price::saturating_from_rational(1, 2));
+}
+
+#[test]
+fn take_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(DutchAuctionParams {
+				start_premium: Ratio::saturating_from_rational(3, 2),
+				decay_per_block: Rate::saturating_from_rational(1, 10),
+				floor_ratio: Ratio::saturating_from_rational(1, 2),
+			})
+		));
+		let alice_ref_count_0 = System::consumers(&ALICE);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 10));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::NewDutchCollateralAuction {
+				auction_id: 0,
+				start_price: Price::saturating_from_rational(3, 2),
+				floor_price: Price::saturating_from_rational(1, 2),
+			},
+		));
+		assert!(AuctionManagerModule::dutch_auctions(0).is_some());
+
+		// Dutch auction cannot be bid
+		assert_noop!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 5), None),
+			Error::<Runtime>::InvalidAuctionKind,
+		);
+		assert_noop!(
+			AuctionManagerModule::take_dutch_auction(RuntimeOrigin::signed(BOB), 1, 10),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		// the price is 1.2 and the lot is worth more than target
+		System::set_block_number(3);
+		assert_noop!(
+			AuctionManagerModule::take_dutch_auction(RuntimeOrigin::signed(BOB), 0, 9),
+			Error::<Runtime>::ExceedMaxPayment,
+		);
+		assert_ok!(AuctionManagerModule::take_dutch_auction(
+			RuntimeOrigin::signed(BOB),
+			0,
+			10
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::CollateralAuctionDealt {
+				auction_id: 0,
+				collateral_type: BTC,
+				collateral_amount: 8,
+				winner: BOB,
+				payment_amount: 10,
+			},
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 990);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1008);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 992);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 10);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
+		assert_eq!(AuctionManagerModule::dutch_auctions(0), None);
+		assert_eq!(AuctionModule::auctions(0), None);
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0);
+
+		// the price decays to the floor price and the whole lot is sold
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 0));
+		System::set_block_number(30);
+		assert_ok!(AuctionManagerModule::take_dutch_auction(
+			RuntimeOrigin::signed(CAROL),
+			1,
+			10
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 995);
+		assert_eq!(Tokens::free_balance(BTC, &CAROL), 1010);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 15);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+	});
+}
+
+#[test]
+fn partial_take_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(DutchAuctionParams {
+				start_premium: Ratio::saturating_from_rational(3, 2),
+				decay_per_block: Rate::saturating_from_rational(1, 10),
+				floor_ratio: Ratio::saturating_from_rational(1, 2),
+			})
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 10));
+
+		// the price is 1.2
+		System::set_block_number(3);
+		assert_noop!(
+			AuctionManagerModule::partial_take_dutch_auction(RuntimeOrigin::signed(BOB), 0, 0, 10),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::partial_take_dutch_auction(RuntimeOrigin::signed(BOB), 0, 5, 5),
+			Error::<Runtime>::ExceedMaxPayment,
+		);
+		assert_ok!(AuctionManagerModule::partial_take_dutch_auction(
+			RuntimeOrigin::signed(BOB),
+			0,
+			5,
+			6
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::CollateralAuctionPartiallyTaken {
+				auction_id: 0,
+				collateral_type: BTC,
+				collateral_amount: 5,
+				taker: BOB,
+				payment_amount: 6,
+			},
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 994);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1005);
+		let collateral_auction = AuctionManagerModule::collateral_auctions(0).unwrap();
+		assert_eq!(collateral_auction.amount, 5);
+		assert_eq!(collateral_auction.target, 4);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 5);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 4);
+		assert!(AuctionModule::auctions(0).is_some());
+
+		// the remaining lot is worth more than the remaining target
+		assert_ok!(AuctionManagerModule::partial_take_dutch_auction(
+			RuntimeOrigin::signed(CAROL),
+			0,
+			5,
+			6
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::CollateralAuctionDealt {
+				auction_id: 0,
+				collateral_type: BTC,
+				collateral_amount: 3,
+				winner: CAROL,
+				payment_amount: 4,
+			},
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 996);
+		assert_eq!(Tokens::free_balance(BTC, &CAROL), 1003);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 992);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 10);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
+		assert_eq!(AuctionManagerModule::dutch_auctions(0), None);
+		assert_eq!(AuctionModule::auctions(0), None);
+	});
+}
+
+#[test]
+fn bid_within_extension_period_extends_auction_end() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_bid_extension_period(RuntimeOrigin::signed(BOB), 200),
+			BadOrigin
+		);
+		assert_ok!(AuctionManagerModule::set_bid_extension_period(
+			RuntimeOrigin::signed(ALICE),
+			200
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::BidExtensionPeriodUpdated { period: 200 },
+		));
+		assert_eq!(AuctionManagerModule::bid_extension_period(), 200);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_eq!(AuctionModule::auctions(0).unwrap().end, Some(2001));
+
+		// not within the extension period
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(1000, 0, (BOB, 5), None),
+			Ok(1100)
+		);
+
+		// within the extension period
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(1900, 0, (CAROL, 10), Some((BOB, 5))),
+			Ok(2201)
+		);
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(crate::Event::AuctionExtended {
+			auction_id: 0,
+			end: 2201,
+		}));
+	});
+}
+
+#[test]
+fn set_collateral_auction_minimum_increment_size_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(1, 1),
+			Rate::saturating_from_rational(1, 20)
+		);
+
+		assert_noop!(
+			AuctionManagerModule::set_collateral_auction_minimum_increment_size(
+				RuntimeOrigin::signed(BOB),
+				Some(Rate::saturating_from_rational(1, 5))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			AuctionManagerModule::set_collateral_auction_minimum_increment_size(
+				RuntimeOrigin::signed(ALICE),
+				Some(Rate::zero())
+			),
+			Error::<Runtime>::InvalidMinimumIncrementSize
+		);
+		assert_ok!(AuctionManagerModule::set_collateral_auction_minimum_increment_size(
+			RuntimeOrigin::signed(ALICE),
+			Some(Rate::saturating_from_rational(1, 5))
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::CollateralAuctionMinimumIncrementSizeUpdated {
+				size: Some(Rate::saturating_from_rational(1, 5)),
+			},
+		));
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(1, 1),
+			Rate::saturating_from_rational(1, 5)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(2001, 1),
+			Rate::saturating_from_rational(2, 5)
+		);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_noop!(
+			AuctionManagerModule::collateral_auction_bid_handler(2, 0, (BOB, 19), None),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_ok!(AuctionManagerModule::collateral_auction_bid_handler(
+			2,
+			0,
+			(BOB, 20),
+			None
+		));
+		assert_noop!(
+			AuctionManagerModule::collateral_auction_bid_handler(3, 0, (CAROL, 39), Some((BOB, 20))),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+
+		assert_ok!(AuctionManagerModule::set_collateral_auction_minimum_increment_size(
+			RuntimeOrigin::signed(ALICE),
+			None
+		));
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(1, 1),
+			Rate::saturating_from_rational(1, 20)
+		);
+	});
+}
+
+#[test]
+fn aborted_collateral_auction_restarted() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_aborted_auction_policy(
+				RuntimeOrigin::signed(BOB),
+				BTC,
+				Some(AbortedAuctionPolicy::SwapByTreasury)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			AuctionManagerModule::set_aborted_auction_policy(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(AbortedAuctionPolicy::Restart {
+					target_discount: Ratio::one()
+				})
+			),
+			Error::<Runtime>::InvalidAbortedAuctionPolicy
+		);
+		assert_ok!(AuctionManagerModule::set_aborted_auction_policy(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(AbortedAuctionPolicy::Restart {
+				target_discount: Ratio::saturating_from_rational(1, 4)
+			})
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::AbortedAuctionPolicyUpdated {
+				collateral_type: BTC,
+				policy: Some(AbortedAuctionPolicy::Restart {
+					target_discount: Ratio::saturating_from_rational(1, 4),
+				}),
+			},
+		));
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		let ref_count_0 = System::consumers(&ALICE);
+
+		// restart with the lowered target
+		AuctionManagerModule::on_auction_ended(0, None);
+		System::assert_has_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::CollateralAuctionAborted {
+				auction_id: 0,
+				collateral_type: BTC,
+				collateral_amount: 100,
+				target_stable_amount: 200,
+				refund_recipient: ALICE,
+			},
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::CollateralAuctionRestarted {
+				auction_id: 0,
+				collateral_type: BTC,
+				collateral_amount: 100,
+				target_stable_amount: 150,
+			},
+		));
+		assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
+		let collateral_auction = AuctionManagerModule::collateral_auctions(1).unwrap();
+		assert_eq!(collateral_auction.amount, 100);
+		assert_eq!(collateral_auction.target, 150);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 150);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
+		assert_eq!(System::consumers(&ALICE), ref_count_0);
+	});
+}
+
+#[test]
+fn aborted_collateral_auction_swapped_by_treasury() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_aborted_auction_policy(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(AbortedAuctionPolicy::SwapByTreasury)
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			0,
+			false
+		));
+
+		// the swap price is below the reserve price
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(6)));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 600));
+		AuctionManagerModule::on_auction_ended(0, None);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+
+		// DEX cannot take the auction for the target
+		MockPriceSource::set_relative_price(Some(Price::one()));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 600));
+		AuctionManagerModule::on_auction_ended(1, None);
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::AbortedCollateralSwapped {
+				auction_id: 1,
+				collateral_type: BTC,
+				supply_collateral_amount: 100,
+				target_stable_amount: 500,
+			},
+		));
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+	});
+}
+
+#[test]
+fn force_cancel_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 80));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 920);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+
+		let alice_ref_count_0 = System::consumers(&ALICE);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		assert_noop!(
+			AuctionManagerModule::force_cancel(RuntimeOrigin::signed(BOB), 0),
+			BadOrigin
+		);
+		assert_noop!(
+			AuctionManagerModule::force_cancel(RuntimeOrigin::signed(ALICE), 1),
+			Error::<Runtime>::AuctionNotExists
+		);
+		assert_ok!(AuctionManagerModule::force_cancel(RuntimeOrigin::signed(ALICE), 0));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(crate::Event::CancelAuction {
+			auction_id: 0,
+		}));
+
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 10);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
+		assert_eq!(AuctionModule::auction_info(0), None);
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+	});
+}
+
+#[test]
+fn force_cancel_in_reverse_stage_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::collateral_auction_bid_handler(
+			1,
+			0,
+			(BOB, 100),
+			None
+		));
+		assert_ok!(AuctionManagerModule::collateral_auction_bid_handler(
+			2,
+			0,
+			(CAROL, 200),
+			Some((BOB, 100))
+		));
+
+		// reverse stage refunded 5 BTC to the refund recipient and escrowed the target from the bidder
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1005);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 900);
+		assert_eq!(AuctionManagerModule::escrowed_bids(0), Some((CAROL, 100)));
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 5);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 5);
+
+		assert_ok!(AuctionManagerModule::force_cancel(RuntimeOrigin::signed(ALICE), 0));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(crate::Event::CancelAuction {
+			auction_id: 0,
+		}));
+
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1005);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 1000);
+		assert_eq!(AuctionManagerModule::escrowed_bids(0), None);
+		assert_eq!(AuctionManagerModule::total_escrowed(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 5);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
+	});
+}
+
+#[test]
+fn bid_below_reserve_price_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_reserve_price_discount(
+				RuntimeOrigin::signed(BOB),
+				BTC,
+				Some(Ratio::saturating_from_rational(1, 4))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			AuctionManagerModule::set_reserve_price_discount(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(Ratio::saturating_from_rational(2, 1))
+			),
+			Error::<Runtime>::InvalidReservePriceDiscount
+		);
+		assert_ok!(AuctionManagerModule::set_reserve_price_discount(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 4))
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(
+			crate::Event::ReservePriceDiscountUpdated {
+				collateral_type: BTC,
+				discount: Some(Ratio::saturating_from_rational(1, 4)),
+			},
+		));
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+
+		// the reserve price is 0.75
+		assert_noop!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 7), None),
+			Error::<Runtime>::BidBelowReservePrice,
+		);
+		MockPriceSource::set_relative_price(None);
+		assert_noop!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 8), None),
+			Error::<Runtime>::InvalidFeedPrice,
+		);
+		MockPriceSource::set_relative_price(Some(Price::one()));
+		assert_ok!(AuctionManagerModule::collateral_auction_bid_handler(
+			1,
+			0,
+			(BOB, 8),
+			None
+		));
+
+		assert_ok!(AuctionManagerModule::set_reserve_price_discount(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			None
+		));
+		assert_eq!(AuctionManagerModule::reserve_price_discount(BTC), None);
+	});
+}
+
+#[test]
+fn get_collateral_auction_detail_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(AuctionManagerModule::get_collateral_auction_detail(0), None);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 30));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&BOB, BTC, 20, 0));
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(CAROL), 0, 10));
+
+		let detail_0 = CollateralAuctionDetail {
+			auction_id: 0,
+			currency_id: BTC,
+			initial_amount: 10,
+			amount: 10,
+			target: 100,
+			refund_recipient: ALICE,
+			start_time: 1,
+			end_time: Some(101),
+			bid: Some((CAROL, 10)),
+			dutch_auction_price: None,
+		};
+		let detail_1 = CollateralAuctionDetail {
+			auction_id: 1,
+			currency_id: BTC,
+			initial_amount: 20,
+			amount: 20,
+			target: 0,
+			refund_recipient: BOB,
+			start_time: 1,
+			end_time: Some(2001),
+			bid: None,
+			dutch_auction_price: None,
+		};
+		assert_eq!(
+			AuctionManagerModule::get_collateral_auction_detail(0),
+			Some(detail_0.clone())
+		);
+
+		let mut active_auctions = AuctionManagerModule::get_active_collateral_auctions();
+		active_auctions.sort_by_key(|detail| detail.auction_id);
+		assert_eq!(active_auctions, vec![detail_0.clone(), detail_1.clone()]);
+
+		assert_eq!(
+			AuctionManagerModule::get_collateral_auctions_of(&ALICE),
+			vec![detail_0.clone()]
+		);
+		assert_eq!(AuctionManagerModule::get_collateral_auctions_of(&BOB), vec![detail_1]);
+		assert_eq!(AuctionManagerModule::get_collateral_auctions_of(&CAROL), vec![detail_0]);
+	});
+}
+
+#[test]
+fn swap_and_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(CAROL),
+			DOT,
+			AUSD,
+			500,
+			500,
+			0,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+
+		assert_noop!(
+			AuctionManagerModule::swap_and_bid(RuntimeOrigin::signed(BOB), 1, DOT, 100, 50),
+			Error::<Runtime>::AuctionNotExists
+		);
+		assert!(AuctionManagerModule::swap_and_bid(RuntimeOrigin::signed(BOB), 0, DOT, 50, 50).is_err());
+
+		assert_ok!(AuctionManagerModule::swap_and_bid(
+			RuntimeOrigin::signed(BOB),
+			0,
+			DOT,
+			100,
+			50
+		));
+		System::assert_last_event(RuntimeEvent::AuctionManagerModule(crate::Event::SwappedAndBid {
+			auction_id: 0,
+			bidder: BOB,
+			supply_currency_id: DOT,
+			supply_amount: 56,
+			bid_price: 50,
+		}));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 944);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(
+			AuctionModule::auctions(0),
+			Some(orml_traits::AuctionInfo {
+				bid: Some((BOB, 50)),
+				start: 1,
+				end: Some(101)
+			})
+		);
+
+		// outbid by swapping
+		assert_ok!(AuctionManagerModule::swap_and_bid(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			DOT,
+			200,
+			80
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1050);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionModule::auctions(0).unwrap().bid, Some((ALICE, 80)));
+	});
+}
+
+#[test]
+fn settlement_receipts_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 0));
+		assert!(AuctionsByRefundRecipient::<Runtime>::contains_key(ALICE, 0));
+		assert_ok!(AuctionManagerModule::collateral_auction_bid_handler(
+			1,
+			0,
+			(BOB, 200),
+			None
+		));
+		assert_eq!(AuctionManagerModule::settlement_receipts(0), None);
+
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 200)));
+		assert_eq!(
+			AuctionManagerModule::settlement_receipts(0),
+			Some(CollateralAuctionReceipt {
+				outcome: CollateralAuctionOutcome::Dealt,
+				currency_id: BTC,
+				refund_recipient: ALICE,
+				winner: Some(BOB),
+				collateral_amount: 100,
+				payment_amount: 200,
+				final_price: Some(Price::saturating_from_integer(2)),
+				settled_at: 1,
+			})
+		);
+		assert!(AuctionsByBidder::<Runtime>::contains_key(BOB, 0));
+
+		// the receipt of Dutch auction accumulates the takes
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(DutchAuctionParams {
+				start_premium: Ratio::saturating_from_rational(3, 2),
+				decay_per_block: Rate::saturating_from_rational(1, 10),
+				floor_ratio: Ratio::saturating_from_rational(1, 2),
+			})
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 10));
+		assert!(AuctionsByRefundRecipient::<Runtime>::contains_key(ALICE, 1));
+
+		System::set_block_number(3);
+		assert_ok!(AuctionManagerModule::partial_take_dutch_auction(
+			RuntimeOrigin::signed(BOB),
+			1,
+			5,
+			6
+		));
+		assert_eq!(
+			AuctionManagerModule::settlement_receipts(1),
+			Some(CollateralAuctionReceipt {
+				outcome: CollateralAuctionOutcome::PartiallyTaken,
+				currency_id: BTC,
+				refund_recipient: ALICE,
+				winner: Some(BOB),
+				collateral_amount: 5,
+				payment_amount: 6,
+				final_price: Some(Price::saturating_from_rational(6, 5)),
+				settled_at: 3,
+			})
+		);
+
+		assert_ok!(AuctionManagerModule::partial_take_dutch_auction(
+			RuntimeOrigin::signed(CAROL),
+			1,
+			5,
+			6
+		));
+		assert_eq!(
+			AuctionManagerModule::settlement_receipts(1),
+			Some(CollateralAuctionReceipt {
+				outcome: CollateralAuctionOutcome::Dealt,
+				currency_id: BTC,
+				refund_recipient: ALICE,
+				winner: Some(CAROL),
+				collateral_amount: 8,
+				payment_amount: 10,
+				final_price: Some(Price::saturating_from_rational(10, 8)),
+				settled_at: 3,
+			})
+		);
+		assert!(AuctionsByBidder::<Runtime>::contains_key(BOB, 1));
+		assert!(AuctionsByBidder::<Runtime>::contains_key(CAROL, 1));
+		assert!(!AuctionsByBidder::<Runtime>::contains_key(CAROL, 0));
+	});
+}
+
+#[test]
+fn bid_funds_escrowed_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let escrow = AuctionManagerModule::escrow_account_id();
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 50));
+		System::assert_has_event(RuntimeEvent::AuctionManagerModule(crate::Event::BidFundsLocked {
+			auction_id: 0,
+			bidder: BOB,
+			amount: 50,
+		}));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 950);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow), 50);
+		assert_eq!(AuctionManagerModule::escrowed_bids(0), Some((BOB, 50)));
+		assert_eq!(AuctionManagerModule::total_escrowed(), 50);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(CAROL), 0, 80));
+		System::assert_has_event(RuntimeEvent::AuctionManagerModule(crate::Event::BidFundsRefunded {
+			auction_id: 0,
+			bidder: BOB,
+			amount: 50,
+		}));
+		System::assert_has_event(RuntimeEvent::AuctionManagerModule(crate::Event::BidFundsLocked {
+			auction_id: 0,
+			bidder: CAROL,
+			amount: 80,
+		}));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 920);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow), 80);
+		assert_eq!(AuctionManagerModule::escrowed_bids(0), Some((CAROL, 80)));
+		assert_eq!(AuctionManagerModule::total_escrowed(), 80);
+
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 100));
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 1000);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow), 100);
+		assert_eq!(AuctionManagerModule::total_escrowed(), 100);
+
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 100)));
+		System::assert_has_event(RuntimeEvent::AuctionManagerModule(crate::Event::BidFundsSettled {
+			auction_id: 0,
+			amount: 100,
+		}));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 900);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow), 0);
+		assert_eq!(AuctionManagerModule::escrowed_bids(0), None);
+		assert_eq!(AuctionManagerModule::total_escrowed(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+	});
+}
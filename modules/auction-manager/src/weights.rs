@@ -48,6 +48,10 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for module_auction_manager.
 pub trait WeightInfo {
 	fn cancel_collateral_auction() -> Weight;
+	fn force_cancel() -> Weight;
+	fn set_dutch_auction_params() -> Weight;
+	fn take_dutch_auction() -> Weight;
+	fn swap_and_bid() -> Weight;
 }
 
 /// Weights for module_auction_manager using the Acala node and recommended hardware.
@@ -58,6 +62,25 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(9 as u64))
 			.saturating_add(T::DbWeight::get().writes(7 as u64))
 	}
+	fn force_cancel() -> Weight {
+		Weight::from_parts(82_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	fn set_dutch_auction_params() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn take_dutch_auction() -> Weight {
+		Weight::from_parts(85_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	fn swap_and_bid() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -67,4 +90,23 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(9 as u64))
 			.saturating_add(RocksDbWeight::get().writes(7 as u64))
 	}
+	fn force_cancel() -> Weight {
+		Weight::from_parts(82_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+	}
+	fn set_dutch_auction_params() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn take_dutch_auction() -> Weight {
+		Weight::from_parts(85_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+	}
+	fn swap_and_bid() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(14 as u64))
+			.saturating_add(RocksDbWeight::get().writes(12 as u64))
+	}
 }
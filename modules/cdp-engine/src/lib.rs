@@ -35,10 +35,10 @@ use frame_system::{
 };
 use module_support::{
 	AddressMapping, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, ExchangeRate, FractionalRate,
-	InvokeContext, LiquidateCollateral, LiquidationEvmBridge, Price, PriceProvider, Rate, Ratio, RiskManager, Swap,
-	SwapLimit,
+	InvokeContext, KeeperAction, KeeperRewards, LiquidateCollateral, LiquidationEvmBridge, Price, PriceFeedStaleness,
+	PriceProvider, Rate, Ratio, RiskManager, Swap, SwapLimit,
 };
-use orml_traits::{Change, GetByKey, MultiCurrency};
+use orml_traits::{Change, DataProvider, GetByKey, MultiCurrency};
 use orml_utilities::OffchainErr;
 use parity_scale_codec::MaxEncodedLen;
 use primitives::{evm::EvmAddress, Amount, Balance, CurrencyId, Position};
@@ -54,12 +54,13 @@ use sp_runtime::{
 		Duration,
 	},
 	traits::{
-		AccountIdConversion, BlockNumberProvider, Bounded, One, Saturating, StaticLookup, UniqueSaturatedInto, Zero,
+		AccountIdConversion, BadOrigin, BlockNumberProvider, Bounded, One, SaturatedConversion, Saturating,
+		StaticLookup, UniqueSaturatedInto, Zero,
 	},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
+	ArithmeticError, DispatchError, DispatchResult, FixedI128, FixedPointNumber, RuntimeDebug, TransactionOutcome,
 };
 use sp_std::{marker::PhantomData, prelude::*};
 
@@ -75,6 +76,8 @@ pub const OFFCHAIN_WORKER_LOCK: &[u8] = b"acala/cdp-engine/lock/";
 pub const OFFCHAIN_WORKER_MAX_ITERATIONS: &[u8] = b"acala/cdp-engine/max-iterations/";
 pub const LOCK_DURATION: u64 = 100;
 pub const DEFAULT_MAX_ITERATIONS: u32 = 1000;
+pub const OFFCHAIN_WORKER_MAX_LIQUIDATIONS: &[u8] = b"acala/cdp-engine/max-liquidations/";
+pub const DEFAULT_MAX_LIQUIDATIONS: u32 = 1000;
 
 pub type LoansOf<T> = module_loans::Pallet<T>;
 pub type CurrencyOf<T> = <T as Config>::Currency;
@@ -106,6 +109,24 @@ pub struct RiskManagementParams {
 	pub required_collateral_ratio: Option<Ratio>,
 }
 
+/// The params of the stability fee controller for specific collateral type.
+/// The controller adjusts the interest rate per sec periodically by the
+/// deviation of the market price of stable currency from its peg, within the
+/// bounds.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct StabilityFeeControllerParams {
+	/// The lower bound of interest rate per sec.
+	pub min_rate: Rate,
+	/// The upper bound of interest rate per sec.
+	pub max_rate: Rate,
+	/// The proportional gain, the rate change per unit change of the peg
+	/// deviation.
+	pub proportional_gain: Rate,
+	/// The integral gain, the rate change per unit of the peg deviation on
+	/// each adjustment.
+	pub integral_gain: Rate,
+}
+
 // typedef to help polkadot.js disambiguate Change with different generic
 // parameters
 type ChangeOptionRate = Change<Option<Rate>>;
@@ -151,7 +172,8 @@ pub mod module {
 		/// Gets the minimum collateral value for the given currency.
 		type MinimumCollateralAmount: GetByKey<CurrencyId, Balance>;
 
-		/// Stablecoin currency id
+		/// The default stablecoin currency id issued against collateral types
+		/// without a configured stable currency.
 		#[pallet::constant]
 		type GetStableCurrencyId: Get<CurrencyId>;
 
@@ -159,12 +181,43 @@ pub mod module {
 		#[pallet::constant]
 		type MaxSwapSlippageCompareToOracle: Get<Ratio>;
 
+		/// The proportion of liquidation penalty paid to the keeper who
+		/// liquidates unsafe CDP with signed origin.
+		#[pallet::constant]
+		type KeeperTipRatio: Get<Rate>;
+
+		/// The maximum tip paid to keeper for a single liquidation.
+		#[pallet::constant]
+		type MaxKeeperTip: Get<Balance>;
+
+		/// The maximum number of CDPs can be liquidated by `liquidate_many`.
+		#[pallet::constant]
+		type MaxLiquidationBatchSize: Get<u32>;
+
+		/// The maximum number of debit exchange rate samples kept for each
+		/// collateral type, the oldest sample is dropped when it's full.
+		#[pallet::constant]
+		type MaxDebitExchangeRateHistory: Get<u32>;
+
+		/// The minimum interval in seconds between two debit exchange rate
+		/// samples of the same collateral type.
+		#[pallet::constant]
+		type DebitExchangeRateSampleInterval: Get<u64>;
+
 		/// The CDP treasury to maintain bad debts and surplus generated by CDPs
 		type CDPTreasury: CDPTreasuryExtended<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
 
 		/// The price source of all types of currencies related to CDP
 		type PriceSource: PriceProvider<CurrencyId>;
 
+		/// The market price source of stable currency, drives the stability
+		/// fee controller.
+		type StableMarketPriceSource: DataProvider<CurrencyId, Price>;
+
+		/// Check whether the price feed of collateral is stale, new debit
+		/// issuance is blocked when it's stale.
+		type PriceStaleness: PriceFeedStaleness<CurrencyId>;
+
 		/// A configuration for base priority of unsigned transactions.
 		///
 		/// This is exposed so that it can be tuned for particular runtime, when
@@ -175,6 +228,9 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// The keeper reward program.
+		type KeeperRewards: KeeperRewards<Self::AccountId>;
+
 		/// Time used for computing era duration.
 		///
 		/// It is guaranteed to start being called from the first `on_finalize`.
@@ -254,6 +310,22 @@ pub mod module {
 		CollateralContractNotFound,
 		/// Invalid rate
 		InvalidRate,
+		/// Exceeds `T::MaxLiquidationBatchSize`.
+		TooManyLiquidations,
+		/// The collateral type has been added already
+		CollateralAlreadyExists,
+		/// The required collateral ratio is below the liquidation ratio
+		InvalidCollateralParams,
+		/// New debit issuance of the collateral type is paused
+		DebitIssuanceIsPaused,
+		/// The feed price of the collateral type is stale
+		StaleFeedPrice,
+		/// The currency is not a stable currency supported by the CDP treasury
+		InvalidStableCurrency,
+		/// The collateral type still has debit or collateral held by the CDP treasury
+		CollateralInUse,
+		/// LP token collaterals and their component tokens can only issue the default stable currency
+		StableCurrencyConflict,
 	}
 
 	#[pallet::event]
@@ -271,6 +343,8 @@ pub mod module {
 		SettleCDPInDebit {
 			collateral_type: CurrencyId,
 			owner: T::AccountId,
+			confiscated_collateral_amount: Balance,
+			refunded_collateral_amount: Balance,
 		},
 		/// Directly close CDP has debit by handle debit with DEX.
 		CloseCDPInDebitByDEX {
@@ -309,6 +383,66 @@ pub mod module {
 		LiquidationContractRegistered { address: EvmAddress },
 		/// A new liquidation contract is deregistered.
 		LiquidationContractDeregistered { address: EvmAddress },
+		/// The partial liquidation margin for specific collateral type updated.
+		PartialLiquidationMarginUpdated {
+			collateral_type: CurrencyId,
+			new_margin: Option<Ratio>,
+		},
+		/// The stability fee controller for specific collateral type updated.
+		StabilityFeeControllerUpdated {
+			collateral_type: CurrencyId,
+			params: Option<StabilityFeeControllerParams>,
+		},
+		/// The block interval of stability fee adjustment updated.
+		StabilityFeeAdjustIntervalUpdated { interval: BlockNumberFor<T> },
+		/// The interest rate per sec for specific collateral type adjusted by
+		/// the stability fee controller.
+		StabilityFeeAdjusted {
+			collateral_type: CurrencyId,
+			peg_deviation: FixedI128,
+			new_interest_rate_per_sec: Rate,
+		},
+		/// Paid tip to the keeper who liquidated the unsafe CDP.
+		KeeperTipPaid {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			keeper: T::AccountId,
+			amount: Balance,
+		},
+		/// Failed to liquidate the CDP in batch liquidation.
+		BatchLiquidationFailed {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			error: DispatchError,
+		},
+		/// Failed to lookup the CDP's owner at `index` of batch liquidation.
+		BatchLiquidationLookupFailed { collateral_type: CurrencyId, index: u32 },
+		/// Batch liquidation completed.
+		BatchLiquidationCompleted { succeeded: u32, failed: u32 },
+		/// The minimum debit value for specific collateral type updated.
+		MinimumDebitValueUpdated {
+			collateral_type: CurrencyId,
+			new_minimum_debit_value: Option<Balance>,
+		},
+		/// New collateral type added.
+		CollateralAdded { collateral_type: CurrencyId },
+		/// New debit issuance of the collateral type paused.
+		DebitIssuancePaused { collateral_type: CurrencyId },
+		/// New debit issuance of the collateral type unpaused.
+		DebitIssuanceUnpaused { collateral_type: CurrencyId },
+		/// The raised liquidation ratio is queued until the grace period ends.
+		LiquidationRatioUpdateScheduled {
+			collateral_type: CurrencyId,
+			new_liquidation_ratio: Ratio,
+			activate_at: BlockNumberFor<T>,
+		},
+		/// The grace period of raised liquidation ratio updated.
+		LiquidationRatioGracePeriodUpdated { grace_period: BlockNumberFor<T> },
+		/// The stable currency issued against specific collateral type updated.
+		CollateralStableCurrencyUpdated {
+			collateral_type: CurrencyId,
+			stable_currency_id: CurrencyId,
+		},
 	}
 
 	/// Mapping from collateral type to its exchange rate of debit units and
@@ -319,6 +453,21 @@ pub mod module {
 	#[pallet::getter(fn debit_exchange_rate)]
 	pub type DebitExchangeRate<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, ExchangeRate, OptionQuery>;
 
+	/// Mapping from collateral type to its recent debit exchange rates with
+	/// the timestamp in seconds, ordered from the oldest to the latest
+	///
+	/// DebitExchangeRateHistory: CurrencyId => BoundedVec<(u64, ExchangeRate),
+	/// MaxDebitExchangeRateHistory>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_exchange_rate_history)]
+	pub type DebitExchangeRateHistory<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		CurrencyId,
+		BoundedVec<(u64, ExchangeRate), T::MaxDebitExchangeRateHistory>,
+		ValueQuery,
+	>;
+
 	/// Mapping from valid collateral type to its risk management params
 	///
 	/// CollateralParams: CurrencyId => Option<RiskManagementParams>
@@ -338,6 +487,80 @@ pub mod module {
 	pub type LiquidationContracts<T: Config> =
 		StorageValue<_, BoundedVec<EvmAddress, T::MaxLiquidationContracts>, ValueQuery>;
 
+	/// Mapping from collateral type to the safety margin above liquidation
+	/// ratio of partial liquidation. If it's set, liquidation only seizes
+	/// enough collateral to bring the CDP back above liquidation ratio plus the
+	/// margin, otherwise the whole CDP is liquidated.
+	///
+	/// PartialLiquidationMargin: CurrencyId => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn partial_liquidation_margin)]
+	pub type PartialLiquidationMargin<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Ratio, OptionQuery>;
+
+	/// Mapping from collateral type to its stability fee controller params
+	///
+	/// StabilityFeeControllers: CurrencyId => Option<StabilityFeeControllerParams>
+	#[pallet::storage]
+	#[pallet::getter(fn stability_fee_controllers)]
+	pub type StabilityFeeControllers<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, StabilityFeeControllerParams, OptionQuery>;
+
+	/// The block interval of stability fee adjustment, zero means disabled.
+	///
+	/// StabilityFeeAdjustInterval: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn stability_fee_adjust_interval)]
+	pub type StabilityFeeAdjustInterval<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// The peg deviation of stable currency at the last stability fee
+	/// adjustment, positive when the market price is below the peg.
+	///
+	/// LastPegDeviation: FixedI128
+	#[pallet::storage]
+	#[pallet::getter(fn last_peg_deviation)]
+	pub type LastPegDeviation<T: Config> = StorageValue<_, FixedI128, ValueQuery>;
+
+	/// Mapping from collateral type to its minimum debit value, overrides
+	/// `MinimumDebitValue` if it's set.
+	///
+	/// MinimumDebitValues: CurrencyId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn minimum_debit_values)]
+	pub type MinimumDebitValues<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, OptionQuery>;
+
+	/// Mapping from collateral type to whether new debit issuance is paused,
+	/// repayments and liquidations are still allowed when paused.
+	///
+	/// PausedDebitIssuance: CurrencyId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn paused_debit_issuance)]
+	pub type PausedDebitIssuance<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
+	/// The blocks that a raised liquidation ratio is delayed for before it
+	/// takes effect, zero means apply immediately.
+	///
+	/// LiquidationRatioGracePeriod: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn liquidation_ratio_grace_period)]
+	pub type LiquidationRatioGracePeriod<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// Mapping from collateral type to the queued liquidation ratio and the
+	/// block it takes effect at.
+	///
+	/// PendingLiquidationRatios: CurrencyId => Option<(Ratio, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_liquidation_ratios)]
+	pub type PendingLiquidationRatios<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, (Ratio, BlockNumberFor<T>), OptionQuery>;
+
+	/// Mapping from collateral type to the stable currency issued against it,
+	/// collateral types without an entry issue `GetStableCurrencyId`.
+	///
+	/// CollateralStableCurrency: CurrencyId => Option<CurrencyId>
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_stable_currency)]
+	pub type CollateralStableCurrency<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, CurrencyId, OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T> {
@@ -398,10 +621,23 @@ pub mod module {
 			} else {
 				Default::default()
 			};
-			<T as Config>::WeightInfo::on_initialize(Self::accumulate_interest(
-				now_as_secs,
-				Self::last_accumulation_secs(),
-			))
+			let accumulated_count = Self::accumulate_interest(now_as_secs, Self::last_accumulation_secs());
+			let mut weight = <T as Config>::WeightInfo::on_initialize(accumulated_count)
+				.saturating_add(T::DbWeight::get().reads_writes(accumulated_count.into(), accumulated_count.into()));
+
+			let interval = Self::stability_fee_adjust_interval();
+			if !interval.is_zero() && (now % interval).is_zero() {
+				weight = weight.saturating_add(<T as Config>::WeightInfo::adjust_stability_fees(
+					Self::adjust_stability_fees(),
+				));
+			}
+
+			let (pending_count, activated_count) = Self::activate_pending_liquidation_ratios(now);
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(
+				pending_count.saturating_add(activated_count).into(),
+				activated_count.saturating_mul(2).into(),
+			));
+			weight
 		}
 
 		/// Runs after every block. Start offchain worker to check CDP and
@@ -428,7 +664,9 @@ pub mod module {
 	impl<T: Config> Pallet<T> {
 		/// Liquidate unsafe CDP
 		///
-		/// The dispatch origin of this call must be _None_.
+		/// The dispatch origin of this call must be _None_ or _Signed_. The
+		/// signed caller is paid a tip from the liquidation penalty realized by
+		/// the liquidation, which is capped by `MaxKeeperTip`.
 		///
 		/// - `currency_id`: CDP's collateral type.
 		/// - `who`: CDP's owner.
@@ -439,11 +677,17 @@ pub mod module {
 			currency_id: CurrencyId,
 			who: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResultWithPostInfo {
-			ensure_none(origin)?;
+			let keeper = match origin.into() {
+				Ok(frame_system::RawOrigin::None) => None,
+				Ok(frame_system::RawOrigin::Signed(keeper)) => Some(keeper),
+				_ => return Err(BadOrigin.into()),
+			};
 			let who = T::Lookup::lookup(who)?;
-			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-			let consumed_weight: Weight = Self::liquidate_unsafe_cdp(who, currency_id)?;
-			Ok(Some(consumed_weight).into())
+			let penalty = Self::do_liquidate_unsafe_cdp(who.clone(), currency_id)?;
+			if let Some(keeper) = keeper {
+				Self::pay_keeper_tip(&keeper, &who, currency_id, penalty);
+			}
+			Ok(Some(T::WeightInfo::liquidate_by_dex()).into())
 		}
 
 		/// Settle CDP has debit after system shutdown
@@ -461,7 +705,10 @@ pub mod module {
 		) -> DispatchResult {
 			ensure_none(origin)?;
 			let who = T::Lookup::lookup(who)?;
-			ensure!(T::EmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
+			ensure!(
+				T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::MustAfterShutdown
+			);
 			Self::settle_cdp_has_debit(who, currency_id)?;
 			Ok(())
 		}
@@ -492,6 +739,9 @@ pub mod module {
 			maximum_total_debit_value: ChangeBalance,
 		) -> DispatchResult {
 			T::UpdateOrigin::ensure_origin(origin)?;
+			if !CollateralParams::<T>::contains_key(currency_id) {
+				Self::ensure_lp_components_issue_default_stable(currency_id)?;
+			}
 
 			let mut collateral_params = Self::collateral_params(currency_id).unwrap_or_default();
 			if let Change::NewValue(maybe_rate) = interest_rate_per_sec {
@@ -509,11 +759,37 @@ pub mod module {
 				});
 			}
 			if let Change::NewValue(update) = liquidation_ratio {
-				collateral_params.liquidation_ratio = update;
-				Self::deposit_event(Event::LiquidationRatioUpdated {
-					collateral_type: currency_id,
-					new_liquidation_ratio: update,
-				});
+				// a new update always overrides the queued one
+				PendingLiquidationRatios::<T>::remove(currency_id);
+
+				let grace_period = Self::liquidation_ratio_grace_period();
+				let current_ratio = collateral_params
+					.liquidation_ratio
+					.unwrap_or_else(T::DefaultLiquidationRatio::get);
+				match update {
+					// raising the ratio of an existing collateral type is delayed, so that the
+					// positions become unsafe by it have time to be adjusted
+					Some(new_ratio)
+						if !grace_period.is_zero()
+							&& Self::collateral_params(currency_id).is_some()
+							&& new_ratio > current_ratio =>
+					{
+						let activate_at = frame_system::Pallet::<T>::block_number().saturating_add(grace_period);
+						PendingLiquidationRatios::<T>::insert(currency_id, (new_ratio, activate_at));
+						Self::deposit_event(Event::LiquidationRatioUpdateScheduled {
+							collateral_type: currency_id,
+							new_liquidation_ratio: new_ratio,
+							activate_at,
+						});
+					}
+					_ => {
+						collateral_params.liquidation_ratio = update;
+						Self::deposit_event(Event::LiquidationRatioUpdated {
+							collateral_type: currency_id,
+							new_liquidation_ratio: update,
+						});
+					}
+				}
 			}
 			if let Change::NewValue(maybe_rate) = liquidation_penalty {
 				match (collateral_params.liquidation_penalty.as_mut(), maybe_rate) {
@@ -566,6 +842,337 @@ pub mod module {
 			Self::deposit_event(Event::LiquidationContractDeregistered { address });
 			Ok(())
 		}
+
+		/// Update the partial liquidation margin for specific collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `margin`: the safety margin above liquidation ratio, `None` means liquidate the whole
+		///   CDP.
+		#[pallet::call_index(5)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_params(), DispatchClass::Operational))]
+		pub fn set_partial_liquidation_margin(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			margin: Option<Ratio>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				CollateralParams::<T>::contains_key(currency_id),
+				Error::<T>::InvalidCollateralType
+			);
+			PartialLiquidationMargin::<T>::set(currency_id, margin);
+			Self::deposit_event(Event::PartialLiquidationMarginUpdated {
+				collateral_type: currency_id,
+				new_margin: margin,
+			});
+			Ok(())
+		}
+
+		/// Update the stability fee controller for specific collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `params`: the controller params, `None` means disable the controller.
+		#[pallet::call_index(6)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_params(), DispatchClass::Operational))]
+		pub fn set_stability_fee_controller(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: Option<StabilityFeeControllerParams>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				CollateralParams::<T>::contains_key(currency_id),
+				Error::<T>::InvalidCollateralType
+			);
+			if let Some(params) = params {
+				ensure!(
+					params.min_rate <= params.max_rate && FractionalRate::try_from(params.max_rate).is_ok(),
+					Error::<T>::InvalidRate
+				);
+			}
+			StabilityFeeControllers::<T>::set(currency_id, params);
+			Self::deposit_event(Event::StabilityFeeControllerUpdated {
+				collateral_type: currency_id,
+				params,
+			});
+			Ok(())
+		}
+
+		/// Update the block interval of stability fee adjustment.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `interval`: the block interval, zero means disable the adjustment.
+		#[pallet::call_index(7)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_params(), DispatchClass::Operational))]
+		pub fn set_stability_fee_adjust_interval(origin: OriginFor<T>, interval: BlockNumberFor<T>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			StabilityFeeAdjustInterval::<T>::put(interval);
+			Self::deposit_event(Event::StabilityFeeAdjustIntervalUpdated { interval });
+			Ok(())
+		}
+
+		/// Liquidate a batch of unsafe CDPs, the failure of any CDP does not
+		/// revert others and is recorded by event.
+		///
+		/// The dispatch origin of this call must be _Signed_, the caller is
+		/// paid a tip for each liquidated CDP.
+		///
+		/// - `targets`: the list of (collateral type, CDP's owner), the length
+		///   must not exceed `MaxLiquidationBatchSize`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::CDPTreasury::max_auction())
+			.saturating_mul(targets.len() as u64))]
+		pub fn liquidate_many(
+			origin: OriginFor<T>,
+			targets: Vec<(CurrencyId, <T::Lookup as StaticLookup>::Source)>,
+		) -> DispatchResultWithPostInfo {
+			let keeper = ensure_signed(origin)?;
+			ensure!(
+				targets.len() <= T::MaxLiquidationBatchSize::get() as usize,
+				Error::<T>::TooManyLiquidations
+			);
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+
+			let mut succeeded: u32 = 0;
+			let mut failed: u32 = 0;
+			let mut consumed_weight = Weight::zero();
+			for (index, (currency_id, who)) in targets.into_iter().enumerate() {
+				let who = match T::Lookup::lookup(who) {
+					Ok(who) => who,
+					Err(_) => {
+						Self::deposit_event(Event::BatchLiquidationLookupFailed {
+							collateral_type: currency_id,
+							index: index as u32,
+						});
+						failed += 1;
+						consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads(1));
+						continue;
+					}
+				};
+				consumed_weight = consumed_weight.saturating_add(<T as Config>::WeightInfo::liquidate_by_dex());
+				let res = frame_support::storage::with_transaction(|| {
+					match Self::do_liquidate_unsafe_cdp(who.clone(), currency_id) {
+						Ok(penalty) => TransactionOutcome::Commit(Ok(penalty)),
+						Err(e) => TransactionOutcome::Rollback(Err(e)),
+					}
+				});
+				match res {
+					Ok(penalty) => {
+						Self::pay_keeper_tip(&keeper, &who, currency_id, penalty);
+						succeeded += 1;
+					}
+					Err(error) => {
+						Self::deposit_event(Event::BatchLiquidationFailed {
+							collateral_type: currency_id,
+							owner: who,
+							error,
+						});
+						failed += 1;
+					}
+				}
+			}
+
+			Self::deposit_event(Event::BatchLiquidationCompleted { succeeded, failed });
+			Ok(Some(consumed_weight).into())
+		}
+
+		/// Update the minimum debit value for specific collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `minimum_debit_value`: the minimum debit value of CDP, `None` means use the default
+		///   `MinimumDebitValue`.
+		#[pallet::call_index(9)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_params(), DispatchClass::Operational))]
+		pub fn set_minimum_debit_value(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			minimum_debit_value: Option<Balance>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				CollateralParams::<T>::contains_key(currency_id),
+				Error::<T>::InvalidCollateralType
+			);
+			MinimumDebitValues::<T>::set(currency_id, minimum_debit_value);
+			Self::deposit_event(Event::MinimumDebitValueUpdated {
+				collateral_type: currency_id,
+				new_minimum_debit_value: minimum_debit_value,
+			});
+			Ok(())
+		}
+
+		/// Add new collateral type with all its risk management params and
+		/// the expected collateral auction size in cdp treasury atomically.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `stability_fee`: extra interest rate per sec.
+		/// - `liquidation_ratio`: liquidation ratio.
+		/// - `liquidation_penalty`: liquidation penalty.
+		/// - `required_ratio`: required collateral ratio.
+		/// - `debit_ceiling`: maximum total debit value.
+		/// - `auction_max_size`: expected size of per lot collateral auction.
+		#[pallet::call_index(10)]
+		#[pallet::weight((
+			<T as Config>::WeightInfo::set_collateral_params()
+				.saturating_add(T::DbWeight::get().writes(1)),
+			DispatchClass::Operational
+		))]
+		#[transactional]
+		#[allow(clippy::too_many_arguments)]
+		pub fn add_collateral(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			stability_fee: Rate,
+			liquidation_ratio: Ratio,
+			liquidation_penalty: Rate,
+			required_ratio: Ratio,
+			debit_ceiling: Balance,
+			auction_max_size: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin.clone())?;
+			ensure!(
+				!CollateralParams::<T>::contains_key(currency_id),
+				Error::<T>::CollateralAlreadyExists
+			);
+			ensure!(required_ratio >= liquidation_ratio, Error::<T>::InvalidCollateralParams);
+
+			Self::set_collateral_params(
+				origin,
+				currency_id,
+				Change::NewValue(Some(stability_fee)),
+				Change::NewValue(Some(liquidation_ratio)),
+				Change::NewValue(Some(liquidation_penalty)),
+				Change::NewValue(Some(required_ratio)),
+				Change::NewValue(debit_ceiling),
+			)?;
+			<T as Config>::CDPTreasury::set_expected_collateral_auction_size(currency_id, auction_max_size)?;
+
+			Self::deposit_event(Event::CollateralAdded {
+				collateral_type: currency_id,
+			});
+			Ok(())
+		}
+
+		/// Pause or unpause new debit issuance for specific collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `paused`: whether to pause new debit issuance.
+		#[pallet::call_index(11)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_params(), DispatchClass::Operational))]
+		pub fn set_debit_issuance_paused(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			paused: bool,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if paused {
+				PausedDebitIssuance::<T>::insert(currency_id, true);
+				Self::deposit_event(Event::DebitIssuancePaused {
+					collateral_type: currency_id,
+				});
+			} else {
+				PausedDebitIssuance::<T>::remove(currency_id);
+				Self::deposit_event(Event::DebitIssuanceUnpaused {
+					collateral_type: currency_id,
+				});
+			}
+			Ok(())
+		}
+
+		/// Update the grace period of raised liquidation ratio.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `grace_period`: the blocks that a raised liquidation ratio is delayed for, zero means
+		///   apply immediately.
+		#[pallet::call_index(12)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_params(), DispatchClass::Operational))]
+		pub fn set_liquidation_ratio_grace_period(
+			origin: OriginFor<T>,
+			grace_period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			LiquidationRatioGracePeriod::<T>::put(grace_period);
+			Self::deposit_event(Event::LiquidationRatioGracePeriodUpdated { grace_period });
+			Ok(())
+		}
+
+		/// Transfer the confiscated vested collateral of the CDP owner which has
+		/// been vested to cdp treasury.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `who`: CDP's owner.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::sweep_vested_collateral())]
+		pub fn sweep_vested_collateral(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			<LoansOf<T>>::sweep_vested_collateral(&who)?;
+			Ok(())
+		}
+
+		/// Update the stable currency issued against specific collateral type.
+		/// It can only be changed while the collateral type has no debit and the
+		/// CDP treasury holds none of it, LP token collaterals and their component
+		/// tokens always issue the default stable currency.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `stable_currency_id`: stable currency supported by the CDP treasury.
+		#[pallet::call_index(14)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_stable_currency(), DispatchClass::Operational))]
+		pub fn set_collateral_stable_currency(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			stable_currency_id: CurrencyId,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				CollateralParams::<T>::contains_key(currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			ensure!(
+				<T as Config>::CDPTreasury::is_stable_currency(stable_currency_id),
+				Error::<T>::InvalidStableCurrency,
+			);
+			ensure!(
+				<LoansOf<T>>::total_positions(currency_id).debit.is_zero()
+					&& <T as Config>::CDPTreasury::get_total_collaterals(currency_id).is_zero(),
+				Error::<T>::CollateralInUse,
+			);
+			ensure!(
+				stable_currency_id == T::GetStableCurrencyId::get()
+					|| (!currency_id.is_dex_share_currency_id() && !Self::is_lp_collateral_component(currency_id)),
+				Error::<T>::StableCurrencyConflict,
+			);
+
+			if stable_currency_id == T::GetStableCurrencyId::get() {
+				CollateralStableCurrency::<T>::remove(currency_id);
+			} else {
+				CollateralStableCurrency::<T>::insert(currency_id, stable_currency_id);
+			}
+			Self::deposit_event(Event::CollateralStableCurrencyUpdated {
+				collateral_type: currency_id,
+				stable_currency_id,
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -577,10 +1184,12 @@ pub mod module {
 				Call::liquidate { currency_id, who } => {
 					let account = T::Lookup::lookup(who.clone())?;
 					let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &account);
+					let collateral =
+						collateral.saturating_sub(<LoansOf<T>>::collateral_haircut(*currency_id, &account));
 					if !matches!(
 						Self::check_cdp_status(*currency_id, collateral, debit),
 						CDPStatus::Unsafe
-					) || T::EmergencyShutdown::is_shutdown()
+					) || T::EmergencyShutdown::is_collateral_shutdown(*currency_id)
 					{
 						return InvalidTransaction::Stale.into();
 					}
@@ -595,7 +1204,7 @@ pub mod module {
 				Call::settle { currency_id, who } => {
 					let account = T::Lookup::lookup(who.clone())?;
 					let Position { debit, .. } = <LoansOf<T>>::positions(currency_id, account);
-					if debit.is_zero() || !T::EmergencyShutdown::is_shutdown() {
+					if debit.is_zero() || !T::EmergencyShutdown::is_collateral_shutdown(*currency_id) {
 						return InvalidTransaction::Stale.into();
 					}
 
@@ -620,6 +1229,10 @@ impl<T: Config> Pallet<T> {
 			let interval_secs = now_secs.saturating_sub(last_accumulation_secs);
 
 			for currency_id in Self::get_collateral_currency_ids() {
+				// the debit of shutdown collateral is frozen
+				if T::EmergencyShutdown::is_collateral_shutdown(currency_id) {
+					continue;
+				}
 				if let Ok(interest_rate) = Self::get_interest_rate_per_sec(currency_id) {
 					let rate_to_accumulate = Self::compound_interest_rate(interest_rate, interval_secs);
 					let total_debits = <LoansOf<T>>::total_positions(currency_id).debit;
@@ -629,19 +1242,23 @@ impl<T: Config> Pallet<T> {
 						let debit_exchange_rate_increment = debit_exchange_rate.saturating_mul(rate_to_accumulate);
 						let issued_stable_coin_balance = debit_exchange_rate_increment.saturating_mul_int(total_debits);
 
-						// issue stablecoin to surplus pool
-						let res = <T as Config>::CDPTreasury::on_system_surplus(issued_stable_coin_balance);
+						// issue stablecoin to surplus pool and incentives
+						let res = <T as Config>::CDPTreasury::on_stability_fee(
+							Self::stable_currency_of(currency_id),
+							issued_stable_coin_balance,
+						);
 						match res {
 							Ok(_) => {
 								// update exchange rate when issue success
 								let new_debit_exchange_rate =
 									debit_exchange_rate.saturating_add(debit_exchange_rate_increment);
 								DebitExchangeRate::<T>::insert(currency_id, new_debit_exchange_rate);
+								Self::record_debit_exchange_rate(currency_id, now_secs, new_debit_exchange_rate);
 							}
 							Err(e) => {
 								log::warn!(
 									target: "cdp-engine",
-									"on_system_surplus: failed to on system surplus {:?}: {:?}. \
+									"on_stability_fee: failed to on stability fee {:?}: {:?}. \
 									This is unexpected but should be safe",
 									issued_stable_coin_balance, e
 								);
@@ -658,6 +1275,126 @@ impl<T: Config> Pallet<T> {
 		count
 	}
 
+	/// Append the debit exchange rate to the history of collateral type if
+	/// the sample interval has passed, drop the oldest sample when it's full.
+	fn record_debit_exchange_rate(currency_id: CurrencyId, now_secs: u64, debit_exchange_rate: ExchangeRate) {
+		let max_len = T::MaxDebitExchangeRateHistory::get() as usize;
+		if max_len.is_zero() {
+			return;
+		}
+
+		DebitExchangeRateHistory::<T>::mutate(currency_id, |history| {
+			if let Some((last_secs, _)) = history.last() {
+				if now_secs < last_secs.saturating_add(T::DebitExchangeRateSampleInterval::get()) {
+					return;
+				}
+			}
+			if history.len() >= max_len {
+				history.remove(0);
+			}
+			// never fails because the history is not full
+			let _ = history.try_push((now_secs, debit_exchange_rate));
+		});
+	}
+
+	/// Apply the queued liquidation ratios whose grace period has ended,
+	/// return the count of pending and activated collateral types.
+	fn activate_pending_liquidation_ratios(now: BlockNumberFor<T>) -> (u32, u32) {
+		let mut pending_count: u32 = 0;
+		let ready: Vec<(CurrencyId, Ratio)> = PendingLiquidationRatios::<T>::iter()
+			.filter_map(|(currency_id, (ratio, activate_at))| {
+				pending_count = pending_count.saturating_add(1);
+				(activate_at <= now).then_some((currency_id, ratio))
+			})
+			.collect();
+
+		for (currency_id, ratio) in ready.iter() {
+			PendingLiquidationRatios::<T>::remove(currency_id);
+			CollateralParams::<T>::mutate(currency_id, |maybe_params| {
+				if let Some(params) = maybe_params {
+					params.liquidation_ratio = Some(*ratio);
+				}
+			});
+			Self::deposit_event(Event::LiquidationRatioUpdated {
+				collateral_type: *currency_id,
+				new_liquidation_ratio: Some(*ratio),
+			});
+		}
+
+		(pending_count, ready.len() as u32)
+	}
+
+	/// Adjust the interest rate per sec of collateral types that have
+	/// stability fee controller by the peg deviation of stable currency,
+	/// return the count of adjusted collateral types.
+	///
+	/// The controller is a PI controller in velocity form:
+	/// `new_rate = rate + proportional_gain * (deviation - last_deviation) +
+	/// integral_gain * deviation`, where `deviation = 1 - market_price`.
+	fn adjust_stability_fees() -> u32 {
+		if T::EmergencyShutdown::is_shutdown() {
+			return 0;
+		}
+		let market_price = match T::StableMarketPriceSource::get(&T::GetStableCurrencyId::get()) {
+			Some(price) => price,
+			None => return 0,
+		};
+
+		let to_signed = |v: Rate| FixedI128::from_inner(v.into_inner().saturated_into());
+		let deviation = FixedI128::one().saturating_sub(to_signed(market_price));
+		let deviation_change = deviation.saturating_sub(Self::last_peg_deviation());
+		LastPegDeviation::<T>::put(deviation);
+
+		let mut count: u32 = 0;
+		for (currency_id, controller) in StabilityFeeControllers::<T>::iter() {
+			// the controller follows the market price of the default stable currency only
+			if Self::stable_currency_of(currency_id) != T::GetStableCurrencyId::get() {
+				continue;
+			}
+			let mut collateral_params = match Self::collateral_params(currency_id) {
+				Some(params) => params,
+				None => continue,
+			};
+			let current_rate = collateral_params
+				.interest_rate_per_sec
+				.map(|v| v.into_inner())
+				.unwrap_or_default();
+			let rate_change = to_signed(controller.proportional_gain)
+				.saturating_mul(deviation_change)
+				.saturating_add(to_signed(controller.integral_gain).saturating_mul(deviation));
+			let new_rate = to_signed(current_rate).saturating_add(rate_change);
+			let new_rate = Rate::from_inner(new_rate.into_inner().max(0).saturated_into())
+				.max(controller.min_rate)
+				.min(controller.max_rate);
+
+			let res = match collateral_params.interest_rate_per_sec.as_mut() {
+				Some(existing) => existing.try_set(new_rate),
+				None => FractionalRate::try_from(new_rate).map(|rate| {
+					collateral_params.interest_rate_per_sec = Some(rate);
+				}),
+			};
+			match res {
+				Ok(_) => {
+					CollateralParams::<T>::insert(currency_id, collateral_params);
+					Self::deposit_event(Event::StabilityFeeAdjusted {
+						collateral_type: currency_id,
+						peg_deviation: deviation,
+						new_interest_rate_per_sec: new_rate,
+					});
+				}
+				Err(e) => {
+					log::warn!(
+						target: "cdp-engine",
+						"adjust_stability_fees: failed to set interest rate per sec {:?} for {:?}: {:?}",
+						new_rate, currency_id, e
+					);
+				}
+			}
+			count += 1;
+		}
+		count
+	}
+
 	fn submit_unsigned_liquidation_tx(currency_id: CurrencyId, who: T::AccountId) {
 		let who = T::Lookup::unlookup(who);
 		let call = Call::<T>::liquidate {
@@ -722,6 +1459,12 @@ impl<T: Config> Pallet<T> {
 			.unwrap_or(Some(DEFAULT_MAX_ITERATIONS))
 			.unwrap_or(DEFAULT_MAX_ITERATIONS);
 
+		// get the max liquidations per run config
+		let max_liquidations = StorageValueRef::persistent(OFFCHAIN_WORKER_MAX_LIQUIDATIONS)
+			.get::<u32>()
+			.unwrap_or(Some(DEFAULT_MAX_LIQUIDATIONS))
+			.unwrap_or(DEFAULT_MAX_LIQUIDATIONS);
+
 		let currency_id = match collateral_currency_ids.get(collateral_position as usize) {
 			Some(currency_id) => *currency_id,
 			None => {
@@ -736,7 +1479,8 @@ impl<T: Config> Pallet<T> {
 			}
 		};
 
-		let is_shutdown = T::EmergencyShutdown::is_shutdown();
+		let is_shutdown = T::EmergencyShutdown::is_collateral_shutdown(currency_id);
+		let feed_price = T::PriceSource::get_relative_price(currency_id, Self::stable_currency_of(currency_id));
 
 		// If start key is Some(value) continue iterating from that point in storage otherwise start
 		// iterating from the beginning of <module_loans::Positions<T>>
@@ -747,17 +1491,22 @@ impl<T: Config> Pallet<T> {
 
 		let mut finished = true;
 		let mut iteration_count = 0;
+		let mut unsafe_cdps: Vec<(Ratio, T::AccountId)> = Vec::new();
 		let iteration_start_time = sp_io::offchain::timestamp();
 
 		#[allow(clippy::while_let_on_iterator)]
 		while let Some((who, Position { collateral, debit })) = map_iterator.next() {
+			let collateral = collateral.saturating_sub(<LoansOf<T>>::collateral_haircut(currency_id, &who));
 			if !is_shutdown
 				&& matches!(
 					Self::check_cdp_status(currency_id, collateral, debit),
 					CDPStatus::Unsafe
 				) {
-				// liquidate unsafe CDPs before emergency shutdown occurs
-				Self::submit_unsigned_liquidation_tx(currency_id, who);
+				// collect unsafe CDPs before emergency shutdown occurs, they are liquidated by risk later
+				let collateral_ratio = feed_price
+					.map(|price| Self::calculate_collateral_ratio(currency_id, collateral, debit, price))
+					.unwrap_or_default();
+				unsafe_cdps.push((collateral_ratio, who));
 			} else if is_shutdown && !debit.is_zero() {
 				// settle CDPs with debit after emergency shutdown occurs.
 				Self::submit_unsigned_settlement_tx(currency_id, who);
@@ -784,9 +1533,19 @@ impl<T: Config> Pallet<T> {
 			iteration_end_time.diff(&iteration_start_time)
 		);
 
-		// if iteration for map storage finished, clear to be continue record
-		// otherwise, update to be continue record
-		if finished {
+		// liquidate the riskiest CDPs first, which have the lowest collateral ratio
+		let liquidations_truncated = unsafe_cdps.len() > max_liquidations as usize;
+		unsafe_cdps.sort_by(|a, b| a.0.cmp(&b.0));
+		for (_, who) in unsafe_cdps.into_iter().take(max_liquidations as usize) {
+			Self::submit_unsigned_liquidation_tx(currency_id, who);
+		}
+
+		// if the liquidations exceed the cap, iterate the same range again to
+		// liquidate the rest. if iteration for map storage finished, clear to
+		// be continue record otherwise, update to be continue record
+		if liquidations_truncated {
+			to_be_continue.set(&(collateral_position, start_key));
+		} else if finished {
 			let next_collateral_position =
 				if collateral_position < collateral_currency_ids.len().saturating_sub(1) as u32 {
 					collateral_position + 1
@@ -804,8 +1563,38 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The stable currency issued against the collateral type.
+	pub fn stable_currency_of(currency_id: CurrencyId) -> CurrencyId {
+		Self::collateral_stable_currency(currency_id).unwrap_or_else(T::GetStableCurrencyId::get)
+	}
+
+	/// LP token collaterals are liquidated as their component tokens, which are
+	/// settled in the stable currency of the component, so an LP token collateral
+	/// can only be added while its components issue the default stable currency.
+	fn ensure_lp_components_issue_default_stable(currency_id: CurrencyId) -> DispatchResult {
+		if let CurrencyId::DexShare(dex_share_0, dex_share_1) = currency_id {
+			ensure!(
+				!CollateralStableCurrency::<T>::contains_key(CurrencyId::from(dex_share_0))
+					&& !CollateralStableCurrency::<T>::contains_key(CurrencyId::from(dex_share_1)),
+				Error::<T>::StableCurrencyConflict
+			);
+		}
+		Ok(())
+	}
+
+	fn is_lp_collateral_component(currency_id: CurrencyId) -> bool {
+		Self::get_collateral_currency_ids()
+			.into_iter()
+			.any(|collateral| match collateral {
+				CurrencyId::DexShare(dex_share_0, dex_share_1) => {
+					CurrencyId::from(dex_share_0) == currency_id || CurrencyId::from(dex_share_1) == currency_id
+				}
+				_ => false,
+			})
+	}
+
 	pub fn check_cdp_status(currency_id: CurrencyId, collateral_amount: Balance, debit_amount: Balance) -> CDPStatus {
-		let stable_currency_id = T::GetStableCurrencyId::get();
+		let stable_currency_id = Self::stable_currency_of(currency_id);
 		if let Some(feed_price) = T::PriceSource::get_relative_price(currency_id, stable_currency_id) {
 			let collateral_ratio =
 				Self::calculate_collateral_ratio(currency_id, collateral_amount, debit_amount, feed_price);
@@ -866,6 +1655,23 @@ impl<T: Config> Pallet<T> {
 		Self::debit_exchange_rate(currency_id).unwrap_or_else(T::DefaultDebitExchangeRate::get)
 	}
 
+	/// Get the interest of `debit_balance` accumulated in `secs` at the
+	/// current interest rate and debit exchange rate of collateral type.
+	pub fn projected_interest(
+		currency_id: CurrencyId,
+		debit_balance: Balance,
+		secs: u64,
+	) -> Result<Balance, DispatchError> {
+		let rate_to_accumulate = Self::compound_interest_rate(Self::get_interest_rate_per_sec(currency_id)?, secs);
+		Ok(Self::get_debit_exchange_rate(currency_id)
+			.saturating_mul(rate_to_accumulate)
+			.saturating_mul_int(debit_balance))
+	}
+
+	pub fn get_minimum_debit_value(currency_id: CurrencyId) -> Balance {
+		Self::minimum_debit_values(currency_id).unwrap_or_else(T::MinimumDebitValue::get)
+	}
+
 	pub fn convert_to_debit_value(currency_id: CurrencyId, debit_balance: Balance) -> Balance {
 		Self::get_debit_exchange_rate(currency_id).saturating_mul_int(debit_balance)
 	}
@@ -902,6 +1708,15 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	pub fn adjust_vested_collateral(who: &T::AccountId, collateral_adjustment: Amount) -> DispatchResult {
+		ensure!(
+			CollateralParams::<T>::contains_key(<T as module_loans::Config>::VestedCollateralCurrencyId::get()),
+			Error::<T>::InvalidCollateralType,
+		);
+		<LoansOf<T>>::adjust_vested_collateral(who, collateral_adjustment)?;
+		Ok(())
+	}
+
 	pub fn adjust_position_by_debit_value(
 		who: &T::AccountId,
 		currency_id: CurrencyId,
@@ -931,14 +1746,14 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// If reverse is false, swap stable coin to given `token`.
-	/// If reverse is true, swap given `token` to stable coin.
+	/// If reverse is false, swap `stable_currency_id` to given `token`.
+	/// If reverse is true, swap given `token` to `stable_currency_id`.
 	fn swap_stable_and_lp_token(
+		stable_currency_id: CurrencyId,
 		token: CurrencyId,
 		amount: Balance,
 		reverse: bool,
 	) -> sp_std::result::Result<Balance, DispatchError> {
-		let stable_currency_id = T::GetStableCurrencyId::get();
 		let loans_module_account = <LoansOf<T>>::account_id();
 
 		// do nothing if given token is stable coin
@@ -978,9 +1793,10 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::InvalidCollateralType,
 		);
 		let loans_module_account = <LoansOf<T>>::account_id();
+		let stable_currency_id = Self::stable_currency_of(currency_id);
 
 		// issue stable coin in advance
-		<T as Config>::CDPTreasury::issue_debit(&loans_module_account, increase_debit_value, true)?;
+		<T as Config>::CDPTreasury::issue_debit(stable_currency_id, &loans_module_account, increase_debit_value, true)?;
 
 		// get the actual increased collateral amount
 		let increase_collateral = match currency_id {
@@ -994,8 +1810,10 @@ impl<T: Config> Pallet<T> {
 				let stable_for_token_1 = increase_debit_value.saturating_sub(stable_for_token_0);
 
 				// swap stable coin to lp component tokens.
-				let available_0 = Self::swap_stable_and_lp_token(token_0, stable_for_token_0, false)?;
-				let available_1 = Self::swap_stable_and_lp_token(token_1, stable_for_token_1, false)?;
+				let available_0 =
+					Self::swap_stable_and_lp_token(stable_currency_id, token_0, stable_for_token_0, false)?;
+				let available_1 =
+					Self::swap_stable_and_lp_token(stable_currency_id, token_1, stable_for_token_1, false)?;
 				let (consumption_0, consumption_1, actual_increase_lp) = T::DEX::add_liquidity(
 					&loans_module_account,
 					token_0,
@@ -1020,7 +1838,7 @@ impl<T: Config> Pallet<T> {
 				// swap stable coin to collateral
 				let limit = SwapLimit::ExactSupply(increase_debit_value, min_increase_collateral);
 				let (_, actual_increase_collateral) =
-					T::Swap::swap(&loans_module_account, T::GetStableCurrencyId::get(), currency_id, limit)?;
+					T::Swap::swap(&loans_module_account, stable_currency_id, currency_id, limit)?;
 
 				actual_increase_collateral
 			}
@@ -1035,7 +1853,12 @@ impl<T: Config> Pallet<T> {
 
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
 		// check the CDP if is still at valid risk
-		Self::check_position_valid(currency_id, collateral, debit, false)?;
+		Self::check_position_valid(
+			currency_id,
+			collateral.saturating_sub(<LoansOf<T>>::collateral_haircut(currency_id, who)),
+			debit,
+			false,
+		)?;
 		// debit cap check due to new issued stable coin
 		Self::check_debit_cap(currency_id, <LoansOf<T>>::total_positions(currency_id).debit)?;
 		Ok(())
@@ -1060,11 +1883,15 @@ impl<T: Config> Pallet<T> {
 		);
 
 		let loans_module_account = <LoansOf<T>>::account_id();
-		let stable_currency_id = T::GetStableCurrencyId::get();
+		let stable_currency_id = Self::stable_currency_of(currency_id);
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
 
-		// ensure collateral of CDP is enough
-		ensure!(decrease_collateral <= collateral, Error::<T>::CollateralNotEnough);
+		// ensure collateral of CDP is enough, the vested collateral is kept in the owner's account and
+		// can't be sold
+		ensure!(
+			decrease_collateral <= collateral.saturating_sub(<LoansOf<T>>::vested_collateral_of(currency_id, who)),
+			Error::<T>::CollateralNotEnough
+		);
 
 		let actual_stable_amount = match currency_id {
 			CurrencyId::DexShare(dex_share_0, dex_share_1) => {
@@ -1082,8 +1909,8 @@ impl<T: Config> Pallet<T> {
 					false,
 				)?;
 
-				let stable_0 = Self::swap_stable_and_lp_token(token_0, available_0, true)?;
-				let stable_1 = Self::swap_stable_and_lp_token(token_1, available_1, true)?;
+				let stable_0 = Self::swap_stable_and_lp_token(stable_currency_id, token_0, available_0, true)?;
+				let stable_1 = Self::swap_stable_and_lp_token(stable_currency_id, token_1, available_1, true)?;
 				let total_stable = stable_0.saturating_add(stable_1);
 
 				// check whether the amount of stable token obtained by selling lptokens is enough as expected
@@ -1128,12 +1955,14 @@ impl<T: Config> Pallet<T> {
 		<LoansOf<T>>::update_loan(who, currency_id, collateral_adjustment, debit_adjustment)?;
 
 		// repay the debit of CDP
-		<T as Config>::CDPTreasury::burn_debit(&loans_module_account, decrease_debit_value)?;
+		<T as Config>::CDPTreasury::burn_debit(stable_currency_id, &loans_module_account, decrease_debit_value)?;
 
 		// check the CDP if is still at valid risk.
 		Self::check_position_valid(
 			currency_id,
-			collateral.saturating_sub(decrease_collateral),
+			collateral
+				.saturating_sub(decrease_collateral)
+				.saturating_sub(<LoansOf<T>>::collateral_haircut(currency_id, who)),
 			debit.saturating_sub(decrease_debit_balance),
 			false,
 		)?;
@@ -1147,8 +1976,9 @@ impl<T: Config> Pallet<T> {
 
 		// confiscate collateral in cdp to cdp treasury
 		// and decrease CDP's debit to zero
-		let settle_price: Price = T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), currency_id)
-			.ok_or(Error::<T>::InvalidFeedPrice)?;
+		let settle_price: Price =
+			T::PriceSource::get_relative_price(Self::stable_currency_of(currency_id), currency_id)
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
 		let confiscate_collateral_amount =
 			sp_std::cmp::min(settle_price.saturating_mul_int(bad_debt_value), collateral);
@@ -1156,9 +1986,18 @@ impl<T: Config> Pallet<T> {
 		// confiscate collateral and all debit
 		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, confiscate_collateral_amount, debit)?;
 
+		// refund the remaining collateral to the owner, the vested collateral is unlocked in place
+		let refund_collateral_amount = collateral.saturating_sub(confiscate_collateral_amount);
+		if !refund_collateral_amount.is_zero() {
+			let collateral_adjustment = <LoansOf<T>>::amount_try_from_balance(refund_collateral_amount)?;
+			<LoansOf<T>>::adjust_position(&who, currency_id, collateral_adjustment.saturating_neg(), Zero::zero())?;
+		}
+
 		Self::deposit_event(Event::SettleCDPInDebit {
 			collateral_type: currency_id,
 			owner: who,
+			confiscated_collateral_amount: confiscate_collateral_amount,
+			refunded_collateral_amount: refund_collateral_amount,
 		});
 		Ok(())
 	}
@@ -1173,10 +2012,22 @@ impl<T: Config> Pallet<T> {
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 		ensure!(!debit.is_zero(), Error::<T>::NoDebitValue);
 		ensure!(
-			matches!(Self::check_cdp_status(currency_id, collateral, debit), CDPStatus::Safe),
+			matches!(
+				Self::check_cdp_status(
+					currency_id,
+					collateral.saturating_sub(<LoansOf<T>>::collateral_haircut(currency_id, &who)),
+					debit
+				),
+				CDPStatus::Safe
+			),
 			Error::<T>::MustBeSafe
 		);
 
+		// release the vested collateral in place, only the regular collateral is sold to repay
+		// the debit
+		<LoansOf<T>>::release_vested_collateral(&who, currency_id)?;
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
+
 		// confiscate all collateral and debit of unsafe cdp to cdp treasury
 		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
 
@@ -1208,23 +2059,45 @@ impl<T: Config> Pallet<T> {
 
 	// liquidate unsafe cdp
 	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<Weight, DispatchError> {
+		Self::do_liquidate_unsafe_cdp(who, currency_id)?;
+		Ok(T::WeightInfo::liquidate_by_dex())
+	}
+
+	/// Liquidate the unsafe CDP, return the part of liquidation penalty that
+	/// is realized to surplus pool by the liquidation, it's zero if the
+	/// collateral is sold by collateral auctions.
+	fn do_liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<Balance, DispatchError> {
+		ensure!(
+			!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+			Error::<T>::AlreadyShutdown
+		);
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
+		let collateral_haircut = <LoansOf<T>>::collateral_haircut(currency_id, &who);
 
 		// ensure the cdp is unsafe
 		ensure!(
 			matches!(
-				Self::check_cdp_status(currency_id, collateral, debit),
+				Self::check_cdp_status(currency_id, collateral.saturating_sub(collateral_haircut), debit),
 				CDPStatus::Unsafe
 			),
 			Error::<T>::MustBeUnsafe
 		);
 
-		// confiscate all collateral and debit of unsafe cdp to cdp treasury
-		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
+		// only liquidate part of the cdp if partial liquidation is enabled, the vested collateral
+		// is counted after the haircut
+		let (collateral, debit) =
+			Self::get_partial_liquidation_amounts(currency_id, collateral.saturating_sub(collateral_haircut), debit)
+				.unwrap_or((collateral, debit));
+
+		// confiscate collateral and debit of unsafe cdp to cdp treasury, the confiscated vested
+		// collateral which is still locked by vesting schedules is transferred to cdp treasury
+		// once it's vested, only the transferred collateral is liquidated now.
+		let collateral = <LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
 
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
 		let liquidation_penalty = Self::get_liquidation_penalty(currency_id)?;
 		let target_stable_amount = liquidation_penalty.saturating_mul_acc_int(bad_debt_value);
+		let surplus_before = <T as Config>::CDPTreasury::get_surplus_pool(Self::stable_currency_of(currency_id));
 
 		match currency_id {
 			CurrencyId::DexShare(dex_share_0, dex_share_1) => {
@@ -1236,7 +2109,7 @@ impl<T: Config> Pallet<T> {
 					<T as Config>::CDPTreasury::remove_liquidity_for_lp_collateral(currency_id, collateral)?;
 
 				// if these's stable
-				let stable_currency_id = T::GetStableCurrencyId::get();
+				let stable_currency_id = Self::stable_currency_of(currency_id);
 				if token_0 == stable_currency_id || token_1 == stable_currency_id {
 					let (existing_stable, need_handle_currency, handle_amount) = if token_0 == stable_currency_id {
 						(amount_0, token_1, amount_1)
@@ -1270,6 +2143,14 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
+		// the penalty part of target is the income of surplus pool
+		let penalty = target_stable_amount.saturating_sub(bad_debt_value);
+		<T as Config>::CDPTreasury::on_liquidation_penalty(currency_id, penalty)?;
+		let realized_penalty = <T as Config>::CDPTreasury::get_surplus_pool(Self::stable_currency_of(currency_id))
+			.saturating_sub(surplus_before)
+			.saturating_sub(bad_debt_value)
+			.min(penalty);
+
 		Self::deposit_event(Event::LiquidateUnsafeCDP {
 			collateral_type: currency_id,
 			owner: who,
@@ -1277,7 +2158,99 @@ impl<T: Config> Pallet<T> {
 			bad_debt_value,
 			target_amount: target_stable_amount,
 		});
-		Ok(T::WeightInfo::liquidate_by_dex())
+		Ok(realized_penalty)
+	}
+
+	/// Pay the keeper a tip from the surplus pool, the tip is a proportion of
+	/// the liquidation penalty realized by the liquidation and capped by
+	/// `MaxKeeperTip`, so that the tip is never paid from the existing surplus
+	/// and liquidating is never profitable for the CDP owner. The keeper is
+	/// also rewarded by `KeeperRewards`. Failing to pay the tip does not revert
+	/// the liquidation.
+	fn pay_keeper_tip(keeper: &T::AccountId, owner: &T::AccountId, currency_id: CurrencyId, penalty: Balance) {
+		if keeper == owner {
+			return;
+		}
+		T::KeeperRewards::reward_keeper(keeper, KeeperAction::Liquidation);
+
+		let tip = T::KeeperTipRatio::get()
+			.saturating_mul_int(penalty)
+			.min(T::MaxKeeperTip::get());
+		if tip.is_zero() {
+			return;
+		}
+
+		match <T as Config>::CDPTreasury::withdraw_surplus(Self::stable_currency_of(currency_id), keeper, tip) {
+			Ok(_) => Self::deposit_event(Event::KeeperTipPaid {
+				collateral_type: currency_id,
+				owner: owner.clone(),
+				keeper: keeper.clone(),
+				amount: tip,
+			}),
+			Err(e) => log::warn!(
+				target: "cdp-engine",
+				"pay_keeper_tip: failed to pay tip {:?} to keeper {:?}: {:?}",
+				tip, keeper, e
+			),
+		}
+	}
+
+	/// Get the (collateral, debit) amount to liquidate for the unsafe CDP. If
+	/// partial liquidation is enabled for the collateral type, only the part
+	/// that brings the CDP back above liquidation ratio plus the margin is
+	/// liquidated, falls back to the whole CDP if it's impossible.
+	pub fn get_liquidation_amounts(currency_id: CurrencyId, collateral: Balance, debit: Balance) -> (Balance, Balance) {
+		Self::get_partial_liquidation_amounts(currency_id, collateral, debit).unwrap_or((collateral, debit))
+	}
+
+	fn get_partial_liquidation_amounts(
+		currency_id: CurrencyId,
+		collateral: Balance,
+		debit: Balance,
+	) -> Option<(Balance, Balance)> {
+		// LP collateral is removed liquidity before liquidation, always liquidate the whole cdp
+		if currency_id.is_dex_share_currency_id() {
+			return None;
+		}
+		let margin = Self::partial_liquidation_margin(currency_id)?;
+		let price = T::PriceSource::get_relative_price(currency_id, Self::stable_currency_of(currency_id))?;
+		let liquidation_ratio = Self::get_liquidation_ratio(currency_id).ok()?;
+		let penalty_factor = Self::get_liquidation_penalty(currency_id)
+			.ok()?
+			.saturating_add(Rate::one());
+		let target_ratio = liquidation_ratio.saturating_add(margin);
+
+		// liquidating debit value `d` costs `d * penalty_factor` value of collateral, so the cdp reaches
+		// target ratio when `d >= (target_ratio * debit_value - collateral_value) / (target_ratio -
+		// penalty_factor)`, it can never reach target ratio if `target_ratio <= penalty_factor`.
+		if target_ratio <= penalty_factor {
+			return None;
+		}
+		let denominator = target_ratio.saturating_sub(penalty_factor);
+		let collateral_value = price.saturating_mul_int(collateral);
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		let shortfall = target_ratio
+			.saturating_mul_int(debit_value)
+			.saturating_sub(collateral_value);
+		let liquidate_debit_value = denominator.reciprocal()?.saturating_mul_int(shortfall);
+		let liquidate_debit = Self::try_convert_to_debit_balance(currency_id, liquidate_debit_value)?;
+		let liquidate_collateral = price
+			.reciprocal()?
+			.saturating_mul_int(penalty_factor.saturating_mul_int(liquidate_debit_value));
+		if liquidate_debit.is_zero() || liquidate_debit >= debit || liquidate_collateral >= collateral {
+			return None;
+		}
+
+		// the remaining cdp must be safe and not dust
+		let remain_collateral = collateral.saturating_sub(liquidate_collateral);
+		let remain_debit = debit.saturating_sub(liquidate_debit);
+		let is_valid = matches!(
+			Self::check_cdp_status(currency_id, remain_collateral, remain_debit),
+			CDPStatus::Safe
+		) && Self::get_debit_value(currency_id, remain_debit)
+			>= Self::get_minimum_debit_value(currency_id)
+			&& remain_collateral >= T::MinimumCollateralAmount::get(&currency_id);
+		is_valid.then_some((liquidate_collateral, liquidate_debit))
 	}
 
 	pub fn handle_liquidated_collateral(
@@ -1326,7 +2299,7 @@ impl<T: Config> LiquidateCollateral<T::AccountId> for LiquidateViaDex<T> {
 			.reciprocal()
 			.unwrap_or_else(Ratio::max_value)
 			.saturating_mul_int(
-				T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), currency_id)
+				T::PriceSource::get_relative_price(Pallet::<T>::stable_currency_of(currency_id), currency_id)
 					.expect("the oracle price should be available because liquidation are triggered by it.")
 					.saturating_mul_int(target_stable_amount),
 			);
@@ -1351,6 +2324,7 @@ impl<T: Config> LiquidateCollateral<T::AccountId> for LiquidateViaDex<T> {
 		// need to be returned to the `who` from cdp treasury account.
 		if actual_target_amount > target_stable_amount {
 			<T as Config>::CDPTreasury::withdraw_surplus(
+				Pallet::<T>::stable_currency_of(currency_id),
 				who,
 				actual_target_amount.saturating_sub(target_stable_amount),
 			)?;
@@ -1379,7 +2353,7 @@ impl<T: Config> LiquidateCollateral<T::AccountId> for LiquidateViaContracts<T> {
 			.reciprocal()
 			.unwrap_or_else(Ratio::max_value)
 			.saturating_mul_int(
-				T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), currency_id)
+				T::PriceSource::get_relative_price(Pallet::<T>::stable_currency_of(currency_id), currency_id)
 					.expect("the oracle price should be available because liquidation are triggered by it.")
 					.saturating_mul_int(target_stable_amount),
 			);
@@ -1391,7 +2365,7 @@ impl<T: Config> LiquidateCollateral<T::AccountId> for LiquidateViaContracts<T> {
 		let repay_dest = Pallet::<T>::evm_address();
 		let repay_dest_account_id = Pallet::<T>::account_id();
 
-		let stable_coin = T::GetStableCurrencyId::get();
+		let stable_coin = Pallet::<T>::stable_currency_of(currency_id);
 
 		let contracts_by_priority = {
 			let now: usize = frame_system::Pallet::<T>::current_block_number()
@@ -1509,6 +2483,10 @@ impl<T: Config> LiquidateCollateral<T::AccountId> for LiquidateViaAuction<T> {
 }
 
 impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pallet<T> {
+	fn get_stable_currency_id(currency_id: CurrencyId) -> CurrencyId {
+		Self::stable_currency_of(currency_id)
+	}
+
 	fn get_debit_value(currency_id: CurrencyId, debit_balance: Balance) -> Balance {
 		Self::convert_to_debit_value(currency_id, debit_balance)
 	}
@@ -1521,8 +2499,9 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 	) -> DispatchResult {
 		if !debit_balance.is_zero() {
 			let debit_value = Self::get_debit_value(currency_id, debit_balance);
-			let feed_price = <T as Config>::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
-				.ok_or(Error::<T>::InvalidFeedPrice)?;
+			let feed_price =
+				<T as Config>::PriceSource::get_relative_price(currency_id, Self::stable_currency_of(currency_id))
+					.ok_or(Error::<T>::InvalidFeedPrice)?;
 			let collateral_ratio =
 				Self::calculate_collateral_ratio(currency_id, collateral_balance, debit_balance, feed_price);
 
@@ -1540,9 +2519,10 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 			let liquidation_ratio = Self::get_liquidation_ratio(currency_id)?;
 			ensure!(collateral_ratio >= liquidation_ratio, Error::<T>::BelowLiquidationRatio);
 
-			// check the minimum_debit_value
+			// check the minimum_debit_value, the CDP with dust debit can still be closed by paying
+			// back all debit
 			ensure!(
-				debit_value >= T::MinimumDebitValue::get(),
+				debit_value >= Self::get_minimum_debit_value(currency_id),
 				Error::<T>::RemainDebitValueTooSmall,
 			);
 		} else if !collateral_balance.is_zero() {
@@ -1557,6 +2537,13 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 	}
 
 	fn check_debit_cap(currency_id: CurrencyId, total_debit_balance: Balance) -> DispatchResult {
+		// it's only called when debit increases, so it also guards the paused debit issuance
+		ensure!(
+			!Self::paused_debit_issuance(currency_id),
+			Error::<T>::DebitIssuanceIsPaused
+		);
+		ensure!(!T::PriceStaleness::is_stale(currency_id), Error::<T>::StaleFeedPrice);
+
 		let hard_cap = Self::maximum_total_debit_value(currency_id)?;
 		let total_debit_value = Self::get_debit_value(currency_id, total_debit_balance);
 
@@ -47,6 +47,7 @@ pub const BOB: AccountId = AccountId32::new([2u8; 32]);
 pub const CAROL: AccountId = AccountId32::new([3u8; 32]);
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const KUSD: CurrencyId = CurrencyId::Token(TokenSymbol::KUSD);
 pub const BTC: CurrencyId = CurrencyId::ForeignAsset(255);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 pub const LP_AUSD_DOT: CurrencyId =
@@ -97,7 +98,7 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = ();
-	type MaxLocks = ();
+	type MaxLocks = ConstU32<2>;
 	type MaxReserves = ();
 	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Nothing;
@@ -134,6 +135,34 @@ impl orml_currencies::Config for Runtime {
 
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub const VestedCollateralCurrencyId: CurrencyId = BTC;
+	pub VestedCollateralHaircut: Ratio = Ratio::saturating_from_rational(20, 100);
+}
+
+thread_local! {
+	static VESTING_BALANCES: RefCell<Vec<(AccountId, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct MockVestingBalance;
+impl MockVestingBalance {
+	pub fn set(who: AccountId, amount: Balance) {
+		VESTING_BALANCES.with(|v| {
+			let mut balances = v.borrow_mut();
+			balances.retain(|(account, _)| *account != who);
+			balances.push((who, amount));
+		})
+	}
+}
+impl module_support::VestingBalance<AccountId, Balance> for MockVestingBalance {
+	fn vesting_balance(who: &AccountId) -> Balance {
+		VESTING_BALANCES.with(|v| {
+			v.borrow()
+				.iter()
+				.find(|(account, _)| account == who)
+				.map(|(_, amount)| *amount)
+				.unwrap_or_default()
+		})
+	}
 }
 
 impl module_loans::Config for Runtime {
@@ -143,6 +172,9 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type VestedCollateralCurrencyId = VestedCollateralCurrencyId;
+	type VestingBalance = MockVestingBalance;
+	type VestedCollateralHaircut = VestedCollateralHaircut;
 }
 
 thread_local! {
@@ -170,6 +202,7 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 			BTC => BTC_PRICE.with(|v| *v.borrow()),
 			DOT => DOT_PRICE.with(|v| *v.borrow()),
 			AUSD => Some(Price::one()),
+			KUSD => Some(Price::saturating_from_integer(2)),
 			LP_AUSD_DOT => LP_AUSD_DOT_PRICE.with(|v| *v.borrow()),
 			LP_DOT_BTC => LP_DOT_BTC_PRICE.with(|v| *v.borrow()),
 			_ => None,
@@ -177,6 +210,25 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 	}
 }
 
+thread_local! {
+	static AUSD_MARKET_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+}
+
+pub struct MockStableMarketPriceSource;
+impl MockStableMarketPriceSource {
+	pub fn set_price(price: Option<Price>) {
+		AUSD_MARKET_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+impl DataProvider<CurrencyId, Price> for MockStableMarketPriceSource {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		match *currency_id {
+			AUSD => AUSD_MARKET_PRICE.with(|v| *v.borrow()),
+			_ => None,
+		}
+	}
+}
+
 thread_local! {
 	pub static AUCTION: RefCell<Option<(AccountId, CurrencyId, Balance, Balance)>> = RefCell::new(None);
 }
@@ -220,7 +272,9 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 }
 
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(20, 100);
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD, KUSD];
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account_truncating();
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
@@ -230,15 +284,28 @@ parameter_types! {
 
 impl module_cdp_treasury::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
 	type Swap = SpecificJointsSwap<DEXModule, AlternativeSwapPathJointList>;
+	type PriceSource = MockPriceSource;
+	type TradingPathLimit = ConstU32<4>;
 	type MaxAuctionsCount = ConstU32<10_000>;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = CDPEngineModule;
+	type MaxCollateralTypes = ConstU32<10>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = ();
 	type TreasuryAccount = TreasuryAccount;
+	type IncentivesRewardsSource = TreasuryAccount;
+	type MetricsEraLength = ConstU64<100>;
+	type MaxMetricsEras = ConstU32<10>;
+	type ParamsUpdateDelay = ConstU64<0>;
 	type WeightInfo = ();
 	type StableAsset = MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber>;
 }
@@ -267,6 +334,8 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = TreasuryAccount;
+	type RuntimeCall = RuntimeCall;
 }
 
 impl pallet_timestamp::Config for Runtime {
@@ -287,12 +356,17 @@ impl module_evm_accounts::Config for Runtime {
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static SHUTDOWN_COLLATERALS: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
 }
 
 pub fn mock_shutdown() {
 	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
 }
 
+pub fn mock_collateral_shutdown(currency_id: CurrencyId) {
+	SHUTDOWN_COLLATERALS.with(|v| v.borrow_mut().push(currency_id))
+}
+
 pub fn liquidation_contract_addr() -> EvmAddress {
 	EvmAddress::from_str(&"0x1000000000000000000000000000000000000000").unwrap()
 }
@@ -302,6 +376,10 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	fn is_shutdown() -> bool {
 		IS_SHUTDOWN.with(|v| *v.borrow_mut())
 	}
+
+	fn is_collateral_shutdown(currency_id: CurrencyId) -> bool {
+		Self::is_shutdown() || SHUTDOWN_COLLATERALS.with(|v| v.borrow().contains(&currency_id))
+	}
 }
 
 thread_local! {
@@ -377,13 +455,24 @@ parameter_types! {
 	pub DefaultDebitExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(1, 10);
 	pub DefaultLiquidationPenalty: FractionalRate = FractionalRate::try_from(Rate::saturating_from_rational(10, 100)).unwrap();
 	pub MaxSwapSlippageCompareToOracle: Ratio = Ratio::saturating_from_rational(50, 100);
+	pub KeeperTipRatio: Rate = Rate::saturating_from_rational(10, 100);
 	pub MaxLiquidationContractSlippage: Ratio = Ratio::saturating_from_rational(80, 100);
 	pub const CDPEnginePalletId: PalletId = PalletId(*b"aca/cdpe");
+	pub static StaleFeedPriceCurrencies: Vec<CurrencyId> = vec![];
+}
+
+pub struct MockPriceStaleness;
+impl PriceFeedStaleness<CurrencyId> for MockPriceStaleness {
+	fn is_stale(currency_id: CurrencyId) -> bool {
+		StaleFeedPriceCurrencies::get().contains(&currency_id)
+	}
 }
 
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PriceSource = MockPriceSource;
+	type StableMarketPriceSource = MockStableMarketPriceSource;
+	type PriceStaleness = MockPriceStaleness;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
@@ -393,8 +482,14 @@ impl Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageCompareToOracle;
+	type KeeperTipRatio = KeeperTipRatio;
+	type MaxKeeperTip = ConstU128<10>;
+	type MaxLiquidationBatchSize = ConstU32<3>;
+	type MaxDebitExchangeRateHistory = ConstU32<3>;
+	type DebitExchangeRateSampleInterval = ConstU64<2>;
 	type UnsignedPriority = ConstU64<1048576>; // 1 << 20
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type KeeperRewards = ();
 	type UnixTime = Timestamp;
 	type Currency = Currencies;
 	type DEX = DEXModule;
@@ -859,6 +859,13 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 			bad_debt_value: 50,
 			target_amount: 60,
 		}));
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(
+			module_cdp_treasury::Event::LiquidationPenaltyAssessed {
+				collateral_type: BTC,
+				amount: 10,
+			},
+		));
+		assert_eq!(CDPTreasuryModule::total_assessed_liquidation_penalty(BTC), 10);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
 		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
@@ -873,6 +880,318 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 	});
 }
 
+#[test]
+fn liquidate_unsafe_cdp_with_vested_collateral() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		setup_default_collateral(AUSD);
+		MockVestingBalance::set(ALICE, 950);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 50, 0));
+		assert_ok!(CDPEngineModule::adjust_vested_collateral(&ALICE, 100));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, 500));
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 950);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 150);
+		assert_eq!(LoansModule::vested_collaterals(ALICE), 100);
+
+		// safe by raw collateral (150 / 50), unsafe after the 20% haircut on vested collateral (130 / 50)
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(14, 5))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+
+		// the vested part is still locked, only the regular collateral is liquidated for now
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 50,
+			bad_debt_value: 50,
+			target_amount: 60,
+		}));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(LoansModule::vested_collaterals(ALICE), 0);
+		assert_eq!(LoansModule::pending_confiscations(ALICE), 100);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 50);
+
+		assert_ok!(CDPEngineModule::sweep_vested_collateral(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		assert_eq!(LoansModule::pending_confiscations(ALICE), 100);
+
+		MockVestingBalance::set(ALICE, 0);
+		assert_ok!(CDPEngineModule::sweep_vested_collateral(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		System::assert_last_event(RuntimeEvent::LoansModule(module_loans::Event::VestedCollateralSwept {
+			owner: ALICE,
+			amount: 100,
+		}));
+		assert_eq!(LoansModule::pending_confiscations(ALICE), 0);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 150);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 850);
+	});
+}
+
+#[test]
+fn liquidate_by_keeper_pay_tip() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		setup_default_collateral(AUSD);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, BTC, 100, 500));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 20));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		assert_noop!(CDPEngineModule::liquidate(RuntimeOrigin::root(), BTC, ALICE), BadOrigin);
+
+		// self-liquidation is not tipped
+		assert_ok!(CDPEngineModule::liquidate(RuntimeOrigin::signed(ALICE), BTC, ALICE));
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 20);
+
+		// the tip is paid from the penalty realized by swap
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			121,
+			0,
+			false
+		));
+		assert_ok!(CDPEngineModule::liquidate(RuntimeOrigin::signed(CAROL), BTC, BOB));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::KeeperTipPaid {
+			collateral_type: BTC,
+			owner: BOB,
+			keeper: CAROL,
+			amount: 1,
+		}));
+		assert_eq!(Currencies::free_balance(AUSD, &CAROL), 10000 - 121 + 1);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 20 + 60 - 1);
+		assert_eq!(LoansModule::positions(BTC, BOB).debit, 0);
+	});
+}
+
+#[test]
+fn liquidate_many_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		setup_default_collateral(AUSD);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, BTC, 100, 200));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 20));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		assert_noop!(
+			CDPEngineModule::liquidate_many(RuntimeOrigin::none(), vec![(BTC, ALICE)]),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::liquidate_many(
+				RuntimeOrigin::signed(CAROL),
+				vec![(BTC, ALICE), (BTC, BOB), (BTC, CAROL), (DOT, ALICE)]
+			),
+			Error::<Runtime>::TooManyLiquidations
+		);
+
+		let post_info =
+			CDPEngineModule::liquidate_many(RuntimeOrigin::signed(CAROL), vec![(BTC, ALICE), (BTC, BOB)]).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as crate::WeightInfo>::liquidate_by_dex().saturating_mul(2))
+		);
+		System::assert_has_event(RuntimeEvent::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 100,
+			bad_debt_value: 50,
+			target_amount: 60,
+		}));
+		System::assert_has_event(RuntimeEvent::CDPEngineModule(crate::Event::BatchLiquidationFailed {
+			collateral_type: BTC,
+			owner: BOB,
+			error: Error::<Runtime>::MustBeUnsafe.into(),
+		}));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::BatchLiquidationCompleted {
+			succeeded: 1,
+			failed: 1,
+		}));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, BOB).debit, 200);
+		assert_eq!(LoansModule::positions(BTC, BOB).collateral, 100);
+
+		// the penalty is not realized by collateral auction, the keeper is not tipped
+		assert_eq!(Currencies::free_balance(AUSD, &CAROL), 10000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 20);
+
+		mock_shutdown();
+		assert_noop!(
+			CDPEngineModule::liquidate_many(RuntimeOrigin::signed(CAROL), vec![(BTC, BOB)]),
+			Error::<Runtime>::AlreadyShutdown
+		);
+	});
+}
+
+#[test]
+fn set_partial_liquidation_margin_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_partial_liquidation_margin(
+				RuntimeOrigin::signed(BOB),
+				BTC,
+				Some(Ratio::saturating_from_rational(1, 10))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_partial_liquidation_margin(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(Ratio::saturating_from_rational(1, 10))
+			),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_partial_liquidation_margin(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 10))
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(
+			crate::Event::PartialLiquidationMarginUpdated {
+				collateral_type: BTC,
+				new_margin: Some(Ratio::saturating_from_rational(1, 10)),
+			},
+		));
+		assert_eq!(
+			CDPEngineModule::partial_liquidation_margin(BTC),
+			Some(Ratio::saturating_from_rational(1, 10))
+		);
+	});
+}
+
+#[test]
+fn partial_liquidate_unsafe_cdp_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		setup_default_collateral(AUSD);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(21, 10))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::set_partial_liquidation_margin(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 10))
+		));
+
+		// liquidate debit value 10 with 12 collateral, the ratio of the remaining cdp is 88 / 40 = 2.2
+		assert_eq!(CDPEngineModule::get_liquidation_amounts(BTC, 100, 500), (12, 100));
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 12,
+			bad_debt_value: 10,
+			target_amount: 12,
+		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 10);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 400);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 88);
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::MustBeUnsafe,
+		);
+
+		// target ratio cannot be reached, liquidate the whole cdp
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(1, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_eq!(CDPEngineModule::get_liquidation_amounts(BTC, 88, 400), (88, 400));
+	});
+}
+
 #[test]
 fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1446,6 +1765,65 @@ fn accumulate_interest_work() {
 	});
 }
 
+#[test]
+fn debit_exchange_rate_history_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(CDPEngineModule::projected_interest(BTC, 10000, 0), Ok(0));
+		assert_eq!(CDPEngineModule::projected_interest(BTC, 10000, 1), Ok(10));
+		assert_eq!(CDPEngineModule::projected_interest(BTC, 10000, 2), Ok(20));
+		assert_err!(
+			CDPEngineModule::projected_interest(DOT, 10000, 1),
+			Error::<Runtime>::InvalidCollateralType
+		);
+
+		// no history without debit
+		CDPEngineModule::accumulate_interest(1, 0);
+		assert!(CDPEngineModule::debit_exchange_rate_history(BTC).is_empty());
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 300));
+
+		let history_secs = || -> Vec<u64> {
+			CDPEngineModule::debit_exchange_rate_history(BTC)
+				.into_iter()
+				.map(|(secs, _)| secs)
+				.collect()
+		};
+
+		CDPEngineModule::accumulate_interest(2, 1);
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate_history(BTC).into_inner(),
+			vec![(2, ExchangeRate::saturating_from_rational(101, 1000))]
+		);
+
+		// skip the sample within the sample interval
+		CDPEngineModule::accumulate_interest(3, 2);
+		assert_eq!(history_secs(), vec![2]);
+
+		CDPEngineModule::accumulate_interest(4, 3);
+		assert_eq!(history_secs(), vec![2, 4]);
+		CDPEngineModule::accumulate_interest(6, 4);
+		assert_eq!(history_secs(), vec![2, 4, 6]);
+
+		// drop the oldest sample when it's full
+		CDPEngineModule::accumulate_interest(8, 6);
+		assert_eq!(history_secs(), vec![4, 6, 8]);
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate_history(BTC)
+				.last()
+				.map(|(_, rate)| *rate),
+			CDPEngineModule::debit_exchange_rate(BTC)
+		);
+	});
+}
+
 #[test]
 fn settle_cdp_has_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1475,8 +1853,12 @@ fn settle_cdp_has_debit_work() {
 		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::SettleCDPInDebit {
 			collateral_type: BTC,
 			owner: ALICE,
+			confiscated_collateral_amount: 50,
+			refunded_collateral_amount: 50,
 		}));
 		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 950);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
 		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 50);
 
@@ -1488,7 +1870,39 @@ fn settle_cdp_has_debit_work() {
 }
 
 #[test]
-fn close_cdp_has_debit_by_dex_work() {
+fn settle_cdp_after_collateral_shutdown_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+
+		mock_collateral_shutdown(BTC);
+		assert!(!<Runtime as Config>::EmergencyShutdown::is_shutdown());
+		assert_noop!(
+			CDPEngineModule::liquidate(RuntimeOrigin::none(), BTC, ALICE),
+			Error::<Runtime>::AlreadyShutdown
+		);
+		assert_noop!(
+			CDPEngineModule::settle(RuntimeOrigin::none(), DOT, ALICE),
+			Error::<Runtime>::MustAfterShutdown
+		);
+		assert_ok!(CDPEngineModule::settle(RuntimeOrigin::none(), BTC, ALICE));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 50);
+	});
+}
+
+#[test]
+fn close_cdp_has_debit_by_dex_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		System::set_block_number(1);
 		assert_ok!(DEXModule::add_liquidity(
@@ -1526,8 +1940,8 @@ fn close_cdp_has_debit_by_dex_work() {
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
 		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 500);
 		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
-		assert_eq!(CDPTreasuryModule::get_surplus_pool(), 0);
-		assert_eq!(CDPTreasuryModule::get_debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(AUSD), 0);
+		assert_eq!(CDPTreasuryModule::get_debit_pool(AUSD), 0);
 
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
@@ -1574,8 +1988,8 @@ fn close_cdp_has_debit_by_dex_work() {
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
 		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
 		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
-		assert_eq!(CDPTreasuryModule::get_surplus_pool(), 50);
-		assert_eq!(CDPTreasuryModule::get_debit_pool(), 50);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(AUSD), 50);
+		assert_eq!(CDPTreasuryModule::get_debit_pool(AUSD), 50);
 	});
 }
 
@@ -1618,8 +2032,8 @@ fn close_cdp_has_debit_by_swap_on_alternative_path() {
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
 		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 500);
 		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
-		assert_eq!(CDPTreasuryModule::get_surplus_pool(), 0);
-		assert_eq!(CDPTreasuryModule::get_debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(AUSD), 0);
+		assert_eq!(CDPTreasuryModule::get_debit_pool(AUSD), 0);
 
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
@@ -1644,8 +2058,8 @@ fn close_cdp_has_debit_by_swap_on_alternative_path() {
 		assert_eq!(Currencies::free_balance(BTC, &ALICE), 994);
 		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
 		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
-		assert_eq!(CDPTreasuryModule::get_surplus_pool(), 50);
-		assert_eq!(CDPTreasuryModule::get_debit_pool(), 50);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(AUSD), 50);
+		assert_eq!(CDPTreasuryModule::get_debit_pool(AUSD), 50);
 	});
 }
 
@@ -1814,6 +2228,76 @@ fn offchain_worker_iteration_limit_works() {
 	});
 }
 
+#[test]
+fn offchain_worker_liquidates_riskiest_cdp_first() {
+	let (mut offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		// sets max liquidations per run to 1
+		offchain.local_storage_set(
+			StorageKind::PERSISTENT,
+			OFFCHAIN_WORKER_MAX_LIQUIDATIONS,
+			&1u32.encode(),
+		);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, BTC, 90, 500));
+		assert_ok!(CDPEngineModule::adjust_position(&CAROL, BTC, 140, 500));
+		// make all positions unsafe
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		// liquidate in the order of collateral ratio, one per run
+		for (block, owner) in [(2, BOB), (3, ALICE), (4, CAROL)] {
+			run_to_block_offchain(block);
+			let tx = pool_state.write().transactions.pop().unwrap();
+			assert!(pool_state.write().transactions.pop().is_none());
+			let tx = Extrinsic::decode(&mut &*tx).unwrap();
+			if let MockCall::CDPEngineModule(crate::Call::liquidate {
+				currency_id: currency_call,
+				who: who_call,
+			}) = tx.call
+			{
+				assert_eq!(currency_call, BTC);
+				assert_eq!(who_call, owner);
+				assert_ok!(CDPEngineModule::liquidate(
+					RuntimeOrigin::none(),
+					currency_call,
+					who_call
+				));
+			} else {
+				panic!("unexpected call");
+			}
+			assert_eq!(LoansModule::positions(BTC, owner).debit, 0);
+		}
+
+		run_to_block_offchain(5);
+		assert!(pool_state.write().transactions.pop().is_none());
+	});
+}
+
 #[test]
 fn offchain_default_max_iterator_works() {
 	let (mut offchain, _offchain_state) = testing::TestOffchainExt::new();
@@ -1865,6 +2349,358 @@ fn offchain_default_max_iterator_works() {
 	});
 }
 
+#[test]
+fn add_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::add_collateral(
+				RuntimeOrigin::signed(BOB),
+				BTC,
+				Rate::saturating_from_rational(1, 100000),
+				Ratio::saturating_from_rational(3, 2),
+				Rate::saturating_from_rational(2, 10),
+				Ratio::saturating_from_rational(9, 5),
+				10000,
+				100,
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::add_collateral(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Rate::saturating_from_rational(1, 100000),
+				Ratio::saturating_from_rational(3, 2),
+				Rate::saturating_from_rational(2, 10),
+				Ratio::saturating_from_rational(5, 4),
+				10000,
+				100,
+			),
+			Error::<Runtime>::InvalidCollateralParams
+		);
+		assert_noop!(
+			CDPEngineModule::add_collateral(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Rate::saturating_from_rational(2, 1),
+				Ratio::saturating_from_rational(3, 2),
+				Rate::saturating_from_rational(2, 10),
+				Ratio::saturating_from_rational(9, 5),
+				10000,
+				100,
+			),
+			Error::<Runtime>::InvalidRate
+		);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(BTC), 0);
+
+		assert_ok!(CDPEngineModule::add_collateral(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Rate::saturating_from_rational(1, 100000),
+			Ratio::saturating_from_rational(3, 2),
+			Rate::saturating_from_rational(2, 10),
+			Ratio::saturating_from_rational(9, 5),
+			10000,
+			100,
+		));
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(
+			module_cdp_treasury::Event::ExpectedCollateralAuctionSizeUpdated {
+				collateral_type: BTC,
+				new_size: 100,
+			},
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::CollateralAdded {
+			collateral_type: BTC,
+		}));
+		let params = CDPEngineModule::collateral_params(BTC).unwrap();
+		assert_eq!(
+			params.interest_rate_per_sec.map(|v| v.into_inner()),
+			Some(Rate::saturating_from_rational(1, 100000))
+		);
+		assert_eq!(params.liquidation_ratio, Some(Ratio::saturating_from_rational(3, 2)));
+		assert_eq!(
+			params.liquidation_penalty.map(|v| v.into_inner()),
+			Some(Rate::saturating_from_rational(2, 10))
+		);
+		assert_eq!(
+			params.required_collateral_ratio,
+			Some(Ratio::saturating_from_rational(9, 5))
+		);
+		assert_eq!(params.maximum_total_debit_value, 10000);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(BTC), 100);
+
+		assert_noop!(
+			CDPEngineModule::add_collateral(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Rate::saturating_from_rational(1, 100000),
+				Ratio::saturating_from_rational(3, 2),
+				Rate::saturating_from_rational(2, 10),
+				Ratio::saturating_from_rational(9, 5),
+				10000,
+				100,
+			),
+			Error::<Runtime>::CollateralAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn set_collateral_stable_currency_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(CDPEngineModule::stable_currency_of(BTC), AUSD);
+
+		assert_noop!(
+			CDPEngineModule::set_collateral_stable_currency(RuntimeOrigin::signed(BOB), BTC, KUSD),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_collateral_stable_currency(RuntimeOrigin::signed(ALICE), DOT, KUSD),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::set_collateral_stable_currency(RuntimeOrigin::signed(ALICE), BTC, DOT),
+			Error::<Runtime>::InvalidStableCurrency
+		);
+		assert_ok!(CDPEngineModule::set_collateral_stable_currency(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			KUSD
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(
+			crate::Event::CollateralStableCurrencyUpdated {
+				collateral_type: BTC,
+				stable_currency_id: KUSD,
+			},
+		));
+		assert_eq!(CDPEngineModule::stable_currency_of(BTC), KUSD);
+		assert_eq!(CDPTreasuryModule::get_stable_currency_id(BTC), KUSD);
+
+		// the debit is issued in KUSD, and valued by the price of BTC in KUSD
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, 100, 300),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 200));
+		assert_eq!(Currencies::free_balance(KUSD, &ALICE), 20);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 0);
+		assert_noop!(
+			CDPEngineModule::set_collateral_stable_currency(RuntimeOrigin::signed(ALICE), BTC, AUSD),
+			Error::<Runtime>::CollateralInUse
+		);
+
+		// the bad debt goes to the debit pool of KUSD
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		assert_eq!(CDPTreasuryModule::get_debit_pool(KUSD), 20);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+
+		// LP token collaterals and their components only issue the default stable currency
+		assert_noop!(
+			CDPEngineModule::set_collateral_params(
+				RuntimeOrigin::signed(ALICE),
+				LP_DOT_BTC,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(10000),
+			),
+			Error::<Runtime>::StableCurrencyConflict
+		);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			LP_AUSD_DOT,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::set_collateral_stable_currency(RuntimeOrigin::signed(ALICE), LP_AUSD_DOT, KUSD),
+			Error::<Runtime>::StableCurrencyConflict
+		);
+		assert_noop!(
+			CDPEngineModule::set_collateral_stable_currency(RuntimeOrigin::signed(ALICE), DOT, KUSD),
+			Error::<Runtime>::StableCurrencyConflict
+		);
+	});
+}
+
+#[test]
+fn set_debit_issuance_paused_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+
+		assert_noop!(
+			CDPEngineModule::set_debit_issuance_paused(RuntimeOrigin::signed(BOB), BTC, true),
+			BadOrigin
+		);
+		assert_ok!(CDPEngineModule::set_debit_issuance_paused(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			true
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::DebitIssuancePaused {
+			collateral_type: BTC,
+		}));
+		assert!(CDPEngineModule::paused_debit_issuance(BTC));
+
+		// cannot borrow more
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, 100, 100),
+			Error::<Runtime>::DebitIssuanceIsPaused
+		);
+		assert_noop!(
+			CDPEngineModule::adjust_position(&BOB, BTC, 100, 100),
+			Error::<Runtime>::DebitIssuanceIsPaused
+		);
+		// can deposit collateral and repay
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 10, 0));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, -100));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 400);
+
+		// can liquidate
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+
+		assert_ok!(CDPEngineModule::set_debit_issuance_paused(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			false
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::DebitIssuanceUnpaused {
+			collateral_type: BTC,
+		}));
+		assert!(!CDPEngineModule::paused_debit_issuance(BTC));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, BTC, 100, 100));
+	});
+}
+
+#[test]
+fn set_minimum_debit_value_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_minimum_debit_value(RuntimeOrigin::signed(BOB), BTC, Some(100)),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_minimum_debit_value(RuntimeOrigin::signed(ALICE), BTC, Some(100)),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		setup_default_collateral(BTC);
+		assert_eq!(CDPEngineModule::get_minimum_debit_value(BTC), 2);
+		assert_ok!(CDPEngineModule::set_minimum_debit_value(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(100)
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::MinimumDebitValueUpdated {
+			collateral_type: BTC,
+			new_minimum_debit_value: Some(100),
+		}));
+		assert_eq!(CDPEngineModule::get_minimum_debit_value(BTC), 100);
+		assert_eq!(CDPEngineModule::get_minimum_debit_value(DOT), 2);
+
+		assert_ok!(CDPEngineModule::set_minimum_debit_value(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			None
+		));
+		assert_eq!(CDPEngineModule::get_minimum_debit_value(BTC), 2);
+	});
+}
+
+#[test]
+fn per_collateral_minimum_debit_value_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_ok!(CDPEngineModule::set_minimum_debit_value(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(100)
+		));
+
+		// cannot open or shrink to the position below the minimum debit value
+		assert_noop!(
+			CDPEngineModule::check_position_valid(BTC, 1000, 500, true),
+			Error::<Runtime>::RemainDebitValueTooSmall,
+		);
+		assert_ok!(CDPEngineModule::check_position_valid(BTC, 1000, 1000, true));
+		assert_noop!(
+			CDPEngineModule::adjust_position(&BOB, BTC, 100, 500),
+			Error::<Runtime>::RemainDebitValueTooSmall,
+		);
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, 0, -100),
+			Error::<Runtime>::RemainDebitValueTooSmall,
+		);
+
+		// the position below the minimum debit value can be closed
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, -100, -500));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
+
 #[test]
 fn minimal_collateral_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -2032,3 +2868,282 @@ fn liquidation_fails_if_insufficient_repayment() {
 		);
 	});
 }
+
+#[test]
+fn set_stability_fee_controller_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = StabilityFeeControllerParams {
+			min_rate: Rate::saturating_from_rational(1, 1000000),
+			max_rate: Rate::saturating_from_rational(1, 10000),
+			proportional_gain: Rate::saturating_from_rational(1, 10000),
+			integral_gain: Rate::saturating_from_rational(1, 100000),
+		};
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_controller(RuntimeOrigin::signed(BOB), BTC, Some(params)),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_controller(RuntimeOrigin::signed(ALICE), BTC, Some(params)),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_controller(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(StabilityFeeControllerParams {
+					min_rate: Rate::saturating_from_rational(1, 1000),
+					..params
+				})
+			),
+			Error::<Runtime>::InvalidRate
+		);
+		assert_ok!(CDPEngineModule::set_stability_fee_controller(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(params)
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(
+			crate::Event::StabilityFeeControllerUpdated {
+				collateral_type: BTC,
+				params: Some(params),
+			},
+		));
+		assert_eq!(CDPEngineModule::stability_fee_controllers(BTC), Some(params));
+
+		assert_ok!(CDPEngineModule::set_stability_fee_adjust_interval(
+			RuntimeOrigin::signed(ALICE),
+			10
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(
+			crate::Event::StabilityFeeAdjustIntervalUpdated { interval: 10 },
+		));
+		assert_eq!(CDPEngineModule::stability_fee_adjust_interval(), 10);
+	});
+}
+
+#[test]
+fn adjust_stability_fees_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_stability_fee_controller(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(StabilityFeeControllerParams {
+				min_rate: Rate::saturating_from_rational(1, 1000000),
+				max_rate: Rate::saturating_from_rational(1, 10000),
+				proportional_gain: Rate::saturating_from_rational(1, 10000),
+				integral_gain: Rate::saturating_from_rational(1, 100000),
+			})
+		));
+		assert_ok!(CDPEngineModule::set_stability_fee_adjust_interval(
+			RuntimeOrigin::signed(ALICE),
+			10
+		));
+
+		// market price below peg, raise the stability fee
+		MockStableMarketPriceSource::set_price(Some(Price::saturating_from_rational(9, 10)));
+		CDPEngineModule::on_initialize(9);
+		assert_eq!(
+			CDPEngineModule::get_interest_rate_per_sec(BTC),
+			Ok(Rate::saturating_from_rational(1, 100000))
+		);
+		CDPEngineModule::on_initialize(10);
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::StabilityFeeAdjusted {
+			collateral_type: BTC,
+			peg_deviation: FixedI128::saturating_from_rational(1, 10),
+			new_interest_rate_per_sec: Rate::saturating_from_rational(21, 1000000),
+		}));
+		assert_eq!(
+			CDPEngineModule::get_interest_rate_per_sec(BTC),
+			Ok(Rate::saturating_from_rational(21, 1000000))
+		);
+		assert_eq!(
+			CDPEngineModule::last_peg_deviation(),
+			FixedI128::saturating_from_rational(1, 10)
+		);
+
+		// market price back to peg, the proportional part is reverted
+		MockStableMarketPriceSource::set_price(Some(Price::one()));
+		CDPEngineModule::on_initialize(20);
+		assert_eq!(
+			CDPEngineModule::get_interest_rate_per_sec(BTC),
+			Ok(Rate::saturating_from_rational(11, 1000000))
+		);
+
+		// market price above peg, the rate is bounded by the min rate
+		MockStableMarketPriceSource::set_price(Some(Price::saturating_from_rational(3, 2)));
+		CDPEngineModule::on_initialize(30);
+		assert_eq!(
+			CDPEngineModule::get_interest_rate_per_sec(BTC),
+			Ok(Rate::saturating_from_rational(1, 1000000))
+		);
+
+		// no market price, not adjust
+		MockStableMarketPriceSource::set_price(None);
+		CDPEngineModule::on_initialize(40);
+		assert_eq!(
+			CDPEngineModule::get_interest_rate_per_sec(BTC),
+			Ok(Rate::saturating_from_rational(1, 1000000))
+		);
+	});
+}
+
+#[test]
+fn raise_liquidation_ratio_with_grace_period_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+
+		assert_noop!(
+			CDPEngineModule::set_liquidation_ratio_grace_period(RuntimeOrigin::signed(BOB), 10),
+			BadOrigin
+		);
+		assert_ok!(CDPEngineModule::set_liquidation_ratio_grace_period(
+			RuntimeOrigin::signed(ALICE),
+			10
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(
+			crate::Event::LiquidationRatioGracePeriodUpdated { grace_period: 10 },
+		));
+		assert_eq!(CDPEngineModule::liquidation_ratio_grace_period(), 10);
+
+		// raising the liquidation ratio is queued
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(
+			crate::Event::LiquidationRatioUpdateScheduled {
+				collateral_type: BTC,
+				new_liquidation_ratio: Ratio::saturating_from_rational(3, 1),
+				activate_at: 11,
+			},
+		));
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ok(Ratio::saturating_from_rational(3, 2))
+		);
+		assert_eq!(
+			CDPEngineModule::pending_liquidation_ratios(BTC),
+			Some((Ratio::saturating_from_rational(3, 1), 11))
+		);
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::MustBeUnsafe,
+		);
+
+		CDPEngineModule::on_initialize(10);
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ok(Ratio::saturating_from_rational(3, 2))
+		);
+
+		CDPEngineModule::on_initialize(11);
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::LiquidationRatioUpdated {
+			collateral_type: BTC,
+			new_liquidation_ratio: Some(Ratio::saturating_from_rational(3, 1)),
+		}));
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ok(Ratio::saturating_from_rational(3, 1))
+		);
+		assert_eq!(CDPEngineModule::pending_liquidation_ratios(BTC), None);
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+
+		// lowering the liquidation ratio applies immediately and drops the queued one
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(4, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert!(CDPEngineModule::pending_liquidation_ratios(BTC).is_some());
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		System::assert_last_event(RuntimeEvent::CDPEngineModule(crate::Event::LiquidationRatioUpdated {
+			collateral_type: BTC,
+			new_liquidation_ratio: Some(Ratio::saturating_from_rational(2, 1)),
+		}));
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ok(Ratio::saturating_from_rational(2, 1))
+		);
+		assert_eq!(CDPEngineModule::pending_liquidation_ratios(BTC), None);
+	});
+}
+
+#[test]
+fn stale_feed_price_blocks_debit_issuance() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+
+		StaleFeedPriceCurrencies::set(vec![BTC]);
+		assert_noop!(
+			CDPEngineModule::check_debit_cap(BTC, 1000),
+			Error::<Runtime>::StaleFeedPrice
+		);
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, 100, 100),
+			Error::<Runtime>::StaleFeedPrice
+		);
+		// can still deposit collateral and repay
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 10, 0));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, -100));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 400);
+
+		StaleFeedPriceCurrencies::set(vec![]);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, 100));
+	});
+}
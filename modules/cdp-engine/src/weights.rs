@@ -54,6 +54,9 @@ pub trait WeightInfo {
 	fn settle() -> Weight;
 	fn register_liquidation_contract() -> Weight;
 	fn deregister_liquidation_contract() -> Weight;
+	fn adjust_stability_fees(c: u32) -> Weight;
+	fn sweep_vested_collateral() -> Weight;
+	fn set_collateral_stable_currency() -> Weight;
 }
 
 /// Weights for module_cdp_engine using the Acala node and recommended hardware.
@@ -95,6 +98,22 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(11 as u64))
 			.saturating_add(T::DbWeight::get().writes(8 as u64))
 	}
+	fn adjust_stability_fees(c: u32, ) -> Weight {
+		Weight::from_parts(6000000, 0)
+			.saturating_add(Weight::from_parts(14000000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn sweep_vested_collateral() -> Weight {
+		Weight::from_parts(61_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	fn set_collateral_stable_currency() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -136,4 +155,20 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(11 as u64))
 			.saturating_add(RocksDbWeight::get().writes(8 as u64))
 	}
+	fn adjust_stability_fees(c: u32, ) -> Weight {
+		Weight::from_parts(6000000, 0)
+			.saturating_add(Weight::from_parts(14000000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn sweep_vested_collateral() -> Weight {
+		Weight::from_parts(61_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn set_collateral_stable_currency() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
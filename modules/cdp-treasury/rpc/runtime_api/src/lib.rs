@@ -0,0 +1,56 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use sp_runtime::{
+	codec::Codec,
+	traits::{MaybeDisplay, MaybeFromStr},
+};
+
+sp_api::decl_runtime_apis! {
+	pub trait CdpTreasuryApi<CurrencyId, Balance, Metrics> where
+		CurrencyId: Codec,
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+		Metrics: Codec,
+	{
+		/// Get the surplus pool of cdp treasury.
+		fn get_surplus_pool() -> Balance;
+
+		/// Get the debit pool of cdp treasury.
+		fn get_debit_pool() -> Balance;
+
+		/// Get the total collateral amount of specific collateral type held by cdp treasury,
+		/// including the collateral deployed into DEX liquidity.
+		fn get_total_collaterals(currency_id: CurrencyId) -> Balance;
+
+		/// Get the count of collateral auctions will be created if auction all the
+		/// collateral of specific collateral type not in auction.
+		fn get_projected_collateral_auctions_count(currency_id: CurrencyId) -> u32;
+
+		/// Get the cumulative accounting metrics of cdp treasury.
+		fn get_cumulative_metrics() -> Metrics;
+
+		/// Get the accounting metrics of specific era, return None if the era has been pruned.
+		fn get_era_metrics(era: u32) -> Option<Metrics>;
+
+		/// Get the total amount of specific collateral type liquidated by cdp treasury.
+		fn get_total_collateral_liquidated(currency_id: CurrencyId) -> Balance;
+	}
+}
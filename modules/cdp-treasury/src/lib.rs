@@ -29,16 +29,25 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::needless_range_loop)]
 
-use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	pallet_prelude::*,
+	transactional, PalletId,
+};
 use frame_system::pallet_prelude::*;
-use module_support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Ratio, Swap, SwapLimit};
+use module_support::{
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, KeeperAction, KeeperRewards, PriceProvider, Rate,
+	Ratio, RiskManager, Swap, SwapLimit,
+};
 use nutsfinance_stable_asset::traits::StableAsset;
 use nutsfinance_stable_asset::RedeemProportionResult;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
-use primitives::{Balance, CurrencyId};
+use primitives::{Balance, CurrencyId, TradingPair};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Zero},
-	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber,
+	traits::{AccountIdConversion, Dispatchable, One, SaturatedConversion, Saturating, StaticLookup, Zero},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, Permill,
 };
 use sp_std::prelude::*;
 
@@ -49,6 +58,115 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The distribution of surplus that exceeds the surplus buffer when settle,
+/// the remainder is retained in surplus pool.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct SurplusDistribution {
+	/// The proportion used to buy back native currency on DEX and burn it.
+	pub buyback: Permill,
+	/// The proportion transferred to the rewards source of incentives.
+	pub incentives: Permill,
+}
+
+impl Default for SurplusDistribution {
+	fn default() -> Self {
+		Self {
+			buyback: Permill::one(),
+			incentives: Permill::zero(),
+		}
+	}
+}
+
+/// The dynamic size policy of surplus settlement, the size is the proportion
+/// of surplus exceeding the surplus buffer, bounded by min and max size.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct SurplusSettlementSizePolicy {
+	/// The proportion of surplus exceeding the surplus buffer.
+	pub proportion: Permill,
+	/// The minimum size of per settlement.
+	pub min_size: Balance,
+	/// The maximum size of per settlement.
+	pub max_size: Balance,
+}
+
+impl SurplusSettlementSizePolicy {
+	/// Calculate the settlement size for `excess_surplus`, it never exceeds
+	/// `excess_surplus`.
+	pub fn settlement_size(&self, excess_surplus: Balance) -> Balance {
+		self.proportion
+			.mul_floor(excess_surplus)
+			.max(self.min_size)
+			.min(self.max_size)
+			.min(excess_surplus)
+	}
+}
+
+/// The accounting metrics of cdp treasury.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TreasuryMetrics {
+	/// The stable amount issued as debit.
+	pub issued_debit: Balance,
+	/// The stable amount burned to offset debit.
+	pub burned_debit: Balance,
+	/// The surplus amount settled via DEX.
+	pub settled_surplus: Balance,
+	/// The debit amount settled via DEX.
+	pub settled_debit: Balance,
+	/// The native amount bought back by surplus and burned.
+	pub burned_native: Balance,
+}
+
+/// The max collateral amount of specific collateral type that can be swapped
+/// to stable via DEX, unlimited if None.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
+pub struct CollateralSwapBudget {
+	/// The budget of per block.
+	pub per_block: Option<Balance>,
+	/// The budget of per metrics era.
+	pub per_era: Option<Balance>,
+}
+
+/// The collateral amount swapped to stable via DEX in the recorded block and
+/// metrics era.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
+pub struct CollateralSwapUsage<BlockNumber> {
+	/// The recorded block.
+	pub block: BlockNumber,
+	/// The collateral amount swapped in the recorded block.
+	pub block_swapped: Balance,
+	/// The recorded metrics era.
+	pub era: u32,
+	/// The collateral amount swapped in the recorded metrics era.
+	pub era_swapped: Balance,
+}
+
+/// The parameter of cdp treasury whose update by `UpdateOrigin` is delayed.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum TreasuryParam {
+	/// The expected collateral auction size of specific collateral type.
+	ExpectedCollateralAuctionSize(CurrencyId),
+	/// The debit offset buffer.
+	DebitOffsetBuffer,
+	/// The surplus buffer size.
+	SurplusBufferSize,
+	/// The expected collateral auction value in stable currency of specific
+	/// collateral type.
+	ExpectedCollateralAuctionValue(CurrencyId),
+	/// The expected collateral auction value of the collateral types issuing
+	/// specific stable currency.
+	ExpectedStableAuctionValue(CurrencyId),
+}
+
+/// The kind of settlement via `trigger_settlement`.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum SettlementKind {
+	/// Settle the surplus exceeding the surplus buffer.
+	Surplus,
+	/// Settle the debit exceeding the debit offset buffer.
+	Debit,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -57,6 +175,11 @@ pub mod module {
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// The aggregated call type, dispatched by the receiver of flash loan.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
+
 		/// The origin which may update parameters and handle
 		/// surplus/collateral.
 		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
@@ -64,10 +187,27 @@ pub mod module {
 		/// The Currency for managing assets related to CDP
 		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
-		/// Stablecoin currency id
+		/// Stablecoin currency id, the primary stable currency which the
+		/// stability reserve, the settlement via DEX and the flash loan work
+		/// for.
 		#[pallet::constant]
 		type GetStableCurrencyId: Get<CurrencyId>;
 
+		/// The stable currencies that can be issued by cdp treasury, each of
+		/// them has its own surplus pool and debit pool. It must contain
+		/// `GetStableCurrencyId`.
+		#[pallet::constant]
+		type StableCurrencyIds: Get<Vec<CurrencyId>>;
+
+		/// The risk manager of CDPs, which provides the stable currency issued
+		/// against specific collateral type.
+		type RiskManager: RiskManager<Self::AccountId, CurrencyId, Balance, Balance>;
+
+		/// Native currency id, bought back and burned by surplus or sold to
+		/// cover debit when settling via DEX.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
 		/// Auction manager creates auction to handle system surplus and debit
 		type AuctionManagerHandler: AuctionManager<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
@@ -77,6 +217,13 @@ pub mod module {
 		/// Swap
 		type Swap: Swap<Self::AccountId, Balance, CurrencyId>;
 
+		/// The price source to provider external market price.
+		type PriceSource: PriceProvider<CurrencyId>;
+
+		/// The limit for length of specified swap path of collateral
+		#[pallet::constant]
+		type TradingPathLimit: Get<u32>;
+
 		type StableAsset: StableAsset<
 			AssetId = CurrencyId,
 			AtLeast64BitUnsigned = Balance,
@@ -91,14 +238,46 @@ pub mod module {
 		#[pallet::constant]
 		type MaxAuctionsCount: Get<u32>;
 
+		/// The default acceptable max slippage compared to the oracle price
+		/// when cdp treasury swaps via DEX, used if `MaxSwapSlippage` of the
+		/// currency is not set.
+		#[pallet::constant]
+		type DefaultMaxSwapSlippage: Get<Ratio>;
+
+		/// The max count of collateral types updated in one batch.
+		#[pallet::constant]
+		type MaxCollateralTypes: Get<u32>;
+
 		#[pallet::constant]
 		type TreasuryAccount: Get<Self::AccountId>;
 
+		/// The rewards source of incentives, receives the incentives part of
+		/// surplus distribution.
+		#[pallet::constant]
+		type IncentivesRewardsSource: Get<Self::AccountId>;
+
+		/// The block length of an era of metrics.
+		#[pallet::constant]
+		type MetricsEraLength: Get<BlockNumberFor<Self>>;
+
+		/// The max count of recent eras whose metrics are kept.
+		#[pallet::constant]
+		type MaxMetricsEras: Get<u32>;
+
+		/// The delay before the update of collateral auction sizes and buffers
+		/// by `UpdateOrigin` takes effect, the update is immediate if zero.
+		#[pallet::constant]
+		type ParamsUpdateDelay: Get<BlockNumberFor<Self>>;
+
 		/// The CDP treasury's module id, keep surplus and collateral assets
 		/// from liquidation.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// The keeper reward program, rewards the account who triggers the
+		/// settlement.
+		type KeeperRewards: KeeperRewards<Self::AccountId>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -115,6 +294,42 @@ pub mod module {
 		CannotSwap,
 		/// The currency id is not DexShare type
 		NotDexShare,
+		/// The DEX settlement is disabled
+		SettlementDisabled,
+		/// The feed price is invalid
+		InvalidFeedPrice,
+		/// The swap price deviates from the oracle price by more than max swap slippage
+		ExceedMaxSwapSlippage,
+		/// The swap path is invalid
+		InvalidSwapPath,
+		/// The sum of surplus distribution proportions exceeds 100%
+		InvalidSurplusDistribution,
+		/// The collateral not in auction is zero or exceeds the dust threshold
+		CollateralNotDust,
+		/// The size policy of surplus settlement is invalid
+		InvalidSettlementSizePolicy,
+		/// The flash loan is disabled
+		FlashLoanDisabled,
+		/// The currency is not supported by flash loan
+		FlashLoanCurrencyNotSupported,
+		/// The flash loan and fee are not repaid at the end of the call
+		FlashLoanNotRepaid,
+		/// The debit pool is empty, nothing to repay
+		NoDebitToRepay,
+		/// The deployed liquidity exceeds the cap of liquidity deployment
+		ExceedLiquidityDeploymentCap,
+		/// The deployed liquidity share is not enough
+		DeployedLiquidityNotEnough,
+		/// The currency cannot be paired with stable currency
+		InvalidLiquidityPair,
+		/// The balance of cdp treasury after deposit is below existential deposit
+		BelowExistentialDeposit,
+		/// The net issuance of stable currency exceeds the issuance limits
+		IssuanceRateExceeded,
+		/// The collateral swapped via DEX exceeds the swap budget
+		ExceedCollateralSwapBudget,
+		/// The stable currency is not supported by cdp treasury
+		StableCurrencyNotSupported,
 	}
 
 	#[pallet::event]
@@ -128,6 +343,146 @@ pub mod module {
 		},
 		/// The buffer amount of debit pool that will not be offset by suplus pool updated.
 		DebitOffsetBufferUpdated { amount: Balance },
+		/// The expected stable value for per lot collateral auction of specific collateral type
+		/// updated.
+		ExpectedCollateralAuctionValueUpdated {
+			collateral_type: CurrencyId,
+			new_value: Balance,
+		},
+		/// The expected value for per lot collateral auction of the collateral types issuing
+		/// specific stable currency updated.
+		ExpectedStableAuctionValueUpdated {
+			stable_currency_id: CurrencyId,
+			new_value: Balance,
+		},
+		/// A lot of collateral auction created.
+		CollateralAuctionLotCreated {
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			target: Balance,
+		},
+		/// Collateral auctions for liquidated collateral created.
+		CollateralAuctionsCreated {
+			collateral_type: CurrencyId,
+			lots_count: u32,
+			collateral_amount: Balance,
+			target: Balance,
+		},
+		/// The buffer amount of surplus pool that will not be settled via DEX updated.
+		SurplusBufferSizeUpdated { amount: Balance },
+		/// The cap of stable amount settled via DEX per settlement updated.
+		DexSettlementCapUpdated { amount: Balance },
+		/// Surplus bought back native currency on DEX and burned it.
+		SurplusSettledViaDex {
+			surplus_amount: Balance,
+			burned_native_amount: Balance,
+		},
+		/// Native currency of treasury sold on DEX to cover debit.
+		DebitSettledViaDex {
+			sold_native_amount: Balance,
+			debit_amount: Balance,
+		},
+		/// The max slippage compared to the oracle price when swap collateral to stable updated.
+		MaxSwapSlippageUpdated {
+			collateral_type: CurrencyId,
+			max_slippage: Option<Ratio>,
+		},
+		/// The specified swap path when swap collateral to stable updated.
+		CollateralSwapPathUpdated {
+			collateral_type: CurrencyId,
+			swap_path: Option<Vec<CurrencyId>>,
+		},
+		/// Surplus exceeding the surplus buffer transferred to beneficiary.
+		SurplusTransferred { beneficiary: T::AccountId, amount: Balance },
+		/// The distribution of surplus exceeding the surplus buffer updated.
+		SurplusDistributionUpdated { buyback: Permill, incentives: Permill },
+		/// Surplus transferred to the rewards source of incentives.
+		SurplusDistributedToIncentives { amount: Balance },
+		/// The target amount of stability reserve updated.
+		StabilityReserveTargetUpdated { amount: Balance },
+		/// Surplus retained in stability reserve.
+		StabilityReserveReplenished { amount: Balance },
+		/// Stability reserve drawn to offset debit pool.
+		StabilityReserveDrawn { amount: Balance },
+		/// The dust threshold of specific collateral type updated.
+		CollateralDustThresholdUpdated {
+			collateral_type: CurrencyId,
+			threshold: Balance,
+		},
+		/// Dust collateral swept to stable via DEX.
+		DustCollateralSwept {
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			stable_amount: Balance,
+		},
+		/// The dynamic size policy of surplus settlement updated.
+		SurplusSettlementSizePolicyUpdated {
+			policy: Option<SurplusSettlementSizePolicy>,
+		},
+		/// The premium over the oracle price when sell native to cover debit updated.
+		DebitSettlementPremiumUpdated { premium: Option<Ratio> },
+		/// The parameters of specific settlement kind updated.
+		SettlementParamsUpdated {
+			kind: SettlementKind,
+			cap: Option<Balance>,
+			min_interval: BlockNumberFor<T>,
+		},
+		/// The fee rate of flash loan updated.
+		FlashLoanFeeRateUpdated { fee_rate: Option<Rate> },
+		/// Flash loan from surplus pool repaid with fee.
+		FlashLoanRepaid {
+			who: T::AccountId,
+			amount: Balance,
+			fee: Balance,
+		},
+		/// Debit pool repaid by burning stable currency of `who`.
+		DebitPoolRepaid { who: T::AccountId, amount: Balance },
+		/// The max ratio of collateral and surplus deployed into DEX liquidity updated.
+		MaxLiquidityDeploymentRatioUpdated { ratio: Permill },
+		/// Collateral and surplus deployed into DEX liquidity.
+		LiquidityDeployed {
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			stable_amount: Balance,
+			share: Balance,
+		},
+		/// DEX liquidity recalled to collateral and surplus.
+		LiquidityRecalled {
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			stable_amount: Balance,
+			share: Balance,
+		},
+		/// Liquidation penalty assessed on liquidation, it's unrealized until
+		/// the collateral is sold.
+		LiquidationPenaltyAssessed {
+			collateral_type: CurrencyId,
+			amount: Balance,
+		},
+		/// The proportion of stability fee to incentives updated.
+		StabilityFeeIncentivesRatioUpdated { ratio: Permill },
+		/// Stability fee issued to the rewards source of incentives.
+		StabilityFeeDistributedToIncentives { amount: Balance },
+		/// The interest rate per block of debit pool updated.
+		DebitPoolInterestRateUpdated { rate: Option<Rate> },
+		/// The limits of net issuance of stable currency updated.
+		IssuanceLimitsUpdated {
+			per_block: Option<Balance>,
+			total: Option<Balance>,
+		},
+		/// The update of treasury parameter scheduled, it takes effect at `activate_at`.
+		ParamUpdateScheduled {
+			param: TreasuryParam,
+			value: Balance,
+			activate_at: BlockNumberFor<T>,
+		},
+		/// The scheduled update of treasury parameter activated.
+		ParamUpdateActivated { param: TreasuryParam, value: Balance },
+		/// The budget of collateral swapped to stable via DEX updated.
+		CollateralSwapBudgetUpdated {
+			collateral_type: CurrencyId,
+			budget: CollateralSwapBudget,
+		},
 	}
 
 	/// The expected amount size for per lot collateral auction of specific
@@ -138,13 +493,51 @@ pub mod module {
 	#[pallet::getter(fn expected_collateral_auction_size)]
 	pub type ExpectedCollateralAuctionSize<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
 
-	/// Current total debit value of system. It's not same as debit in CDP
-	/// engine, it is the bad debt of the system.
+	/// The expected stable value for per lot collateral auction of specific
+	/// collateral type, it's converted to collateral amount by the oracle price
+	/// when create auctions. If it's zero or the price is unavailable, the
+	/// `ExpectedCollateralAuctionSize` is used.
+	///
+	/// ExpectedCollateralAuctionValue: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn expected_collateral_auction_value)]
+	pub type ExpectedCollateralAuctionValue<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The expected value in specific stable currency for per lot collateral
+	/// auction of the collateral types issuing it, used if the
+	/// `ExpectedCollateralAuctionValue` of the collateral type is zero.
 	///
-	/// DebitPool: Balance
+	/// ExpectedStableAuctionValue: map CurrencyId => Balance
 	#[pallet::storage]
-	#[pallet::getter(fn debit_pool)]
-	pub type DebitPool<T: Config> = StorageValue<_, Balance, ValueQuery>;
+	#[pallet::getter(fn expected_stable_auction_value)]
+	pub type ExpectedStableAuctionValue<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The acceptable max slippage compared to the oracle price when swap
+	/// specific collateral type to stable. If it's not set, the swap price is
+	/// not checked, except for swaps of settlement which fallback to
+	/// `DefaultMaxSwapSlippage`.
+	///
+	/// MaxSwapSlippage: map CurrencyId => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn max_swap_slippage)]
+	pub type MaxSwapSlippage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Ratio, OptionQuery>;
+
+	/// The specified DEX swap path when swap specific collateral type to
+	/// stable. If it's not set, the swap path is discovered by `Swap`.
+	///
+	/// CollateralSwapPath: map CurrencyId => Option<BoundedVec<CurrencyId, TradingPathLimit>>
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_swap_path)]
+	pub type CollateralSwapPath<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, BoundedVec<CurrencyId, T::TradingPathLimit>, OptionQuery>;
+
+	/// Current total debit value of system in specific stable currency. It's
+	/// not same as debit in CDP engine, it is the bad debt of the system.
+	///
+	/// DebitPools: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn debit_pool_of)]
+	pub type DebitPools<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
 
 	/// The buffer amount of debit pool that will not be offset by surplus pool.
 	///
@@ -153,6 +546,240 @@ pub mod module {
 	#[pallet::getter(fn debit_offset_buffer)]
 	pub type DebitOffsetBuffer<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// The buffer amount of surplus pool that will not be settled via DEX.
+	///
+	/// SurplusBufferSize: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_buffer_size)]
+	pub type SurplusBufferSize<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The cap of stable amount settled via DEX per `trigger_settlement`, for
+	/// both surplus and debit. If it's zero, DEX settlement is disabled. The
+	/// cap for surplus is replaced by `SurplusSettlementSize` if it's set.
+	///
+	/// DexSettlementCap: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn dex_settlement_cap)]
+	pub type DexSettlementCap<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The distribution of surplus exceeding the surplus buffer when settle.
+	/// Default is all used to buy back native.
+	///
+	/// SurplusDistributionParams: SurplusDistribution
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_distribution)]
+	pub type SurplusDistributionParams<T: Config> = StorageValue<_, SurplusDistribution, ValueQuery>;
+
+	/// The target amount of stability reserve. The surplus exceeding the
+	/// surplus buffer is retained in stability reserve until reach the
+	/// target, and the reserve is consumed first to offset debit pool.
+	///
+	/// StabilityReserveTarget: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn stability_reserve_target)]
+	pub type StabilityReserveTarget<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The dust threshold of specific collateral type. The collateral not in
+	/// auction below it can be swept to stable via DEX. If it's zero, sweep
+	/// is disabled.
+	///
+	/// CollateralDustThreshold: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_dust_threshold)]
+	pub type CollateralDustThreshold<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The dynamic size policy of surplus settlement. If it's set, the size of
+	/// surplus settled per `trigger_settlement` is determined by it instead of
+	/// `DexSettlementCap`.
+	///
+	/// SurplusSettlementSize: Option<SurplusSettlementSizePolicy>
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_settlement_size)]
+	pub type SurplusSettlementSize<T: Config> = StorageValue<_, SurplusSettlementSizePolicy, OptionQuery>;
+
+	/// The premium over the oracle price when sell native to cover debit. The
+	/// max native amount sold per settlement is calculated from the debit
+	/// amount and the oracle price of native with the premium, if it's not
+	/// set, the max swap slippage of native is used as the premium.
+	///
+	/// DebitSettlementPremium: Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_settlement_premium)]
+	pub type DebitSettlementPremium<T: Config> = StorageValue<_, Ratio, OptionQuery>;
+
+	/// The cap of stable amount settled via DEX per `trigger_settlement` of
+	/// specific settlement kind. If it's not set, `DexSettlementCap` is used.
+	///
+	/// SettlementCapOf: map SettlementKind => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_cap_of)]
+	pub type SettlementCapOf<T: Config> = StorageMap<_, Twox64Concat, SettlementKind, Balance, OptionQuery>;
+
+	/// The min block interval between two settlements of specific settlement
+	/// kind. If it's zero, not limited.
+	///
+	/// SettlementMinInterval: map SettlementKind => BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_min_interval)]
+	pub type SettlementMinInterval<T: Config> =
+		StorageMap<_, Twox64Concat, SettlementKind, BlockNumberFor<T>, ValueQuery>;
+
+	/// The block number of the last settlement of specific settlement kind.
+	///
+	/// LastSettledAt: map SettlementKind => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn last_settled_at)]
+	pub type LastSettledAt<T: Config> = StorageMap<_, Twox64Concat, SettlementKind, BlockNumberFor<T>, OptionQuery>;
+
+	/// The cumulative metrics of cdp treasury.
+	///
+	/// CumulativeMetrics: TreasuryMetrics
+	#[pallet::storage]
+	#[pallet::getter(fn cumulative_metrics)]
+	pub type CumulativeMetrics<T: Config> = StorageValue<_, TreasuryMetrics, ValueQuery>;
+
+	/// The metrics of cdp treasury of recent eras, only `MaxMetricsEras` eras
+	/// are kept.
+	///
+	/// EraMetrics: map EraIndex => Option<TreasuryMetrics>
+	#[pallet::storage]
+	#[pallet::getter(fn era_metrics)]
+	pub type EraMetrics<T: Config> = StorageMap<_, Twox64Concat, u32, TreasuryMetrics, OptionQuery>;
+
+	/// The latest era index which has metrics recorded.
+	///
+	/// LatestMetricsEra: u32
+	#[pallet::storage]
+	#[pallet::getter(fn latest_metrics_era)]
+	pub type LatestMetricsEra<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The cumulative liquidated amount of specific collateral type, by
+	/// collateral auction or swap.
+	///
+	/// TotalCollateralLiquidated: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_collateral_liquidated)]
+	pub type TotalCollateralLiquidated<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The fee rate of flash loan from surplus pool, flash loan is disabled if
+	/// None.
+	///
+	/// FlashLoanFeeRate: Option<Rate>
+	#[pallet::storage]
+	#[pallet::getter(fn flash_loan_fee_rate)]
+	pub type FlashLoanFeeRate<T: Config> = StorageValue<_, Rate, OptionQuery>;
+
+	/// The max ratio of collateral and surplus that can be deployed into DEX
+	/// liquidity of the pair of collateral and stable currency.
+	///
+	/// MaxLiquidityDeploymentRatio: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn max_liquidity_deployment_ratio)]
+	pub type MaxLiquidityDeploymentRatio<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The DEX liquidity share of the pair of collateral and stable currency
+	/// deployed by cdp treasury.
+	///
+	/// DeployedLiquidityShares: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn deployed_liquidity_shares)]
+	pub type DeployedLiquidityShares<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// Whether the module accounts of cdp treasury have been provided, so that
+	/// they are not reaped when the balance of any currency is dusted.
+	///
+	/// ModuleAccountInitialized: bool
+	#[pallet::storage]
+	#[pallet::getter(fn module_account_initialized)]
+	pub type ModuleAccountInitialized<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The cumulative liquidation penalty of specific collateral type assessed
+	/// on liquidations. It's the unrealized income of surplus pool, the
+	/// actual income depends on the proceeds of selling the collateral.
+	///
+	/// TotalAssessedLiquidationPenalty: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_assessed_liquidation_penalty)]
+	pub type TotalAssessedLiquidationPenalty<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The proportion of accrued stability fee issued to the rewards source of
+	/// incentives to fund loans rewards, the remainder goes to surplus pool.
+	///
+	/// StabilityFeeIncentivesRatio: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn stability_fee_incentives_ratio)]
+	pub type StabilityFeeIncentivesRatio<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The interest rate per block accrued on the debit pool, so that the
+	/// unresolved bad debt grows until it's offset by surplus or settled.
+	/// Interest is not accrued if None.
+	///
+	/// DebitPoolInterestRate: Option<Rate>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_pool_interest_rate)]
+	pub type DebitPoolInterestRate<T: Config> = StorageValue<_, Rate, OptionQuery>;
+
+	/// The max net amount of stable currency issued to users by `issue_debit`
+	/// in a block, unlimited if None. The system surplus and the stability fee
+	/// are not limited.
+	///
+	/// MaxNetIssuancePerBlock: Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn max_net_issuance_per_block)]
+	pub type MaxNetIssuancePerBlock<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// The max total issuance of stable currency after issuing to users by
+	/// `issue_debit`, unlimited if None.
+	///
+	/// MaxNetIssuance: Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn max_net_issuance)]
+	pub type MaxNetIssuance<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// The net amount of stable currency issued to users by `issue_debit` and
+	/// burned by `burn_debit` in the recorded block.
+	///
+	/// BlockNetIssuance: (BlockNumber, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn block_net_issuance)]
+	pub type BlockNetIssuance<T: Config> = StorageValue<_, (BlockNumberFor<T>, Balance), ValueQuery>;
+
+	/// The scheduled updates of treasury parameters, keyed by the activation
+	/// block.
+	///
+	/// ScheduledParamUpdates: double_map BlockNumber, TreasuryParam => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_param_updates)]
+	pub type ScheduledParamUpdates<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Twox64Concat, TreasuryParam, Balance, OptionQuery>;
+
+	/// The activation block of the scheduled update of treasury parameters, a
+	/// new update of the same parameter replaces the scheduled one.
+	///
+	/// ScheduledParamUpdateBlocks: map TreasuryParam => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_param_update_blocks)]
+	pub type ScheduledParamUpdateBlocks<T: Config> =
+		StorageMap<_, Twox64Concat, TreasuryParam, BlockNumberFor<T>, OptionQuery>;
+
+	/// Mapping from collateral type to the budget of collateral swapped to
+	/// stable via DEX.
+	///
+	/// CollateralSwapBudgets: map CurrencyId => CollateralSwapBudget
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_swap_budgets)]
+	pub type CollateralSwapBudgets<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, CollateralSwapBudget, ValueQuery>;
+
+	/// Mapping from collateral type to the collateral amount swapped to stable
+	/// via DEX in the recorded block and metrics era.
+	///
+	/// CollateralSwapUsages: map CurrencyId => CollateralSwapUsage
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_swap_usages)]
+	pub type CollateralSwapUsages<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, CollateralSwapUsage<BlockNumberFor<T>>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T> {
@@ -168,6 +795,7 @@ pub mod module {
 				.for_each(|(currency_id, size)| {
 					ExpectedCollateralAuctionSize::<T>::insert(currency_id, size);
 				});
+			Pallet::<T>::initialize_module_accounts();
 		}
 	}
 
@@ -176,10 +804,27 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Activate the scheduled parameter updates, and return the weight
+		/// used in `on_finalize`.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::activate_scheduled_param_updates(now).saturating_add(T::WeightInfo::on_finalize())
+		}
+
 		/// Handle excessive surplus or debits of system when block end
 		fn on_finalize(_now: BlockNumberFor<T>) {
+			// accrue the interest of bad debt
+			Self::accrue_debit_pool_interest();
+
 			// offset the same amount between debit pool and surplus pool
 			Self::offset_surplus_and_debit();
+
+			// draw or replenish stability reserve
+			Self::handle_stability_reserve();
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_now: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
 		}
 	}
 
@@ -266,11 +911,7 @@ pub mod module {
 			#[pallet::compact] size: Balance,
 		) -> DispatchResult {
 			T::UpdateOrigin::ensure_origin(origin)?;
-			ExpectedCollateralAuctionSize::<T>::insert(currency_id, size);
-			Self::deposit_event(Event::ExpectedCollateralAuctionSizeUpdated {
-				collateral_type: currency_id,
-				new_size: size,
-			});
+			Self::schedule_param_update(TreasuryParam::ExpectedCollateralAuctionSize(currency_id), size);
 			Ok(())
 		}
 
@@ -280,126 +921,1579 @@ pub mod module {
 		///
 		/// - `amount`: the buffer amount of debit pool
 		#[pallet::call_index(4)]
-		#[pallet::weight((T::WeightInfo::set_expected_collateral_auction_size(), DispatchClass::Operational))]
+		#[pallet::weight((T::WeightInfo::set_debit_offset_buffer(), DispatchClass::Operational))]
 		pub fn set_debit_offset_buffer(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			T::UpdateOrigin::ensure_origin(origin)?;
-			DebitOffsetBuffer::<T>::mutate(|v| {
+			Self::schedule_param_update(TreasuryParam::DebitOffsetBuffer, amount);
+			Ok(())
+		}
+
+		/// Update the surplus buffer size
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `amount`: the buffer amount of surplus pool
+		#[pallet::call_index(5)]
+		#[pallet::weight((T::WeightInfo::set_surplus_buffer_size(), DispatchClass::Operational))]
+		pub fn set_surplus_buffer_size(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::schedule_param_update(TreasuryParam::SurplusBufferSize, amount);
+			Ok(())
+		}
+
+		/// Update the cap of stable amount settled via DEX per settlement
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `amount`: the cap amount, zero means disable DEX settlement
+		#[pallet::call_index(6)]
+		#[pallet::weight((T::WeightInfo::set_dex_settlement_cap(), DispatchClass::Operational))]
+		pub fn set_dex_settlement_cap(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			DexSettlementCap::<T>::mutate(|v| {
 				if *v != amount {
 					*v = amount;
-					Self::deposit_event(Event::DebitOffsetBufferUpdated { amount });
+					Self::deposit_event(Event::DexSettlementCapUpdated { amount });
 				}
 			});
 			Ok(())
 		}
-	}
-}
-
-impl<T: Config> Pallet<T> {
-	/// Get account of cdp treasury module.
-	pub fn account_id() -> T::AccountId {
-		T::PalletId::get().into_account_truncating()
-	}
-
-	/// Get current total surplus of system.
-	pub fn surplus_pool() -> Balance {
-		T::Currency::free_balance(T::GetStableCurrencyId::get(), &Self::account_id())
-	}
-
-	/// Get total collateral amount of cdp treasury module.
-	pub fn total_collaterals(currency_id: CurrencyId) -> Balance {
-		T::Currency::free_balance(currency_id, &Self::account_id())
-	}
-
-	/// Get collateral amount not in auction
-	pub fn total_collaterals_not_in_auction(currency_id: CurrencyId) -> Balance {
-		T::Currency::free_balance(currency_id, &Self::account_id())
-			.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
-	}
 
-	fn offset_surplus_and_debit() {
-		// The part of the debit pool that exceeds the debit offset buffer can be offset by the surplus
-		let offset_amount = sp_std::cmp::min(
-			Self::debit_pool().saturating_sub(Self::debit_offset_buffer()),
-			Self::surplus_pool(),
-		);
+		/// Settle the surplus or debit that exceeds the buffers via DEX, up to
+		/// `DexSettlementCap` each. Anyone can call it as a keeper, the caller
+		/// is rewarded by `KeeperRewards` if anything is settled.
+		///
+		/// The dispatch origin of this call must be `Signed` by the transactor.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::trigger_settlement())]
+		pub fn trigger_settlement(origin: OriginFor<T>) -> DispatchResult {
+			let keeper = ensure_signed(origin)?;
+			ensure!(!Self::dex_settlement_cap().is_zero(), Error::<T>::SettlementDisabled);
 
-		// Burn the amount that is equal to offset amount of stable currency.
-		if !offset_amount.is_zero() {
-			let res = Self::burn_debit(&Self::account_id(), offset_amount);
-			match res {
-				Ok(_) => {
-					DebitPool::<T>::mutate(|debit| {
-						*debit = debit
-							.checked_sub(offset_amount)
-							.expect("offset = min(debit, surplus); qed")
-					});
-				}
-				Err(e) => {
-					log::warn!(
-						target: "cdp-treasury",
-						"get_swap_supply_amount: Attempt to burn surplus {:?} failed: {:?}, this is unexpected but should be safe",
-						offset_amount, e
-					);
-				}
+			Self::offset_surplus_and_debit();
+			Self::handle_stability_reserve();
+			if Self::settle_surplus_and_debit() {
+				T::KeeperRewards::reward_keeper(&keeper, KeeperAction::TreasurySettlement);
 			}
+			Ok(())
 		}
-	}
-}
-
-impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
-	type Balance = Balance;
-	type CurrencyId = CurrencyId;
-
-	fn get_surplus_pool() -> Self::Balance {
-		Self::surplus_pool()
-	}
 
-	fn get_debit_pool() -> Self::Balance {
-		Self::debit_pool()
-	}
-
-	fn get_total_collaterals(id: Self::CurrencyId) -> Self::Balance {
-		Self::total_collaterals(id)
-	}
+		/// Update the max slippage compared to the oracle price when swap
+		/// specific collateral type to stable
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `max_slippage`: the max slippage ratio, `None` means no check
+		#[pallet::call_index(8)]
+		#[pallet::weight((T::WeightInfo::set_max_swap_slippage(), DispatchClass::Operational))]
+		pub fn set_max_swap_slippage(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_slippage: Option<Ratio>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MaxSwapSlippage::<T>::set(currency_id, max_slippage);
+			Self::deposit_event(Event::MaxSwapSlippageUpdated {
+				collateral_type: currency_id,
+				max_slippage,
+			});
+			Ok(())
+		}
 
-	fn get_debit_proportion(amount: Self::Balance) -> Ratio {
-		let stable_total_supply = T::Currency::total_issuance(T::GetStableCurrencyId::get());
+		/// Update the budget of specific collateral type swapped to stable via
+		/// DEX, so that the swaps of cdp treasury do not crash the collateral
+		/// price during mass liquidations.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `per_block`: the budget of per block, `None` means unlimited
+		/// - `per_era`: the budget of per metrics era, `None` means unlimited
+		#[pallet::call_index(28)]
+		#[pallet::weight((T::WeightInfo::set_collateral_swap_budget(), DispatchClass::Operational))]
+		pub fn set_collateral_swap_budget(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			per_block: Option<Balance>,
+			per_era: Option<Balance>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let budget = CollateralSwapBudget { per_block, per_era };
+			CollateralSwapBudgets::<T>::insert(currency_id, budget);
+			Self::deposit_event(Event::CollateralSwapBudgetUpdated {
+				collateral_type: currency_id,
+				budget,
+			});
+			Ok(())
+		}
+
+		/// Update the expected stable value of per lot collateral auction under
+		/// specific collateral type, it takes precedence over the expected
+		/// collateral auction size.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `value`: expected stable value of per lot collateral auction, zero means unset
+		#[pallet::call_index(29)]
+		#[pallet::weight((T::WeightInfo::set_expected_collateral_auction_value(), DispatchClass::Operational))]
+		pub fn set_expected_collateral_auction_value(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] value: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::schedule_param_update(TreasuryParam::ExpectedCollateralAuctionValue(currency_id), value);
+			Ok(())
+		}
+
+		/// Update the expected value for per lot collateral auction of the
+		/// collateral types issuing specific stable currency.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `stable_currency_id`: stable currency type
+		/// - `value`: expected value of per lot collateral auction in the stable currency, zero
+		///   means unset
+		#[pallet::call_index(30)]
+		#[pallet::weight((T::WeightInfo::set_expected_stable_auction_value(), DispatchClass::Operational))]
+		pub fn set_expected_stable_auction_value(
+			origin: OriginFor<T>,
+			stable_currency_id: CurrencyId,
+			#[pallet::compact] value: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::ensure_stable_currency(stable_currency_id)?;
+			Self::schedule_param_update(TreasuryParam::ExpectedStableAuctionValue(stable_currency_id), value);
+			Ok(())
+		}
+
+		/// Update the specified DEX swap path when swap specific collateral
+		/// type to stable, e.g. [renBTC, DOT, aUSD]
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `swap_path`: the swap path, `None` means discover it automatically
+		#[pallet::call_index(9)]
+		#[pallet::weight((T::WeightInfo::set_collateral_swap_path(), DispatchClass::Operational))]
+		pub fn set_collateral_swap_path(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			swap_path: Option<Vec<CurrencyId>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let bounded_swap_path = match swap_path.clone() {
+				Some(path) => {
+					ensure!(
+						path.len() >= 2
+							&& path.first() == Some(&currency_id)
+							&& path.last() == Some(&T::RiskManager::get_stable_currency_id(currency_id)),
+						Error::<T>::InvalidSwapPath
+					);
+					Some(BoundedVec::try_from(path).map_err(|_| Error::<T>::InvalidSwapPath)?)
+				}
+				None => None,
+			};
+			CollateralSwapPath::<T>::set(currency_id, bounded_swap_path);
+			Self::deposit_event(Event::CollateralSwapPathUpdated {
+				collateral_type: currency_id,
+				swap_path,
+			});
+			Ok(())
+		}
+
+		/// Transfer the surplus exceeding the surplus buffer to beneficiary.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `beneficiary`: the account receives the surplus
+		/// - `amount`: surplus amount
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::transfer_surplus())]
+		pub fn transfer_surplus(
+			origin: OriginFor<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(
+				Self::surplus_pool().saturating_sub(Self::surplus_buffer_size()) >= amount,
+				Error::<T>::SurplusPoolNotEnough
+			);
+
+			Self::withdraw_surplus(T::GetStableCurrencyId::get(), &beneficiary, amount)?;
+			Self::deposit_event(Event::SurplusTransferred { beneficiary, amount });
+			Ok(())
+		}
+
+		/// Update the distribution of surplus exceeding the surplus buffer,
+		/// the remainder is retained in surplus pool.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `buyback`: the proportion used to buy back native and burn it
+		/// - `incentives`: the proportion transferred to the rewards source of incentives
+		#[pallet::call_index(11)]
+		#[pallet::weight((T::WeightInfo::set_surplus_distribution(), DispatchClass::Operational))]
+		pub fn set_surplus_distribution(origin: OriginFor<T>, buyback: Permill, incentives: Permill) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				buyback.deconstruct().saturating_add(incentives.deconstruct()) <= Permill::one().deconstruct(),
+				Error::<T>::InvalidSurplusDistribution
+			);
+			SurplusDistributionParams::<T>::put(SurplusDistribution { buyback, incentives });
+			Self::deposit_event(Event::SurplusDistributionUpdated { buyback, incentives });
+			Ok(())
+		}
+
+		/// Update the target amount of stability reserve
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `amount`: the target amount of stability reserve
+		#[pallet::call_index(12)]
+		#[pallet::weight((T::WeightInfo::set_stability_reserve_target(), DispatchClass::Operational))]
+		pub fn set_stability_reserve_target(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			StabilityReserveTarget::<T>::mutate(|v| {
+				if *v != amount {
+					*v = amount;
+					Self::deposit_event(Event::StabilityReserveTargetUpdated { amount });
+				}
+			});
+			Ok(())
+		}
+
+		/// Update the dust threshold of specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `threshold`: the dust threshold, zero means disable sweep
+		#[pallet::call_index(13)]
+		#[pallet::weight((T::WeightInfo::set_collateral_dust_threshold(), DispatchClass::Operational))]
+		pub fn set_collateral_dust_threshold(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] threshold: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			CollateralDustThreshold::<T>::insert(currency_id, threshold);
+			Self::deposit_event(Event::CollateralDustThresholdUpdated {
+				collateral_type: currency_id,
+				threshold,
+			});
+			Ok(())
+		}
+
+		/// Swap the dust collateral not in auction to stable via DEX, the
+		/// stable is booked to surplus pool. Anyone can call it as a keeper, the
+		/// swap is bounded by the oracle price with the max swap slippage.
+		///
+		/// The dispatch origin of this call must be `Signed` by the transactor.
+		///
+		/// - `currency_id`: collateral type
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::sweep_dust_collateral())]
+		pub fn sweep_dust_collateral(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let collateral_amount = Self::total_collaterals_not_in_auction(currency_id);
+			ensure!(
+				!collateral_amount.is_zero() && collateral_amount <= Self::collateral_dust_threshold(currency_id),
+				Error::<T>::CollateralNotDust
+			);
+
+			let min_target_amount = Self::oracle_bounded_min_target(
+				currency_id,
+				T::RiskManager::get_stable_currency_id(currency_id),
+				collateral_amount,
+				Self::get_max_swap_slippage(currency_id),
+			)?;
+			let (collateral_amount, stable_amount) = Self::swap_collateral_to_stable(
+				currency_id,
+				SwapLimit::ExactSupply(collateral_amount, min_target_amount),
+				false,
+			)?;
+			Self::deposit_event(Event::DustCollateralSwept {
+				collateral_type: currency_id,
+				collateral_amount,
+				stable_amount,
+			});
+			Ok(())
+		}
+
+		/// Update the dynamic size policy of surplus settlement
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `policy`: the size policy, `None` means use `DexSettlementCap`
+		#[pallet::call_index(15)]
+		#[pallet::weight((T::WeightInfo::set_surplus_settlement_size(), DispatchClass::Operational))]
+		pub fn set_surplus_settlement_size(
+			origin: OriginFor<T>,
+			policy: Option<SurplusSettlementSizePolicy>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(p) = policy {
+				ensure!(
+					!p.proportion.is_zero() && !p.max_size.is_zero() && p.min_size <= p.max_size,
+					Error::<T>::InvalidSettlementSizePolicy
+				);
+			}
+			SurplusSettlementSize::<T>::set(policy);
+			Self::deposit_event(Event::SurplusSettlementSizePolicyUpdated { policy });
+			Ok(())
+		}
+
+		/// Update the premium over the oracle price when sell native to cover
+		/// debit
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `premium`: the premium ratio, `None` means the max swap slippage of
+		///   native is used
+		#[pallet::call_index(16)]
+		#[pallet::weight((T::WeightInfo::set_debit_settlement_premium(), DispatchClass::Operational))]
+		pub fn set_debit_settlement_premium(origin: OriginFor<T>, premium: Option<Ratio>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			DebitSettlementPremium::<T>::set(premium);
+			Self::deposit_event(Event::DebitSettlementPremiumUpdated { premium });
+			Ok(())
+		}
+
+		/// Update the parameters of specific settlement kind
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `kind`: settlement kind
+		/// - `cap`: the cap per settlement, `None` means use `DexSettlementCap`
+		/// - `min_interval`: the min block interval between two settlements
+		#[pallet::call_index(17)]
+		#[pallet::weight((T::WeightInfo::set_settlement_params(), DispatchClass::Operational))]
+		pub fn set_settlement_params(
+			origin: OriginFor<T>,
+			kind: SettlementKind,
+			cap: Option<Balance>,
+			min_interval: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			SettlementCapOf::<T>::set(kind, cap);
+			SettlementMinInterval::<T>::insert(kind, min_interval);
+			Self::deposit_event(Event::SettlementParamsUpdated {
+				kind,
+				cap,
+				min_interval,
+			});
+			Ok(())
+		}
+
+		/// Update the expected collateral auction size of multiple collateral types,
+		/// each update is scheduled after `ParamsUpdateDelay`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `updates`: list of (collateral type, expected size of per lot collateral auction)
+		#[pallet::call_index(18)]
+		#[pallet::weight((
+			T::WeightInfo::set_collateral_auction_params(updates.len() as u32),
+			DispatchClass::Operational
+		))]
+		pub fn set_collateral_auction_params(
+			origin: OriginFor<T>,
+			updates: BoundedVec<(CurrencyId, Balance), T::MaxCollateralTypes>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (currency_id, size) in updates {
+				Self::schedule_param_update(TreasuryParam::ExpectedCollateralAuctionSize(currency_id), size);
+			}
+			Ok(())
+		}
+
+		/// Update the fee rate of flash loan
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `fee_rate`: the fee rate of flash loan, `None` means disable flash loan
+		#[pallet::call_index(19)]
+		#[pallet::weight((T::WeightInfo::set_flash_loan_fee_rate(), DispatchClass::Operational))]
+		pub fn set_flash_loan_fee_rate(origin: OriginFor<T>, fee_rate: Option<Rate>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			FlashLoanFeeRate::<T>::set(fee_rate);
+			Self::deposit_event(Event::FlashLoanFeeRateUpdated { fee_rate });
+			Ok(())
+		}
+
+		/// Borrow stable currency from surplus pool and dispatch `receiver_call`,
+		/// the borrowed amount and the fee must be repaid to cdp treasury at the
+		/// end of `receiver_call`, otherwise the whole call is reverted. The fee
+		/// is credited to surplus pool.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `currency_id`: the currency to borrow, must be stable currency
+		/// - `amount`: the amount to borrow
+		/// - `receiver_call`: the call dispatched by the borrower with the loan
+		#[pallet::call_index(20)]
+		#[pallet::weight({
+			let dispatch_info = receiver_call.get_dispatch_info();
+			(T::WeightInfo::flash_loan().saturating_add(dispatch_info.weight), dispatch_info.class)
+		})]
+		#[transactional]
+		pub fn flash_loan(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			receiver_call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin.clone())?;
+			let fee_rate = Self::flash_loan_fee_rate().ok_or(Error::<T>::FlashLoanDisabled)?;
+			ensure!(
+				currency_id == T::GetStableCurrencyId::get(),
+				Error::<T>::FlashLoanCurrencyNotSupported
+			);
+			ensure!(Self::surplus_pool() >= amount, Error::<T>::SurplusPoolNotEnough);
+
+			let fee = fee_rate.saturating_mul_int(amount);
+			Self::withdraw_surplus(T::GetStableCurrencyId::get(), &who, amount)?;
+
+			let dispatch_info = receiver_call.get_dispatch_info();
+			let post_info = receiver_call.dispatch(origin).map_err(|e| e.error)?;
+
+			Self::deposit_surplus(T::GetStableCurrencyId::get(), &who, amount.saturating_add(fee))
+				.map_err(|_| Error::<T>::FlashLoanNotRepaid)?;
+			Self::deposit_event(Event::FlashLoanRepaid { who, amount, fee });
+
+			let actual_weight = post_info
+				.actual_weight
+				.unwrap_or(dispatch_info.weight)
+				.saturating_add(T::WeightInfo::flash_loan());
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Burn stable currency of the caller to reduce debit pool, the amount
+		/// exceeding debit pool is not burned.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `amount`: the max amount of stable currency to burn
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::repay_debit_pool())]
+		pub fn repay_debit_pool(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let stable_currency_id = T::GetStableCurrencyId::get();
+			let repay_amount = sp_std::cmp::min(amount, Self::debit_pool());
+			ensure!(!repay_amount.is_zero(), Error::<T>::NoDebitToRepay);
+
+			Self::do_burn_debit(stable_currency_id, &who, repay_amount)?;
+			DebitPools::<T>::mutate(stable_currency_id, |debit| *debit = debit.saturating_sub(repay_amount));
+			Self::deposit_event(Event::DebitPoolRepaid {
+				who,
+				amount: repay_amount,
+			});
+			Ok(())
+		}
+
+		/// Update the max ratio of collateral and surplus deployed into DEX
+		/// liquidity
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `ratio`: the max ratio, zero means disable liquidity deployment
+		#[pallet::call_index(22)]
+		#[pallet::weight((T::WeightInfo::set_max_liquidity_deployment_ratio(), DispatchClass::Operational))]
+		pub fn set_max_liquidity_deployment_ratio(origin: OriginFor<T>, ratio: Permill) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MaxLiquidityDeploymentRatio::<T>::mutate(|v| {
+				if *v != ratio {
+					*v = ratio;
+					Self::deposit_event(Event::MaxLiquidityDeploymentRatioUpdated { ratio });
+				}
+			});
+			Ok(())
+		}
+
+		/// Deploy collateral and surplus into the DEX liquidity of the pair of
+		/// collateral and stable currency
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `collateral_amount`: the max collateral amount to deploy
+		/// - `stable_amount`: the max surplus amount to deploy
+		/// - `min_share_increment`: the min liquidity share to receive
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::deploy_liquidity())]
+		#[transactional]
+		pub fn deploy_liquidity(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] collateral_amount: Balance,
+			#[pallet::compact] stable_amount: Balance,
+			#[pallet::compact] min_share_increment: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let stable_currency_id = T::RiskManager::get_stable_currency_id(currency_id);
+			TradingPair::from_currency_ids(currency_id, stable_currency_id).ok_or(Error::<T>::InvalidLiquidityPair)?;
+			ensure!(
+				Self::total_collaterals_not_in_auction(currency_id) >= collateral_amount,
+				Error::<T>::CollateralNotEnough,
+			);
+
+			let liquidity_account = Self::liquidity_account_id();
+			Self::ensure_not_below_existential_deposit(currency_id, &liquidity_account, collateral_amount)?;
+			Self::ensure_not_below_existential_deposit(stable_currency_id, &liquidity_account, stable_amount)?;
+			T::Currency::transfer(currency_id, &Self::account_id(), &liquidity_account, collateral_amount)?;
+			T::Currency::transfer(
+				stable_currency_id,
+				&Self::account_id(),
+				&liquidity_account,
+				stable_amount,
+			)?;
+			let (actual_collateral_amount, actual_stable_amount, share) = T::DEX::add_liquidity(
+				&liquidity_account,
+				currency_id,
+				stable_currency_id,
+				collateral_amount,
+				stable_amount,
+				min_share_increment,
+				false,
+			)?;
+			// return the remnant that is not added into liquidity
+			T::Currency::transfer(
+				currency_id,
+				&liquidity_account,
+				&Self::account_id(),
+				collateral_amount.saturating_sub(actual_collateral_amount),
+			)?;
+			T::Currency::transfer(
+				stable_currency_id,
+				&liquidity_account,
+				&Self::account_id(),
+				stable_amount.saturating_sub(actual_stable_amount),
+			)?;
+			DeployedLiquidityShares::<T>::mutate(currency_id, |v| *v = v.saturating_add(share));
+
+			let ratio = Self::max_liquidity_deployment_ratio();
+			let (deployed_collateral, deployed_stable) = Self::deployed_liquidity(currency_id);
+			let collateral_cap =
+				ratio.mul_floor(deployed_collateral.saturating_add(Self::total_collaterals(currency_id)));
+			let stable_cap = ratio.mul_floor(deployed_stable.saturating_add(Self::surplus_pool_of(stable_currency_id)));
+			ensure!(
+				deployed_collateral <= collateral_cap && deployed_stable <= stable_cap,
+				Error::<T>::ExceedLiquidityDeploymentCap
+			);
+
+			Self::deposit_event(Event::LiquidityDeployed {
+				collateral_type: currency_id,
+				collateral_amount: actual_collateral_amount,
+				stable_amount: actual_stable_amount,
+				share,
+			});
+			Ok(())
+		}
+
+		/// Recall the deployed DEX liquidity to collateral and surplus
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `share`: the liquidity share to recall
+		/// - `min_collateral_amount`: the min collateral amount to receive
+		/// - `min_stable_amount`: the min stable amount to receive
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::recall_liquidity())]
+		#[transactional]
+		pub fn recall_liquidity(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] share: Balance,
+			#[pallet::compact] min_collateral_amount: Balance,
+			#[pallet::compact] min_stable_amount: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::do_recall_liquidity(currency_id, share, min_collateral_amount, min_stable_amount)
+		}
+
+		/// Update the proportion of accrued stability fee issued to the
+		/// rewards source of incentives
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `ratio`: the proportion to incentives, the remainder goes to surplus pool
+		#[pallet::call_index(25)]
+		#[pallet::weight((T::WeightInfo::set_stability_fee_incentives_ratio(), DispatchClass::Operational))]
+		pub fn set_stability_fee_incentives_ratio(origin: OriginFor<T>, ratio: Permill) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			StabilityFeeIncentivesRatio::<T>::put(ratio);
+			Self::deposit_event(Event::StabilityFeeIncentivesRatioUpdated { ratio });
+			Ok(())
+		}
+
+		/// Update the interest rate per block accrued on the debit pool.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `rate`: the interest rate per block, `None` means disable the accrual
+		#[pallet::call_index(26)]
+		#[pallet::weight((T::WeightInfo::set_debit_pool_interest_rate(), DispatchClass::Operational))]
+		pub fn set_debit_pool_interest_rate(origin: OriginFor<T>, rate: Option<Rate>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			DebitPoolInterestRate::<T>::set(rate);
+			Self::deposit_event(Event::DebitPoolInterestRateUpdated { rate });
+			Ok(())
+		}
+
+		/// Update the limits of issuance of stable currency to users by
+		/// `issue_debit`, as the throttle against unbounded issuance.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `per_block`: the max net issuance in a block, `None` means unlimited
+		/// - `total`: the max total issuance of stable currency, `None` means unlimited
+		#[pallet::call_index(27)]
+		#[pallet::weight((T::WeightInfo::set_issuance_limits(), DispatchClass::Operational))]
+		pub fn set_issuance_limits(
+			origin: OriginFor<T>,
+			per_block: Option<Balance>,
+			total: Option<Balance>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MaxNetIssuancePerBlock::<T>::set(per_block);
+			MaxNetIssuance::<T>::set(total);
+			Self::deposit_event(Event::IssuanceLimitsUpdated { per_block, total });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get account of cdp treasury module.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Get account of stability reserve.
+	pub fn stability_reserve_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"reserve")
+	}
+
+	/// Get current stability reserve.
+	pub fn stability_reserve() -> Balance {
+		T::Currency::free_balance(T::GetStableCurrencyId::get(), &Self::stability_reserve_account_id())
+	}
+
+	/// Get current total surplus of system in the primary stable currency.
+	pub fn surplus_pool() -> Balance {
+		Self::surplus_pool_of(T::GetStableCurrencyId::get())
+	}
+
+	/// Get current total surplus of system in specific stable currency.
+	pub fn surplus_pool_of(stable_currency_id: CurrencyId) -> Balance {
+		T::Currency::free_balance(stable_currency_id, &Self::account_id())
+	}
+
+	/// Get current total debit of system in the primary stable currency.
+	pub fn debit_pool() -> Balance {
+		Self::debit_pool_of(T::GetStableCurrencyId::get())
+	}
+
+	/// Get the debit offset buffer of specific stable currency, the buffer
+	/// only applies to the primary stable currency.
+	fn debit_offset_buffer_of(stable_currency_id: CurrencyId) -> Balance {
+		if stable_currency_id == T::GetStableCurrencyId::get() {
+			Self::debit_offset_buffer()
+		} else {
+			Zero::zero()
+		}
+	}
+
+	/// Get total collateral amount of cdp treasury module.
+	pub fn total_collaterals(currency_id: CurrencyId) -> Balance {
+		T::Currency::free_balance(currency_id, &Self::account_id())
+	}
+
+	/// Add a provider reference to the module accounts of cdp treasury once,
+	/// keep them alive even if all their balances are reaped.
+	pub fn initialize_module_accounts() {
+		if !Self::module_account_initialized() {
+			frame_system::Pallet::<T>::inc_providers(&Self::account_id());
+			frame_system::Pallet::<T>::inc_providers(&Self::stability_reserve_account_id());
+			frame_system::Pallet::<T>::inc_providers(&Self::liquidity_account_id());
+			ModuleAccountInitialized::<T>::put(true);
+		}
+	}
+
+	/// Ensure the balance of the sub-account `who` of cdp treasury is not below
+	/// existential deposit after receiving `amount`. The sub-accounts are not
+	/// in the dust removal whitelist, the transfer fails with an opaque error
+	/// of currency otherwise.
+	fn ensure_not_below_existential_deposit(
+		currency_id: CurrencyId,
+		who: &T::AccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		let balance = T::Currency::free_balance(currency_id, who).saturating_add(amount);
+		ensure!(
+			amount.is_zero() || balance >= T::Currency::minimum_balance(currency_id),
+			Error::<T>::BelowExistentialDeposit
+		);
+		Ok(())
+	}
+
+	/// Get account holding the DEX liquidity deployed by cdp treasury.
+	pub fn liquidity_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"liquidity")
+	}
+
+	/// Get the claimable (collateral, stable) amount of the DEX liquidity
+	/// deployed for specific collateral type.
+	pub fn deployed_liquidity(currency_id: CurrencyId) -> (Balance, Balance) {
+		let stable_currency_id = T::RiskManager::get_stable_currency_id(currency_id);
+		let share = Self::deployed_liquidity_shares(currency_id);
+		let total_share = TradingPair::from_currency_ids(currency_id, stable_currency_id)
+			.map(|pair| T::Currency::total_issuance(pair.dex_share_currency_id()))
+			.unwrap_or_default();
+		if share.is_zero() || total_share.is_zero() {
+			return (Zero::zero(), Zero::zero());
+		}
+
+		let (collateral_pool, stable_pool) = T::DEX::get_liquidity_pool(currency_id, stable_currency_id);
+		let proportion = Ratio::checked_from_rational(share, total_share).unwrap_or_default();
+		(
+			proportion.saturating_mul_int(collateral_pool),
+			proportion.saturating_mul_int(stable_pool),
+		)
+	}
+
+	fn do_recall_liquidity(
+		currency_id: CurrencyId,
+		share: Balance,
+		min_collateral_amount: Balance,
+		min_stable_amount: Balance,
+	) -> DispatchResult {
+		DeployedLiquidityShares::<T>::try_mutate(currency_id, |v| -> DispatchResult {
+			*v = v.checked_sub(share).ok_or(Error::<T>::DeployedLiquidityNotEnough)?;
+			Ok(())
+		})?;
+
+		let stable_currency_id = T::RiskManager::get_stable_currency_id(currency_id);
+		let liquidity_account = Self::liquidity_account_id();
+		let (collateral_amount, stable_amount) = T::DEX::remove_liquidity(
+			&liquidity_account,
+			currency_id,
+			stable_currency_id,
+			share,
+			min_collateral_amount,
+			min_stable_amount,
+			false,
+		)?;
+		T::Currency::transfer(currency_id, &liquidity_account, &Self::account_id(), collateral_amount)?;
+		T::Currency::transfer(
+			stable_currency_id,
+			&liquidity_account,
+			&Self::account_id(),
+			stable_amount,
+		)?;
+
+		Self::deposit_event(Event::LiquidityRecalled {
+			collateral_type: currency_id,
+			collateral_amount,
+			stable_amount,
+			share,
+		});
+		Ok(())
+	}
+
+	/// Get total claimable collateral amount of cdp treasury, including the
+	/// collateral deployed into DEX liquidity, used by runtime api.
+	pub fn total_claimable_collaterals(currency_id: CurrencyId) -> Balance {
+		Self::total_collaterals(currency_id).saturating_add(Self::deployed_liquidity(currency_id).0)
+	}
+
+	/// Get collateral amount not in auction
+	pub fn total_collaterals_not_in_auction(currency_id: CurrencyId) -> Balance {
+		T::Currency::free_balance(currency_id, &Self::account_id())
+			.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
+	}
+
+	/// Get the count of collateral auctions will be created if auction all the
+	/// collateral not in auction, used by runtime api.
+	pub fn get_projected_collateral_auctions_count(currency_id: CurrencyId) -> u32 {
+		let amount = Self::total_collaterals_not_in_auction(currency_id);
+		if amount.is_zero() {
+			return 0;
+		}
+
+		Self::get_collateral_auction_lots_count(currency_id, amount, true).saturated_into()
+	}
+
+	/// Get the expected collateral amount of per lot collateral auction. The
+	/// expected stable value of the collateral type, or of the stable currency
+	/// issued against it, is converted by the oracle price if it's set,
+	/// otherwise fallback to the expected collateral auction size.
+	pub fn get_expected_collateral_auction_size(currency_id: CurrencyId) -> Balance {
+		let stable_currency_id = T::RiskManager::get_stable_currency_id(currency_id);
+		let expected_value = match Self::expected_collateral_auction_value(currency_id) {
+			value if value.is_zero() => Self::expected_stable_auction_value(stable_currency_id),
+			value => value,
+		};
+		if !expected_value.is_zero() {
+			let size = T::PriceSource::get_relative_price(currency_id, stable_currency_id)
+				.and_then(|price| price.reciprocal())
+				.map(|n| n.saturating_mul_int(expected_value))
+				.unwrap_or_default();
+			if !size.is_zero() {
+				return size;
+			}
+		}
+
+		Self::expected_collateral_auction_size(currency_id)
+	}
+
+	/// Calculate the lots count when create collateral auctions for `amount`
+	/// collateral, it's at least 1.
+	fn get_collateral_auction_lots_count(currency_id: CurrencyId, amount: Balance, splited: bool) -> Balance {
+		let expected_collateral_auction_size = Self::get_expected_collateral_auction_size(currency_id);
+		let max_auctions_count: Balance = T::MaxAuctionsCount::get().into();
+		if !splited
+			|| max_auctions_count.is_zero()
+			|| expected_collateral_auction_size.is_zero()
+			|| amount <= expected_collateral_auction_size
+		{
+			One::one()
+		} else {
+			let mut count = amount
+				.checked_div(expected_collateral_auction_size)
+				.expect("collateral auction maximum size is not zero; qed");
+
+			let remainder = amount
+				.checked_rem(expected_collateral_auction_size)
+				.expect("collateral auction maximum size is not zero; qed");
+			if !remainder.is_zero() {
+				count = count.saturating_add(One::one());
+			}
+			sp_std::cmp::min(count, max_auctions_count)
+		}
+	}
+
+	/// Accrue the interest of debit pool, the penalty of unresolved bad debt
+	/// is offset by surplus like the debit itself.
+	fn accrue_debit_pool_interest() {
+		if let Some(rate) = Self::debit_pool_interest_rate() {
+			for stable_currency_id in T::StableCurrencyIds::get() {
+				DebitPools::<T>::mutate_exists(stable_currency_id, |maybe_debit| {
+					if let Some(debit) = maybe_debit {
+						let interest = rate.saturating_mul_int(*debit);
+						*debit = debit.saturating_add(interest);
+					}
+				});
+			}
+		}
+	}
+
+	fn offset_surplus_and_debit() {
+		for stable_currency_id in T::StableCurrencyIds::get() {
+			// The part of the debit pool that exceeds the debit offset buffer can be offset by the surplus
+			let offset_amount = sp_std::cmp::min(
+				Self::debit_pool_of(stable_currency_id)
+					.saturating_sub(Self::debit_offset_buffer_of(stable_currency_id)),
+				Self::surplus_pool_of(stable_currency_id),
+			);
+
+			// Burn the amount that is equal to offset amount of stable currency.
+			if !offset_amount.is_zero() {
+				let res = Self::do_burn_debit(stable_currency_id, &Self::account_id(), offset_amount);
+				match res {
+					Ok(_) => {
+						DebitPools::<T>::mutate(stable_currency_id, |debit| {
+							*debit = debit
+								.checked_sub(offset_amount)
+								.expect("offset = min(debit, surplus); qed")
+						});
+					}
+					Err(e) => {
+						log::warn!(
+							target: "cdp-treasury",
+							"get_swap_supply_amount: Attempt to burn surplus {:?} of {:?} failed: {:?}, this is unexpected but should be safe",
+							offset_amount, stable_currency_id, e
+						);
+					}
+				}
+			}
+		}
+	}
+
+	fn handle_stability_reserve() {
+		// The part of the debit pool that exceeds the debit offset buffer is offset by reserve first
+		let draw_amount = sp_std::cmp::min(
+			Self::debit_pool().saturating_sub(Self::debit_offset_buffer()),
+			Self::stability_reserve(),
+		);
+		if !draw_amount.is_zero() {
+			let stable_currency_id = T::GetStableCurrencyId::get();
+			match Self::do_burn_debit(stable_currency_id, &Self::stability_reserve_account_id(), draw_amount) {
+				Ok(_) => {
+					DebitPools::<T>::mutate(stable_currency_id, |debit| *debit = debit.saturating_sub(draw_amount));
+					Self::deposit_event(Event::StabilityReserveDrawn { amount: draw_amount });
+				}
+				Err(e) => {
+					log::warn!(
+						target: "cdp-treasury",
+						"handle_stability_reserve: Attempt to draw reserve {:?} failed: {:?}, this is unexpected but should be safe",
+						draw_amount, e
+					);
+				}
+			}
+			return;
+		}
+
+		// The part of the surplus pool that exceeds the surplus buffer is retained in reserve until target
+		let replenish_amount = sp_std::cmp::min(
+			Self::surplus_pool().saturating_sub(Self::surplus_buffer_size()),
+			Self::stability_reserve_target().saturating_sub(Self::stability_reserve()),
+		);
+		if !replenish_amount.is_zero() {
+			let stable_currency_id = T::GetStableCurrencyId::get();
+			let reserve_account = Self::stability_reserve_account_id();
+			match Self::ensure_not_below_existential_deposit(stable_currency_id, &reserve_account, replenish_amount)
+				.and_then(|_| {
+					T::Currency::transfer(
+						stable_currency_id,
+						&Self::account_id(),
+						&reserve_account,
+						replenish_amount,
+					)
+				}) {
+				Ok(_) => Self::deposit_event(Event::StabilityReserveReplenished {
+					amount: replenish_amount,
+				}),
+				Err(e) => {
+					log::warn!(
+						target: "cdp-treasury",
+						"handle_stability_reserve: Attempt to replenish reserve {:?} failed: {:?}, this is unexpected but should be safe",
+						replenish_amount, e
+					);
+				}
+			}
+		}
+	}
+
+	/// Settle the surplus and debit via DEX, returns true if anything is
+	/// settled.
+	fn settle_surplus_and_debit() -> bool {
+		let cap = Self::dex_settlement_cap();
+		if cap.is_zero() {
+			return false;
+		}
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut settled = false;
+
+		if Self::is_settlement_ready(SettlementKind::Surplus, now) {
+			// the cap of surplus settlement limits the size of the dynamic policy too
+			let cap = Self::settlement_cap_of(SettlementKind::Surplus).unwrap_or(cap);
+			if Self::settle_surplus(cap) {
+				LastSettledAt::<T>::insert(SettlementKind::Surplus, now);
+				settled = true;
+			}
+		}
+
+		if Self::is_settlement_ready(SettlementKind::Debit, now) {
+			let cap = Self::settlement_cap_of(SettlementKind::Debit).unwrap_or(cap);
+			if Self::settle_debit(cap) {
+				LastSettledAt::<T>::insert(SettlementKind::Debit, now);
+				settled = true;
+			}
+		}
+		settled
+	}
+
+	/// Get the current era index of metrics.
+	pub fn current_metrics_era() -> u32 {
+		let era_length = T::MetricsEraLength::get();
+		if era_length.is_zero() {
+			return Zero::zero();
+		}
+		(frame_system::Pallet::<T>::block_number() / era_length).saturated_into()
+	}
+
+	/// Update the cumulative metrics and the metrics of current era, and
+	/// remove the expired era metrics.
+	fn record_metrics(f: impl Fn(&mut TreasuryMetrics)) {
+		CumulativeMetrics::<T>::mutate(|metrics| f(metrics));
+
+		let era = Self::current_metrics_era();
+		let latest_era = Self::latest_metrics_era();
+		if era > latest_era {
+			// only eras after `era - MaxMetricsEras` are kept, the expired eras must be
+			// not after `latest_era`, so it removes `MaxMetricsEras` eras at most.
+			let max_eras = T::MaxMetricsEras::get();
+			let first_expired_era = latest_era.saturating_add(1).saturating_sub(max_eras);
+			if let Some(last_expired_era) = era.checked_sub(max_eras) {
+				for expired_era in first_expired_era..=last_expired_era.min(latest_era) {
+					EraMetrics::<T>::remove(expired_era);
+				}
+			}
+			LatestMetricsEra::<T>::put(era);
+		}
+		EraMetrics::<T>::mutate(era, |maybe_metrics| {
+			f(maybe_metrics.get_or_insert_with(Default::default))
+		});
+	}
+
+	/// Ensure the stable currency is one of `StableCurrencyIds`, which have
+	/// the surplus and debit pools.
+	fn ensure_stable_currency(stable_currency_id: CurrencyId) -> DispatchResult {
+		ensure!(
+			T::StableCurrencyIds::get().contains(&stable_currency_id),
+			Error::<T>::StableCurrencyNotSupported
+		);
+		Ok(())
+	}
+
+	/// Check the invariants of the accounting of cdp treasury, flag the drift
+	/// between the recorded states and the balances of module accounts.
+	///
+	/// It's expected to be called after `on_finalize`, when the debit pool has
+	/// been offset by the surplus pool and the stability reserve.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), DispatchError> {
+		// the debit pool that exceeds the offset buffer can't coexist with the stable held by
+		// the module account or the stability reserve account
+		for stable_currency_id in T::StableCurrencyIds::get() {
+			if Self::debit_pool_of(stable_currency_id) > Self::debit_offset_buffer_of(stable_currency_id) {
+				ensure!(
+					Self::surplus_pool_of(stable_currency_id).is_zero(),
+					"cdp-treasury: surplus pool is not offset against debit pool"
+				);
+			}
+		}
+		if Self::debit_pool() > Self::debit_offset_buffer() {
+			ensure!(
+				Self::stability_reserve().is_zero(),
+				"cdp-treasury: stability reserve is not drawn against debit pool"
+			);
+		}
+
+		// only the stable currencies have debit pools
+		let stable_currency_ids = T::StableCurrencyIds::get();
+		ensure!(
+			DebitPools::<T>::iter_keys().all(|stable_currency_id| stable_currency_ids.contains(&stable_currency_id)),
+			"cdp-treasury: debit pool of unsupported stable currency"
+		);
+
+		// the collateral in auction is kept by cdp treasury until the auction ends
+		let mut collateral_types: Vec<CurrencyId> = ExpectedCollateralAuctionSize::<T>::iter_keys()
+			.chain(TotalCollateralLiquidated::<T>::iter_keys())
+			.collect();
+		collateral_types.sort();
+		collateral_types.dedup();
+		for currency_id in collateral_types {
+			ensure!(
+				Self::total_collaterals(currency_id)
+					>= T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id),
+				"cdp-treasury: collateral in auction exceeds total collaterals"
+			);
+		}
+
+		// the deployed liquidity share is held by the liquidity account
+		for (currency_id, share) in DeployedLiquidityShares::<T>::iter() {
+			let stable_currency_id = T::RiskManager::get_stable_currency_id(currency_id);
+			let dex_share_currency_id = TradingPair::from_currency_ids(currency_id, stable_currency_id)
+				.ok_or("cdp-treasury: deployed liquidity of invalid pair")?
+				.dex_share_currency_id();
+			ensure!(
+				T::Currency::free_balance(dex_share_currency_id, &Self::liquidity_account_id()) >= share,
+				"cdp-treasury: deployed liquidity share exceeds the balance of liquidity account"
+			);
+		}
+
+		// only the metrics of recent eras are kept
+		let latest_era = Self::latest_metrics_era();
+		let max_eras = T::MaxMetricsEras::get();
+		ensure!(
+			EraMetrics::<T>::iter_keys().all(|era| era <= latest_era && era.saturating_add(max_eras) > latest_era),
+			"cdp-treasury: metrics of expired or future era are kept"
+		);
+
+		// the metrics of kept eras are parts of the cumulative metrics
+		let cumulative = Self::cumulative_metrics();
+		let total = EraMetrics::<T>::iter_values().fold(TreasuryMetrics::default(), |total, metrics| TreasuryMetrics {
+			issued_debit: total.issued_debit.saturating_add(metrics.issued_debit),
+			burned_debit: total.burned_debit.saturating_add(metrics.burned_debit),
+			settled_surplus: total.settled_surplus.saturating_add(metrics.settled_surplus),
+			settled_debit: total.settled_debit.saturating_add(metrics.settled_debit),
+			burned_native: total.burned_native.saturating_add(metrics.burned_native),
+		});
+		ensure!(
+			total.issued_debit <= cumulative.issued_debit
+				&& total.burned_debit <= cumulative.burned_debit
+				&& total.settled_surplus <= cumulative.settled_surplus
+				&& total.settled_debit <= cumulative.settled_debit
+				&& total.burned_native <= cumulative.burned_native,
+			"cdp-treasury: metrics of eras exceed cumulative metrics"
+		);
+
+		Ok(())
+	}
+
+	/// Update the net issuance of current block by `amount` issued to or burned
+	/// from users, ensure the issuance does not exceed the issuance limits.
+	fn update_net_issuance(amount: Balance, issued: bool) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		BlockNetIssuance::<T>::try_mutate(|(block_number, net_issuance)| -> DispatchResult {
+			if *block_number != now {
+				*block_number = now;
+				*net_issuance = Zero::zero();
+			}
+
+			if issued {
+				*net_issuance = net_issuance.saturating_add(amount);
+				if let Some(limit) = Self::max_net_issuance_per_block() {
+					ensure!(*net_issuance <= limit, Error::<T>::IssuanceRateExceeded);
+				}
+				if let Some(limit) = Self::max_net_issuance() {
+					let total_issuance =
+						T::Currency::total_issuance(T::GetStableCurrencyId::get()).saturating_add(amount);
+					ensure!(total_issuance <= limit, Error::<T>::IssuanceRateExceeded);
+				}
+			} else {
+				*net_issuance = net_issuance.saturating_sub(amount);
+			}
+			Ok(())
+		})
+	}
+
+	/// Issue `debit` amount of stable currency to `who` without the issuance
+	/// limits, the debit is added to the debit pool if it is unbacked.
+	fn do_issue_debit(
+		stable_currency_id: CurrencyId,
+		who: &T::AccountId,
+		debit: Balance,
+		backed: bool,
+	) -> DispatchResult {
+		// increase system debit if the debit is unbacked
+		if !backed {
+			Self::on_system_debit(stable_currency_id, debit)?;
+		}
+		T::Currency::deposit(stable_currency_id, who, debit)?;
+		Self::record_metrics(|metrics| metrics.issued_debit = metrics.issued_debit.saturating_add(debit));
+
+		Ok(())
+	}
+
+	/// Burn `debit` amount of stable currency from `who` without netting the
+	/// issuance of current block.
+	fn do_burn_debit(stable_currency_id: CurrencyId, who: &T::AccountId, debit: Balance) -> DispatchResult {
+		T::Currency::withdraw(stable_currency_id, who, debit)?;
+		Self::record_metrics(|metrics| metrics.burned_debit = metrics.burned_debit.saturating_add(debit));
+
+		Ok(())
+	}
+
+	/// Record `amount` of collateral swapped via DEX, ensure the swapped amount
+	/// in current block and metrics era does not exceed the swap budget.
+	fn use_collateral_swap_budget(currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		let budget = Self::collateral_swap_budgets(currency_id);
+		if budget.per_block.is_none() && budget.per_era.is_none() {
+			return Ok(());
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let era = Self::current_metrics_era();
+		CollateralSwapUsages::<T>::try_mutate(currency_id, |usage| -> DispatchResult {
+			if usage.block != now {
+				usage.block = now;
+				usage.block_swapped = Zero::zero();
+			}
+			if usage.era != era {
+				usage.era = era;
+				usage.era_swapped = Zero::zero();
+			}
+			usage.block_swapped = usage.block_swapped.saturating_add(amount);
+			usage.era_swapped = usage.era_swapped.saturating_add(amount);
+
+			ensure!(
+				budget.per_block.map_or(true, |limit| usage.block_swapped <= limit)
+					&& budget.per_era.map_or(true, |limit| usage.era_swapped <= limit),
+				Error::<T>::ExceedCollateralSwapBudget
+			);
+			Ok(())
+		})
+	}
+
+	/// Update `param` to `value` after `ParamsUpdateDelay`, or immediately if
+	/// the delay is zero.
+	fn schedule_param_update(param: TreasuryParam, value: Balance) {
+		let delay = T::ParamsUpdateDelay::get();
+		if delay.is_zero() {
+			Self::do_update_param(param, value);
+		} else {
+			let activate_at = frame_system::Pallet::<T>::block_number().saturating_add(delay);
+			if let Some(scheduled_at) = ScheduledParamUpdateBlocks::<T>::take(param) {
+				ScheduledParamUpdates::<T>::remove(scheduled_at, param);
+			}
+			ScheduledParamUpdates::<T>::insert(activate_at, param, value);
+			ScheduledParamUpdateBlocks::<T>::insert(param, activate_at);
+			Self::deposit_event(Event::ParamUpdateScheduled {
+				param,
+				value,
+				activate_at,
+			});
+		}
+	}
+
+	fn do_update_param(param: TreasuryParam, value: Balance) {
+		match param {
+			TreasuryParam::ExpectedCollateralAuctionSize(currency_id) => {
+				ExpectedCollateralAuctionSize::<T>::insert(currency_id, value);
+				Self::deposit_event(Event::ExpectedCollateralAuctionSizeUpdated {
+					collateral_type: currency_id,
+					new_size: value,
+				});
+			}
+			TreasuryParam::DebitOffsetBuffer => DebitOffsetBuffer::<T>::mutate(|v| {
+				if *v != value {
+					*v = value;
+					Self::deposit_event(Event::DebitOffsetBufferUpdated { amount: value });
+				}
+			}),
+			TreasuryParam::SurplusBufferSize => SurplusBufferSize::<T>::mutate(|v| {
+				if *v != value {
+					*v = value;
+					Self::deposit_event(Event::SurplusBufferSizeUpdated { amount: value });
+				}
+			}),
+			TreasuryParam::ExpectedCollateralAuctionValue(currency_id) => {
+				ExpectedCollateralAuctionValue::<T>::insert(currency_id, value);
+				Self::deposit_event(Event::ExpectedCollateralAuctionValueUpdated {
+					collateral_type: currency_id,
+					new_value: value,
+				});
+			}
+			TreasuryParam::ExpectedStableAuctionValue(stable_currency_id) => {
+				ExpectedStableAuctionValue::<T>::insert(stable_currency_id, value);
+				Self::deposit_event(Event::ExpectedStableAuctionValueUpdated {
+					stable_currency_id,
+					new_value: value,
+				});
+			}
+		}
+	}
+
+	/// Activate the scheduled parameter updates which are due at `now`, return
+	/// the weight used.
+	fn activate_scheduled_param_updates(now: BlockNumberFor<T>) -> Weight {
+		let mut activated_count: u64 = 0;
+		for (param, value) in ScheduledParamUpdates::<T>::drain_prefix(now) {
+			activated_count += 1;
+			ScheduledParamUpdateBlocks::<T>::remove(param);
+			Self::do_update_param(param, value);
+			Self::deposit_event(Event::ParamUpdateActivated { param, value });
+		}
+
+		T::DbWeight::get().reads_writes(activated_count.saturating_add(1), activated_count.saturating_mul(3))
+	}
+
+	/// Whether the min interval has passed since the last settlement of `kind`.
+	fn is_settlement_ready(kind: SettlementKind, now: BlockNumberFor<T>) -> bool {
+		let min_interval = Self::settlement_min_interval(kind);
+		min_interval.is_zero()
+			|| Self::last_settled_at(kind).map_or(true, |last| now >= last.saturating_add(min_interval))
+	}
+
+	/// Settle the surplus exceeding the surplus buffer, return whether any is settled.
+	/// The settlement size is limited by `cap`, whether it's sized by the dynamic
+	/// policy or not.
+	fn settle_surplus(cap: Balance) -> bool {
+		// The part of the surplus pool that exceeds the surplus buffer is distributed
+		let excess_surplus = Self::surplus_pool().saturating_sub(Self::surplus_buffer_size());
+		let surplus_amount = match Self::surplus_settlement_size() {
+			Some(policy) => policy.settlement_size(excess_surplus).min(cap),
+			None => sp_std::cmp::min(excess_surplus, cap),
+		};
+		let distribution = Self::surplus_distribution();
+		let mut settled = false;
+
+		let buyback_amount = distribution.buyback.mul_floor(surplus_amount);
+		if !buyback_amount.is_zero() {
+			match Self::settle_surplus_via_dex(buyback_amount) {
+				Ok(_) => settled = true,
+				Err(e) => {
+					log::debug!(
+						target: "cdp-treasury",
+						"settle_surplus_via_dex: Attempt to settle surplus {:?} failed: {:?}, remain it in surplus pool",
+						buyback_amount, e
+					);
+				}
+			}
+		}
+
+		let incentives_amount = distribution.incentives.mul_floor(surplus_amount);
+		if !incentives_amount.is_zero() {
+			match Self::withdraw_surplus(
+				T::GetStableCurrencyId::get(),
+				&T::IncentivesRewardsSource::get(),
+				incentives_amount,
+			) {
+				Ok(_) => {
+					settled = true;
+					Self::deposit_event(Event::SurplusDistributedToIncentives {
+						amount: incentives_amount,
+					});
+				}
+				Err(e) => {
+					log::debug!(
+						target: "cdp-treasury",
+						"withdraw_surplus: Attempt to distribute surplus {:?} to incentives failed: {:?}, remain it in surplus pool",
+						incentives_amount, e
+					);
+				}
+			}
+		}
+
+		settled
+	}
+
+	/// Settle the debit exceeding the debit offset buffer, return whether any is settled.
+	fn settle_debit(cap: Balance) -> bool {
+		// The part of the debit pool that exceeds the debit offset buffer can be covered by native
+		let debit_amount = sp_std::cmp::min(Self::debit_pool().saturating_sub(Self::debit_offset_buffer()), cap);
+		if debit_amount.is_zero() {
+			return false;
+		}
+
+		match Self::settle_debit_via_dex(debit_amount) {
+			Ok(_) => true,
+			Err(e) => {
+				log::debug!(
+					target: "cdp-treasury",
+					"settle_debit_via_dex: Attempt to settle debit {:?} failed: {:?}, remain it in debit pool",
+					debit_amount, e
+				);
+				false
+			}
+		}
+	}
+
+	/// The acceptable max slippage of swapping `currency_id` via DEX, fallback
+	/// to `DefaultMaxSwapSlippage` if it's not set.
+	pub fn get_max_swap_slippage(currency_id: CurrencyId) -> Ratio {
+		Self::max_swap_slippage(currency_id).unwrap_or_else(T::DefaultMaxSwapSlippage::get)
+	}
+
+	/// The minimum target amount of swapping `supply_amount` `supply_currency_id`
+	/// to `target_currency_id`, derived from the oracle price with
+	/// `max_slippage`.
+	fn oracle_bounded_min_target(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+		max_slippage: Ratio,
+	) -> Result<Balance, DispatchError> {
+		let price =
+			T::PriceSource::get_relative_price(supply_currency_id, target_currency_id).ok_or(Error::<T>::CannotSwap)?;
+		Ok(Ratio::one()
+			.saturating_sub(max_slippage)
+			.saturating_mul_int(price.saturating_mul_int(supply_amount)))
+	}
+
+	/// Swap `surplus_amount` stable currency to native currency and burn it.
+	#[transactional]
+	fn settle_surplus_via_dex(surplus_amount: Balance) -> DispatchResult {
+		let native_currency_id = T::GetNativeCurrencyId::get();
+		let min_target_amount = Self::oracle_bounded_min_target(
+			T::GetStableCurrencyId::get(),
+			native_currency_id,
+			surplus_amount,
+			Self::get_max_swap_slippage(native_currency_id),
+		)?;
+		let (_, burned_native_amount) = T::Swap::swap(
+			&Self::account_id(),
+			T::GetStableCurrencyId::get(),
+			native_currency_id,
+			SwapLimit::ExactSupply(surplus_amount, min_target_amount),
+		)?;
+		T::Currency::withdraw(native_currency_id, &Self::account_id(), burned_native_amount)?;
+
+		Self::record_metrics(|metrics| {
+			metrics.settled_surplus = metrics.settled_surplus.saturating_add(surplus_amount);
+			metrics.burned_native = metrics.burned_native.saturating_add(burned_native_amount);
+		});
+
+		Self::deposit_event(Event::SurplusSettledViaDex {
+			surplus_amount,
+			burned_native_amount,
+		});
+		Ok(())
+	}
+
+	/// Sell native currency held by cdp treasury to get `debit_amount` stable
+	/// currency, and burn it to offset debit pool.
+	#[transactional]
+	fn settle_debit_via_dex(debit_amount: Balance) -> DispatchResult {
+		let native_currency_id = T::GetNativeCurrencyId::get();
+		let native_balance = T::Currency::free_balance(native_currency_id, &Self::account_id());
+		ensure!(!native_balance.is_zero(), Error::<T>::CannotSwap);
+
+		// calculate the max supply amount by the oracle price of native with premium
+		let premium =
+			Self::debit_settlement_premium().unwrap_or_else(|| Self::get_max_swap_slippage(native_currency_id));
+		let price = T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), native_currency_id)
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+		let max_supply_amount = Ratio::one()
+			.saturating_add(premium)
+			.saturating_mul_int(price.saturating_mul_int(debit_amount))
+			.min(native_balance);
+
+		let (sold_native_amount, actual_debit_amount) = T::Swap::swap(
+			&Self::account_id(),
+			native_currency_id,
+			T::GetStableCurrencyId::get(),
+			SwapLimit::ExactTarget(max_supply_amount, debit_amount),
+		)?;
+		Self::do_burn_debit(T::GetStableCurrencyId::get(), &Self::account_id(), actual_debit_amount)?;
+		DebitPools::<T>::mutate(T::GetStableCurrencyId::get(), |debit| {
+			*debit = debit.saturating_sub(actual_debit_amount)
+		});
+		Self::record_metrics(|metrics| {
+			metrics.settled_debit = metrics.settled_debit.saturating_add(actual_debit_amount)
+		});
+
+		Self::deposit_event(Event::DebitSettledViaDex {
+			sold_native_amount,
+			debit_amount: actual_debit_amount,
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+
+	fn is_stable_currency(stable_currency_id: Self::CurrencyId) -> bool {
+		Self::ensure_stable_currency(stable_currency_id).is_ok()
+	}
+
+	fn get_stable_currency_id(currency_id: Self::CurrencyId) -> Self::CurrencyId {
+		T::RiskManager::get_stable_currency_id(currency_id)
+	}
+
+	fn get_surplus_pool(stable_currency_id: Self::CurrencyId) -> Self::Balance {
+		if Self::ensure_stable_currency(stable_currency_id).is_err() {
+			return Zero::zero();
+		}
+		Self::surplus_pool_of(stable_currency_id)
+	}
+
+	fn get_debit_pool(stable_currency_id: Self::CurrencyId) -> Self::Balance {
+		if Self::ensure_stable_currency(stable_currency_id).is_err() {
+			return Zero::zero();
+		}
+		Self::debit_pool_of(stable_currency_id)
+	}
+
+	fn get_total_collaterals(id: Self::CurrencyId) -> Self::Balance {
+		Self::total_claimable_collaterals(id)
+	}
+
+	fn get_debit_proportion(stable_currency_id: Self::CurrencyId, amount: Self::Balance) -> Ratio {
+		if Self::ensure_stable_currency(stable_currency_id).is_err() {
+			return Zero::zero();
+		}
+		let stable_total_supply = T::Currency::total_issuance(stable_currency_id);
 		Ratio::checked_from_rational(amount, stable_total_supply).unwrap_or_default()
 	}
 
-	fn on_system_debit(amount: Self::Balance) -> DispatchResult {
-		DebitPool::<T>::try_mutate(|debit_pool| -> DispatchResult {
+	fn on_system_debit(stable_currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		DebitPools::<T>::try_mutate(stable_currency_id, |debit_pool| -> DispatchResult {
 			*debit_pool = debit_pool.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
 			Ok(())
 		})
 	}
 
-	fn on_system_surplus(amount: Self::Balance) -> DispatchResult {
-		Self::issue_debit(&Self::account_id(), amount, true)
+	fn on_system_surplus(stable_currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		Self::do_issue_debit(stable_currency_id, &Self::account_id(), amount, true)
 	}
 
-	/// This should be the only function in the system that issues stable coin
-	fn issue_debit(who: &T::AccountId, debit: Self::Balance, backed: bool) -> DispatchResult {
-		// increase system debit if the debit is unbacked
-		if !backed {
-			Self::on_system_debit(debit)?;
+	fn on_stability_fee(stable_currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		let incentives_amount = Self::stability_fee_incentives_ratio().mul_floor(amount);
+		if !incentives_amount.is_zero() {
+			Self::do_issue_debit(
+				stable_currency_id,
+				&T::IncentivesRewardsSource::get(),
+				incentives_amount,
+				true,
+			)?;
+			Self::deposit_event(Event::StabilityFeeDistributedToIncentives {
+				amount: incentives_amount,
+			});
 		}
-		T::Currency::deposit(T::GetStableCurrencyId::get(), who, debit)?;
+		Self::on_system_surplus(stable_currency_id, amount.saturating_sub(incentives_amount))
+	}
 
+	fn on_liquidation_penalty(currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
+		if !amount.is_zero() {
+			TotalAssessedLiquidationPenalty::<T>::mutate(currency_id, |v| *v = v.saturating_add(amount));
+			Self::deposit_event(Event::LiquidationPenaltyAssessed {
+				collateral_type: currency_id,
+				amount,
+			});
+		}
 		Ok(())
 	}
 
+	/// This should be the only function in the system that issues stable coin
+	/// to users, subject to the issuance limits of the primary stable currency
+	fn issue_debit(
+		stable_currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		debit: Self::Balance,
+		backed: bool,
+	) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		if stable_currency_id == T::GetStableCurrencyId::get() {
+			Self::update_net_issuance(debit, true)?;
+		}
+		Self::do_issue_debit(stable_currency_id, who, debit, backed)
+	}
+
 	/// This should be the only function in the system that burns stable coin
-	fn burn_debit(who: &T::AccountId, debit: Self::Balance) -> DispatchResult {
-		T::Currency::withdraw(T::GetStableCurrencyId::get(), who, debit)
+	/// from users
+	fn burn_debit(stable_currency_id: Self::CurrencyId, who: &T::AccountId, debit: Self::Balance) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		Self::do_burn_debit(stable_currency_id, who, debit)?;
+		if stable_currency_id == T::GetStableCurrencyId::get() {
+			Self::update_net_issuance(debit, false)?;
+		}
+		Ok(())
 	}
 
-	fn deposit_surplus(from: &T::AccountId, surplus: Self::Balance) -> DispatchResult {
-		T::Currency::transfer(T::GetStableCurrencyId::get(), from, &Self::account_id(), surplus)
+	fn deposit_surplus(
+		stable_currency_id: Self::CurrencyId,
+		from: &T::AccountId,
+		surplus: Self::Balance,
+	) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		T::Currency::transfer(stable_currency_id, from, &Self::account_id(), surplus)
 	}
 
-	fn withdraw_surplus(to: &T::AccountId, surplus: Self::Balance) -> DispatchResult {
-		T::Currency::transfer(T::GetStableCurrencyId::get(), &Self::account_id(), to, surplus)
+	fn withdraw_surplus(
+		stable_currency_id: Self::CurrencyId,
+		to: &T::AccountId,
+		surplus: Self::Balance,
+	) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		T::Currency::transfer(stable_currency_id, &Self::account_id(), to, surplus)
 	}
 
 	fn deposit_collateral(from: &T::AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
@@ -407,6 +2501,16 @@ impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
 	}
 
 	fn withdraw_collateral(to: &T::AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
+		// recall all the deployed liquidity if the collateral held is not enough, it's at least
+		// the shortfall.
+		let shortfall = amount.saturating_sub(Self::total_collaterals(currency_id));
+		let share = Self::deployed_liquidity_shares(currency_id);
+		if !shortfall.is_zero() && !share.is_zero() {
+			Self::do_recall_liquidity(currency_id, share, shortfall, Zero::zero())?;
+		}
+
+		// The remaining collateral may be dusted if it's below existential deposit and cdp treasury is
+		// not in the dust removal whitelist, the module account is still kept alive by its provider.
 		T::Currency::transfer(currency_id, &Self::account_id(), to, amount)
 	}
 }
@@ -440,7 +2544,8 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 			);
 		}
 
-		match currency_id {
+		let stable_currency_id = T::RiskManager::get_stable_currency_id(currency_id);
+		let (actual_supply_amount, actual_target_amount) = match currency_id {
 			CurrencyId::StableAssetPoolToken(stable_asset_id) => {
 				let pool_info = T::StableAsset::pool(stable_asset_id).ok_or(Error::<T>::CannotSwap)?;
 				let updated_balance_info =
@@ -472,22 +2577,41 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 
 					if !amount.is_zero() {
 						let swap_limit = SwapLimit::ExactSupply(amount, 0);
-						let response = T::Swap::swap(
-							&Self::account_id(),
-							redemption_currency,
-							T::GetStableCurrencyId::get(),
-							swap_limit,
-						)?;
+						let response =
+							T::Swap::swap(&Self::account_id(), redemption_currency, stable_currency_id, swap_limit)?;
 						supply_sum = supply_sum.checked_add(response.0).ok_or(ArithmeticError::Overflow)?;
 						target_sum = target_sum.checked_add(response.1).ok_or(ArithmeticError::Overflow)?;
 					}
 				}
 
 				ensure!(target_sum >= target_limit, Error::<T>::CannotSwap);
-				Ok((supply_sum, target_sum))
+				(supply_sum, target_sum)
 			}
-			_ => T::Swap::swap(&Self::account_id(), currency_id, T::GetStableCurrencyId::get(), limit),
+			_ => match Self::collateral_swap_path(currency_id) {
+				Some(swap_path) => T::DEX::swap_with_specific_path(&Self::account_id(), &swap_path, limit)?,
+				None => T::Swap::swap(&Self::account_id(), currency_id, stable_currency_id, limit)?,
+			},
+		};
+
+		// reject the swap if its price is worse than the oracle price by more than max slippage.
+		if let Some(max_slippage) = Self::max_swap_slippage(currency_id) {
+			let oracle_price = T::PriceSource::get_relative_price(currency_id, stable_currency_id)
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
+			let minimum_target_amount = Ratio::one()
+				.saturating_sub(max_slippage)
+				.saturating_mul_int(oracle_price.saturating_mul_int(actual_supply_amount));
+			ensure!(
+				actual_target_amount >= minimum_target_amount,
+				Error::<T>::ExceedMaxSwapSlippage
+			);
 		}
+
+		Self::use_collateral_swap_budget(currency_id, actual_supply_amount)?;
+		TotalCollateralLiquidated::<T>::mutate(currency_id, |amount| {
+			*amount = amount.saturating_add(actual_supply_amount)
+		});
+
+		Ok((actual_supply_amount, actual_target_amount))
 	}
 
 	fn create_collateral_auctions(
@@ -504,27 +2628,7 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 
 		let mut unhandled_collateral_amount = amount;
 		let mut unhandled_target = target;
-		let expected_collateral_auction_size = Self::expected_collateral_auction_size(currency_id);
-		let max_auctions_count: Balance = T::MaxAuctionsCount::get().into();
-		let lots_count = if !splited
-			|| max_auctions_count.is_zero()
-			|| expected_collateral_auction_size.is_zero()
-			|| amount <= expected_collateral_auction_size
-		{
-			One::one()
-		} else {
-			let mut count = amount
-				.checked_div(expected_collateral_auction_size)
-				.expect("collateral auction maximum size is not zero; qed");
-
-			let remainder = amount
-				.checked_rem(expected_collateral_auction_size)
-				.expect("collateral auction maximum size is not zero; qed");
-			if !remainder.is_zero() {
-				count = count.saturating_add(One::one());
-			}
-			sp_std::cmp::min(count, max_auctions_count)
-		};
+		let lots_count = Self::get_collateral_auction_lots_count(currency_id, amount, splited);
 		let average_amount_per_lot = amount.checked_div(lots_count).expect("lots count is at least 1; qed");
 		let average_target_per_lot = target.checked_div(lots_count).expect("lots count is at least 1; qed");
 		let mut created_lots: Balance = Zero::zero();
@@ -544,11 +2648,26 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 				lot_collateral_amount,
 				lot_target,
 			)?;
+			Self::deposit_event(Event::CollateralAuctionLotCreated {
+				collateral_type: currency_id,
+				collateral_amount: lot_collateral_amount,
+				target: lot_target,
+			});
 
 			unhandled_collateral_amount = unhandled_collateral_amount.saturating_sub(lot_collateral_amount);
 			unhandled_target = unhandled_target.saturating_sub(lot_target);
 		}
+		TotalCollateralLiquidated::<T>::mutate(currency_id, |liquidated| {
+			*liquidated = liquidated.saturating_add(amount)
+		});
+
 		let created_auctions: u32 = created_lots.try_into().map_err(|_| ArithmeticError::Overflow)?;
+		Self::deposit_event(Event::CollateralAuctionsCreated {
+			collateral_type: currency_id,
+			lots_count: created_auctions,
+			collateral_amount: amount,
+			target,
+		});
 		Ok(created_auctions)
 	}
 
@@ -573,6 +2692,11 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 	fn max_auction() -> u32 {
 		T::MaxAuctionsCount::get()
 	}
+
+	fn set_expected_collateral_auction_size(currency_id: CurrencyId, size: Balance) -> DispatchResult {
+		Self::schedule_param_update(TreasuryParam::ExpectedCollateralAuctionSize(currency_id), size);
+		Ok(())
+	}
 }
 
 pub struct InitializeDebitOffsetBuffer<T, GetBufferSize>(
@@ -594,3 +2718,31 @@ impl<T: Config, GetBufferSize: Get<Balance>> frame_support::traits::OnRuntimeUpg
 		Weight::from_parts(0, 0)
 	}
 }
+
+pub struct InitializeModuleAccounts<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> frame_support::traits::OnRuntimeUpgrade for InitializeModuleAccounts<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::module_account_initialized() {
+			return T::DbWeight::get().reads(1);
+		}
+		Pallet::<T>::initialize_module_accounts();
+		T::DbWeight::get().reads_writes(1, 4)
+	}
+}
+
+#[frame_support::storage_alias]
+type DebitPool<T: Config> = StorageValue<Pallet<T>, Balance, ValueQuery>;
+
+/// Move the single debit pool into the per-stable `DebitPools` under the primary
+/// stable currency.
+pub struct MigrateDebitPool<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> frame_support::traits::OnRuntimeUpgrade for MigrateDebitPool<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if !DebitPool::<T>::exists() {
+			return T::DbWeight::get().reads(1);
+		}
+		let debit = DebitPool::<T>::take();
+		DebitPools::<T>::mutate(T::GetStableCurrencyId::get(), |v| *v = v.saturating_add(debit));
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+}
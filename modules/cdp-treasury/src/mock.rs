@@ -23,10 +23,10 @@
 use super::*;
 use frame_support::{
 	construct_runtime, ord_parameter_types, parameter_types,
-	traits::{ConstU128, ConstU32, ConstU64, EitherOfDiverse, Everything, Nothing},
+	traits::{ConstU128, ConstU32, ConstU64, Contains, EitherOfDiverse, Everything},
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
-use module_support::SpecificJointsSwap;
+use module_support::{Price, SpecificJointsSwap};
 use nutsfinance_stable_asset::traits::StableAsset;
 use nutsfinance_stable_asset::{
 	PoolTokenIndex, RedeemProportionResult, StableAssetPoolId, StableAssetPoolInfo, SwapResult,
@@ -34,7 +34,10 @@ use nutsfinance_stable_asset::{
 use orml_traits::parameter_type_with_key;
 use primitives::{DexShare, TokenSymbol, TradingPair};
 use sp_core::H256;
-use sp_runtime::{traits::IdentityLookup, BuildStorage};
+use sp_runtime::{
+	traits::{IdentityLookup, One as OneT},
+	BuildStorage,
+};
 use sp_std::cell::RefCell;
 
 pub type AccountId = u128;
@@ -47,8 +50,10 @@ pub const BOB: AccountId = 1;
 pub const CHARLIE: AccountId = 2;
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const KUSD: CurrencyId = CurrencyId::Token(TokenSymbol::KUSD);
 pub const BTC: CurrencyId = CurrencyId::ForeignAsset(255);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const KSM: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
 pub const STABLE_ASSET_LP: CurrencyId = CurrencyId::StableAssetPoolToken(0);
 pub const LP_AUSD_DOT: CurrencyId =
 	CurrencyId::DexShare(DexShare::Token(TokenSymbol::AUSD), DexShare::Token(TokenSymbol::DOT));
@@ -84,11 +89,22 @@ impl frame_system::Config for Runtime {
 }
 
 parameter_type_with_key! {
-	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
-		Default::default()
+	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
+		if *currency_id == KSM {
+			10
+		} else {
+			Default::default()
+		}
 	};
 }
 
+pub struct MockDustRemovalWhitelist;
+impl Contains<AccountId> for MockDustRemovalWhitelist {
+	fn contains(a: &AccountId) -> bool {
+		*a == CDPTreasuryModule::account_id()
+	}
+}
+
 impl orml_tokens::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = Balance;
@@ -100,7 +116,7 @@ impl orml_tokens::Config for Runtime {
 	type MaxLocks = ();
 	type MaxReserves = ();
 	type ReserveIdentifier = [u8; 8];
-	type DustRemovalWhitelist = Nothing;
+	type DustRemovalWhitelist = MockDustRemovalWhitelist;
 }
 
 impl pallet_balances::Config for Runtime {
@@ -134,11 +150,13 @@ impl orml_currencies::Config for Runtime {
 
 parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD, KUSD];
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::from_currency_ids(AUSD, BTC).unwrap(),
 		TradingPair::from_currency_ids(AUSD, DOT).unwrap(),
 		TradingPair::from_currency_ids(BTC, DOT).unwrap(),
+		TradingPair::from_currency_ids(AUSD, ACA).unwrap(),
 	];
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 }
@@ -155,6 +173,8 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = TreasuryAccount;
+	type RuntimeCall = RuntimeCall;
 }
 
 thread_local! {
@@ -197,28 +217,70 @@ ord_parameter_types! {
 }
 
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(20, 100);
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub const TreasuryAccount: AccountId = 10;
+	pub const IncentivesRewardsSource: AccountId = 11;
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
 		vec![DOT],
 	];
+	pub static ParamsUpdateDelay: u64 = 0;
 }
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
 }
 
+thread_local! {
+	static ACA_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+	static DOT_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+}
+
+pub struct MockPriceSource;
+impl MockPriceSource {
+	pub fn set_price(currency_id: CurrencyId, price: Option<Price>) {
+		match currency_id {
+			ACA => ACA_PRICE.with(|v| *v.borrow_mut() = price),
+			DOT => DOT_PRICE.with(|v| *v.borrow_mut() = price),
+			_ => {}
+		}
+	}
+}
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_price(currency_id: CurrencyId) -> Option<Price> {
+		match currency_id {
+			ACA => ACA_PRICE.with(|v| *v.borrow()),
+			DOT => DOT_PRICE.with(|v| *v.borrow()),
+			AUSD => Some(Price::one()),
+			_ => None,
+		}
+	}
+}
+
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EitherOfDiverse<EnsureRoot<AccountId>, EnsureSignedBy<One, AccountId>>;
 	type DEX = DEXModule;
 	type Swap = SpecificJointsSwap<DEXModule, AlternativeSwapPathJointList>;
+	type PriceSource = MockPriceSource;
+	type TradingPathLimit = ConstU32<4>;
 	type MaxAuctionsCount = ConstU32<5>;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = module_support::DefaultRiskManager<GetStableCurrencyId>;
+	type MaxCollateralTypes = ConstU32<5>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = ();
 	type TreasuryAccount = TreasuryAccount;
+	type IncentivesRewardsSource = IncentivesRewardsSource;
+	type MetricsEraLength = ConstU64<10>;
+	type MaxMetricsEras = ConstU32<2>;
+	type ParamsUpdateDelay = ParamsUpdateDelay;
 	type WeightInfo = ();
 	type StableAsset = MockStableAsset;
 }
@@ -248,6 +310,7 @@ impl Default for ExtBuilder {
 				(ALICE, AUSD, 1000),
 				(ALICE, BTC, 1000),
 				(ALICE, STABLE_ASSET_LP, 1000),
+				(ALICE, KSM, 1000),
 				(BOB, DOT, 1000),
 				(BOB, AUSD, 1000),
 				(BOB, BTC, 1000),
@@ -271,6 +334,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 1000), (BOB, 1000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		module_dex::GenesisConfig::<Runtime> {
 			initial_listing_trading_pairs: vec![],
 			initial_enabled_trading_pairs: EnabledTradingPairs::get(),
@@ -279,10 +348,21 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		cdp_treasury::GenesisConfig::<Runtime>::default()
+			.assimilate_storage(&mut t)
+			.unwrap();
+
 		t.into()
 	}
 }
 
+/// Finalize the block of cdp treasury, and check the invariants after it like
+/// `try_state` does after every block.
+pub fn finalize_block(n: BlockNumber) {
+	CDPTreasuryModule::on_finalize(n);
+	assert_eq!(CDPTreasuryModule::do_try_state(), Ok(()));
+}
+
 pub struct MockStableAsset;
 
 impl StableAsset for MockStableAsset {
@@ -21,9 +21,9 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
 use mock::{RuntimeEvent, *};
-use module_support::SwapError;
+use module_support::{Price, SwapError};
 use sp_runtime::traits::BadOrigin;
 
 #[test]
@@ -52,10 +52,10 @@ fn total_collaterals_work() {
 fn on_system_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-		assert_ok!(CDPTreasuryModule::on_system_debit(1000));
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 1000));
 		assert_eq!(CDPTreasuryModule::debit_pool(), 1000);
 		assert_noop!(
-			CDPTreasuryModule::on_system_debit(Balance::max_value()),
+			CDPTreasuryModule::on_system_debit(AUSD, Balance::max_value()),
 			ArithmeticError::Overflow,
 		);
 	});
@@ -66,7 +66,7 @@ fn on_system_surplus_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 0);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
-		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 1000));
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 1000);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
 	});
@@ -78,62 +78,190 @@ fn offset_surplus_and_debit_on_finalize_work() {
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 0);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 1000));
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 1000);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
-		CDPTreasuryModule::on_finalize(1);
+		finalize_block(1);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 1000);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-		assert_ok!(CDPTreasuryModule::on_system_debit(300));
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 300));
 		assert_eq!(CDPTreasuryModule::debit_pool(), 300);
-		CDPTreasuryModule::on_finalize(2);
+		finalize_block(2);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 700);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 700);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-		assert_ok!(CDPTreasuryModule::on_system_debit(800));
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 800));
 		assert_eq!(CDPTreasuryModule::debit_pool(), 800);
-		CDPTreasuryModule::on_finalize(3);
+		finalize_block(3);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 0);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
 	});
 }
 
+#[test]
+fn surplus_and_debit_pools_per_stable_currency() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::on_system_debit(KUSD, 300));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(KUSD, 1000));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 100));
+		assert_eq!(CDPTreasuryModule::get_debit_pool(KUSD), 300);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(KUSD), 1000);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+
+		// the debit of each stable currency is only offset by its own surplus
+		finalize_block(1);
+		assert_eq!(CDPTreasuryModule::get_debit_pool(KUSD), 0);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(KUSD), 700);
+		assert_eq!(Currencies::total_issuance(KUSD), 700);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+
+		assert_ok!(CDPTreasuryModule::issue_debit(KUSD, &ALICE, 500, false));
+		assert_eq!(Currencies::free_balance(KUSD, &ALICE), 500);
+		assert_eq!(CDPTreasuryModule::get_debit_pool(KUSD), 500);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_ok!(CDPTreasuryModule::burn_debit(KUSD, &ALICE, 200));
+		assert_eq!(Currencies::free_balance(KUSD, &ALICE), 300);
+	});
+}
+
+#[test]
+fn migrate_debit_pool_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		DebitPool::<Runtime>::put(1000);
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 100));
+
+		MigrateDebitPool::<Runtime>::on_runtime_upgrade();
+		assert!(!DebitPool::<Runtime>::exists());
+		assert_eq!(CDPTreasuryModule::debit_pool(), 1100);
+		assert_eq!(CDPTreasuryModule::get_debit_pool(KUSD), 0);
+	});
+}
+
 #[test]
 fn issue_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 1000);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 
-		assert_ok!(CDPTreasuryModule::issue_debit(&ALICE, 1000, true));
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 1000, true));
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 2000);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 
-		assert_ok!(CDPTreasuryModule::issue_debit(&ALICE, 1000, false));
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 1000, false));
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 3000);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 1000);
 	});
 }
 
+#[test]
+fn unsupported_stable_currency_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CDPTreasuryModule::issue_debit(DOT, &ALICE, 1000, true),
+			Error::<Runtime>::StableCurrencyNotSupported
+		);
+		assert_noop!(
+			CDPTreasuryModule::burn_debit(DOT, &ALICE, 100),
+			Error::<Runtime>::StableCurrencyNotSupported
+		);
+		assert_noop!(
+			CDPTreasuryModule::on_system_debit(DOT, 100),
+			Error::<Runtime>::StableCurrencyNotSupported
+		);
+		assert_noop!(
+			CDPTreasuryModule::deposit_surplus(DOT, &ALICE, 100),
+			Error::<Runtime>::StableCurrencyNotSupported
+		);
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 1000);
+
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 100));
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(AUSD), 100);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(DOT), 0);
+		assert_eq!(CDPTreasuryModule::get_debit_pool(DOT), 0);
+	});
+}
+
 #[test]
 fn burn_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 1000);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-		assert_ok!(CDPTreasuryModule::burn_debit(&ALICE, 300));
+		assert_ok!(CDPTreasuryModule::burn_debit(AUSD, &ALICE, 300));
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 700);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 	});
 }
 
+#[test]
+fn issue_debit_limited_by_issuance_limits() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_issuance_limits(RuntimeOrigin::signed(5), Some(500), Some(800)),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_issuance_limits(
+			RuntimeOrigin::signed(1),
+			Some(500),
+			Some(2800)
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::IssuanceLimitsUpdated {
+			per_block: Some(500),
+			total: Some(2800),
+		}));
+		assert_eq!(Currencies::total_issuance(AUSD), 2000);
+
+		// exceed the per block limit
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 400, true));
+		assert_noop!(
+			CDPTreasuryModule::issue_debit(AUSD, &ALICE, 101, true),
+			Error::<Runtime>::IssuanceRateExceeded
+		);
+		// the burned debit is netted in the block
+		assert_ok!(CDPTreasuryModule::burn_debit(AUSD, &ALICE, 100));
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 200, false));
+		assert_eq!(CDPTreasuryModule::block_net_issuance(), (1, 500));
+
+		// exceed the total limit
+		System::set_block_number(2);
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 300, true));
+		assert_eq!(CDPTreasuryModule::block_net_issuance(), (2, 300));
+		assert_eq!(Currencies::total_issuance(AUSD), 2800);
+		assert_noop!(
+			CDPTreasuryModule::issue_debit(AUSD, &ALICE, 1, true),
+			Error::<Runtime>::IssuanceRateExceeded
+		);
+
+		// the system surplus and the stability fee are not limited
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 300));
+		assert_ok!(CDPTreasuryModule::on_stability_fee(AUSD, 300));
+		assert_eq!(CDPTreasuryModule::block_net_issuance(), (2, 300));
+		assert_eq!(Currencies::total_issuance(AUSD), 3400);
+		assert_noop!(
+			CDPTreasuryModule::issue_debit(AUSD, &ALICE, 1, true),
+			Error::<Runtime>::IssuanceRateExceeded
+		);
+
+		assert_ok!(CDPTreasuryModule::set_issuance_limits(
+			RuntimeOrigin::signed(1),
+			None,
+			None
+		));
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 1000, true));
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 2800);
+	});
+}
+
 #[test]
 fn deposit_surplus_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 1000);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 0);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
-		assert_ok!(CDPTreasuryModule::deposit_surplus(&ALICE, 300));
+		assert_ok!(CDPTreasuryModule::deposit_surplus(AUSD, &ALICE, 300));
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 700);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 300);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 300);
@@ -143,12 +271,12 @@ fn deposit_surplus_work() {
 #[test]
 fn withdraw_surplus_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(CDPTreasuryModule::deposit_surplus(&ALICE, 300));
+		assert_ok!(CDPTreasuryModule::deposit_surplus(AUSD, &ALICE, 300));
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 700);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 300);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 300);
 
-		assert_ok!(CDPTreasuryModule::withdraw_surplus(&ALICE, 200));
+		assert_ok!(CDPTreasuryModule::withdraw_surplus(AUSD, &ALICE, 200));
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 900);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 100);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
@@ -196,7 +324,7 @@ fn get_total_collaterals_work() {
 fn get_debit_proportion_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(
-			CDPTreasuryModule::get_debit_proportion(100),
+			CDPTreasuryModule::get_debit_proportion(AUSD, 100),
 			Ratio::saturating_from_rational(100, Currencies::total_issuance(AUSD))
 		);
 	});
@@ -428,6 +556,39 @@ fn create_collateral_auctions_work() {
 	});
 }
 
+#[test]
+fn create_collateral_auctions_emit_events() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Currencies::deposit(BTC, &CDPTreasuryModule::account_id(), 10000));
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			RuntimeOrigin::signed(1),
+			BTC,
+			300
+		));
+
+		assert_eq!(
+			CDPTreasuryModule::create_collateral_auctions(BTC, 600, 1000, ALICE, true),
+			Ok(2)
+		);
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::CollateralAuctionLotCreated {
+				collateral_type: BTC,
+				collateral_amount: 300,
+				target: 500,
+			},
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::CollateralAuctionsCreated {
+				collateral_type: BTC,
+				lots_count: 2,
+				collateral_amount: 600,
+				target: 1000,
+			},
+		));
+	});
+}
+
 #[test]
 fn remove_liquidity_for_lp_collateral_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -493,10 +654,73 @@ fn set_expected_collateral_auction_size_work() {
 	});
 }
 
+#[test]
+fn set_collateral_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_auction_params(
+				RuntimeOrigin::signed(5),
+				vec![(BTC, 200)].try_into().unwrap()
+			),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_params(
+			RuntimeOrigin::signed(1),
+			vec![(BTC, 200), (DOT, 300)].try_into().unwrap()
+		));
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::ExpectedCollateralAuctionSizeUpdated {
+				collateral_type: BTC,
+				new_size: 200,
+			},
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::ExpectedCollateralAuctionSizeUpdated {
+				collateral_type: DOT,
+				new_size: 300,
+			},
+		));
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(BTC), 200);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(DOT), 300);
+
+		// the per-collateral updates are emitted when the scheduled update activates
+		ParamsUpdateDelay::set(10);
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_params(
+			RuntimeOrigin::signed(1),
+			vec![(BTC, 400), (DOT, 500)].try_into().unwrap()
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::ParamUpdateScheduled {
+			param: TreasuryParam::ExpectedCollateralAuctionSize(DOT),
+			value: 500,
+			activate_at: 11,
+		}));
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(BTC), 200);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(DOT), 300);
+
+		System::reset_events();
+		CDPTreasuryModule::on_initialize(11);
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::ExpectedCollateralAuctionSizeUpdated {
+				collateral_type: BTC,
+				new_size: 400,
+			},
+		));
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::ExpectedCollateralAuctionSizeUpdated {
+				collateral_type: DOT,
+				new_size: 500,
+			},
+		));
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(BTC), 400);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(DOT), 500);
+	});
+}
+
 #[test]
 fn extract_surplus_to_treasury_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 1000));
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 1000);
 		assert_eq!(Currencies::free_balance(AUSD, &TreasuryAccount::get()), 0);
@@ -638,11 +862,100 @@ fn set_debit_offset_buffer_work() {
 	});
 }
 
+#[test]
+fn param_updates_delayed_by_params_update_delay() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		ParamsUpdateDelay::set(10);
+
+		assert_ok!(CDPTreasuryModule::set_debit_offset_buffer(
+			RuntimeOrigin::signed(1),
+			200
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::ParamUpdateScheduled {
+			param: TreasuryParam::DebitOffsetBuffer,
+			value: 200,
+			activate_at: 11,
+		}));
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			RuntimeOrigin::signed(1),
+			BTC,
+			100
+		));
+		assert_eq!(CDPTreasuryModule::debit_offset_buffer(), 0);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(BTC), 0);
+
+		// the new update of the same param replaces the scheduled one
+		System::set_block_number(5);
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			RuntimeOrigin::signed(1),
+			BTC,
+			300
+		));
+		assert_eq!(
+			CDPTreasuryModule::scheduled_param_update_blocks(TreasuryParam::ExpectedCollateralAuctionSize(BTC)),
+			Some(15)
+		);
+		assert_eq!(
+			CDPTreasuryModule::scheduled_param_updates(11, TreasuryParam::ExpectedCollateralAuctionSize(BTC)),
+			None
+		);
+		assert_eq!(
+			CDPTreasuryModule::scheduled_param_updates(15, TreasuryParam::ExpectedCollateralAuctionSize(BTC)),
+			Some(300)
+		);
+
+		// the extended interface is subject to the same delay
+		assert_ok!(
+			<CDPTreasuryModule as CDPTreasuryExtended<AccountId>>::set_expected_collateral_auction_size(DOT, 50)
+		);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(DOT), 0);
+		assert_eq!(
+			CDPTreasuryModule::scheduled_param_updates(15, TreasuryParam::ExpectedCollateralAuctionSize(DOT)),
+			Some(50)
+		);
+
+		CDPTreasuryModule::on_initialize(10);
+		assert_eq!(CDPTreasuryModule::debit_offset_buffer(), 0);
+
+		CDPTreasuryModule::on_initialize(11);
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::DebitOffsetBufferUpdated { amount: 200 },
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::ParamUpdateActivated {
+			param: TreasuryParam::DebitOffsetBuffer,
+			value: 200,
+		}));
+		assert_eq!(CDPTreasuryModule::debit_offset_buffer(), 200);
+		assert_eq!(
+			CDPTreasuryModule::scheduled_param_update_blocks(TreasuryParam::DebitOffsetBuffer),
+			None
+		);
+		assert_eq!(
+			CDPTreasuryModule::scheduled_param_updates(11, TreasuryParam::DebitOffsetBuffer),
+			None
+		);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(BTC), 0);
+
+		CDPTreasuryModule::on_initialize(15);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(BTC), 300);
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_size(DOT), 50);
+		assert_eq!(
+			CDPTreasuryModule::scheduled_param_update_blocks(TreasuryParam::ExpectedCollateralAuctionSize(BTC)),
+			None
+		);
+		assert_eq!(
+			CDPTreasuryModule::scheduled_param_updates(15, TreasuryParam::ExpectedCollateralAuctionSize(BTC)),
+			None
+		);
+	});
+}
+
 #[test]
 fn offset_surplus_and_debit_limited_by_debit_offset_buffer() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
-		assert_ok!(CDPTreasuryModule::on_system_debit(2000));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 1000));
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 2000));
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 2000);
 		assert_eq!(CDPTreasuryModule::debit_offset_buffer(), 0);
@@ -658,7 +971,7 @@ fn offset_surplus_and_debit_limited_by_debit_offset_buffer() {
 			100
 		));
 		assert_eq!(CDPTreasuryModule::debit_offset_buffer(), 100);
-		assert_ok!(CDPTreasuryModule::on_system_surplus(2000));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 2000));
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 2000);
 
 		// keep the buffer for debit pool when surplus is enough
@@ -672,7 +985,7 @@ fn offset_surplus_and_debit_limited_by_debit_offset_buffer() {
 			200
 		));
 		assert_eq!(CDPTreasuryModule::debit_offset_buffer(), 200);
-		assert_ok!(CDPTreasuryModule::on_system_debit(1400));
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 1400));
 		assert_eq!(CDPTreasuryModule::debit_pool(), 1500);
 
 		CDPTreasuryModule::offset_surplus_and_debit();
@@ -681,3 +994,1778 @@ fn offset_surplus_and_debit_limited_by_debit_offset_buffer() {
 		assert_eq!(CDPTreasuryModule::debit_offset_buffer(), 200);
 	});
 }
+
+#[test]
+fn set_surplus_buffer_size_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::surplus_buffer_size(), 0);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_buffer_size(RuntimeOrigin::signed(5), 200),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			200
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::SurplusBufferSizeUpdated { amount: 200 },
+		));
+		assert_eq!(CDPTreasuryModule::surplus_buffer_size(), 200);
+	});
+}
+
+#[test]
+fn set_dex_settlement_cap_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::dex_settlement_cap(), 0);
+		assert_noop!(
+			CDPTreasuryModule::set_dex_settlement_cap(RuntimeOrigin::signed(5), 200),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(RuntimeOrigin::signed(1), 200));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::DexSettlementCapUpdated {
+			amount: 200,
+		}));
+		assert_eq!(CDPTreasuryModule::dex_settlement_cap(), 200);
+	});
+}
+
+#[test]
+fn trigger_settlement_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			AUSD,
+			ACA,
+			1000,
+			500,
+			0,
+			false
+		));
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(2)));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			100
+		));
+
+		// DEX settlement is disabled when cap is zero
+		assert_noop!(
+			CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)),
+			Error::<Runtime>::SettlementDisabled
+		);
+
+		// DEX settlement is not triggered by on_finalize
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(RuntimeOrigin::signed(1), 200));
+		finalize_block(1);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+
+		// buy back native by the surplus exceeding the buffer, limited by the cap
+		let native_issuance = Currencies::total_issuance(ACA);
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 200,
+			burned_native_amount: 83,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 300);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, ACA), (1200, 417));
+		assert_eq!(Currencies::free_balance(ACA, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(Currencies::total_issuance(ACA), native_issuance - 83);
+
+		// debit remains in pool if the treasury has no native to sell
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 400));
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+
+		// sell native of the treasury to cover debit
+		assert_ok!(Currencies::deposit(ACA, &CDPTreasuryModule::account_id(), 100));
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::DebitSettledViaDex {
+			sold_native_amount: 38,
+			debit_amount: 100,
+		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(Currencies::free_balance(ACA, &CDPTreasuryModule::account_id()), 62);
+	});
+}
+
+#[test]
+fn trigger_settlement_fails_if_dex_price_is_skewed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// DEX price of ACA is 2 AUSD
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			AUSD,
+			ACA,
+			1000,
+			500,
+			0,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			100
+		));
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(RuntimeOrigin::signed(1), 200));
+
+		// oracle price of ACA is 1 AUSD, buying back native via DEX exceeds the slippage
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, ACA), (1000, 500));
+		assert_eq!(CDPTreasuryModule::last_settled_at(SettlementKind::Surplus), None);
+
+		// no oracle price of ACA
+		MockPriceSource::set_price(ACA, None);
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(CDPTreasuryModule::last_settled_at(SettlementKind::Surplus), None);
+
+		// oracle price of ACA is 4 AUSD, selling native via DEX exceeds the premium
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 600));
+		assert_ok!(Currencies::deposit(ACA, &CDPTreasuryModule::account_id(), 100));
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(4)));
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+		assert_eq!(Currencies::free_balance(ACA, &CDPTreasuryModule::account_id()), 100);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, ACA), (1000, 500));
+		assert_eq!(CDPTreasuryModule::last_settled_at(SettlementKind::Debit), None);
+	});
+}
+
+#[test]
+fn set_max_swap_slippage_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::max_swap_slippage(DOT), None);
+		assert_noop!(
+			CDPTreasuryModule::set_max_swap_slippage(
+				RuntimeOrigin::signed(5),
+				DOT,
+				Some(Ratio::saturating_from_rational(5, 100))
+			),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_max_swap_slippage(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(5, 100))
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::MaxSwapSlippageUpdated {
+			collateral_type: DOT,
+			max_slippage: Some(Ratio::saturating_from_rational(5, 100)),
+		}));
+		assert_eq!(
+			CDPTreasuryModule::max_swap_slippage(DOT),
+			Some(Ratio::saturating_from_rational(5, 100))
+		);
+
+		assert_ok!(CDPTreasuryModule::set_max_swap_slippage(
+			RuntimeOrigin::signed(1),
+			DOT,
+			None
+		));
+		assert_eq!(CDPTreasuryModule::max_swap_slippage(DOT), None);
+	});
+}
+
+#[test]
+fn swap_collateral_to_stable_limited_by_max_swap_slippage() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CHARLIE, DOT, 1000));
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(BOB),
+			DOT,
+			AUSD,
+			1000,
+			1000,
+			0,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::set_max_swap_slippage(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(5, 100))
+		));
+
+		// swap 100 DOT to 90 AUSD, the price deviates from oracle price by 10%
+		assert_noop!(
+			CDPTreasuryModule::swap_collateral_to_stable(DOT, SwapLimit::ExactSupply(100, 0), false),
+			Error::<Runtime>::ExceedMaxSwapSlippage
+		);
+
+		MockPriceSource::set_price(DOT, None);
+		assert_noop!(
+			CDPTreasuryModule::swap_collateral_to_stable(DOT, SwapLimit::ExactSupply(100, 0), false),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+
+		MockPriceSource::set_price(DOT, Some(Price::one()));
+		assert_ok!(CDPTreasuryModule::set_max_swap_slippage(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(10, 100))
+		));
+		assert_eq!(
+			CDPTreasuryModule::swap_collateral_to_stable(DOT, SwapLimit::ExactSupply(100, 0), false).unwrap(),
+			(100, 90)
+		);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 90);
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(DOT), 900);
+	});
+}
+
+#[test]
+fn swap_collateral_to_stable_limited_by_collateral_swap_budget() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CHARLIE, DOT, 1000));
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(BOB),
+			DOT,
+			AUSD,
+			1000,
+			1000,
+			0,
+			false
+		));
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_swap_budget(RuntimeOrigin::signed(5), DOT, Some(150), Some(250)),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_swap_budget(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(150),
+			Some(250)
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::CollateralSwapBudgetUpdated {
+				collateral_type: DOT,
+				budget: CollateralSwapBudget {
+					per_block: Some(150),
+					per_era: Some(250),
+				},
+			},
+		));
+
+		// exceed the budget of per block
+		assert_ok!(CDPTreasuryModule::swap_collateral_to_stable(
+			DOT,
+			SwapLimit::ExactSupply(100, 0),
+			false
+		));
+		assert_noop!(
+			CDPTreasuryModule::swap_collateral_to_stable(DOT, SwapLimit::ExactSupply(100, 0), false),
+			Error::<Runtime>::ExceedCollateralSwapBudget
+		);
+
+		// exceed the budget of per era
+		System::set_block_number(2);
+		assert_ok!(CDPTreasuryModule::swap_collateral_to_stable(
+			DOT,
+			SwapLimit::ExactSupply(100, 0),
+			false
+		));
+		System::set_block_number(3);
+		assert_noop!(
+			CDPTreasuryModule::swap_collateral_to_stable(DOT, SwapLimit::ExactSupply(100, 0), false),
+			Error::<Runtime>::ExceedCollateralSwapBudget
+		);
+		assert_eq!(
+			CDPTreasuryModule::collateral_swap_usages(DOT),
+			CollateralSwapUsage {
+				block: 2,
+				block_swapped: 100,
+				era: 0,
+				era_swapped: 200,
+			}
+		);
+
+		// the budget of next era is available
+		System::set_block_number(10);
+		assert_ok!(CDPTreasuryModule::swap_collateral_to_stable(
+			DOT,
+			SwapLimit::ExactSupply(100, 0),
+			false
+		));
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(DOT), 700);
+	});
+}
+
+#[test]
+fn set_collateral_swap_path_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_swap_path(RuntimeOrigin::signed(5), BTC, Some(vec![BTC, DOT, AUSD])),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_swap_path(RuntimeOrigin::signed(1), BTC, Some(vec![BTC])),
+			Error::<Runtime>::InvalidSwapPath
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_swap_path(RuntimeOrigin::signed(1), BTC, Some(vec![DOT, AUSD])),
+			Error::<Runtime>::InvalidSwapPath
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_swap_path(RuntimeOrigin::signed(1), BTC, Some(vec![BTC, DOT])),
+			Error::<Runtime>::InvalidSwapPath
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_swap_path(
+				RuntimeOrigin::signed(1),
+				BTC,
+				Some(vec![BTC, DOT, ACA, DOT, AUSD])
+			),
+			Error::<Runtime>::InvalidSwapPath
+		);
+
+		assert_ok!(CDPTreasuryModule::set_collateral_swap_path(
+			RuntimeOrigin::signed(1),
+			BTC,
+			Some(vec![BTC, DOT, AUSD])
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::CollateralSwapPathUpdated {
+				collateral_type: BTC,
+				swap_path: Some(vec![BTC, DOT, AUSD]),
+			},
+		));
+		assert_eq!(
+			CDPTreasuryModule::collateral_swap_path(BTC).map(|path| path.into_inner()),
+			Some(vec![BTC, DOT, AUSD])
+		);
+
+		assert_ok!(CDPTreasuryModule::set_collateral_swap_path(
+			RuntimeOrigin::signed(1),
+			BTC,
+			None
+		));
+		assert_eq!(CDPTreasuryModule::collateral_swap_path(BTC), None);
+	});
+}
+
+#[test]
+fn swap_collateral_to_stable_by_collateral_swap_path() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, BTC, 200));
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(BOB),
+			DOT,
+			AUSD,
+			1000,
+			1000,
+			0,
+			false
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			DOT,
+			100,
+			1000,
+			0,
+			false
+		));
+
+		// the specified swap path has no liquidity
+		assert_ok!(CDPTreasuryModule::set_collateral_swap_path(
+			RuntimeOrigin::signed(1),
+			BTC,
+			Some(vec![BTC, AUSD])
+		));
+		assert_noop!(
+			CDPTreasuryModule::swap_collateral_to_stable(BTC, SwapLimit::ExactTarget(200, 399), false),
+			module_dex::Error::<Runtime>::InsufficientLiquidity
+		);
+
+		// swap by multi-hop path
+		assert_ok!(CDPTreasuryModule::set_collateral_swap_path(
+			RuntimeOrigin::signed(1),
+			BTC,
+			Some(vec![BTC, DOT, AUSD])
+		));
+		assert_eq!(
+			CDPTreasuryModule::swap_collateral_to_stable(BTC, SwapLimit::ExactTarget(200, 399), false).unwrap(),
+			(198, 399)
+		);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 399);
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(BTC), 2);
+	});
+}
+
+#[test]
+fn get_projected_collateral_auctions_count_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 0);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CHARLIE, DOT, 1000));
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 1);
+
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			RuntimeOrigin::signed(1),
+			DOT,
+			300
+		));
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 4);
+
+		// limited by MaxAuctionsCount
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			RuntimeOrigin::signed(1),
+			DOT,
+			100
+		));
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 5);
+	});
+}
+
+#[test]
+fn expected_collateral_auction_value_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CHARLIE, DOT, 1000));
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			RuntimeOrigin::signed(1),
+			DOT,
+			300
+		));
+		assert_eq!(CDPTreasuryModule::get_expected_collateral_auction_size(DOT), 300);
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 4);
+
+		assert_noop!(
+			CDPTreasuryModule::set_expected_collateral_auction_value(RuntimeOrigin::signed(5), DOT, 500),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_value(
+			RuntimeOrigin::signed(1),
+			DOT,
+			500
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::ExpectedCollateralAuctionValueUpdated {
+				collateral_type: DOT,
+				new_value: 500,
+			},
+		));
+		assert_eq!(CDPTreasuryModule::expected_collateral_auction_value(DOT), 500);
+		assert_eq!(CDPTreasuryModule::get_expected_collateral_auction_size(DOT), 500);
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 2);
+
+		// the lot keeps the stable value when the price drops
+		MockPriceSource::set_price(DOT, Some(Price::saturating_from_rational(1, 2)));
+		assert_eq!(CDPTreasuryModule::get_expected_collateral_auction_size(DOT), 1000);
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 1);
+
+		// fallback to the expected collateral auction size without price
+		MockPriceSource::set_price(DOT, None);
+		assert_eq!(CDPTreasuryModule::get_expected_collateral_auction_size(DOT), 300);
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 4);
+
+		MockPriceSource::set_price(DOT, Some(Price::one()));
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_value(
+			RuntimeOrigin::signed(1),
+			DOT,
+			0
+		));
+		assert_eq!(CDPTreasuryModule::get_expected_collateral_auction_size(DOT), 300);
+	});
+}
+
+#[test]
+fn expected_stable_auction_value_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CHARLIE, DOT, 1000));
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			RuntimeOrigin::signed(1),
+			DOT,
+			300
+		));
+
+		assert_noop!(
+			CDPTreasuryModule::set_expected_stable_auction_value(RuntimeOrigin::signed(5), AUSD, 500),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_expected_stable_auction_value(RuntimeOrigin::signed(1), DOT, 500),
+			Error::<Runtime>::StableCurrencyNotSupported
+		);
+		assert_ok!(CDPTreasuryModule::set_expected_stable_auction_value(
+			RuntimeOrigin::signed(1),
+			AUSD,
+			500
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::ExpectedStableAuctionValueUpdated {
+				stable_currency_id: AUSD,
+				new_value: 500,
+			},
+		));
+		assert_eq!(CDPTreasuryModule::expected_stable_auction_value(AUSD), 500);
+		assert_eq!(CDPTreasuryModule::get_expected_collateral_auction_size(DOT), 500);
+		assert_eq!(CDPTreasuryModule::get_projected_collateral_auctions_count(DOT), 2);
+
+		// the value of the collateral type overrides the one of its stable currency
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_value(
+			RuntimeOrigin::signed(1),
+			DOT,
+			200
+		));
+		assert_eq!(CDPTreasuryModule::get_expected_collateral_auction_size(DOT), 200);
+
+		// the value of other stable currency doesn't affect the collateral type
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_value(
+			RuntimeOrigin::signed(1),
+			DOT,
+			0
+		));
+		assert_ok!(CDPTreasuryModule::set_expected_stable_auction_value(
+			RuntimeOrigin::signed(1),
+			AUSD,
+			0
+		));
+		assert_ok!(CDPTreasuryModule::set_expected_stable_auction_value(
+			RuntimeOrigin::signed(1),
+			KUSD,
+			800
+		));
+		assert_eq!(CDPTreasuryModule::get_expected_collateral_auction_size(DOT), 300);
+	});
+}
+
+#[test]
+fn transfer_surplus_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			200
+		));
+		assert_eq!(Currencies::free_balance(AUSD, &CHARLIE), 0);
+
+		assert_noop!(
+			CDPTreasuryModule::transfer_surplus(RuntimeOrigin::signed(5), CHARLIE, 300),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::transfer_surplus(RuntimeOrigin::signed(1), CHARLIE, 301),
+			Error::<Runtime>::SurplusPoolNotEnough
+		);
+
+		assert_ok!(CDPTreasuryModule::transfer_surplus(
+			RuntimeOrigin::signed(1),
+			CHARLIE,
+			300
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusTransferred {
+			beneficiary: CHARLIE,
+			amount: 300,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(Currencies::free_balance(AUSD, &CHARLIE), 300);
+	});
+}
+
+#[test]
+fn set_surplus_distribution_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(
+			CDPTreasuryModule::surplus_distribution(),
+			SurplusDistribution {
+				buyback: Permill::one(),
+				incentives: Permill::zero(),
+			}
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_distribution(
+				RuntimeOrigin::signed(5),
+				Permill::from_percent(50),
+				Permill::from_percent(25)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_distribution(
+				RuntimeOrigin::signed(1),
+				Permill::from_percent(80),
+				Permill::from_percent(25)
+			),
+			Error::<Runtime>::InvalidSurplusDistribution
+		);
+
+		assert_ok!(CDPTreasuryModule::set_surplus_distribution(
+			RuntimeOrigin::signed(1),
+			Permill::from_percent(50),
+			Permill::from_percent(25)
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::SurplusDistributionUpdated {
+				buyback: Permill::from_percent(50),
+				incentives: Permill::from_percent(25),
+			},
+		));
+		assert_eq!(
+			CDPTreasuryModule::surplus_distribution(),
+			SurplusDistribution {
+				buyback: Permill::from_percent(50),
+				incentives: Permill::from_percent(25),
+			}
+		);
+	});
+}
+
+#[test]
+fn trigger_settlement_by_surplus_distribution() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			AUSD,
+			ACA,
+			1000,
+			500,
+			0,
+			false
+		));
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(2)));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			100
+		));
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(RuntimeOrigin::signed(1), 200));
+		assert_ok!(CDPTreasuryModule::set_surplus_distribution(
+			RuntimeOrigin::signed(1),
+			Permill::from_percent(50),
+			Permill::from_percent(25)
+		));
+
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 100,
+			burned_native_amount: 45,
+		}));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::SurplusDistributedToIncentives { amount: 50 },
+		));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 350);
+		assert_eq!(Currencies::free_balance(AUSD, &IncentivesRewardsSource::get()), 50);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, ACA), (1100, 455));
+	});
+}
+
+#[test]
+fn set_stability_reserve_target_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::stability_reserve_target(), 0);
+		assert_noop!(
+			CDPTreasuryModule::set_stability_reserve_target(RuntimeOrigin::signed(5), 300),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_stability_reserve_target(
+			RuntimeOrigin::signed(1),
+			300
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::StabilityReserveTargetUpdated { amount: 300 },
+		));
+		assert_eq!(CDPTreasuryModule::stability_reserve_target(), 300);
+	});
+}
+
+#[test]
+fn stability_reserve_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::set_stability_reserve_target(
+			RuntimeOrigin::signed(1),
+			300
+		));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			100
+		));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+
+		// retain surplus exceeding the buffer in reserve until reach the target
+		finalize_block(1);
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::StabilityReserveReplenished { amount: 300 },
+		));
+		assert_eq!(CDPTreasuryModule::stability_reserve(), 300);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+
+		finalize_block(2);
+		assert_eq!(CDPTreasuryModule::stability_reserve(), 300);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+
+		// the debit remaining after offset by surplus is offset by reserve
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 400));
+		let total_issuance = Currencies::total_issuance(AUSD);
+		finalize_block(3);
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::StabilityReserveDrawn {
+			amount: 200,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::stability_reserve(), 100);
+		assert_eq!(Currencies::total_issuance(AUSD), total_issuance - 400);
+	});
+}
+
+#[test]
+fn set_collateral_dust_threshold_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_dust_threshold(RuntimeOrigin::signed(5), DOT, 10),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_dust_threshold(
+			RuntimeOrigin::signed(1),
+			DOT,
+			10
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::CollateralDustThresholdUpdated {
+				collateral_type: DOT,
+				threshold: 10,
+			},
+		));
+		assert_eq!(CDPTreasuryModule::collateral_dust_threshold(DOT), 10);
+	});
+}
+
+#[test]
+fn sweep_dust_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CHARLIE, DOT, 10));
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(BOB),
+			DOT,
+			AUSD,
+			1000,
+			1000,
+			0,
+			false
+		));
+
+		assert_noop!(
+			CDPTreasuryModule::sweep_dust_collateral(RuntimeOrigin::signed(ALICE), DOT),
+			Error::<Runtime>::CollateralNotDust
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_dust_threshold(
+			RuntimeOrigin::signed(1),
+			DOT,
+			5
+		));
+		assert_noop!(
+			CDPTreasuryModule::sweep_dust_collateral(RuntimeOrigin::signed(ALICE), DOT),
+			Error::<Runtime>::CollateralNotDust
+		);
+
+		assert_ok!(CDPTreasuryModule::set_collateral_dust_threshold(
+			RuntimeOrigin::signed(1),
+			DOT,
+			10
+		));
+
+		// the swap is bounded by oracle price
+		MockPriceSource::set_price(DOT, None);
+		assert_noop!(
+			CDPTreasuryModule::sweep_dust_collateral(RuntimeOrigin::signed(ALICE), DOT),
+			Error::<Runtime>::CannotSwap
+		);
+		MockPriceSource::set_price(DOT, Some(Price::one()));
+		assert_ok!(CDPTreasuryModule::sweep_dust_collateral(
+			RuntimeOrigin::signed(ALICE),
+			DOT
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::DustCollateralSwept {
+			collateral_type: DOT,
+			collateral_amount: 10,
+			stable_amount: 9,
+		}));
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(DOT), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 9);
+
+		assert_noop!(
+			CDPTreasuryModule::sweep_dust_collateral(RuntimeOrigin::signed(ALICE), DOT),
+			Error::<Runtime>::CollateralNotDust
+		);
+	});
+}
+
+#[test]
+fn surplus_settlement_size_policy_work() {
+	let policy = SurplusSettlementSizePolicy {
+		proportion: Permill::from_percent(50),
+		min_size: 50,
+		max_size: 150,
+	};
+	assert_eq!(policy.settlement_size(0), 0);
+	assert_eq!(policy.settlement_size(40), 40);
+	assert_eq!(policy.settlement_size(80), 50);
+	assert_eq!(policy.settlement_size(200), 100);
+	assert_eq!(policy.settlement_size(1000), 150);
+}
+
+#[test]
+fn set_surplus_settlement_size_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let policy = SurplusSettlementSizePolicy {
+			proportion: Permill::from_percent(50),
+			min_size: 50,
+			max_size: 150,
+		};
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_settlement_size(RuntimeOrigin::signed(5), Some(policy)),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_settlement_size(
+				RuntimeOrigin::signed(1),
+				Some(SurplusSettlementSizePolicy {
+					proportion: Permill::from_percent(50),
+					min_size: 200,
+					max_size: 150,
+				})
+			),
+			Error::<Runtime>::InvalidSettlementSizePolicy
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_settlement_size(
+				RuntimeOrigin::signed(1),
+				Some(SurplusSettlementSizePolicy {
+					proportion: Permill::zero(),
+					min_size: 50,
+					max_size: 150,
+				})
+			),
+			Error::<Runtime>::InvalidSettlementSizePolicy
+		);
+
+		assert_ok!(CDPTreasuryModule::set_surplus_settlement_size(
+			RuntimeOrigin::signed(1),
+			Some(policy)
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::SurplusSettlementSizePolicyUpdated { policy: Some(policy) },
+		));
+		assert_eq!(CDPTreasuryModule::surplus_settlement_size(), Some(policy));
+
+		assert_ok!(CDPTreasuryModule::set_surplus_settlement_size(
+			RuntimeOrigin::signed(1),
+			None
+		));
+		assert_eq!(CDPTreasuryModule::surplus_settlement_size(), None);
+	});
+}
+
+#[test]
+fn trigger_settlement_by_surplus_settlement_size_policy() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			AUSD,
+			ACA,
+			1000,
+			500,
+			0,
+			false
+		));
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(2)));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			100
+		));
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(
+			RuntimeOrigin::signed(1),
+			1000
+		));
+		assert_ok!(CDPTreasuryModule::set_surplus_settlement_size(
+			RuntimeOrigin::signed(1),
+			Some(SurplusSettlementSizePolicy {
+				proportion: Permill::from_percent(50),
+				min_size: 50,
+				max_size: 150,
+			})
+		));
+
+		// settle 50% of 400 excess surplus, bounded by the max size
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 150,
+			burned_native_amount: 65,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 350);
+	});
+}
+
+#[test]
+fn trigger_settlement_by_surplus_settlement_size_policy_limited_by_cap() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			AUSD,
+			ACA,
+			1000,
+			500,
+			0,
+			false
+		));
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(2)));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			100
+		));
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(RuntimeOrigin::signed(1), 120));
+		assert_ok!(CDPTreasuryModule::set_surplus_settlement_size(
+			RuntimeOrigin::signed(1),
+			Some(SurplusSettlementSizePolicy {
+				proportion: Permill::from_percent(50),
+				min_size: 50,
+				max_size: 150,
+			})
+		));
+
+		// the size of policy is 150, but limited by the cap
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 120,
+			burned_native_amount: 53,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 380);
+
+		// settle 50% of 280 excess surplus when it's below the cap
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(3)));
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(
+			RuntimeOrigin::signed(1),
+			1000
+		));
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 140,
+			burned_native_amount: 49,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 240);
+	});
+}
+
+#[test]
+fn set_debit_settlement_premium_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_debit_settlement_premium(
+				RuntimeOrigin::signed(5),
+				Some(Ratio::saturating_from_rational(10, 100))
+			),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_debit_settlement_premium(
+			RuntimeOrigin::signed(1),
+			Some(Ratio::saturating_from_rational(10, 100))
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::DebitSettlementPremiumUpdated {
+				premium: Some(Ratio::saturating_from_rational(10, 100)),
+			},
+		));
+		assert_eq!(
+			CDPTreasuryModule::debit_settlement_premium(),
+			Some(Ratio::saturating_from_rational(10, 100))
+		);
+	});
+}
+
+#[test]
+fn settle_debit_limited_by_debit_settlement_premium() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			AUSD,
+			ACA,
+			1000,
+			500,
+			0,
+			false
+		));
+		assert_ok!(Currencies::deposit(ACA, &CDPTreasuryModule::account_id(), 100));
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 100));
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(RuntimeOrigin::signed(1), 200));
+
+		// premium is not set, still limited by oracle price
+		MockPriceSource::set_price(ACA, None);
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+		assert_eq!(Currencies::free_balance(ACA, &CDPTreasuryModule::account_id()), 100);
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(2)));
+
+		// 56 ACA is required, but max supply is 55 ACA
+		assert_ok!(CDPTreasuryModule::set_debit_settlement_premium(
+			RuntimeOrigin::signed(1),
+			Some(Ratio::saturating_from_rational(10, 100))
+		));
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+		assert_eq!(Currencies::free_balance(ACA, &CDPTreasuryModule::account_id()), 100);
+
+		// no oracle price
+		assert_ok!(CDPTreasuryModule::set_debit_settlement_premium(
+			RuntimeOrigin::signed(1),
+			Some(Ratio::saturating_from_rational(20, 100))
+		));
+		MockPriceSource::set_price(ACA, None);
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+
+		// max supply is 60 ACA
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(2)));
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::DebitSettledViaDex {
+			sold_native_amount: 56,
+			debit_amount: 100,
+		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(Currencies::free_balance(ACA, &CDPTreasuryModule::account_id()), 44);
+	});
+}
+
+#[test]
+fn set_settlement_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_settlement_params(RuntimeOrigin::signed(5), SettlementKind::Debit, Some(100), 10),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_settlement_params(
+			RuntimeOrigin::signed(1),
+			SettlementKind::Debit,
+			Some(100),
+			10
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SettlementParamsUpdated {
+			kind: SettlementKind::Debit,
+			cap: Some(100),
+			min_interval: 10,
+		}));
+		assert_eq!(CDPTreasuryModule::settlement_cap_of(SettlementKind::Debit), Some(100));
+		assert_eq!(CDPTreasuryModule::settlement_min_interval(SettlementKind::Debit), 10);
+		assert_eq!(CDPTreasuryModule::settlement_cap_of(SettlementKind::Surplus), None);
+		assert_eq!(CDPTreasuryModule::settlement_min_interval(SettlementKind::Surplus), 0);
+	});
+}
+
+#[test]
+fn trigger_settlement_limited_by_settlement_params() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			AUSD,
+			ACA,
+			1000,
+			500,
+			0,
+			false
+		));
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(2)));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			100
+		));
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(RuntimeOrigin::signed(1), 200));
+		assert_ok!(CDPTreasuryModule::set_settlement_params(
+			RuntimeOrigin::signed(1),
+			SettlementKind::Surplus,
+			Some(50),
+			2
+		));
+
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 50,
+			burned_native_amount: 23,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 450);
+		assert_eq!(CDPTreasuryModule::last_settled_at(SettlementKind::Surplus), Some(1));
+
+		// not reach the min interval
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 450);
+		System::set_block_number(2);
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 450);
+
+		System::set_block_number(3);
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 50,
+			burned_native_amount: 21,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 400);
+		assert_eq!(CDPTreasuryModule::last_settled_at(SettlementKind::Surplus), Some(3));
+		assert_eq!(CDPTreasuryModule::cumulative_metrics().settled_surplus, 100);
+		assert_eq!(CDPTreasuryModule::cumulative_metrics().burned_native, 23 + 21);
+	});
+}
+
+#[test]
+fn trigger_settlement_by_surplus_settlement_size_policy_limited_by_settlement_cap() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			AUSD,
+			ACA,
+			1000,
+			500,
+			0,
+			false
+		));
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(2)));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(
+			RuntimeOrigin::signed(1),
+			100
+		));
+		assert_ok!(CDPTreasuryModule::set_dex_settlement_cap(
+			RuntimeOrigin::signed(1),
+			1000
+		));
+		assert_ok!(CDPTreasuryModule::set_surplus_settlement_size(
+			RuntimeOrigin::signed(1),
+			Some(SurplusSettlementSizePolicy {
+				proportion: Permill::from_percent(50),
+				min_size: 50,
+				max_size: 150,
+			})
+		));
+		assert_ok!(CDPTreasuryModule::set_settlement_params(
+			RuntimeOrigin::signed(1),
+			SettlementKind::Surplus,
+			Some(100),
+			0
+		));
+
+		// the size of policy is 150, but limited by the cap of surplus settlement
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 100,
+			burned_native_amount: 45,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 400);
+
+		// the policy size below the cap of surplus settlement is not limited
+		assert_ok!(CDPTreasuryModule::set_settlement_params(
+			RuntimeOrigin::signed(1),
+			SettlementKind::Surplus,
+			Some(200),
+			0
+		));
+		MockPriceSource::set_price(ACA, Some(Price::saturating_from_integer(3)));
+		assert_ok!(CDPTreasuryModule::trigger_settlement(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::SurplusSettledViaDex {
+			surplus_amount: 150,
+			burned_native_amount: 54,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 250);
+	});
+}
+
+#[test]
+fn record_debit_metrics_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::cumulative_metrics(), TreasuryMetrics::default());
+		assert_eq!(CDPTreasuryModule::era_metrics(0), None);
+
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 1000, true));
+		assert_ok!(CDPTreasuryModule::burn_debit(AUSD, &ALICE, 300));
+		let metrics = TreasuryMetrics {
+			issued_debit: 1000,
+			burned_debit: 300,
+			settled_surplus: 0,
+			settled_debit: 0,
+			burned_native: 0,
+		};
+		assert_eq!(CDPTreasuryModule::cumulative_metrics(), metrics);
+		assert_eq!(CDPTreasuryModule::era_metrics(0), Some(metrics));
+
+		System::set_block_number(10);
+		assert_eq!(CDPTreasuryModule::current_metrics_era(), 1);
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 500, true));
+		assert_eq!(
+			CDPTreasuryModule::cumulative_metrics(),
+			TreasuryMetrics {
+				issued_debit: 1500,
+				burned_debit: 300,
+				settled_surplus: 0,
+				settled_debit: 0,
+				burned_native: 0,
+			}
+		);
+		assert_eq!(CDPTreasuryModule::era_metrics(0), Some(metrics));
+		assert_eq!(
+			CDPTreasuryModule::era_metrics(1),
+			Some(TreasuryMetrics {
+				issued_debit: 500,
+				..Default::default()
+			})
+		);
+		assert_eq!(CDPTreasuryModule::latest_metrics_era(), 1);
+	});
+}
+
+#[test]
+fn expired_era_metrics_are_pruned() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 100, true));
+		System::set_block_number(11);
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 100, true));
+		assert!(CDPTreasuryModule::era_metrics(0).is_some());
+		assert!(CDPTreasuryModule::era_metrics(1).is_some());
+
+		// MaxMetricsEras is 2, era 0 expires at era 2
+		System::set_block_number(21);
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 100, true));
+		assert_eq!(CDPTreasuryModule::era_metrics(0), None);
+		assert!(CDPTreasuryModule::era_metrics(1).is_some());
+		assert!(CDPTreasuryModule::era_metrics(2).is_some());
+
+		// skip several eras, all the stale eras are pruned
+		System::set_block_number(71);
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 100, true));
+		assert_eq!(CDPTreasuryModule::era_metrics(1), None);
+		assert_eq!(CDPTreasuryModule::era_metrics(2), None);
+		assert_eq!(
+			CDPTreasuryModule::era_metrics(7),
+			Some(TreasuryMetrics {
+				issued_debit: 100,
+				..Default::default()
+			})
+		);
+		assert_eq!(CDPTreasuryModule::cumulative_metrics().issued_debit, 400);
+	});
+}
+
+#[test]
+fn total_collateral_liquidated_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Currencies::deposit(BTC, &CDPTreasuryModule::account_id(), 10000));
+		assert_eq!(CDPTreasuryModule::total_collateral_liquidated(BTC), 0);
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 1000, 1000, ALICE, true
+		));
+		assert_eq!(CDPTreasuryModule::total_collateral_liquidated(BTC), 1000);
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 500, 1000, ALICE, true
+		));
+		assert_eq!(CDPTreasuryModule::total_collateral_liquidated(BTC), 1500);
+	});
+}
+
+#[test]
+fn set_flash_loan_fee_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::flash_loan_fee_rate(), None);
+		assert_noop!(
+			CDPTreasuryModule::set_flash_loan_fee_rate(
+				RuntimeOrigin::signed(5),
+				Some(Rate::saturating_from_rational(1, 100))
+			),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_flash_loan_fee_rate(
+			RuntimeOrigin::signed(1),
+			Some(Rate::saturating_from_rational(1, 100))
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::FlashLoanFeeRateUpdated {
+			fee_rate: Some(Rate::saturating_from_rational(1, 100)),
+		}));
+		assert_eq!(
+			CDPTreasuryModule::flash_loan_fee_rate(),
+			Some(Rate::saturating_from_rational(1, 100))
+		);
+	});
+}
+
+#[test]
+fn flash_loan_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 1000));
+		let remark_call = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+
+		assert_noop!(
+			CDPTreasuryModule::flash_loan(RuntimeOrigin::signed(BOB), AUSD, 500, remark_call.clone()),
+			Error::<Runtime>::FlashLoanDisabled
+		);
+		assert_ok!(CDPTreasuryModule::set_flash_loan_fee_rate(
+			RuntimeOrigin::signed(1),
+			Some(Rate::saturating_from_rational(1, 100))
+		));
+		assert_noop!(
+			CDPTreasuryModule::flash_loan(RuntimeOrigin::signed(BOB), DOT, 500, remark_call.clone()),
+			Error::<Runtime>::FlashLoanCurrencyNotSupported
+		);
+		assert_noop!(
+			CDPTreasuryModule::flash_loan(RuntimeOrigin::signed(BOB), AUSD, 1001, remark_call.clone()),
+			Error::<Runtime>::SurplusPoolNotEnough
+		);
+
+		// the receiver call sends the loan away and cannot repay
+		let transfer_call = Box::new(RuntimeCall::Currencies(orml_currencies::Call::transfer {
+			dest: CHARLIE,
+			currency_id: AUSD,
+			amount: 1500,
+		}));
+		assert_noop!(
+			CDPTreasuryModule::flash_loan(RuntimeOrigin::signed(BOB), AUSD, 500, transfer_call),
+			Error::<Runtime>::FlashLoanNotRepaid
+		);
+
+		assert_ok!(CDPTreasuryModule::flash_loan(
+			RuntimeOrigin::signed(BOB),
+			AUSD,
+			500,
+			remark_call
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::FlashLoanRepaid {
+			who: BOB,
+			amount: 500,
+			fee: 5,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 1005);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 995);
+	});
+}
+
+#[test]
+fn repay_debit_pool_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::repay_debit_pool(RuntimeOrigin::signed(ALICE), 100),
+			Error::<Runtime>::NoDebitToRepay
+		);
+
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 300));
+		assert_ok!(CDPTreasuryModule::repay_debit_pool(RuntimeOrigin::signed(ALICE), 100));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::DebitPoolRepaid {
+			who: ALICE,
+			amount: 100,
+		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 200);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 900);
+
+		// only burn the amount of debit pool
+		assert_ok!(CDPTreasuryModule::repay_debit_pool(RuntimeOrigin::signed(ALICE), 500));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::DebitPoolRepaid {
+			who: ALICE,
+			amount: 200,
+		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 700);
+		assert_eq!(CDPTreasuryModule::cumulative_metrics().burned_debit, 300);
+	});
+}
+
+#[test]
+fn set_max_liquidity_deployment_ratio_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_max_liquidity_deployment_ratio(RuntimeOrigin::signed(5), Permill::from_percent(60)),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_max_liquidity_deployment_ratio(
+			RuntimeOrigin::signed(1),
+			Permill::from_percent(60)
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::MaxLiquidityDeploymentRatioUpdated {
+				ratio: Permill::from_percent(60),
+			},
+		));
+		assert_eq!(
+			CDPTreasuryModule::max_liquidity_deployment_ratio(),
+			Permill::from_percent(60)
+		);
+	});
+}
+
+#[test]
+fn deploy_and_recall_liquidity_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(BOB),
+			AUSD,
+			DOT,
+			1000,
+			100,
+			0,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, DOT, 100));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 1000));
+
+		assert_noop!(
+			CDPTreasuryModule::deploy_liquidity(RuntimeOrigin::signed(5), DOT, 50, 600, 0),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::deploy_liquidity(RuntimeOrigin::signed(1), DOT, 50, 600, 0),
+			Error::<Runtime>::ExceedLiquidityDeploymentCap
+		);
+		assert_noop!(
+			CDPTreasuryModule::deploy_liquidity(RuntimeOrigin::signed(1), DOT, 101, 600, 0),
+			Error::<Runtime>::CollateralNotEnough
+		);
+
+		assert_ok!(CDPTreasuryModule::set_max_liquidity_deployment_ratio(
+			RuntimeOrigin::signed(1),
+			Permill::from_percent(60)
+		));
+		assert_noop!(
+			CDPTreasuryModule::deploy_liquidity(RuntimeOrigin::signed(1), DOT, 50, 600, 1001),
+			module_dex::Error::<Runtime>::UnacceptableShareIncrement
+		);
+		assert_ok!(CDPTreasuryModule::deploy_liquidity(
+			RuntimeOrigin::signed(1),
+			DOT,
+			50,
+			600,
+			1000
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::LiquidityDeployed {
+			collateral_type: DOT,
+			collateral_amount: 50,
+			stable_amount: 500,
+			share: 1000,
+		}));
+		assert_eq!(CDPTreasuryModule::deployed_liquidity_shares(DOT), 1000);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, DOT), (1500, 150));
+		assert_eq!(CDPTreasuryModule::total_collaterals(DOT), 50);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(CDPTreasuryModule::deployed_liquidity(DOT), (49, 499));
+		assert_eq!(CDPTreasuryModule::total_claimable_collaterals(DOT), 99);
+		assert_eq!(CDPTreasuryModule::get_total_collaterals(DOT), 99);
+		assert_ok!(CDPTreasuryModule::do_try_state());
+
+		// exceed the max deployment ratio
+		assert_noop!(
+			CDPTreasuryModule::deploy_liquidity(RuntimeOrigin::signed(1), DOT, 40, 400, 0),
+			Error::<Runtime>::ExceedLiquidityDeploymentCap
+		);
+
+		assert_noop!(
+			CDPTreasuryModule::recall_liquidity(RuntimeOrigin::signed(1), DOT, 1001, 0, 0),
+			Error::<Runtime>::DeployedLiquidityNotEnough
+		);
+		assert_noop!(
+			CDPTreasuryModule::recall_liquidity(RuntimeOrigin::signed(1), DOT, 1000, 50, 0),
+			module_dex::Error::<Runtime>::UnacceptableLiquidityWithdrawn
+		);
+		assert_ok!(CDPTreasuryModule::recall_liquidity(
+			RuntimeOrigin::signed(1),
+			DOT,
+			1000,
+			49,
+			499
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(crate::Event::LiquidityRecalled {
+			collateral_type: DOT,
+			collateral_amount: 49,
+			stable_amount: 499,
+			share: 1000,
+		}));
+		assert_eq!(CDPTreasuryModule::deployed_liquidity_shares(DOT), 0);
+		assert_eq!(CDPTreasuryModule::total_collaterals(DOT), 99);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 999);
+		assert_eq!(CDPTreasuryModule::total_claimable_collaterals(DOT), 99);
+		assert_ok!(CDPTreasuryModule::do_try_state());
+	});
+}
+
+#[test]
+fn withdraw_collateral_recall_deployed_liquidity() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			RuntimeOrigin::signed(BOB),
+			AUSD,
+			DOT,
+			1000,
+			100,
+			0,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, DOT, 100));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 1000));
+		assert_ok!(CDPTreasuryModule::set_max_liquidity_deployment_ratio(
+			RuntimeOrigin::signed(1),
+			Permill::from_percent(60)
+		));
+		assert_ok!(CDPTreasuryModule::deploy_liquidity(
+			RuntimeOrigin::signed(1),
+			DOT,
+			50,
+			600,
+			0
+		));
+		assert_eq!(CDPTreasuryModule::total_collaterals(DOT), 50);
+		assert_eq!(CDPTreasuryModule::get_total_collaterals(DOT), 99);
+
+		// the collateral held is enough, the liquidity is not recalled
+		let alice_dot = Currencies::free_balance(DOT, &ALICE);
+		assert_ok!(CDPTreasuryModule::withdraw_collateral(&ALICE, DOT, 10));
+		assert_eq!(CDPTreasuryModule::deployed_liquidity_shares(DOT), 1000);
+		assert_eq!(CDPTreasuryModule::total_collaterals(DOT), 40);
+
+		// recall all the deployed liquidity to cover the shortfall
+		assert_ok!(CDPTreasuryModule::withdraw_collateral(&ALICE, DOT, 80));
+		assert_eq!(CDPTreasuryModule::deployed_liquidity_shares(DOT), 0);
+		assert_eq!(CDPTreasuryModule::total_collaterals(DOT), 9);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 999);
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), alice_dot + 90);
+	});
+}
+
+#[test]
+fn module_accounts_initialized_at_genesis() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(CDPTreasuryModule::module_account_initialized());
+		assert_eq!(System::providers(&CDPTreasuryModule::account_id()), 1);
+		assert_eq!(System::providers(&CDPTreasuryModule::stability_reserve_account_id()), 1);
+		assert_eq!(System::providers(&CDPTreasuryModule::liquidity_account_id()), 1);
+
+		// initialize again does not add provider
+		CDPTreasuryModule::initialize_module_accounts();
+		assert_eq!(System::providers(&CDPTreasuryModule::account_id()), 1);
+	});
+}
+
+#[test]
+fn deposit_collateral_below_existential_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		// cdp treasury is in the dust removal whitelist
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, KSM, 9));
+		assert_eq!(CDPTreasuryModule::total_collaterals(KSM), 9);
+		assert_ok!(CDPTreasuryModule::withdraw_collateral(&ALICE, KSM, 5));
+		assert_eq!(CDPTreasuryModule::total_collaterals(KSM), 4);
+		assert_eq!(System::providers(&CDPTreasuryModule::account_id()), 1);
+
+		// the sub-account of cdp treasury is not in the dust removal whitelist
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, KSM, 100));
+		assert_noop!(
+			CDPTreasuryModule::deploy_liquidity(RuntimeOrigin::signed(1), KSM, 9, 0, 0),
+			Error::<Runtime>::BelowExistentialDeposit
+		);
+	});
+}
+
+#[test]
+fn on_liquidation_penalty_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_liquidation_penalty(BTC, 0));
+		assert_eq!(CDPTreasuryModule::total_assessed_liquidation_penalty(BTC), 0);
+
+		assert_ok!(CDPTreasuryModule::on_liquidation_penalty(BTC, 100));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::LiquidationPenaltyAssessed {
+				collateral_type: BTC,
+				amount: 100,
+			},
+		));
+		assert_ok!(CDPTreasuryModule::on_liquidation_penalty(BTC, 50));
+		assert_eq!(CDPTreasuryModule::total_assessed_liquidation_penalty(BTC), 150);
+		assert_eq!(CDPTreasuryModule::total_assessed_liquidation_penalty(DOT), 0);
+	});
+}
+
+#[test]
+fn on_stability_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_stability_fee(AUSD, 1000));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
+		assert_eq!(Currencies::free_balance(AUSD, &IncentivesRewardsSource::get()), 0);
+
+		assert_noop!(
+			CDPTreasuryModule::set_stability_fee_incentives_ratio(RuntimeOrigin::signed(5), Permill::from_percent(30)),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_stability_fee_incentives_ratio(
+			RuntimeOrigin::signed(1),
+			Permill::from_percent(30)
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::StabilityFeeIncentivesRatioUpdated {
+				ratio: Permill::from_percent(30),
+			},
+		));
+		assert_eq!(
+			CDPTreasuryModule::stability_fee_incentives_ratio(),
+			Permill::from_percent(30)
+		);
+
+		assert_ok!(CDPTreasuryModule::on_stability_fee(AUSD, 1000));
+		System::assert_has_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::StabilityFeeDistributedToIncentives { amount: 300 },
+		));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 1700);
+		assert_eq!(Currencies::free_balance(AUSD, &IncentivesRewardsSource::get()), 300);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+	});
+}
+
+#[test]
+fn debit_pool_interest_accrue_on_finalize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_debit_pool_interest_rate(
+				RuntimeOrigin::signed(5),
+				Some(Rate::saturating_from_rational(1, 100))
+			),
+			BadOrigin
+		);
+
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 1000));
+		finalize_block(1);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 1000);
+
+		assert_ok!(CDPTreasuryModule::set_debit_pool_interest_rate(
+			RuntimeOrigin::signed(1),
+			Some(Rate::saturating_from_rational(1, 100))
+		));
+		System::assert_last_event(RuntimeEvent::CDPTreasuryModule(
+			crate::Event::DebitPoolInterestRateUpdated {
+				rate: Some(Rate::saturating_from_rational(1, 100)),
+			},
+		));
+		finalize_block(2);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 1010);
+		finalize_block(3);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 1020);
+
+		// the accrued debit is offset by surplus like the debit itself
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 500));
+		finalize_block(4);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 530);
+
+		assert_ok!(CDPTreasuryModule::set_debit_pool_interest_rate(
+			RuntimeOrigin::signed(1),
+			None
+		));
+		finalize_block(5);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 530);
+	});
+}
+
+#[test]
+fn try_state_detect_accounting_drift() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::do_try_state());
+
+		// collateral in auction is not kept by cdp treasury
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, BTC, 100));
+		TotalCollateralLiquidated::<Runtime>::insert(BTC, 100);
+		TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut() = 100);
+		assert_ok!(CDPTreasuryModule::do_try_state());
+		TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut() = 101);
+		assert_noop!(
+			CDPTreasuryModule::do_try_state(),
+			DispatchError::Other("cdp-treasury: collateral in auction exceeds total collaterals")
+		);
+		TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut() = 0);
+
+		// deployed liquidity share is not held by liquidity account
+		DeployedLiquidityShares::<Runtime>::insert(DOT, 1000);
+		assert_noop!(
+			CDPTreasuryModule::do_try_state(),
+			DispatchError::Other("cdp-treasury: deployed liquidity share exceeds the balance of liquidity account")
+		);
+		DeployedLiquidityShares::<Runtime>::remove(DOT);
+
+		// surplus pool is not offset against debit pool
+		assert_ok!(CDPTreasuryModule::on_system_debit(AUSD, 100));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(AUSD, 50));
+		assert_noop!(
+			CDPTreasuryModule::do_try_state(),
+			DispatchError::Other("cdp-treasury: surplus pool is not offset against debit pool")
+		);
+		finalize_block(1);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+
+		// stability reserve is not drawn against debit pool
+		assert_ok!(Currencies::deposit(
+			AUSD,
+			&CDPTreasuryModule::stability_reserve_account_id(),
+			20
+		));
+		assert_noop!(
+			CDPTreasuryModule::do_try_state(),
+			DispatchError::Other("cdp-treasury: stability reserve is not drawn against debit pool")
+		);
+		finalize_block(2);
+		assert_eq!(CDPTreasuryModule::stability_reserve(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 30);
+
+		// metrics of era exceed cumulative metrics
+		let era = CDPTreasuryModule::latest_metrics_era();
+		let era_metrics = CDPTreasuryModule::era_metrics(era);
+		EraMetrics::<Runtime>::insert(
+			era,
+			TreasuryMetrics {
+				burned_native: CDPTreasuryModule::cumulative_metrics().burned_native + 1,
+				..Default::default()
+			},
+		);
+		assert_noop!(
+			CDPTreasuryModule::do_try_state(),
+			DispatchError::Other("cdp-treasury: metrics of eras exceed cumulative metrics")
+		);
+		EraMetrics::<Runtime>::set(era, era_metrics);
+		assert_ok!(CDPTreasuryModule::do_try_state());
+
+		// metrics of expired era is kept
+		EraMetrics::<Runtime>::insert(
+			<Runtime as Config>::MaxMetricsEras::get() + 1,
+			TreasuryMetrics::default(),
+		);
+		assert_noop!(
+			CDPTreasuryModule::do_try_state(),
+			DispatchError::Other("cdp-treasury: metrics of expired or future era are kept")
+		);
+	});
+}
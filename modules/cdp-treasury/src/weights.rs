@@ -51,6 +51,34 @@ pub trait WeightInfo {
 	fn auction_collateral(b: u32) -> Weight;
 	fn exchange_collateral_to_stable() -> Weight;
 	fn set_expected_collateral_auction_size() -> Weight;
+	fn set_expected_collateral_auction_value() -> Weight;
+	fn set_expected_stable_auction_value() -> Weight;
+	fn set_collateral_auction_params(n: u32) -> Weight;
+	fn trigger_settlement() -> Weight;
+	fn flash_loan() -> Weight;
+	fn repay_debit_pool() -> Weight;
+	fn deploy_liquidity() -> Weight;
+	fn recall_liquidity() -> Weight;
+	fn set_debit_offset_buffer() -> Weight;
+	fn set_surplus_buffer_size() -> Weight;
+	fn set_dex_settlement_cap() -> Weight;
+	fn set_max_swap_slippage() -> Weight;
+	fn set_collateral_swap_path() -> Weight;
+	fn transfer_surplus() -> Weight;
+	fn set_surplus_distribution() -> Weight;
+	fn set_stability_reserve_target() -> Weight;
+	fn set_collateral_dust_threshold() -> Weight;
+	fn sweep_dust_collateral() -> Weight;
+	fn set_surplus_settlement_size() -> Weight;
+	fn set_debit_settlement_premium() -> Weight;
+	fn set_settlement_params() -> Weight;
+	fn set_flash_loan_fee_rate() -> Weight;
+	fn set_max_liquidity_deployment_ratio() -> Weight;
+	fn set_stability_fee_incentives_ratio() -> Weight;
+	fn set_debit_pool_interest_rate() -> Weight;
+	fn set_issuance_limits() -> Weight;
+	fn set_collateral_swap_budget() -> Weight;
+	fn on_finalize() -> Weight;
 }
 
 /// Weights for module_cdp_treasury using the Acala node and recommended hardware.
@@ -73,11 +101,135 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 		Weight::from_parts(25_000_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	fn set_expected_collateral_auction_value() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_expected_stable_auction_value() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_collateral_auction_params(n: u32, ) -> Weight {
+		Weight::from_parts(3_218_000, 0)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
+	}
 	fn extract_surplus_to_treasury() -> Weight {
 		Weight::from_parts(75_000_000, 0)
 			.saturating_add(T::DbWeight::get().reads(4 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	fn trigger_settlement() -> Weight {
+		Weight::from_parts(160_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	fn flash_loan() -> Weight {
+		Weight::from_parts(43000000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	fn repay_debit_pool() -> Weight {
+		Weight::from_parts(36000000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn deploy_liquidity() -> Weight {
+		Weight::from_parts(98000000, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	fn recall_liquidity() -> Weight {
+		Weight::from_parts(86000000, 0)
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	fn set_debit_offset_buffer() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_surplus_buffer_size() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_dex_settlement_cap() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_max_swap_slippage() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_collateral_swap_path() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn transfer_surplus() -> Weight {
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn set_surplus_distribution() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_stability_reserve_target() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_collateral_dust_threshold() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn sweep_dust_collateral() -> Weight {
+		Weight::from_parts(180_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	fn set_surplus_settlement_size() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_debit_settlement_premium() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_settlement_params() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn set_flash_loan_fee_rate() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_max_liquidity_deployment_ratio() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_stability_fee_incentives_ratio() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_debit_pool_interest_rate() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_issuance_limits() -> Weight {
+		Weight::from_parts(26_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn set_collateral_swap_budget() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn on_finalize() -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -98,9 +250,133 @@ impl WeightInfo for () {
 		Weight::from_parts(25_000_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	fn set_expected_collateral_auction_value() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_expected_stable_auction_value() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_collateral_auction_params(n: u32, ) -> Weight {
+		Weight::from_parts(3_218_000, 0)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
+	}
 	fn extract_surplus_to_treasury() -> Weight {
 		Weight::from_parts(75_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(4 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	fn trigger_settlement() -> Weight {
+		Weight::from_parts(160_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(14 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	fn flash_loan() -> Weight {
+		Weight::from_parts(43000000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn repay_debit_pool() -> Weight {
+		Weight::from_parts(36000000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn deploy_liquidity() -> Weight {
+		Weight::from_parts(98000000, 0)
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+	}
+	fn recall_liquidity() -> Weight {
+		Weight::from_parts(86000000, 0)
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	fn set_debit_offset_buffer() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_surplus_buffer_size() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_dex_settlement_cap() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_max_swap_slippage() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_collateral_swap_path() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn transfer_surplus() -> Weight {
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn set_surplus_distribution() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_stability_reserve_target() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_collateral_dust_threshold() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn sweep_dust_collateral() -> Weight {
+		Weight::from_parts(180_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	fn set_surplus_settlement_size() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_debit_settlement_premium() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_settlement_params() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_flash_loan_fee_rate() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_max_liquidity_deployment_ratio() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_stability_fee_incentives_ratio() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_debit_pool_interest_rate() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_issuance_limits() -> Weight {
+		Weight::from_parts(26_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_collateral_swap_budget() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn on_finalize() -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
 }
@@ -131,6 +131,14 @@ impl DEXManager<AccountId, Balance, CurrencyId> for MockDEX {
 		unimplemented!()
 	}
 
+	fn get_best_route(
+		_: CurrencyId,
+		_: CurrencyId,
+		_: SwapLimit<Balance>,
+	) -> Option<(Vec<CurrencyId>, Balance, Balance)> {
+		unimplemented!()
+	}
+
 	fn swap_with_specific_path(
 		_: &AccountId,
 		_: &[CurrencyId],
@@ -0,0 +1,42 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use sp_runtime::codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait DexApi<CurrencyId, Balance> where
+		CurrencyId: Codec,
+		Balance: Codec,
+	{
+		/// Get the target amount of swapping the exact supply amount by the path, return None if
+		/// the swap can't be done.
+		fn get_swap_target_amount(path: Vec<CurrencyId>, supply_amount: Balance) -> Option<Balance>;
+
+		/// Get the supply amount of swapping for the exact target amount by the path, return None
+		/// if the swap can't be done.
+		fn get_swap_supply_amount(path: Vec<CurrencyId>, target_amount: Balance) -> Option<Balance>;
+
+		/// Get the liquidity pool of the trading pair, in the order of `currency_id_a` and
+		/// `currency_id_b`.
+		fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance);
+	}
+}
@@ -33,7 +33,11 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::collapsible_if)]
 
-use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	pallet_prelude::*,
+	transactional, PalletId,
+};
 use frame_system::pallet_prelude::*;
 use module_support::{DEXIncentives, DEXManager, Erc20InfoMapping, ExchangeRate, Ratio, SwapLimit};
 use orml_traits::{Happened, MultiCurrency, MultiCurrencyExtended};
@@ -42,8 +46,9 @@ use primitives::{Balance, CurrencyId, TradingPair};
 use scale_info::TypeInfo;
 use sp_core::{H160, U256};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Saturating, Zero},
-	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, SaturatedConversion,
+	traits::{AccountIdConversion, Dispatchable, One, Saturating, Zero},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, PerThing, Permill, RuntimeDebug,
+	SaturatedConversion,
 };
 use sp_std::{prelude::*, vec};
 
@@ -133,6 +138,14 @@ pub mod module {
 
 		/// Event handler which calls when update liquidity pool.
 		type OnLiquidityPoolUpdated: Happened<(TradingPair, Balance, Balance)>;
+
+		/// The account receives the protocol fee share of exchange fee.
+		type ProtocolFeeReceiver: Get<Self::AccountId>;
+
+		/// The receiver call dispatched by flash swap.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
 	}
 
 	#[pallet::error]
@@ -183,6 +196,14 @@ pub mod module {
 		NotAllowedRefund,
 		/// Cannot swap
 		CannotSwap,
+		/// The swap is submitted after the deadline
+		SwapDeadlineExpired,
+		/// The exchange fee is invalid
+		InvalidExchangeFee,
+		/// The trading pair is locked by an ongoing flash swap
+		TradingPairLocked,
+		/// The supply amount of flash swap is not paid
+		FlashSwapNotRepaid,
 	}
 
 	#[pallet::event]
@@ -247,6 +268,13 @@ pub mod module {
 			accumulated_provision_0: Balance,
 			accumulated_provision_1: Balance,
 		},
+		/// The exchange fee of trading pair updated.
+		ExchangeFeeUpdated {
+			trading_pair: TradingPair,
+			exchange_fee: Option<(u32, u32)>,
+		},
+		/// The protocol fee share of exchange fee updated.
+		ProtocolFeeShareUpdated { share: Permill },
 	}
 
 	/// Liquidity pool for TradingPair.
@@ -264,6 +292,28 @@ pub mod module {
 	pub type TradingPairStatuses<T: Config> =
 		StorageMap<_, Twox64Concat, TradingPair, TradingPairStatus<Balance, BlockNumberFor<T>>, ValueQuery>;
 
+	/// Exchange fee of TradingPair, overrides `GetExchangeFee` if it's set.
+	///
+	/// ExchangeFees: map TradingPair => Option<(u32, u32)>
+	#[pallet::storage]
+	#[pallet::getter(fn exchange_fees)]
+	pub type ExchangeFees<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (u32, u32), OptionQuery>;
+
+	/// The share of exchange fee transferred to `ProtocolFeeReceiver` rather
+	/// than left in the liquidity pool.
+	///
+	/// ProtocolFeeShare: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee_share)]
+	pub type ProtocolFeeShare<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The trading pairs locked by ongoing flash swaps, their liquidity pools can't be
+	/// changed until the flash swap is repaid.
+	///
+	/// FlashSwapLocks: map TradingPair => Option<()>
+	#[pallet::storage]
+	pub type FlashSwapLocks<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (), OptionQuery>;
+
 	/// Provision of TradingPair by AccountId.
 	///
 	/// ProvisioningPool: double_map TradingPair, AccountId => (Balance,
@@ -834,6 +884,222 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Trading with DEX, swap with exact supply amount before the deadline
+		///
+		/// - `path`: trading path.
+		/// - `supply_amount`: exact supply amount.
+		/// - `min_target_amount`: acceptable minimum target amount.
+		/// - `deadline`: the swap fails if it's executed after this block.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_supply(path.len() as u32))]
+		pub fn swap_with_exact_supply_before_deadline(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] supply_amount: Balance,
+			#[pallet::compact] min_target_amount: Balance,
+			deadline: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_before_deadline(deadline)?;
+			Self::do_swap_with_exact_supply(&who, &path, supply_amount, min_target_amount)?;
+			Ok(())
+		}
+
+		/// Trading with DEX, swap with exact target amount before the deadline
+		///
+		/// - `path`: trading path.
+		/// - `target_amount`: exact target amount.
+		/// - `max_supply_amount`: acceptable maximum supply amount.
+		/// - `deadline`: the swap fails if it's executed after this block.
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_target(path.len() as u32))]
+		pub fn swap_with_exact_target_before_deadline(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] target_amount: Balance,
+			#[pallet::compact] max_supply_amount: Balance,
+			deadline: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_before_deadline(deadline)?;
+			Self::do_swap_with_exact_target(&who, &path, target_amount, max_supply_amount)?;
+			Ok(())
+		}
+
+		/// Set the exchange fee of trading pair.
+		///
+		/// The dispatch origin of this call must be `ListingOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `exchange_fee`: the numerator and denominator of fee rate, `None` means to use
+		///   `GetExchangeFee`.
+		#[pallet::call_index(15)]
+		#[pallet::weight((<T as Config>::WeightInfo::enable_trading_pair(), DispatchClass::Operational))]
+		pub fn set_exchange_fee(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			exchange_fee: Option<(u32, u32)>,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+			if let Some((fee_numerator, fee_denominator)) = exchange_fee {
+				ensure!(fee_numerator < fee_denominator, Error::<T>::InvalidExchangeFee);
+			}
+
+			ExchangeFees::<T>::set(trading_pair, exchange_fee);
+			Self::deposit_event(Event::ExchangeFeeUpdated {
+				trading_pair,
+				exchange_fee,
+			});
+			Ok(())
+		}
+
+		/// Set the protocol fee share of exchange fee.
+		///
+		/// The dispatch origin of this call must be `ListingOrigin`.
+		///
+		/// - `share`: the share of exchange fee transferred to `ProtocolFeeReceiver`.
+		#[pallet::call_index(16)]
+		#[pallet::weight((<T as Config>::WeightInfo::enable_trading_pair(), DispatchClass::Operational))]
+		pub fn set_protocol_fee_share(origin: OriginFor<T>, share: Permill) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			ProtocolFeeShare::<T>::put(share);
+			Self::deposit_event(Event::ProtocolFeeShareUpdated { share });
+			Ok(())
+		}
+
+		/// Add liquidity to Enabled trading pair with only one currency, half of the supply
+		/// amount will be swapped into the other currency of the trading pair first.
+		///
+		/// - `supply_currency_id`: the currency supplied by caller.
+		/// - `other_currency_id`: the other currency of the trading pair.
+		/// - `supply_amount`: the amount of supply_currency_id.
+		/// - `min_share_increment`: minimum acceptable share amount.
+		/// - `stake_increment_share`: indicates whether to stake increased dex share to earn
+		///   incentives
+		#[pallet::call_index(17)]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_supply(2).saturating_add(if *stake_increment_share {
+			<T as Config>::WeightInfo::add_liquidity_and_stake()
+		} else {
+			<T as Config>::WeightInfo::add_liquidity()
+		}))]
+		pub fn add_liquidity_with_single_currency(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyId,
+			other_currency_id: CurrencyId,
+			#[pallet::compact] supply_amount: Balance,
+			#[pallet::compact] min_share_increment: Balance,
+			stake_increment_share: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_add_liquidity_with_single_currency(
+				&who,
+				supply_currency_id,
+				other_currency_id,
+				supply_amount,
+				min_share_increment,
+				stake_increment_share,
+			)?;
+			Ok(())
+		}
+
+		/// Remove liquidity from specific liquidity pool and withdraw all of it in one currency,
+		/// the withdrawn other currency of the trading pair will be swapped into target currency.
+		///
+		/// - `target_currency_id`: the currency withdrawn to caller.
+		/// - `other_currency_id`: the other currency of the trading pair.
+		/// - `remove_share`: liquidity amount to remove.
+		/// - `min_target_amount`: minimum acceptable withdrawn for target_currency_id.
+		/// - `by_unstake`: this flag indicates whether to withdraw share which is on incentives.
+		#[pallet::call_index(18)]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_supply(2).saturating_add(if *by_unstake {
+			<T as Config>::WeightInfo::remove_liquidity_by_unstake()
+		} else {
+			<T as Config>::WeightInfo::remove_liquidity()
+		}))]
+		pub fn remove_liquidity_to_single_currency(
+			origin: OriginFor<T>,
+			target_currency_id: CurrencyId,
+			other_currency_id: CurrencyId,
+			#[pallet::compact] remove_share: Balance,
+			#[pallet::compact] min_target_amount: Balance,
+			by_unstake: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_remove_liquidity_to_single_currency(
+				&who,
+				target_currency_id,
+				other_currency_id,
+				remove_share,
+				min_target_amount,
+				by_unstake,
+			)?;
+			Ok(())
+		}
+
+		/// Receive the target amount from liquidity pool first and dispatch `receiver_call`,
+		/// the supply amount calculated by the liquidity pool before the swap must be paid at the
+		/// end of `receiver_call`, otherwise the whole call is reverted. The trading pair is
+		/// locked during `receiver_call`.
+		///
+		/// - `supply_currency_id`: the currency to pay.
+		/// - `target_currency_id`: the currency to receive.
+		/// - `target_amount`: exact target amount.
+		/// - `max_supply_amount`: acceptable maximum supply amount.
+		/// - `receiver_call`: the call dispatched by caller with the target amount.
+		#[pallet::call_index(19)]
+		#[pallet::weight({
+			let dispatch_info = receiver_call.get_dispatch_info();
+			(
+				<T as Config>::WeightInfo::swap_with_exact_target(2).saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		})]
+		#[transactional]
+		pub fn flash_swap(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			#[pallet::compact] target_amount: Balance,
+			#[pallet::compact] max_supply_amount: Balance,
+			receiver_call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin.clone())?;
+			let path = vec![supply_currency_id, target_currency_id];
+			let amounts = Self::get_supply_amounts(&path, target_amount)?;
+			let supply_amount = amounts[0];
+			ensure!(supply_amount <= max_supply_amount, Error::<T>::ExcessiveSupplyAmount);
+			let trading_pair = TradingPair::from_currency_ids(supply_currency_id, target_currency_id)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			let module_account_id = Self::account_id();
+
+			T::Currency::transfer(target_currency_id, &module_account_id, &who, target_amount)?;
+			FlashSwapLocks::<T>::insert(trading_pair, ());
+
+			let dispatch_info = receiver_call.get_dispatch_info();
+			let post_info = receiver_call.dispatch(origin).map_err(|e| e.error)?;
+
+			FlashSwapLocks::<T>::remove(trading_pair);
+			T::Currency::transfer(supply_currency_id, &who, &module_account_id, supply_amount)
+				.map_err(|_| Error::<T>::FlashSwapNotRepaid)?;
+			Self::_swap_by_path(&path, &amounts)?;
+
+			Self::deposit_event(Event::Swap {
+				trader: who,
+				path,
+				liquidity_changes: amounts,
+			});
+
+			let actual_weight = post_info
+				.actual_weight
+				.unwrap_or(dispatch_info.weight)
+				.saturating_add(<T as Config>::WeightInfo::swap_with_exact_target(2));
+			Ok(Some(actual_weight).into())
+		}
 	}
 }
 
@@ -842,10 +1108,14 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account_truncating()
 	}
 
-	fn try_mutate_liquidity_pool<R, E>(
+	fn try_mutate_liquidity_pool<R, E: From<Error<T>>>(
 		trading_pair: &TradingPair,
 		f: impl FnOnce((&mut Balance, &mut Balance)) -> sp_std::result::Result<R, E>,
 	) -> sp_std::result::Result<R, E> {
+		if FlashSwapLocks::<T>::contains_key(trading_pair) {
+			return Err(Error::<T>::TradingPairLocked.into());
+		}
+
 		LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> sp_std::result::Result<R, E> {
 			let old_pool_0 = *pool_0;
 			let old_pool_1 = *pool_1;
@@ -1167,7 +1437,68 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
-	fn get_liquidity(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+	#[transactional]
+	fn do_add_liquidity_with_single_currency(
+		who: &T::AccountId,
+		supply_currency_id: CurrencyId,
+		other_currency_id: CurrencyId,
+		supply_amount: Balance,
+		min_share_increment: Balance,
+		stake_increment_share: bool,
+	) -> sp_std::result::Result<(Balance, Balance, Balance), DispatchError> {
+		let swap_amount = supply_amount / 2;
+		let other_amount =
+			Self::do_swap_with_exact_supply(who, &[supply_currency_id, other_currency_id], swap_amount, Zero::zero())?;
+
+		Self::do_add_liquidity(
+			who,
+			supply_currency_id,
+			other_currency_id,
+			supply_amount.saturating_sub(swap_amount),
+			other_amount,
+			min_share_increment,
+			stake_increment_share,
+		)
+	}
+
+	#[transactional]
+	fn do_remove_liquidity_to_single_currency(
+		who: &T::AccountId,
+		target_currency_id: CurrencyId,
+		other_currency_id: CurrencyId,
+		remove_share: Balance,
+		min_target_amount: Balance,
+		by_unstake: bool,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		let (withdrawn_target, withdrawn_other) = Self::do_remove_liquidity(
+			who,
+			target_currency_id,
+			other_currency_id,
+			remove_share,
+			Zero::zero(),
+			Zero::zero(),
+			by_unstake,
+		)?;
+		let swapped_target = if withdrawn_other.is_zero() {
+			Zero::zero()
+		} else {
+			Self::do_swap_with_exact_supply(
+				who,
+				&[other_currency_id, target_currency_id],
+				withdrawn_other,
+				Zero::zero(),
+			)?
+		};
+
+		let target_amount = withdrawn_target.saturating_add(swapped_target);
+		ensure!(
+			target_amount >= min_target_amount,
+			Error::<T>::UnacceptableLiquidityWithdrawn
+		);
+		Ok(target_amount)
+	}
+
+	pub fn get_liquidity(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
 		if let Some(trading_pair) = TradingPair::from_currency_ids(currency_id_a, currency_id_b) {
 			let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
 			if currency_id_a == trading_pair.first() {
@@ -1180,12 +1511,22 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Get the exchange fee of trading pair.
+	fn get_exchange_fee(trading_pair: &TradingPair) -> (u32, u32) {
+		Self::exchange_fees(trading_pair).unwrap_or_else(T::GetExchangeFee::get)
+	}
+
 	/// Get how much target amount will be got for specific supply amount.
-	fn get_target_amount(supply_pool: Balance, target_pool: Balance, supply_amount: Balance) -> Balance {
+	fn get_target_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_amount: Balance,
+		exchange_fee: (u32, u32),
+	) -> Balance {
 		if supply_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+			let (fee_numerator, fee_denominator) = exchange_fee;
 			let supply_amount_with_fee: U256 =
 				U256::from(supply_amount).saturating_mul(U256::from(fee_denominator.saturating_sub(fee_numerator)));
 			let numerator: U256 = supply_amount_with_fee.saturating_mul(U256::from(target_pool));
@@ -1201,11 +1542,16 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Get how much supply amount will be paid for specific target amount.
-	fn get_supply_amount(supply_pool: Balance, target_pool: Balance, target_amount: Balance) -> Balance {
+	fn get_supply_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		target_amount: Balance,
+		exchange_fee: (u32, u32),
+	) -> Balance {
 		if target_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+			let (fee_numerator, fee_denominator) = exchange_fee;
 			let numerator: U256 = U256::from(supply_pool)
 				.saturating_mul(U256::from(target_amount))
 				.saturating_mul(U256::from(fee_denominator));
@@ -1221,6 +1567,20 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Get the target amount of swapping the exact supply amount by the path.
+	pub fn get_swap_target_amount(path: &[CurrencyId], supply_amount: Balance) -> Option<Balance> {
+		Self::get_target_amounts(path, supply_amount)
+			.ok()
+			.map(|amounts| amounts[amounts.len() - 1])
+	}
+
+	/// Get the supply amount of swapping for the exact target amount by the path.
+	pub fn get_swap_supply_amount(path: &[CurrencyId], target_amount: Balance) -> Option<Balance> {
+		Self::get_supply_amounts(path, target_amount)
+			.ok()
+			.map(|amounts| amounts[0])
+	}
+
 	fn get_target_amounts(
 		path: &[CurrencyId],
 		supply_amount: Balance,
@@ -1247,7 +1607,12 @@ impl<T: Config> Pallet<T> {
 				!supply_pool.is_zero() && !target_pool.is_zero(),
 				Error::<T>::InsufficientLiquidity
 			);
-			let target_amount = Self::get_target_amount(supply_pool, target_pool, target_amounts[i]);
+			let target_amount = Self::get_target_amount(
+				supply_pool,
+				target_pool,
+				target_amounts[i],
+				Self::get_exchange_fee(&trading_pair),
+			);
 			ensure!(!target_amount.is_zero(), Error::<T>::ZeroTargetAmount);
 
 			target_amounts[i + 1] = target_amount;
@@ -1283,7 +1648,12 @@ impl<T: Config> Pallet<T> {
 				!supply_pool.is_zero() && !target_pool.is_zero(),
 				Error::<T>::InsufficientLiquidity
 			);
-			let supply_amount = Self::get_supply_amount(supply_pool, target_pool, supply_amounts[i]);
+			let supply_amount = Self::get_supply_amount(
+				supply_pool,
+				target_pool,
+				supply_amounts[i],
+				Self::get_exchange_fee(&trading_pair),
+			);
 			ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
 
 			supply_amounts[i - 1] = supply_amount;
@@ -1293,6 +1663,14 @@ impl<T: Config> Pallet<T> {
 		Ok(supply_amounts)
 	}
 
+	fn ensure_before_deadline(deadline: BlockNumberFor<T>) -> DispatchResult {
+		ensure!(
+			frame_system::Pallet::<T>::block_number() <= deadline,
+			Error::<T>::SwapDeadlineExpired
+		);
+		Ok(())
+	}
+
 	fn validate_path(path: &[CurrencyId]) -> DispatchResult {
 		let path_length = path.len();
 		ensure!(
@@ -1334,11 +1712,46 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Transfer the protocol fee share of the exchange fee charged on
+	/// `supply_amount` to `ProtocolFeeReceiver`, return the remaining amount
+	/// for liquidity pool.
+	fn take_protocol_fee(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+	) -> Balance {
+		let protocol_fee_share = Self::protocol_fee_share();
+		let trading_pair = match TradingPair::from_currency_ids(supply_currency_id, target_currency_id) {
+			Some(trading_pair) if !protocol_fee_share.is_zero() => trading_pair,
+			_ => return supply_amount,
+		};
+
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee(&trading_pair);
+		let exchange_fee: Balance = U256::from(supply_amount)
+			.saturating_mul(U256::from(fee_numerator))
+			.checked_div(U256::from(fee_denominator))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.unwrap_or_else(Zero::zero);
+		let protocol_fee = protocol_fee_share.mul_floor(exchange_fee);
+
+		let receiver = T::ProtocolFeeReceiver::get();
+
+		// leave the fee in liquidity pool if failed to transfer
+		if !protocol_fee.is_zero()
+			&& T::Currency::transfer(supply_currency_id, &Self::account_id(), &receiver, protocol_fee).is_ok()
+		{
+			supply_amount.saturating_sub(protocol_fee)
+		} else {
+			supply_amount
+		}
+	}
+
 	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance]) -> DispatchResult {
 		let mut i: usize = 0;
 		while i + 1 < path.len() {
 			let (supply_currency_id, target_currency_id) = (path[i], path[i + 1]);
-			let (supply_increment, target_decrement) = (amounts[i], amounts[i + 1]);
+			let supply_increment = Self::take_protocol_fee(supply_currency_id, target_currency_id, amounts[i]);
+			let target_decrement = amounts[i + 1];
 			Self::_swap(
 				supply_currency_id,
 				target_currency_id,
@@ -1483,6 +1896,43 @@ impl<T: Config> DEXManager<T::AccountId, Balance, CurrencyId> for Pallet<T> {
 		maybe_best
 	}
 
+	/// Note: iterates all the trading pairs, should only be used in runtime api
+	/// or for the trading pairs bounded by governance.
+	fn get_best_route(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> Option<(Vec<CurrencyId>, Balance, Balance)> {
+		let alternative_path_joint_list = TradingPairStatuses::<T>::iter()
+			.filter_map(|(trading_pair, status)| {
+				if !matches!(status, TradingPairStatus::<_, _>::Enabled) {
+					return None;
+				}
+
+				let joint = if trading_pair.first() == supply_currency_id {
+					trading_pair.second()
+				} else if trading_pair.second() == supply_currency_id {
+					trading_pair.first()
+				} else {
+					return None;
+				};
+
+				if joint == target_currency_id {
+					None
+				} else {
+					Some(vec![joint])
+				}
+			})
+			.collect::<Vec<_>>();
+
+		Self::get_best_price_swap_path(
+			supply_currency_id,
+			target_currency_id,
+			limit,
+			alternative_path_joint_list,
+		)
+	}
+
 	fn swap_with_specific_path(
 		who: &T::AccountId,
 		path: &[CurrencyId],
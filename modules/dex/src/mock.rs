@@ -119,6 +119,7 @@ ord_parameter_types! {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
+	pub const ProtocolFeeReceiver: AccountId = 100;
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
 		vec![DOT],
 	];
@@ -150,6 +151,8 @@ impl Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<2000>;
 	type OnLiquidityPoolUpdated = MockOnLiquidityPoolUpdated;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type RuntimeCall = RuntimeCall;
 }
 
 parameter_types! {
@@ -23,8 +23,9 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	ACAJointSwap, AUSDBTCPair, AUSDDOTPair, AUSDJointSwap, DOTBTCPair, DexModule, ExtBuilder, ListingOrigin, Runtime,
-	RuntimeEvent, RuntimeOrigin, System, Tokens, ACA, ALICE, AUSD, AUSD_DOT_POOL_RECORD, BOB, BTC, CAROL, DOT,
+	ACAJointSwap, AUSDBTCPair, AUSDDOTPair, AUSDJointSwap, DOTBTCPair, DexModule, ExtBuilder, GetExchangeFee,
+	ListingOrigin, ProtocolFeeReceiver, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, System, Tokens, ACA, ALICE,
+	AUSD, AUSD_DOT_POOL_RECORD, BOB, BTC, CAROL, DOT,
 };
 use module_support::{Swap, SwapError};
 use orml_traits::MultiReservableCurrency;
@@ -973,26 +974,28 @@ fn get_liquidity_work() {
 #[test]
 fn get_target_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_target_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000), 9949);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		let fee = GetExchangeFee::get();
+		assert_eq!(DexModule::get_target_amount(10000, 0, 1000, fee), 0);
+		assert_eq!(DexModule::get_target_amount(0, 20000, 1000, fee), 0);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 0, fee), 0);
+		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000, fee), 0);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000, fee), 9949);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000, fee), 1801);
 	});
 }
 
 #[test]
 fn get_supply_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 1, 1), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 9949), 9999);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999), 9949);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 1801), 1000);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		let fee = GetExchangeFee::get();
+		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000, fee), 0);
+		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000, fee), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0, fee), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 1, 1, fee), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 20000, 9949, fee), 9999);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999, fee), 9949);
+		assert_eq!(DexModule::get_supply_amount(10000, 20000, 1801, fee), 1000);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000, fee), 1801);
 	});
 }
 
@@ -1046,6 +1049,7 @@ fn get_target_amounts_work() {
 #[test]
 fn calculate_amount_for_big_number_work() {
 	ExtBuilder::default().build().execute_with(|| {
+		let fee = GetExchangeFee::get();
 		LiquidityPool::<Runtime>::insert(
 			AUSDDOTPair::get(),
 			(171_000_000_000_000_000_000_000, 56_000_000_000_000_000_000_000),
@@ -1054,7 +1058,8 @@ fn calculate_amount_for_big_number_work() {
 			DexModule::get_supply_amount(
 				171_000_000_000_000_000_000_000,
 				56_000_000_000_000_000_000_000,
-				1_000_000_000_000_000_000_000
+				1_000_000_000_000_000_000_000,
+				fee
 			),
 			3_140_495_867_768_595_041_323
 		);
@@ -1062,7 +1067,8 @@ fn calculate_amount_for_big_number_work() {
 			DexModule::get_target_amount(
 				171_000_000_000_000_000_000_000,
 				56_000_000_000_000_000_000_000,
-				3_140_495_867_768_595_041_323
+				3_140_495_867_768_595_041_323,
+				fee
 			),
 			1_000_000_000_000_000_000_000
 		);
@@ -1825,6 +1831,39 @@ fn get_best_price_swap_path_work() {
 		});
 }
 
+#[test]
+fn get_best_route_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (300000, 100000));
+			LiquidityPool::<Runtime>::insert(AUSDBTCPair::get(), (50000, 10000));
+			LiquidityPool::<Runtime>::insert(DOTBTCPair::get(), (10000, 10000));
+
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(10, 0)),
+				Some((vec![DOT, BTC, AUSD], 10, 44))
+			);
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(10000, 0)),
+				Some((vec![DOT, AUSD], 10000, 27024))
+			);
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(10, 50)),
+				None
+			);
+			assert_eq!(DexModule::get_best_route(DOT, ACA, SwapLimit::ExactSupply(10, 0)), None);
+
+			// the route through disabled trading pair is not considered
+			TradingPairStatuses::<Runtime>::insert(DOTBTCPair::get(), TradingPairStatus::<_, _>::Disabled);
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(10, 0)),
+				Some((vec![DOT, AUSD], 10, 29))
+			);
+		});
+}
+
 #[test]
 fn swap_with_specific_path_work() {
 	ExtBuilder::default()
@@ -1993,3 +2032,508 @@ fn specific_joint_swap_work() {
 			);
 		});
 }
+
+#[test]
+fn swap_before_deadline_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				RuntimeOrigin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				0,
+				false,
+			));
+
+			System::set_block_number(10);
+			assert_noop!(
+				DexModule::swap_with_exact_supply_before_deadline(
+					RuntimeOrigin::signed(BOB),
+					vec![DOT, AUSD],
+					100_000_000_000_000,
+					0,
+					9
+				),
+				Error::<Runtime>::SwapDeadlineExpired
+			);
+			assert_noop!(
+				DexModule::swap_with_exact_target_before_deadline(
+					RuntimeOrigin::signed(BOB),
+					vec![DOT, AUSD],
+					250_000_000_000_000,
+					200_000_000_000_000,
+					9
+				),
+				Error::<Runtime>::SwapDeadlineExpired
+			);
+			assert_noop!(
+				DexModule::swap_with_exact_supply_before_deadline(
+					RuntimeOrigin::signed(BOB),
+					vec![DOT, AUSD],
+					100_000_000_000_000,
+					248_743_718_592_965,
+					10
+				),
+				Error::<Runtime>::InsufficientTargetAmount
+			);
+
+			assert_ok!(DexModule::swap_with_exact_supply_before_deadline(
+				RuntimeOrigin::signed(BOB),
+				vec![DOT, AUSD],
+				100_000_000_000_000,
+				0,
+				10
+			));
+			System::assert_last_event(RuntimeEvent::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![DOT, AUSD],
+				liquidity_changes: vec![100_000_000_000_000, 248_743_718_592_964],
+			}));
+
+			assert_ok!(DexModule::swap_with_exact_target_before_deadline(
+				RuntimeOrigin::signed(BOB),
+				vec![AUSD, DOT],
+				10_000_000_000_000,
+				20_000_000_000_000,
+				11
+			));
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(264_613_872_125_114, 190_000_000_000_000)
+			);
+		});
+}
+
+#[test]
+fn set_exchange_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			DexModule::set_exchange_fee(RuntimeOrigin::signed(ALICE), AUSD, DOT, Some((2, 100))),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::set_exchange_fee(RuntimeOrigin::signed(ListingOrigin::get()), DOT, DOT, Some((2, 100))),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			DexModule::set_exchange_fee(RuntimeOrigin::signed(ListingOrigin::get()), AUSD, DOT, Some((100, 100))),
+			Error::<Runtime>::InvalidExchangeFee
+		);
+
+		assert_eq!(DexModule::get_exchange_fee(&AUSDDOTPair::get()), GetExchangeFee::get());
+		assert_ok!(DexModule::set_exchange_fee(
+			RuntimeOrigin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			Some((2, 100))
+		));
+		System::assert_last_event(RuntimeEvent::DexModule(crate::Event::ExchangeFeeUpdated {
+			trading_pair: AUSDDOTPair::get(),
+			exchange_fee: Some((2, 100)),
+		}));
+		assert_eq!(DexModule::exchange_fees(AUSDDOTPair::get()), Some((2, 100)));
+		assert_eq!(DexModule::get_exchange_fee(&AUSDDOTPair::get()), (2, 100));
+		assert_eq!(DexModule::get_exchange_fee(&AUSDBTCPair::get()), GetExchangeFee::get());
+
+		assert_ok!(DexModule::set_exchange_fee(
+			RuntimeOrigin::signed(ListingOrigin::get()),
+			DOT,
+			AUSD,
+			None
+		));
+		System::assert_last_event(RuntimeEvent::DexModule(crate::Event::ExchangeFeeUpdated {
+			trading_pair: AUSDDOTPair::get(),
+			exchange_fee: None,
+		}));
+		assert_eq!(DexModule::exchange_fees(AUSDDOTPair::get()), None);
+		assert_eq!(DexModule::get_exchange_fee(&AUSDDOTPair::get()), GetExchangeFee::get());
+	});
+}
+
+#[test]
+fn swap_with_protocol_fee_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				RuntimeOrigin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				0,
+				false,
+			));
+			assert_ok!(DexModule::set_exchange_fee(
+				RuntimeOrigin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Some((2, 100))
+			));
+
+			assert_noop!(
+				DexModule::set_protocol_fee_share(RuntimeOrigin::signed(ALICE), Permill::from_percent(50)),
+				BadOrigin
+			);
+			assert_ok!(DexModule::set_protocol_fee_share(
+				RuntimeOrigin::signed(ListingOrigin::get()),
+				Permill::from_percent(50)
+			));
+			System::assert_last_event(RuntimeEvent::DexModule(crate::Event::ProtocolFeeShareUpdated {
+				share: Permill::from_percent(50),
+			}));
+			assert_eq!(DexModule::protocol_fee_share(), Permill::from_percent(50));
+
+			assert_ok!(DexModule::swap_with_exact_supply(
+				RuntimeOrigin::signed(BOB),
+				vec![DOT, AUSD],
+				100_000_000_000_000,
+				0,
+			));
+			System::assert_last_event(RuntimeEvent::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![DOT, AUSD],
+				liquidity_changes: vec![100_000_000_000_000, 247_474_747_474_747],
+			}));
+
+			// half of the 2% exchange fee goes to the protocol fee receiver
+			assert_eq!(
+				Tokens::free_balance(DOT, &ProtocolFeeReceiver::get()),
+				1_000_000_000_000
+			);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(252_525_252_525_253, 199_000_000_000_000)
+			);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 199_000_000_000_000);
+		});
+}
+
+#[test]
+fn transferred_dex_share_can_remove_liquidity() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let dex_share_currency_id = AUSDDOTPair::get().dex_share_currency_id();
+
+			assert_ok!(DexModule::add_liquidity(
+				RuntimeOrigin::signed(ALICE),
+				AUSD,
+				DOT,
+				5_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				false
+			));
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &ALICE), 10_000_000_000_000);
+
+			assert_ok!(Tokens::transfer(
+				RuntimeOrigin::signed(ALICE),
+				BOB,
+				dex_share_currency_id,
+				4_000_000_000_000
+			));
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &ALICE), 6_000_000_000_000);
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &BOB), 4_000_000_000_000);
+
+			assert_ok!(DexModule::remove_liquidity(
+				RuntimeOrigin::signed(BOB),
+				AUSD,
+				DOT,
+				4_000_000_000_000,
+				0,
+				0,
+				false,
+			));
+			System::assert_last_event(RuntimeEvent::DexModule(crate::Event::RemoveLiquidity {
+				who: BOB,
+				currency_0: AUSD,
+				pool_0: 2_000_000_000_000,
+				currency_1: DOT,
+				pool_1: 400_000_000_000,
+				share_decrement: 4_000_000_000_000,
+			}));
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &BOB), 0);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_002_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_400_000_000_000);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(3_000_000_000_000, 600_000_000_000)
+			);
+			assert_eq!(Tokens::total_issuance(dex_share_currency_id), 6_000_000_000_000);
+		});
+}
+
+#[test]
+fn add_and_remove_liquidity_with_single_currency_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let dex_share_currency_id = AUSDDOTPair::get().dex_share_currency_id();
+
+			assert_ok!(DexModule::add_liquidity(
+				RuntimeOrigin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				0,
+				false,
+			));
+			assert_eq!(Tokens::total_issuance(dex_share_currency_id), 1_000_000_000_000_000);
+
+			assert_noop!(
+				DexModule::add_liquidity_with_single_currency(
+					RuntimeOrigin::signed(BOB),
+					DOT,
+					AUSD,
+					10_000_000_000_000,
+					47_619_047_619_048,
+					false,
+				),
+				Error::<Runtime>::UnacceptableShareIncrement
+			);
+			assert_ok!(DexModule::add_liquidity_with_single_currency(
+				RuntimeOrigin::signed(BOB),
+				DOT,
+				AUSD,
+				10_000_000_000_000,
+				47_619_047_619_047,
+				false,
+			));
+			System::assert_has_event(RuntimeEvent::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![DOT, AUSD],
+				liquidity_changes: vec![5_000_000_000_000, 23_582_658_408_766],
+			}));
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &BOB), 47_619_047_619_047);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_990_000_000_000_000);
+			// the AUSD not matched by the DOT at pool price is left to caller
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_896_118_332_993);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(499_103_881_667_007, 110_000_000_000_000)
+			);
+
+			assert_noop!(
+				DexModule::remove_liquidity_to_single_currency(
+					RuntimeOrigin::signed(BOB),
+					DOT,
+					AUSD,
+					47_619_047_619_047,
+					9_727_148_703_956,
+					false,
+				),
+				Error::<Runtime>::UnacceptableLiquidityWithdrawn
+			);
+			assert_ok!(DexModule::remove_liquidity_to_single_currency(
+				RuntimeOrigin::signed(BOB),
+				DOT,
+				AUSD,
+				47_619_047_619_047,
+				9_727_148_703_955,
+				false,
+			));
+			System::assert_last_event(RuntimeEvent::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![AUSD, DOT],
+				liquidity_changes: vec![22_686_540_075_772, 4_727_148_703_956],
+			}));
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &BOB), 0);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_999_727_148_703_955);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_896_118_332_993);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(499_103_881_667_007, 100_272_851_296_045)
+			);
+		});
+}
+
+#[test]
+fn flash_swap_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				RuntimeOrigin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				0,
+				false,
+			));
+			let remark_call = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+
+			assert_noop!(
+				DexModule::flash_swap(
+					RuntimeOrigin::signed(BOB),
+					AUSD,
+					DOT,
+					10_000_000_000_000,
+					56_116_722_783_389,
+					remark_call.clone()
+				),
+				Error::<Runtime>::ExcessiveSupplyAmount
+			);
+
+			// the liquidity pool can't be changed by the receiver call
+			let swap_call = Box::new(RuntimeCall::DexModule(crate::Call::swap_with_exact_supply {
+				path: vec![DOT, AUSD],
+				supply_amount: 10_000_000_000_000,
+				min_target_amount: 0,
+			}));
+			assert_noop!(
+				DexModule::flash_swap(
+					RuntimeOrigin::signed(BOB),
+					AUSD,
+					DOT,
+					10_000_000_000_000,
+					56_116_722_783_390,
+					swap_call
+				),
+				Error::<Runtime>::TradingPairLocked
+			);
+
+			// the receiver call sends the supply currency away and cannot repay
+			let transfer_call = Box::new(RuntimeCall::Tokens(orml_tokens::Call::transfer_all {
+				dest: CAROL,
+				currency_id: AUSD,
+				keep_alive: false,
+			}));
+			assert_noop!(
+				DexModule::flash_swap(
+					RuntimeOrigin::signed(BOB),
+					AUSD,
+					DOT,
+					10_000_000_000_000,
+					56_116_722_783_390,
+					transfer_call
+				),
+				Error::<Runtime>::FlashSwapNotRepaid
+			);
+
+			assert_ok!(DexModule::flash_swap(
+				RuntimeOrigin::signed(BOB),
+				AUSD,
+				DOT,
+				10_000_000_000_000,
+				56_116_722_783_390,
+				remark_call
+			));
+			System::assert_last_event(RuntimeEvent::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![AUSD, DOT],
+				liquidity_changes: vec![56_116_722_783_390, 10_000_000_000_000],
+			}));
+			assert_eq!(FlashSwapLocks::<Runtime>::get(AUSDDOTPair::get()), None);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 999_943_883_277_216_610);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_010_000_000_000_000);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(556_116_722_783_390, 90_000_000_000_000)
+			);
+		});
+}
+
+#[test]
+fn disabled_trading_pair_only_allows_remove_liquidity() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				RuntimeOrigin::signed(ALICE),
+				AUSD,
+				DOT,
+				5_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				false
+			));
+			assert_ok!(DexModule::disable_trading_pair(
+				RuntimeOrigin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT
+			));
+
+			assert_noop!(
+				DexModule::swap_with_exact_supply(RuntimeOrigin::signed(BOB), vec![DOT, AUSD], 100_000_000, 0),
+				Error::<Runtime>::MustBeEnabled
+			);
+			assert_noop!(
+				DexModule::swap_with_exact_target(
+					RuntimeOrigin::signed(BOB),
+					vec![DOT, AUSD],
+					100_000_000,
+					1_000_000_000
+				),
+				Error::<Runtime>::MustBeEnabled
+			);
+			assert_noop!(
+				DexModule::add_liquidity(
+					RuntimeOrigin::signed(BOB),
+					AUSD,
+					DOT,
+					5_000_000_000_000,
+					1_000_000_000_000,
+					0,
+					false
+				),
+				Error::<Runtime>::MustBeEnabled
+			);
+			assert_eq!(
+				DexModule::get_swap_amount(&[DOT, AUSD], SwapLimit::ExactSupply(100_000_000, 0)),
+				None
+			);
+
+			assert_ok!(DexModule::remove_liquidity(
+				RuntimeOrigin::signed(ALICE),
+				AUSD,
+				DOT,
+				10_000_000_000_000,
+				0,
+				0,
+				false,
+			));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (0, 0));
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000_000_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000_000_000_000_000_000);
+		});
+}
+
+#[test]
+fn get_swap_target_and_supply_amount_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+
+			assert_eq!(DexModule::get_swap_target_amount(&[DOT, AUSD], 10000), Some(24874));
+			assert_eq!(DexModule::get_swap_supply_amount(&[DOT, AUSD], 24874), Some(10000));
+			assert_eq!(DexModule::get_swap_target_amount(&[DOT, BTC], 10000), None);
+			assert_eq!(DexModule::get_swap_supply_amount(&[DOT, AUSD], 50000), None);
+			assert_eq!(DexModule::get_liquidity(DOT, AUSD), (10000, 50000));
+		});
+}
@@ -28,15 +28,27 @@
 //! CDPs has debit, cancel all active auctions module, when debits and gaps are
 //! settled, the stable currency holder are allowed to refund a basket of
 //! remaining collateral assets.
+//!
+//! The shutdown goes through the following phases:
+//! 1. `Settlement`: feed prices are frozen, CDPs and collateral auctions are
+//!    processed.
+//! 2. `PricesFixed`: settlement is completed and the final collateral prices are
+//!    fixed.
+//! 3. `Redemption`: stable currency holders reclaim pro-rata baskets of the
+//!    collateral assets in CDP treasury.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::pallet_prelude::*;
+use frame_support::{pallet_prelude::*, PalletId};
 use frame_system::{ensure_signed, pallet_prelude::*};
 use module_support::{AuctionManager, CDPTreasury, EmergencyShutdown, LockablePrice, Ratio};
+use orml_traits::MultiCurrency;
 use primitives::{Balance, CurrencyId};
-use sp_runtime::{traits::Zero, FixedPointNumber};
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	ArithmeticError, FixedPointNumber,
+};
 use sp_std::prelude::*;
 
 mod mock;
@@ -46,6 +58,17 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The phase of emergency shutdown.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ShutdownPhase {
+	/// Feed prices are frozen, CDPs and collateral auctions are being settled.
+	Settlement,
+	/// Settlement is completed and final prices of collaterals are fixed.
+	PricesFixed,
+	/// Stable currency holders can reclaim collaterals.
+	Redemption,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -71,6 +94,19 @@ pub mod module {
 		/// this.
 		type ShutdownOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// The maximum number of queued refund claims processed per block
+		#[pallet::constant]
+		type RefundsPerBlock: Get<u32>;
+
+		/// Stablecoin currency id
+		#[pallet::constant]
+		type GetStableCurrencyId: Get<CurrencyId>;
+
+		/// The emergency shutdown's module id, keep the stable currency of
+		/// queued refund claims.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -87,6 +123,18 @@ pub mod module {
 		ExistPotentialSurplus,
 		/// Exist unhandled debit, means settlement has not been completed
 		ExistUnhandledDebit,
+		/// Final prices of collaterals have already been fixed
+		FinalPricesAlreadyFixed,
+		/// Final prices of collaterals are still not fixed
+		FinalPricesNotFixed,
+		/// The currency is not a valid collateral type
+		InvalidCollateralType,
+		/// The collateral type has already been shutdown
+		CollateralAlreadyShutdown,
+		/// Settlement has already begun, the shutdown can not be cancelled
+		SettlementAlreadyBegun,
+		/// The stable currency amount to refund is zero
+		ZeroRefundAmount,
 	}
 
 	#[pallet::event]
@@ -94,6 +142,12 @@ pub mod module {
 	pub enum Event<T: Config> {
 		/// Emergency shutdown occurs.
 		Shutdown { block_number: BlockNumberFor<T> },
+		/// Emergency shutdown is cancelled before settlement.
+		ShutdownCancelled { block_number: BlockNumberFor<T> },
+		/// The CDPs of a single collateral type are shutdown.
+		CollateralShutdown { currency_id: CurrencyId },
+		/// Settlement is completed and final prices of collaterals are fixed.
+		FinalPricesFixed { block_number: BlockNumberFor<T> },
 		/// The final redemption opened.
 		OpenRefund { block_number: BlockNumberFor<T> },
 		/// Refund info.
@@ -102,6 +156,21 @@ pub mod module {
 			stable_coin_amount: Balance,
 			refund_list: Vec<(CurrencyId, Balance)>,
 		},
+		/// A refund claim is queued.
+		RefundClaimQueued {
+			index: u32,
+			who: T::AccountId,
+			stable_coin_amount: Balance,
+		},
+		/// A queued refund claim failed.
+		RefundClaimFailed {
+			index: u32,
+			who: T::AccountId,
+			stable_coin_amount: Balance,
+			error: DispatchError,
+		},
+		/// Queued refund claims are processed.
+		RefundQueueProcessed { processed: u32, remaining: u32 },
 	}
 
 	/// Emergency shutdown flag
@@ -118,11 +187,65 @@ pub mod module {
 	#[pallet::getter(fn can_refund)]
 	pub type CanRefund<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+	/// Collateral types which are shutdown without halting the whole system
+	///
+	/// ShutdownCollaterals: map CurrencyId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn shutdown_collaterals)]
+	pub type ShutdownCollaterals<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
+	/// The total debit and the total collateral in auction of collateral types
+	/// at the time of shutdown, used to check whether settlement has begun
+	///
+	/// SettlementSnapshot: map CurrencyId => (Balance, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_snapshot)]
+	pub type SettlementSnapshot<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, (Balance, Balance), OptionQuery>;
+
+	/// The queued refund claims, the stable currency of claims is kept by
+	/// the module account until they are processed.
+	///
+	/// RefundQueue: map u32 => (AccountId, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn refund_queue)]
+	pub type RefundQueue<T: Config> = StorageMap<_, Twox64Concat, u32, (T::AccountId, Balance), OptionQuery>;
+
+	/// The index of the next refund claim to be processed
+	///
+	/// RefundQueueHead: u32
+	#[pallet::storage]
+	#[pallet::getter(fn refund_queue_head)]
+	pub type RefundQueueHead<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The index of the next refund claim to be queued
+	///
+	/// RefundQueueTail: u32
+	#[pallet::storage]
+	#[pallet::getter(fn refund_queue_tail)]
+	pub type RefundQueueTail<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Current phase of emergency shutdown, `None` before shutdown
+	///
+	/// Phase: ShutdownPhase
+	#[pallet::storage]
+	#[pallet::getter(fn phase)]
+	pub type Phase<T: Config> = StorageValue<_, ShutdownPhase, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+			if !Self::can_refund() {
+				return T::DbWeight::get().reads(1);
+			}
+			let processed = Self::process_refund_queue();
+			T::WeightInfo::refund_collaterals(T::CollateralCurrencyIds::get().len() as u32)
+				.saturating_mul(processed.into())
+				.saturating_add(T::DbWeight::get().reads_writes(3, 1))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -142,16 +265,18 @@ pub mod module {
 			for currency_id in collateral_currency_ids {
 				// TODO: check the results
 				let _ = <T as Config>::PriceSource::lock_price(currency_id);
+				SettlementSnapshot::<T>::insert(currency_id, Self::settlement_status(currency_id));
 			}
 
 			IsShutdown::<T>::put(true);
+			Phase::<T>::put(ShutdownPhase::Settlement);
 			Self::deposit_event(Event::Shutdown {
 				block_number: <frame_system::Pallet<T>>::block_number(),
 			});
 			Ok(())
 		}
 
-		/// Open final redemption if settlement is completed.
+		/// Open final redemption after the final prices of collaterals are fixed.
 		///
 		/// The dispatch origin of this call must be `ShutdownOrigin`.
 		#[pallet::call_index(1)]
@@ -159,27 +284,15 @@ pub mod module {
 		pub fn open_collateral_refund(origin: OriginFor<T>) -> DispatchResult {
 			T::ShutdownOrigin::ensure_origin(origin)?;
 			ensure!(Self::is_shutdown(), Error::<T>::MustAfterShutdown); // must after shutdown
-
-			// Ensure all debits of CDPs have been settled, and all collateral auction has
-			// been done or canceled. Settle all collaterals type CDPs which have debit,
-			// cancel all collateral auctions in forward stage and wait for all collateral
-			// auctions in reverse stage to be ended.
-			let collateral_currency_ids = T::CollateralCurrencyIds::get();
-			for currency_id in collateral_currency_ids {
-				// there's no collateral auction
-				ensure!(
-					<T as Config>::AuctionManagerHandler::get_total_collateral_in_auction(currency_id).is_zero(),
-					Error::<T>::ExistPotentialSurplus,
-				);
-				// there's on debit in CDP
-				ensure!(
-					<module_loans::Pallet<T>>::total_positions(currency_id).debit.is_zero(),
-					Error::<T>::ExistUnhandledDebit,
-				);
-			}
+			ensure!(
+				Self::phase() == Some(ShutdownPhase::PricesFixed),
+				Error::<T>::FinalPricesNotFixed
+			);
+			Self::ensure_settlement_completed()?;
 
 			// Open refund stage
 			CanRefund::<T>::put(true);
+			Phase::<T>::put(ShutdownPhase::Redemption);
 			Self::deposit_event(Event::OpenRefund {
 				block_number: <frame_system::Pallet<T>>::block_number(),
 			});
@@ -194,39 +307,255 @@ pub mod module {
 		pub fn refund_collaterals(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::can_refund(), Error::<T>::CanNotRefund);
+			Self::do_refund_collaterals(&who, who.clone(), amount)
+		}
 
-			let refund_ratio: Ratio = <T as Config>::CDPTreasury::get_debit_proportion(amount);
-			let collateral_currency_ids = T::CollateralCurrencyIds::get();
+		/// Fix the final prices of collaterals if settlement is completed.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight((
+			T::WeightInfo::emergency_shutdown(T::CollateralCurrencyIds::get().len() as u32)
+				.saturating_add(T::WeightInfo::open_collateral_refund()),
+			DispatchClass::Operational
+		))]
+		pub fn fix_final_prices(origin: OriginFor<T>) -> DispatchResult {
+			T::ShutdownOrigin::ensure_origin(origin)?;
+			match Self::phase() {
+				None => return Err(Error::<T>::MustAfterShutdown.into()),
+				Some(ShutdownPhase::Settlement) => {}
+				Some(_) => return Err(Error::<T>::FinalPricesAlreadyFixed.into()),
+			}
+			Self::ensure_settlement_completed()?;
 
-			// burn caller's stable currency by CDP treasury
-			<T as Config>::CDPTreasury::burn_debit(&who, amount)?;
+			// relock price for every collateral, keep the price locked at shutdown if
+			// there's no feed price now
+			for currency_id in T::CollateralCurrencyIds::get() {
+				let _ = <T as Config>::PriceSource::lock_price(currency_id);
+			}
+
+			let _ = SettlementSnapshot::<T>::clear(u32::MAX, None);
+			Phase::<T>::put(ShutdownPhase::PricesFixed);
+			Self::deposit_event(Event::FinalPricesFixed {
+				block_number: <frame_system::Pallet<T>>::block_number(),
+			});
+			Ok(())
+		}
 
-			let mut refund_assets: Vec<(CurrencyId, Balance)> = vec![];
-			// refund collaterals to caller by CDP treasury
+		/// Shutdown the CDPs of a single collateral type without halting the
+		/// whole system, the price of collateral is locked and its CDPs are settled
+		/// against CDP treasury.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[pallet::call_index(4)]
+		#[pallet::weight((T::WeightInfo::emergency_shutdown(1), DispatchClass::Operational))]
+		pub fn shutdown_collateral(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::ShutdownOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType
+			);
+			ensure!(
+				!Self::shutdown_collaterals(currency_id),
+				Error::<T>::CollateralAlreadyShutdown
+			);
+
+			<T as Config>::PriceSource::lock_price(currency_id)?;
+			ShutdownCollaterals::<T>::insert(currency_id, true);
+			Self::deposit_event(Event::CollateralShutdown { currency_id });
+			Ok(())
+		}
+
+		/// Cancel the emergency shutdown which was triggered erroneously, only
+		/// if no CDP has been settled and no collateral auction has been
+		/// cancelled yet.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		#[pallet::call_index(5)]
+		#[pallet::weight((T::WeightInfo::emergency_shutdown(T::CollateralCurrencyIds::get().len() as u32), DispatchClass::Operational))]
+		pub fn cancel_emergency_shutdown(origin: OriginFor<T>) -> DispatchResult {
+			T::ShutdownOrigin::ensure_origin(origin)?;
+			match Self::phase() {
+				None => return Err(Error::<T>::MustAfterShutdown.into()),
+				Some(ShutdownPhase::Settlement) => {}
+				Some(_) => return Err(Error::<T>::SettlementAlreadyBegun.into()),
+			}
+
+			let collateral_currency_ids = T::CollateralCurrencyIds::get();
+			for currency_id in collateral_currency_ids.iter() {
+				ensure!(
+					Self::settlement_snapshot(currency_id) == Some(Self::settlement_status(*currency_id)),
+					Error::<T>::SettlementAlreadyBegun
+				);
+			}
+
+			// unlock price for every collateral except those shutdown separately
 			for currency_id in collateral_currency_ids {
-				let refund_amount =
-					refund_ratio.saturating_mul_int(<T as Config>::CDPTreasury::get_total_collaterals(currency_id));
-
-				if !refund_amount.is_zero() {
-					let res = <T as Config>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_amount);
-					if res.is_ok() {
-						refund_assets.push((currency_id, refund_amount));
-					}
+				if !Self::shutdown_collaterals(currency_id) {
+					let _ = <T as Config>::PriceSource::unlock_price(currency_id);
 				}
 			}
 
-			Self::deposit_event(Event::Refund {
+			let _ = SettlementSnapshot::<T>::clear(u32::MAX, None);
+			IsShutdown::<T>::put(false);
+			Phase::<T>::kill();
+			Self::deposit_event(Event::ShutdownCancelled {
+				block_number: <frame_system::Pallet<T>>::block_number(),
+			});
+			Ok(())
+		}
+
+		/// Queue a claim to refund a basket of remaining collateral assets to
+		/// caller, queued claims are processed in order at the beginning of
+		/// blocks, at most `RefundsPerBlock` claims per block. The stable
+		/// currency `amount` is transferred to the module account when queued.
+		///
+		/// - `amount`: stable currency amount used to refund.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::open_collateral_refund().saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
+		pub fn claim_collateral_refund(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::can_refund(), Error::<T>::CanNotRefund);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroRefundAmount);
+
+			<T as module_loans::Config>::Currency::transfer(
+				T::GetStableCurrencyId::get(),
+				&who,
+				&Self::account_id(),
+				amount,
+			)?;
+			let index = Self::refund_queue_tail();
+			RefundQueue::<T>::insert(index, (who.clone(), amount));
+			RefundQueueTail::<T>::put(index.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+			Self::deposit_event(Event::RefundClaimQueued {
+				index,
 				who,
 				stable_coin_amount: amount,
-				refund_list: refund_assets,
 			});
 			Ok(())
 		}
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// Get account of emergency shutdown module.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Burn `amount` stable currency of `payer` and refund collaterals to
+	/// `who`, only the collaterals issuing the stable currency are refunded.
+	fn do_refund_collaterals(payer: &T::AccountId, who: T::AccountId, amount: Balance) -> DispatchResult {
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let refund_ratio: Ratio = <T as Config>::CDPTreasury::get_debit_proportion(stable_currency_id, amount);
+		let collateral_currency_ids = T::CollateralCurrencyIds::get();
+
+		// burn payer's stable currency by CDP treasury
+		<T as Config>::CDPTreasury::burn_debit(stable_currency_id, payer, amount)?;
+
+		let mut refund_assets: Vec<(CurrencyId, Balance)> = vec![];
+		// refund collaterals to caller by CDP treasury
+		for currency_id in collateral_currency_ids {
+			if <T as Config>::CDPTreasury::get_stable_currency_id(currency_id) != stable_currency_id {
+				continue;
+			}
+			let refund_amount =
+				refund_ratio.saturating_mul_int(<T as Config>::CDPTreasury::get_total_collaterals(currency_id));
+
+			if !refund_amount.is_zero() {
+				let res = <T as Config>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_amount);
+				if res.is_ok() {
+					refund_assets.push((currency_id, refund_amount));
+				}
+			}
+		}
+
+		Self::deposit_event(Event::Refund {
+			who,
+			stable_coin_amount: amount,
+			refund_list: refund_assets,
+		});
+		Ok(())
+	}
+
+	/// Process at most `RefundsPerBlock` queued refund claims, return the count
+	/// of processed claims.
+	fn process_refund_queue() -> u32 {
+		let head = Self::refund_queue_head();
+		let tail = Self::refund_queue_tail();
+		if head == tail {
+			return 0;
+		}
+
+		let end = tail.min(head.saturating_add(T::RefundsPerBlock::get()));
+		for index in head..end {
+			if let Some((who, amount)) = RefundQueue::<T>::take(index) {
+				if let Err(error) = Self::do_refund_collaterals(&Self::account_id(), who.clone(), amount) {
+					// return the stable currency of the failed claim
+					let _ = <T as module_loans::Config>::Currency::transfer(
+						T::GetStableCurrencyId::get(),
+						&Self::account_id(),
+						&who,
+						amount,
+					);
+					Self::deposit_event(Event::RefundClaimFailed {
+						index,
+						who,
+						stable_coin_amount: amount,
+						error,
+					});
+				}
+			}
+		}
+		RefundQueueHead::<T>::put(end);
+
+		let processed = end.saturating_sub(head);
+		Self::deposit_event(Event::RefundQueueProcessed {
+			processed,
+			remaining: tail.saturating_sub(end),
+		});
+		processed
+	}
+
+	/// The total debit in CDPs and the total collateral in auction of
+	/// `currency_id`, both only decrease by settlement after shutdown.
+	fn settlement_status(currency_id: CurrencyId) -> (Balance, Balance) {
+		(
+			<module_loans::Pallet<T>>::total_positions(currency_id).debit,
+			<T as Config>::AuctionManagerHandler::get_total_collateral_in_auction(currency_id),
+		)
+	}
+
+	/// Ensure all debits of CDPs have been settled, and all collateral auction has
+	/// been done or canceled. Settle all collaterals type CDPs which have debit,
+	/// cancel all collateral auctions in forward stage and wait for all collateral
+	/// auctions in reverse stage to be ended.
+	fn ensure_settlement_completed() -> DispatchResult {
+		for currency_id in T::CollateralCurrencyIds::get() {
+			// there's no collateral auction
+			ensure!(
+				<T as Config>::AuctionManagerHandler::get_total_collateral_in_auction(currency_id).is_zero(),
+				Error::<T>::ExistPotentialSurplus,
+			);
+			// there's on debit in CDP
+			ensure!(
+				<module_loans::Pallet<T>>::total_positions(currency_id).debit.is_zero(),
+				Error::<T>::ExistUnhandledDebit,
+			);
+		}
+		Ok(())
+	}
+}
+
 impl<T: Config> EmergencyShutdown for Pallet<T> {
 	fn is_shutdown() -> bool {
 		Self::is_shutdown()
 	}
+
+	fn is_collateral_shutdown(currency_id: CurrencyId) -> bool {
+		Self::is_shutdown() || Self::shutdown_collaterals(currency_id)
+	}
 }
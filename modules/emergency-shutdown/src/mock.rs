@@ -27,7 +27,9 @@ use frame_support::{
 	PalletId,
 };
 use frame_system::EnsureSignedBy;
-use module_support::{mocks::MockStableAsset, AuctionManager, LockablePrice, RiskManager, SpecificJointsSwap};
+use module_support::{
+	mocks::MockStableAsset, AuctionManager, LockablePrice, Price, PriceProvider, RiskManager, SpecificJointsSwap,
+};
 use orml_traits::parameter_type_with_key;
 use primitives::{Amount, TokenSymbol};
 use sp_core::H256;
@@ -128,6 +130,10 @@ impl orml_currencies::Config for Runtime {
 
 pub struct MockRiskManager;
 impl RiskManager<AccountId, CurrencyId, Balance, Balance> for MockRiskManager {
+	fn get_stable_currency_id(_currency_id: CurrencyId) -> CurrencyId {
+		GetStableCurrencyId::get()
+	}
+
 	fn get_debit_value(_currency_id: CurrencyId, debit_balance: Balance) -> Balance {
 		debit_balance
 	}
@@ -148,6 +154,7 @@ impl RiskManager<AccountId, CurrencyId, Balance, Balance> for MockRiskManager {
 
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub VestedCollateralHaircut: Ratio = Ratio::saturating_from_rational(20, 100);
 }
 
 impl module_loans::Config for Runtime {
@@ -157,6 +164,9 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type VestedCollateralCurrencyId = GetNativeCurrencyId;
+	type VestingBalance = ();
+	type VestedCollateralHaircut = VestedCollateralHaircut;
 }
 
 pub struct MockLockablePrice;
@@ -170,6 +180,13 @@ impl LockablePrice<CurrencyId> for MockLockablePrice {
 	}
 }
 
+pub struct MockPriceSource;
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+}
+
 pub struct MockAuctionManager;
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type Balance = Balance;
@@ -203,23 +220,39 @@ ord_parameter_types! {
 }
 
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(20, 100);
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD];
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
+	pub const EmergencyShutdownPalletId: PalletId = PalletId(*b"aca/emsd");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account_truncating();
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![];
 }
 
 impl module_cdp_treasury::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
 	type Swap = SpecificJointsSwap<(), AlternativeSwapPathJointList>;
+	type PriceSource = MockPriceSource;
+	type TradingPathLimit = ConstU32<4>;
 	type MaxAuctionsCount = ConstU32<10_000>;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = MockRiskManager;
+	type MaxCollateralTypes = ConstU32<10>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = ();
 	type TreasuryAccount = TreasuryAccount;
+	type IncentivesRewardsSource = TreasuryAccount;
+	type MetricsEraLength = ConstU64<100>;
+	type MaxMetricsEras = ConstU32<10>;
+	type ParamsUpdateDelay = ConstU64<0>;
 	type WeightInfo = ();
 	type StableAsset = MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber>;
 }
@@ -235,6 +268,9 @@ impl Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type AuctionManagerHandler = MockAuctionManager;
 	type ShutdownOrigin = EnsureSignedBy<One, AccountId>;
+	type RefundsPerBlock = ConstU32<2>;
+	type GetStableCurrencyId = GetStableCurrencyId;
+	type PalletId = EmergencyShutdownPalletId;
 	type WeightInfo = ();
 }
 
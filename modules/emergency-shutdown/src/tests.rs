@@ -23,6 +23,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{RuntimeEvent, *};
+use orml_traits::MultiCurrency;
 use sp_runtime::traits::BadOrigin;
 
 #[test]
@@ -39,6 +40,7 @@ fn emergency_shutdown_work() {
 			block_number: 1,
 		}));
 		assert!(EmergencyShutdownModule::is_shutdown());
+		assert_eq!(EmergencyShutdownModule::phase(), Some(ShutdownPhase::Settlement));
 		assert_noop!(
 			EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)),
 			Error::<Runtime>::AlreadyShutdown,
@@ -46,6 +48,124 @@ fn emergency_shutdown_work() {
 	});
 }
 
+#[test]
+fn shutdown_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::shutdown_collateral(RuntimeOrigin::signed(5), BTC),
+			BadOrigin,
+		);
+		assert_noop!(
+			EmergencyShutdownModule::shutdown_collateral(RuntimeOrigin::signed(1), ACA),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+		assert_ok!(EmergencyShutdownModule::shutdown_collateral(
+			RuntimeOrigin::signed(1),
+			BTC
+		));
+		System::assert_last_event(RuntimeEvent::EmergencyShutdownModule(
+			crate::Event::CollateralShutdown { currency_id: BTC },
+		));
+		assert!(!EmergencyShutdownModule::is_shutdown());
+		assert!(<EmergencyShutdownModule as EmergencyShutdown>::is_collateral_shutdown(
+			BTC
+		));
+		assert!(!<EmergencyShutdownModule as EmergencyShutdown>::is_collateral_shutdown(
+			DOT
+		));
+		assert_noop!(
+			EmergencyShutdownModule::shutdown_collateral(RuntimeOrigin::signed(1), BTC),
+			Error::<Runtime>::CollateralAlreadyShutdown,
+		);
+
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)));
+		assert!(<EmergencyShutdownModule as EmergencyShutdown>::is_collateral_shutdown(
+			DOT
+		));
+		assert_noop!(
+			EmergencyShutdownModule::shutdown_collateral(RuntimeOrigin::signed(1), DOT),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
+
+#[test]
+fn cancel_emergency_shutdown_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::cancel_emergency_shutdown(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::MustAfterShutdown,
+		);
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::cancel_emergency_shutdown(RuntimeOrigin::signed(5)),
+			BadOrigin,
+		);
+		assert_ok!(EmergencyShutdownModule::cancel_emergency_shutdown(
+			RuntimeOrigin::signed(1)
+		));
+		System::assert_last_event(RuntimeEvent::EmergencyShutdownModule(crate::Event::ShutdownCancelled {
+			block_number: 1,
+		}));
+		assert!(!EmergencyShutdownModule::is_shutdown());
+		assert_eq!(EmergencyShutdownModule::phase(), None);
+		assert_eq!(EmergencyShutdownModule::settlement_snapshot(BTC), None);
+
+		// can not cancel after final prices fixed
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)));
+		assert_ok!(EmergencyShutdownModule::fix_final_prices(RuntimeOrigin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::cancel_emergency_shutdown(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::SettlementAlreadyBegun,
+		);
+	});
+}
+
+#[test]
+fn cancel_emergency_shutdown_fail_after_settlement_begun() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Loans::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)));
+		assert_eq!(EmergencyShutdownModule::settlement_snapshot(BTC), Some((50, 0)));
+
+		// settle the CDP
+		assert_ok!(Loans::confiscate_collateral_and_debit(&ALICE, BTC, 50, 50));
+		assert_noop!(
+			EmergencyShutdownModule::cancel_emergency_shutdown(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::SettlementAlreadyBegun,
+		);
+		assert!(EmergencyShutdownModule::is_shutdown());
+	});
+}
+
+#[test]
+fn fix_final_prices_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::fix_final_prices(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::MustAfterShutdown,
+		);
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::fix_final_prices(RuntimeOrigin::signed(5)),
+			BadOrigin,
+		);
+		assert_ok!(EmergencyShutdownModule::fix_final_prices(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::EmergencyShutdownModule(crate::Event::FinalPricesFixed {
+			block_number: 1,
+		}));
+		assert_eq!(EmergencyShutdownModule::phase(), Some(ShutdownPhase::PricesFixed));
+		assert!(!EmergencyShutdownModule::can_refund());
+		assert_noop!(
+			EmergencyShutdownModule::fix_final_prices(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::FinalPricesAlreadyFixed,
+		);
+	});
+}
+
 #[test]
 fn open_collateral_refund_fail() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -54,6 +174,11 @@ fn open_collateral_refund_fail() {
 			EmergencyShutdownModule::open_collateral_refund(RuntimeOrigin::signed(1)),
 			Error::<Runtime>::MustAfterShutdown,
 		);
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_refund(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::FinalPricesNotFixed,
+		);
 	});
 }
 
@@ -63,6 +188,7 @@ fn open_collateral_refund_work() {
 		System::set_block_number(1);
 		assert!(!EmergencyShutdownModule::can_refund());
 		assert_ok!(EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)));
+		assert_ok!(EmergencyShutdownModule::fix_final_prices(RuntimeOrigin::signed(1)));
 		assert_noop!(
 			EmergencyShutdownModule::open_collateral_refund(RuntimeOrigin::signed(5)),
 			BadOrigin,
@@ -74,6 +200,11 @@ fn open_collateral_refund_work() {
 			block_number: 1,
 		}));
 		assert!(EmergencyShutdownModule::can_refund());
+		assert_eq!(EmergencyShutdownModule::phase(), Some(ShutdownPhase::Redemption));
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_refund(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::FinalPricesNotFixed,
+		);
 	});
 }
 
@@ -86,3 +217,81 @@ fn refund_collaterals_fail() {
 		);
 	});
 }
+
+#[test]
+fn claim_collateral_refund_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::claim_collateral_refund(RuntimeOrigin::signed(ALICE), 50),
+			Error::<Runtime>::CanNotRefund,
+		);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, BTC, 100));
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &ALICE, 100, true));
+		assert_ok!(CDPTreasuryModule::issue_debit(AUSD, &BOB, 100, true));
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(RuntimeOrigin::signed(1)));
+		assert_ok!(EmergencyShutdownModule::fix_final_prices(RuntimeOrigin::signed(1)));
+		assert_ok!(EmergencyShutdownModule::open_collateral_refund(RuntimeOrigin::signed(
+			1
+		)));
+
+		assert_noop!(
+			EmergencyShutdownModule::claim_collateral_refund(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Runtime>::ZeroRefundAmount,
+		);
+		assert_ok!(EmergencyShutdownModule::claim_collateral_refund(
+			RuntimeOrigin::signed(ALICE),
+			50
+		));
+		System::assert_last_event(RuntimeEvent::EmergencyShutdownModule(crate::Event::RefundClaimQueued {
+			index: 0,
+			who: ALICE,
+			stable_coin_amount: 50,
+		}));
+		assert_ok!(EmergencyShutdownModule::claim_collateral_refund(
+			RuntimeOrigin::signed(BOB),
+			100
+		));
+		assert_ok!(EmergencyShutdownModule::claim_collateral_refund(
+			RuntimeOrigin::signed(ALICE),
+			50
+		));
+		// the claim must be funded when queued
+		assert_noop!(
+			EmergencyShutdownModule::claim_collateral_refund(RuntimeOrigin::signed(3), 10),
+			orml_tokens::Error::<Runtime>::BalanceTooLow,
+		);
+		assert_eq!(EmergencyShutdownModule::refund_queue(0), Some((ALICE, 50)));
+		assert_eq!(EmergencyShutdownModule::refund_queue_tail(), 3);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &EmergencyShutdownModule::account_id()), 200);
+
+		// process 2 claims per block
+		EmergencyShutdownModule::on_initialize(2);
+		System::assert_last_event(RuntimeEvent::EmergencyShutdownModule(
+			crate::Event::RefundQueueProcessed {
+				processed: 2,
+				remaining: 1,
+			},
+		));
+		assert_eq!(EmergencyShutdownModule::refund_queue_head(), 2);
+		assert_eq!(EmergencyShutdownModule::refund_queue(0), None);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1025);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 949);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 26);
+
+		EmergencyShutdownModule::on_initialize(3);
+		System::assert_last_event(RuntimeEvent::EmergencyShutdownModule(
+			crate::Event::RefundQueueProcessed {
+				processed: 1,
+				remaining: 0,
+			},
+		));
+		assert_eq!(EmergencyShutdownModule::refund_queue_head(), 3);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1051);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &EmergencyShutdownModule::account_id()), 0);
+	});
+}
@@ -270,6 +270,10 @@ impl module_transaction_payment::Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type CDPTreasuryAccount = KaruraTreasuryAccount;
+	type StableCurrencyId = GetStableCurrencyId;
+	type FreeTxCallFilter = Nothing;
+	type FreeTxQuotaPeriod = ConstU32<100>;
 }
 
 pub struct MockDEXIncentives;
@@ -301,6 +305,8 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId32>;
 	type ExtendedProvisioningBlocks = ConstU32<0>;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = KaruraTreasuryAccount;
+	type RuntimeCall = RuntimeCall;
 }
 
 pub type SignedExtra = (frame_system::CheckWeight<Runtime>,);
@@ -174,6 +174,8 @@ pub mod module {
 		InvalidRate,
 		/// Invalid last era bumped block config
 		InvalidLastEraBumpedBlock,
+		/// The target era of redeem request must be greater than RelayChainCurrentEra
+		InvalidTargetEra,
 	}
 
 	#[pallet::event]
@@ -192,6 +194,11 @@ pub mod module {
 			liquid_amount: Balance,
 			allow_fast_match: bool,
 		},
+		/// Redeem request is scheduled to be unbonded at the target era.
+		RedeemRequestScheduled {
+			redeemer: T::AccountId,
+			target_era: EraIndex,
+		},
 		/// Redeem request has been cancelled.
 		RedeemRequestCancelled {
 			redeemer: T::AccountId,
@@ -242,6 +249,15 @@ pub mod module {
 		LastEraBumpedBlockUpdated { last_era_bumped_block: BlockNumberFor<T> },
 		/// The frequency to bump era has been updated.
 		BumpEraFrequencyUpdated { frequency: BlockNumberFor<T> },
+		/// The target free ratio of staking currency has been updated.
+		TargetFreeRatioUpdated { target_free_ratio: Rate },
+		/// The max ratio of bonded to unbond for rebalance per era has been updated.
+		RebalanceUnbondRatioPerEraUpdated { rebalance_unbond_ratio: Rate },
+		/// Homa unbonded on relaychain to replenish ToBondPool.
+		RebalanceUnbonded {
+			era_index_when_unbond: EraIndex,
+			unbonding_staking_amount: Balance,
+		},
 	}
 
 	/// The current era of relaychain
@@ -304,6 +320,14 @@ pub mod module {
 	#[pallet::getter(fn redeem_requests)]
 	pub type RedeemRequests<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (Balance, bool), OptionQuery>;
 
+	/// The era from which the redeem request can be unbonded, redeem requests
+	/// without target era are unbonded at the next era.
+	///
+	/// RedeemRequestTargetEras: Map: AccountId => Option<EraIndex>
+	#[pallet::storage]
+	#[pallet::getter(fn redeem_request_target_eras)]
+	pub type RedeemRequestTargetEras<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, EraIndex, OptionQuery>;
+
 	/// The records of unbonding by AccountId.
 	///
 	/// Unbondings: double_map AccountId, ExpireEraIndex => UnbondingStakingCurrencyAmount
@@ -353,6 +377,34 @@ pub mod module {
 	#[pallet::getter(fn bump_era_frequency)]
 	pub type BumpEraFrequency<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+	/// The target ratio of free staking currency in ToBondPool to the total staking currency.
+	/// It's kept unbonded when new era to match redeem requests, and Homa unbonds on relaychain
+	/// to replenish ToBondPool if it's below the target.
+	///
+	/// TargetFreeRatio: value: Rate
+	#[pallet::storage]
+	pub type TargetFreeRatio<T: Config> = StorageValue<_, FractionalRate, ValueQuery>;
+
+	/// The max ratio of total bonded can be unbonded per era to replenish ToBondPool.
+	///
+	/// RebalanceUnbondRatioPerEra: value: Rate
+	#[pallet::storage]
+	pub type RebalanceUnbondRatioPerEra<T: Config> = StorageValue<_, FractionalRate, ValueQuery>;
+
+	/// The staking currency unbonding on relaychain to replenish ToBondPool.
+	///
+	/// RebalanceUnbondings: map: ExpireEraIndex => UnbondingStakingCurrencyAmount
+	#[pallet::storage]
+	#[pallet::getter(fn rebalance_unbondings)]
+	pub type RebalanceUnbondings<T: Config> = StorageMap<_, Twox64Concat, EraIndex, Balance, ValueQuery>;
+
+	/// The total staking currency unbonding on relaychain to replenish ToBondPool.
+	///
+	/// TotalRebalanceUnbonding: value: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_rebalance_unbonding)]
+	pub type TotalRebalanceUnbonding<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -397,6 +449,8 @@ pub mod module {
 		/// in current era, Homa will unbond staking currency on relaychain when the next era
 		/// bumped. So redeemer at least wait for the unbonding period + extra 1 era to get the
 		/// redemption.
+		/// Redeem request can also be scheduled to unbond at a target era by
+		/// `request_redeem_at_era`, overwriting it by this call cancels the schedule.
 		///
 		/// Parameters:
 		/// - `amount`: The amount of liquid currency to be requested  redeemed into Staking
@@ -411,7 +465,7 @@ pub mod module {
 			allow_fast_match: bool,
 		) -> DispatchResult {
 			let redeemer = ensure_signed(origin)?;
-			Self::do_request_redeem(redeemer, amount, allow_fast_match)
+			Self::do_request_redeem(redeemer, amount, allow_fast_match, None)
 		}
 
 		/// Execute fast match for specific redeem requests.
@@ -654,6 +708,64 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Sets the params of rebalancing between bonded and free staking currency.
+		/// Requires `GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `target_free_ratio`: the target ratio of free staking currency in ToBondPool to the
+		///   total staking currency.
+		/// - `rebalance_unbond_ratio`: the max ratio of total bonded can be unbonded per era to
+		///   replenish ToBondPool.
+		#[pallet::call_index(11)]
+		#[pallet::weight(< T as Config >::WeightInfo::update_homa_params())]
+		pub fn update_rebalance_params(
+			origin: OriginFor<T>,
+			target_free_ratio: Option<Rate>,
+			rebalance_unbond_ratio: Option<Rate>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			if let Some(target_free_ratio) = target_free_ratio {
+				TargetFreeRatio::<T>::mutate(|rate| -> DispatchResult {
+					rate.try_set(target_free_ratio)
+						.map_err(|_| Error::<T>::InvalidRate.into())
+				})?;
+				Self::deposit_event(Event::<T>::TargetFreeRatioUpdated { target_free_ratio });
+			}
+			if let Some(rebalance_unbond_ratio) = rebalance_unbond_ratio {
+				RebalanceUnbondRatioPerEra::<T>::mutate(|rate| -> DispatchResult {
+					rate.try_set(rebalance_unbond_ratio)
+						.map_err(|_| Error::<T>::InvalidRate.into())
+				})?;
+				Self::deposit_event(Event::<T>::RebalanceUnbondRatioPerEraUpdated { rebalance_unbond_ratio });
+			}
+
+			Ok(())
+		}
+
+		/// Build/Overwrite a redeem request which is unbonded on relaychain at the target era,
+		/// the request is not allowed to be fast matched. So redeemer can get the redemption
+		/// after the unbonding period of the target era.
+		///
+		/// Parameters:
+		/// - `amount`: The amount of liquid currency to be requested redeemed into Staking
+		///   currency.
+		/// - `target_era`: The era to unbond, must be greater than the current era.
+		#[pallet::call_index(10)]
+		#[pallet::weight(< T as Config >::WeightInfo::request_redeem())]
+		pub fn request_redeem_at_era(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+			target_era: EraIndex,
+		) -> DispatchResult {
+			let redeemer = ensure_signed(origin)?;
+			ensure!(
+				target_era > Self::relay_chain_current_era(),
+				Error::<T>::InvalidTargetEra
+			);
+			Self::do_request_redeem(redeemer, amount, false, Some(target_era))
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -674,6 +786,14 @@ pub mod module {
 			FastMatchFeeRate::<T>::get().into_inner()
 		}
 
+		pub(crate) fn target_free_ratio() -> Rate {
+			TargetFreeRatio::<T>::get().into_inner()
+		}
+
+		pub(crate) fn rebalance_unbond_ratio_per_era() -> Rate {
+			RebalanceUnbondRatioPerEra::<T>::get().into_inner()
+		}
+
 		pub fn do_update_ledger<R, E>(
 			sub_account_index: u16,
 			f: impl FnOnce(&mut StakingLedger) -> sp_std::result::Result<R, E>,
@@ -740,6 +860,7 @@ pub mod module {
 			redeemer: T::AccountId,
 			amount: Balance,
 			allow_fast_match: bool,
+			target_era: Option<EraIndex>,
 		) -> DispatchResult {
 			RedeemRequests::<T>::try_mutate_exists(&redeemer, |maybe_request| -> DispatchResult {
 				let (previous_request_amount, _) = maybe_request.take().unwrap_or_default();
@@ -772,6 +893,7 @@ pub mod module {
 					_ => Ok(()),
 				}?;
 
+				RedeemRequestTargetEras::<T>::remove(&redeemer);
 				if !amount.is_zero() {
 					*maybe_request = Some((amount, allow_fast_match));
 					Self::deposit_event(Event::<T>::RequestedRedeem {
@@ -779,6 +901,13 @@ pub mod module {
 						liquid_amount: amount,
 						allow_fast_match,
 					});
+					if let Some(target_era) = target_era {
+						RedeemRequestTargetEras::<T>::insert(&redeemer, target_era);
+						Self::deposit_event(Event::<T>::RedeemRequestScheduled {
+							redeemer: redeemer.clone(),
+							target_era,
+						});
+					}
 				} else if !previous_request_amount.is_zero() {
 					Self::deposit_event(Event::<T>::RedeemRequestCancelled {
 						redeemer: redeemer.clone(),
@@ -797,8 +926,16 @@ pub mod module {
 		}
 
 		/// Calculate the total amount of staking currency belong to Homa.
+		/// The staking currency unbonding to replenish ToBondPool still belongs to Homa.
 		pub fn get_total_staking_currency() -> Balance {
-			TotalStakingBonded::<T>::get().saturating_add(Self::to_bond_pool())
+			TotalStakingBonded::<T>::get()
+				.saturating_add(Self::to_bond_pool())
+				.saturating_add(Self::total_rebalance_unbonding())
+		}
+
+		/// The amount of free staking currency should be kept in ToBondPool.
+		pub fn get_target_free_staking_currency() -> Balance {
+			Self::target_free_ratio().saturating_mul_int(Self::get_total_staking_currency())
 		}
 
 		/// Calculate the total amount of liquid currency.
@@ -975,18 +1112,35 @@ pub mod module {
 				}
 			}
 
+			// the withdrawn unbonded for rebalance replenish ToBondPool.
+			let mut rebalance_withdrawn_staking: Balance = Zero::zero();
+			let expired_rebalance_eras: Vec<EraIndex> = RebalanceUnbondings::<T>::iter_keys()
+				.filter(|expired_era_index| *expired_era_index <= new_era)
+				.collect();
+			for expired_era_index in expired_rebalance_eras {
+				rebalance_withdrawn_staking =
+					rebalance_withdrawn_staking.saturating_add(RebalanceUnbondings::<T>::take(expired_era_index));
+			}
+			let rebalance_withdrawn_staking = rebalance_withdrawn_staking.min(total_withdrawn_staking);
+			TotalRebalanceUnbonding::<T>::mutate(|total| *total = total.saturating_sub(rebalance_withdrawn_staking));
+			ToBondPool::<T>::mutate(|pool| *pool = pool.saturating_add(rebalance_withdrawn_staking));
+
 			// issue withdrawn unbonded to module account for redeemer to claim
 			Self::issue_staking_currency(&Self::account_id(), total_withdrawn_staking)?;
-			UnclaimedRedemption::<T>::mutate(|total| *total = total.saturating_add(total_withdrawn_staking));
+			UnclaimedRedemption::<T>::mutate(|total| {
+				*total = total.saturating_add(total_withdrawn_staking.saturating_sub(rebalance_withdrawn_staking))
+			});
 
 			Ok(())
 		}
 
 		/// Distribute PoolToBond to ActiveSubAccountsIndexList, then cross-transfer the
 		/// distribution amount to the subaccounts on relaychain and bond it by XCM.
+		/// The target free staking currency is kept in ToBondPool.
 		#[transactional]
 		pub fn process_to_bond_pool() -> DispatchResult {
-			let to_bond_pool = Self::to_bond_pool();
+			let target_free = Self::get_target_free_staking_currency();
+			let to_bond_pool = Self::to_bond_pool().saturating_sub(target_free);
 
 			// if to_bond is gte than MintThreshold, try to bond_extra on relaychain
 			if to_bond_pool >= T::MintThreshold::get() {
@@ -1023,7 +1177,61 @@ pub mod module {
 				}
 
 				// update pool
-				ToBondPool::<T>::mutate(|pool| *pool = remainder);
+				ToBondPool::<T>::mutate(|pool| *pool = pool.saturating_sub(to_bond_pool).saturating_add(remainder));
+			}
+
+			Ok(())
+		}
+
+		/// Unbond on relaychain by XCM message if the free staking currency is below the
+		/// target, capped by RebalanceUnbondRatioPerEra. The unbonded will replenish ToBondPool
+		/// when expired.
+		#[transactional]
+		pub fn process_rebalance_unbond(new_era: EraIndex) -> DispatchResult {
+			let era_index_to_expire = new_era + T::BondingDuration::get();
+			let total_bonded = TotalStakingBonded::<T>::get();
+			let shortfall = Self::get_target_free_staking_currency()
+				.saturating_sub(Self::to_bond_pool())
+				.saturating_sub(Self::total_rebalance_unbonding());
+			let staking_amount_to_unbond = shortfall
+				.min(Self::rebalance_unbond_ratio_per_era().saturating_mul_int(total_bonded))
+				.min(total_bonded);
+			if staking_amount_to_unbond.is_zero() {
+				return Ok(());
+			}
+
+			let bonded_list: Vec<(u16, Balance)> = T::ActiveSubAccountsIndexList::get()
+				.iter()
+				.map(|index| (*index, Self::staking_ledgers(index).unwrap_or_default().bonded))
+				.collect();
+			let (distribution, _) = distribute_decrement::<u16>(bonded_list, staking_amount_to_unbond, None, None);
+
+			// subaccounts execute the distribution
+			let mut total_unbonded: Balance = Zero::zero();
+			for (sub_account_index, unbond_amount) in distribution {
+				if !unbond_amount.is_zero() {
+					T::XcmInterface::unbond_on_sub_account(sub_account_index, unbond_amount)?;
+
+					// update ledger
+					Self::do_update_ledger(sub_account_index, |ledger| -> DispatchResult {
+						ledger.bonded = ledger.bonded.saturating_sub(unbond_amount);
+						ledger.unlocking.push(UnlockChunk {
+							value: unbond_amount,
+							era: era_index_to_expire,
+						});
+						Ok(())
+					})?;
+					total_unbonded = total_unbonded.saturating_add(unbond_amount);
+				}
+			}
+
+			if !total_unbonded.is_zero() {
+				RebalanceUnbondings::<T>::mutate(era_index_to_expire, |n| *n = n.saturating_add(total_unbonded));
+				TotalRebalanceUnbonding::<T>::mutate(|total| *total = total.saturating_add(total_unbonded));
+				Self::deposit_event(Event::<T>::RebalanceUnbonded {
+					era_index_when_unbond: new_era,
+					unbonding_staking_amount: total_unbonded,
+				});
 			}
 
 			Ok(())
@@ -1037,14 +1245,19 @@ pub mod module {
 			let mut total_redeem_amount: Balance = Zero::zero();
 			let mut remain_total_bonded = total_bonded;
 
-			// iter RedeemRequests and insert to Unbondings if remain_total_bonded is enough.
+			// iter RedeemRequests and insert to Unbondings if remain_total_bonded is enough,
+			// the requests whose target era has not been reached are kept.
 			for (redeemer, (redeem_amount, _)) in RedeemRequests::<T>::iter() {
+				if Self::redeem_request_target_eras(&redeemer).map_or(false, |target_era| target_era > new_era) {
+					continue;
+				}
 				let redemption_amount = Self::convert_liquid_to_staking(redeem_amount)?;
 
 				if remain_total_bonded >= redemption_amount {
 					total_redeem_amount = total_redeem_amount.saturating_add(redeem_amount);
 					remain_total_bonded = remain_total_bonded.saturating_sub(redemption_amount);
 					RedeemRequests::<T>::remove(&redeemer);
+					RedeemRequestTargetEras::<T>::remove(&redeemer);
 					Unbondings::<T>::mutate(&redeemer, era_index_to_expire, |n| {
 						*n = n.saturating_add(redemption_amount)
 					});
@@ -1114,6 +1327,7 @@ pub mod module {
 				Self::process_scheduled_unbond(new_era)?;
 				Self::process_to_bond_pool()?;
 				Self::process_redeem_requests(new_era)?;
+				Self::process_rebalance_unbond(new_era)?;
 				Ok(())
 			}();
 
@@ -1156,7 +1370,7 @@ impl<T: Config> HomaManager<T::AccountId, Balance> for Pallet<T> {
 	}
 
 	fn request_redeem(who: T::AccountId, amount: Balance, fast_match: bool) -> DispatchResult {
-		Self::do_request_redeem(who, amount, fast_match)
+		Self::do_request_redeem(who, amount, fast_match, None)
 	}
 
 	fn get_exchange_rate() -> ExchangeRate {
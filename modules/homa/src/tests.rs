@@ -1414,3 +1414,181 @@ fn last_era_bumped_block_config_check_works() {
 		assert_eq!(MockRelayBlockNumberProvider::current_block_number(), 100);
 	});
 }
+
+#[test]
+fn request_redeem_at_era_works() {
+	ExtBuilder::default()
+		.balances(vec![
+			(ALICE, LIQUID_CURRENCY_ID, 20_000_000),
+			(BOB, LIQUID_CURRENCY_ID, 10_000_000),
+		])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Homa::reset_ledgers(
+				RuntimeOrigin::signed(HomaAdmin::get()),
+				vec![(0, Some(2_000_000), None), (1, Some(3_000_000), None),]
+			));
+			ToBondPool::<Runtime>::put(1_000_000);
+			assert_eq!(Homa::relay_chain_current_era(), 0);
+
+			assert_noop!(
+				Homa::request_redeem_at_era(RuntimeOrigin::signed(ALICE), 10_000_000, 0),
+				Error::<Runtime>::InvalidTargetEra
+			);
+
+			assert_ok!(Homa::request_redeem_at_era(RuntimeOrigin::signed(ALICE), 10_000_000, 2));
+			System::assert_last_event(RuntimeEvent::Homa(crate::Event::RedeemRequestScheduled {
+				redeemer: ALICE,
+				target_era: 2,
+			}));
+			assert_eq!(Homa::redeem_requests(&ALICE), Some((10_000_000, false)));
+			assert_eq!(Homa::redeem_request_target_eras(&ALICE), Some(2));
+			assert_eq!(Currencies::free_balance(LIQUID_CURRENCY_ID, &ALICE), 10_000_000);
+
+			// scheduled request cannot be fast matched
+			assert_noop!(
+				Homa::fast_match_redeems(RuntimeOrigin::signed(BOB), vec![ALICE]),
+				Error::<Runtime>::FastMatchIsNotAllowed
+			);
+
+			// the target era has not been reached
+			assert_ok!(Homa::process_redeem_requests(1));
+			assert_eq!(Homa::redeem_requests(&ALICE), Some((10_000_000, false)));
+			assert_eq!(Homa::unbondings(&ALICE, 1 + BondingDuration::get()), 0);
+			assert_eq!(Homa::get_total_bonded(), 5_000_000);
+
+			assert_ok!(Homa::process_redeem_requests(2));
+			System::assert_has_event(RuntimeEvent::Homa(crate::Event::RedeemedByUnbond {
+				redeemer: ALICE,
+				era_index_when_unbond: 2,
+				liquid_amount: 10_000_000,
+				unbonding_staking_amount: 2_000_000,
+			}));
+			assert_eq!(Homa::redeem_requests(&ALICE), None);
+			assert_eq!(Homa::redeem_request_target_eras(&ALICE), None);
+			assert_eq!(Homa::unbondings(&ALICE, 2 + BondingDuration::get()), 2_000_000);
+			assert_eq!(Homa::get_total_bonded(), 3_000_000);
+
+			// overwrite the scheduled request
+			assert_ok!(Homa::request_redeem_at_era(RuntimeOrigin::signed(BOB), 5_000_000, 5));
+			assert_eq!(Homa::redeem_request_target_eras(&BOB), Some(5));
+			assert_ok!(Homa::request_redeem(RuntimeOrigin::signed(BOB), 5_000_000, true));
+			assert_eq!(Homa::redeem_requests(&BOB), Some((5_000_000, true)));
+			assert_eq!(Homa::redeem_request_target_eras(&BOB), None);
+
+			// cancel the scheduled request
+			assert_ok!(Homa::request_redeem_at_era(RuntimeOrigin::signed(BOB), 5_000_000, 5));
+			assert_ok!(Homa::request_redeem(RuntimeOrigin::signed(BOB), 0, false));
+			assert_eq!(Homa::redeem_requests(&BOB), None);
+			assert_eq!(Homa::redeem_request_target_eras(&BOB), None);
+			assert_eq!(Currencies::free_balance(LIQUID_CURRENCY_ID, &BOB), 10_000_000);
+		});
+}
+
+#[test]
+fn rebalance_works() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, STAKING_CURRENCY_ID, 10_000_000)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Homa::update_rebalance_params(
+					RuntimeOrigin::signed(ALICE),
+					Some(Rate::saturating_from_rational(20, 100)),
+					Some(Rate::saturating_from_rational(10, 100)),
+				),
+				BadOrigin
+			);
+			assert_ok!(Homa::update_rebalance_params(
+				RuntimeOrigin::signed(HomaAdmin::get()),
+				Some(Rate::saturating_from_rational(20, 100)),
+				Some(Rate::saturating_from_rational(10, 100)),
+			));
+			System::assert_has_event(RuntimeEvent::Homa(crate::Event::TargetFreeRatioUpdated {
+				target_free_ratio: Rate::saturating_from_rational(20, 100),
+			}));
+			System::assert_has_event(RuntimeEvent::Homa(crate::Event::RebalanceUnbondRatioPerEraUpdated {
+				rebalance_unbond_ratio: Rate::saturating_from_rational(10, 100),
+			}));
+			assert_eq!(Homa::target_free_ratio(), Rate::saturating_from_rational(20, 100));
+			assert_eq!(
+				Homa::rebalance_unbond_ratio_per_era(),
+				Rate::saturating_from_rational(10, 100)
+			);
+
+			assert_ok!(Homa::update_homa_params(
+				RuntimeOrigin::signed(HomaAdmin::get()),
+				Some(10_000_000),
+				None,
+				None,
+				None,
+			));
+			assert_ok!(Homa::reset_ledgers(
+				RuntimeOrigin::signed(HomaAdmin::get()),
+				vec![(0, Some(3_000_000), None), (1, Some(2_000_000), None)]
+			));
+			assert_eq!(Homa::get_total_staking_currency(), 5_000_000);
+			assert_eq!(Homa::get_target_free_staking_currency(), 1_000_000);
+
+			// unbond is capped by RebalanceUnbondRatioPerEra
+			assert_ok!(Homa::process_rebalance_unbond(1));
+			System::assert_last_event(RuntimeEvent::Homa(crate::Event::RebalanceUnbonded {
+				era_index_when_unbond: 1,
+				unbonding_staking_amount: 500_000,
+			}));
+			assert_eq!(
+				Homa::staking_ledgers(0),
+				Some(StakingLedger {
+					bonded: 2_500_000,
+					unlocking: vec![UnlockChunk {
+						value: 500_000,
+						era: 1 + BondingDuration::get()
+					}]
+				})
+			);
+			assert_eq!(Homa::rebalance_unbondings(1 + BondingDuration::get()), 500_000);
+			assert_eq!(Homa::total_rebalance_unbonding(), 500_000);
+			assert_eq!(Homa::get_total_staking_currency(), 5_000_000);
+
+			// the unbonding is counted to the free staking currency
+			assert_ok!(Homa::process_rebalance_unbond(2));
+			assert_eq!(Homa::rebalance_unbondings(2 + BondingDuration::get()), 450_000);
+			assert_eq!(Homa::total_rebalance_unbonding(), 950_000);
+			assert_eq!(Homa::get_total_bonded(), 4_050_000);
+			assert_ok!(Homa::process_rebalance_unbond(3));
+			assert_eq!(Homa::rebalance_unbondings(3 + BondingDuration::get()), 50_000);
+			assert_eq!(Homa::total_rebalance_unbonding(), 1_000_000);
+			assert_ok!(Homa::process_rebalance_unbond(4));
+			assert_eq!(Homa::rebalance_unbondings(4 + BondingDuration::get()), 0);
+
+			// the expired unbonding replenishes ToBondPool
+			assert_ok!(Homa::process_scheduled_unbond(1 + BondingDuration::get()));
+			assert_eq!(Homa::rebalance_unbondings(1 + BondingDuration::get()), 0);
+			assert_eq!(Homa::total_rebalance_unbonding(), 500_000);
+			assert_eq!(Homa::to_bond_pool(), 500_000);
+			assert_eq!(Homa::unclaimed_redemption(), 0);
+			assert_eq!(
+				Currencies::free_balance(STAKING_CURRENCY_ID, &Homa::account_id()),
+				500_000
+			);
+			assert_eq!(Homa::get_total_staking_currency(), 5_000_000);
+
+			// keep the target free staking currency in ToBondPool
+			assert_ok!(Homa::mint(RuntimeOrigin::signed(ALICE), 5_000_000));
+			assert_eq!(Homa::to_bond_pool(), 5_500_000);
+			assert_eq!(Homa::get_target_free_staking_currency(), 2_000_000);
+			assert_ok!(Homa::process_to_bond_pool());
+			assert_eq!(Homa::to_bond_pool(), 2_000_000);
+			assert_eq!(
+				Homa::staking_ledgers(2),
+				Some(StakingLedger {
+					bonded: 2_500_000,
+					unlocking: vec![]
+				})
+			);
+			assert_eq!(
+				Currencies::free_balance(STAKING_CURRENCY_ID, &Homa::account_id()),
+				2_000_000
+			);
+		});
+}
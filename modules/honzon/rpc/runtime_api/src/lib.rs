@@ -0,0 +1,54 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use sp_runtime::codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait HonzonApi<AccountId, CurrencyId, PositionInfo, SystemHealth, Balance, ExchangeRate> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		PositionInfo: Codec,
+		SystemHealth: Codec,
+		Balance: Codec,
+		ExchangeRate: Codec,
+	{
+		/// Get the health information of the CDP of specific account and collateral type,
+		/// including the debit value, collateral ratio, max borrowable stable value and the
+		/// collateral required to reach the required collateral ratio. Return None if the
+		/// collateral type is invalid or the feed price is unavailable.
+		fn get_position_info(who: AccountId, currency_id: CurrencyId) -> Option<PositionInfo>;
+
+		/// Get the health information of the whole CDP system, including the collateral ratio
+		/// of the system, the debit and surplus pool of CDP treasury, the total issuance of
+		/// stable currency and the utilization of each collateral type.
+		fn get_system_health() -> SystemHealth;
+
+		/// Get the interest the CDP of specific account and collateral type will owe in the next
+		/// `blocks` blocks at the current interest rate. Return None if the collateral type is
+		/// invalid.
+		fn get_projected_interest(who: AccountId, currency_id: CurrencyId, blocks: u32) -> Option<Balance>;
+
+		/// Get the recent debit exchange rates of specific collateral type with the timestamp in
+		/// seconds, ordered from the oldest to the latest.
+		fn get_debit_exchange_rate_history(currency_id: CurrencyId) -> Vec<(u64, ExchangeRate)>;
+	}
+}
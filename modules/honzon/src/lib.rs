@@ -31,12 +31,13 @@
 
 use frame_support::{pallet_prelude::*, traits::NamedReservableCurrency};
 use frame_system::pallet_prelude::*;
-use module_support::{CDPTreasury, EmergencyShutdown, ExchangeRate, HonzonManager, PriceProvider, Ratio};
+use module_support::{CDPTreasury, EmergencyShutdown, ExchangeRate, HonzonManager, Price, PriceProvider, Ratio};
+use orml_traits::MultiCurrency;
 use primitives::{Amount, Balance, CurrencyId, Position, ReserveIdentifier};
 use sp_core::U256;
 use sp_runtime::{
-	traits::{StaticLookup, Zero},
-	ArithmeticError, DispatchResult,
+	traits::{Bounded, IdentifyAccount, StaticLookup, Verify, Zero},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber,
 };
 use sp_std::prelude::*;
 
@@ -47,11 +48,101 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The actions an authorizee is permitted to do on the CDP of authorizer.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum AuthorizationPermission {
+	/// Only deposit collateral into the CDP.
+	AddCollateralOnly,
+	/// Only payback debit of the CDP.
+	RepayOnly,
+	/// Manipulate the CDP without limit.
+	FullManage,
+}
+
+impl AuthorizationPermission {
+	/// Check if the permission allows the adjustment of CDP.
+	pub fn allows(&self, collateral_adjustment: Amount, debit_adjustment: Amount) -> bool {
+		match self {
+			AuthorizationPermission::AddCollateralOnly => {
+				!collateral_adjustment.is_negative() && debit_adjustment.is_zero()
+			}
+			AuthorizationPermission::RepayOnly => collateral_adjustment.is_zero() && !debit_adjustment.is_positive(),
+			AuthorizationPermission::FullManage => true,
+		}
+	}
+}
+
+/// The scope of authorization.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct AuthorizationScope<BlockNumber> {
+	/// The permitted actions.
+	pub permission: AuthorizationPermission,
+	/// The authorization is invalid after this block, `None` means never
+	/// expire.
+	pub expiry: Option<BlockNumber>,
+}
+
+/// The health information of a CDP at the current feed price.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct PositionInfo {
+	/// The collateral amount.
+	pub collateral: Balance,
+	/// The debit value in stable currency.
+	pub debit_value: Balance,
+	/// The collateral ratio.
+	pub collateral_ratio: Ratio,
+	/// The max stable value can be additionally issued without being below the
+	/// required collateral ratio or exceeding the debit cap.
+	pub max_borrowable: Balance,
+	/// The collateral amount needs to be additionally deposited to reach the
+	/// required collateral ratio.
+	pub required_collateral: Balance,
+}
+
+/// The utilization of a collateral type at the current feed price.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct CollateralHealth {
+	/// The collateral type.
+	pub currency_id: CurrencyId,
+	/// The total collateral amount of all CDPs.
+	pub collateral: Balance,
+	/// The total debit value of all CDPs in stable currency.
+	pub debit_value: Balance,
+	/// The collateral ratio of all CDPs, None if the feed price is unavailable.
+	pub collateral_ratio: Option<Ratio>,
+	/// The proportion of the total debit value to the maximum total debit value.
+	pub utilization: Ratio,
+}
+
+/// The health information of the whole CDP system.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct SystemHealth {
+	/// The total collateral value in the default stable currency, excluding the
+	/// collateral types issuing other stable currencies or whose feed price is
+	/// unavailable.
+	pub collateral_value: Balance,
+	/// The total debit value in the default stable currency, excluding the
+	/// collateral types issuing other stable currencies or whose feed price is
+	/// unavailable.
+	pub debit_value: Balance,
+	/// The collateral ratio of the whole system.
+	pub collateral_ratio: Ratio,
+	/// The surplus pool of CDP treasury.
+	pub surplus_pool: Balance,
+	/// The debit pool of CDP treasury.
+	pub debit_pool: Balance,
+	/// The total issuance of stable currency.
+	pub stable_total_issuance: Balance,
+	/// The utilization of each collateral type.
+	pub collaterals: Vec<CollateralHealth>,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
 	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::Honzon;
+	pub const PERMIT_PREFIX: &[u8] = b"acala/honzon-loan-permit";
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config + module_cdp_engine::Config {
@@ -71,6 +162,12 @@ pub mod module {
 		/// The list of valid collateral currency types
 		type CollateralCurrencyIds: Get<Vec<CurrencyId>>;
 
+		/// Off-chain signature of loan permits.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// Off-chain public key which identifies the signer of loan permits.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -85,6 +182,24 @@ pub mod module {
 		AuthorizationNotExists,
 		// Have authorized already
 		AlreadyAuthorized,
+		// Cannot transfer the loan to the same account
+		CannotTransferToSelf,
+		// The loan to transfer is empty
+		NoLoanToTransfer,
+		// The authorization has expired
+		AuthorizationExpired,
+		// The expiry block must be in the future
+		InvalidExpiry,
+		// The count of adjustments exceeds the count of collateral types
+		TooManyAdjustments,
+		// The target collateral ratio is zero
+		InvalidTargetRatio,
+		// The signature of permit is invalid
+		InvalidPermitSignature,
+		// The permit has expired
+		PermitExpired,
+		// The collateral types issue different stable currencies
+		StableCurrencyMismatch,
 	}
 
 	#[pallet::event]
@@ -110,6 +225,29 @@ pub mod module {
 			to_currency: CurrencyId,
 			amount: Balance,
 		},
+		/// The scope of authorization updated.
+		AuthorizationScopeUpdated {
+			authorizer: T::AccountId,
+			authorizee: T::AccountId,
+			collateral_type: CurrencyId,
+			scope: AuthorizationScope<BlockNumberFor<T>>,
+		},
+		/// Adjust the loan of authorizer by authorizee.
+		LoanAdjustedOnBehalf {
+			owner: T::AccountId,
+			operator: T::AccountId,
+			collateral_type: CurrencyId,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+		},
+		/// Adjust the loan of owner by the permit signed by owner.
+		LoanAdjustedByPermit {
+			owner: T::AccountId,
+			relayer: T::AccountId,
+			collateral_type: CurrencyId,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+		},
 	}
 
 	/// The authorization relationship map from
@@ -128,6 +266,31 @@ pub mod module {
 		OptionQuery,
 	>;
 
+	/// The scope of authorization, the authorization without scope can fully
+	/// manage the CDP and never expires.
+	///
+	/// AuthorizationScopes: double_map AccountId, (CurrencyId, T::AccountId) =>
+	/// Option<AuthorizationScope>
+	#[pallet::storage]
+	#[pallet::getter(fn authorization_scopes)]
+	pub type AuthorizationScopes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(CurrencyId, T::AccountId),
+		AuthorizationScope<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The nonce of loan permits of the owner, the permit must be signed
+	/// with the current nonce.
+	///
+	/// PermitNonces: map AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn permit_nonces)]
+	pub type PermitNonces<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -189,7 +352,10 @@ pub mod module {
 		) -> DispatchResult {
 			let to = ensure_signed(origin)?;
 			let from = T::Lookup::lookup(from)?;
-			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
 			Self::check_authorization(&from, &to, currency_id)?;
 			<module_loans::Pallet<T>>::transfer_loan(&from, &to, currency_id)?;
 			Ok(())
@@ -243,6 +409,7 @@ pub mod module {
 			let to = T::Lookup::lookup(to)?;
 			let reserved =
 				Authorization::<T>::take(&from, (currency_id, &to)).ok_or(Error::<T>::AuthorizationNotExists)?;
+			AuthorizationScopes::<T>::remove(&from, (currency_id, &to));
 			<T as Config>::Currency::unreserve_named(&RESERVE_ID, &from, reserved);
 			Self::deposit_event(Event::UnAuthorization {
 				authorizer: from,
@@ -258,6 +425,7 @@ pub mod module {
 		pub fn unauthorize_all(origin: OriginFor<T>) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 			let _ = Authorization::<T>::clear_prefix(&from, u32::MAX, None);
+			let _ = AuthorizationScopes::<T>::clear_prefix(&from, u32::MAX, None);
 			<T as Config>::Currency::unreserve_all_named(&RESERVE_ID, &from);
 			Self::deposit_event(Event::UnAuthorizationAll { authorizer: from });
 			Ok(())
@@ -327,9 +495,12 @@ pub mod module {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			// not allowed to adjust the debit after system shutdown
+			// not allowed to adjust the debit after the collateral is shutdown
 			if !debit_value_adjustment.is_zero() {
-				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+				ensure!(
+					!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+					Error::<T>::AlreadyShutdown
+				);
 			}
 			<module_cdp_engine::Pallet<T>>::adjust_position_by_debit_value(
 				&who,
@@ -354,15 +525,25 @@ pub mod module {
 			debit_transfer: Balance,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(from_currency)
+					&& !T::EmergencyShutdown::is_collateral_shutdown(to_currency),
+				Error::<T>::AlreadyShutdown
+			);
+			let stable_currency_id = <module_cdp_engine::Pallet<T>>::stable_currency_of(from_currency);
+			ensure!(
+				stable_currency_id == <module_cdp_engine::Pallet<T>>::stable_currency_of(to_currency),
+				Error::<T>::StableCurrencyMismatch
+			);
 			let debit_amount: Amount = debit_transfer.try_into().map_err(|_| ArithmeticError::Overflow)?;
 			let negative_debit = debit_amount.checked_neg().ok_or(ArithmeticError::Overflow)?;
-			// Adds ausd to user account momentarily to adjust loan
-			<T as module_cdp_engine::Config>::CDPTreasury::issue_debit(&who, debit_transfer, true)?;
+			// Adds stable currency to user account momentarily to adjust loan
+			<T as module_cdp_engine::Config>::CDPTreasury::issue_debit(stable_currency_id, &who, debit_transfer, true)?;
 
 			<module_cdp_engine::Pallet<T>>::adjust_position(&who, from_currency, Zero::zero(), negative_debit)?;
 			<module_cdp_engine::Pallet<T>>::adjust_position(&who, to_currency, Zero::zero(), debit_amount)?;
 			// Removes debit issued for debit transfer
-			<T as module_cdp_engine::Config>::CDPTreasury::burn_debit(&who, debit_transfer)?;
+			<T as module_cdp_engine::Config>::CDPTreasury::burn_debit(stable_currency_id, &who, debit_transfer)?;
 
 			Self::deposit_event(Event::TransferDebit {
 				from_currency,
@@ -371,16 +552,306 @@ pub mod module {
 			});
 			Ok(())
 		}
+
+		/// Transfer the whole CDP of caller under `currency_id` to `to`'s CDP
+		/// under the same `currency_id`, `to` must have authorized the caller
+		/// for the specific collateral type, and the merged CDP must be above
+		/// the required collateral ratio.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `to`: receiver account
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_loan_from())]
+		pub fn transfer_loan_to(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::check_authorization(&to, &from, currency_id)?;
+			Self::do_transfer_loan(&from, &to, currency_id)
+		}
+
+		/// Merge the whole CDP of `from` under `currency_id` into the CDP of
+		/// `to` under the same `currency_id`, caller must have the
+		/// authorization of both `from` and `to` for the specific collateral
+		/// type, and the merged CDP must be above the required collateral
+		/// ratio.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `from`: the account whose CDP is merged
+		/// - `to`: the account whose CDP receives the merged CDP
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_loan_from())]
+		pub fn merge_loans(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::check_authorization(&from, &who, currency_id)?;
+			Self::check_authorization(&to, &who, currency_id)?;
+			Self::do_transfer_loan(&from, &to, currency_id)
+		}
+
+		/// Authorize `to` to manipulate the loan under `currency_id` with
+		/// limited permission and expiry. The existing authorization of `to`
+		/// is updated to the new scope.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `to`: authorizee account
+		/// - `permission`: the permitted actions
+		/// - `expiry`: the authorization is invalid after this block, `None` means never expire
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::authorize_with_scope())]
+		pub fn authorize_with_scope(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			to: <T::Lookup as StaticLookup>::Source,
+			permission: AuthorizationPermission,
+			expiry: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			if from == to {
+				return Ok(());
+			}
+			if let Some(expiry) = expiry {
+				ensure!(
+					expiry > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
+
+			if !Authorization::<T>::contains_key(&from, (currency_id, &to)) {
+				let reserve_amount = T::DepositPerAuthorization::get();
+				<T as Config>::Currency::reserve_named(&RESERVE_ID, &from, reserve_amount)?;
+				Authorization::<T>::insert(&from, (currency_id, &to), reserve_amount);
+				Self::deposit_event(Event::Authorization {
+					authorizer: from.clone(),
+					authorizee: to.clone(),
+					collateral_type: currency_id,
+				});
+			}
+
+			let scope = AuthorizationScope { permission, expiry };
+			AuthorizationScopes::<T>::insert(&from, (currency_id, &to), scope);
+			Self::deposit_event(Event::AuthorizationScopeUpdated {
+				authorizer: from,
+				authorizee: to,
+				collateral_type: currency_id,
+				scope,
+			});
+			Ok(())
+		}
+
+		/// Adjust the loan of `owner` under `currency_id` by caller, caller must
+		/// have the authorization of `owner` which permits the adjustment. The
+		/// collateral deposited and the stablecoin paid back are provided by
+		/// caller, the collateral withdrawn and the stablecoin issued are sent
+		/// to `owner`.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `owner`: authorizer account
+		/// - `collateral_adjustment`: signed amount, positive means to deposit collateral currency
+		///   into CDP, negative means withdraw collateral currency from CDP.
+		/// - `debit_adjustment`: signed amount, positive means to issue some amount of stablecoin,
+		///   negative means to payback some amount of stablecoin to CDP.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan_on_behalf())]
+		pub fn adjust_loan_on_behalf(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+		) -> DispatchResult {
+			let operator = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			Self::check_permission(&owner, &operator, currency_id, |permission| {
+				permission.allows(collateral_adjustment, debit_adjustment)
+			})?;
+
+			if operator != owner {
+				// the operator provides the collateral deposited and the stablecoin paid back
+				if collateral_adjustment.is_positive() {
+					<T as module_cdp_engine::Config>::Currency::transfer(
+						currency_id,
+						&operator,
+						&owner,
+						collateral_adjustment.unsigned_abs(),
+					)?;
+				}
+				if debit_adjustment.is_negative() {
+					let payback_value = <module_cdp_engine::Pallet<T>>::convert_to_debit_value(
+						currency_id,
+						debit_adjustment.unsigned_abs(),
+					);
+					<T as module_cdp_engine::Config>::Currency::transfer(
+						<module_cdp_engine::Pallet<T>>::stable_currency_of(currency_id),
+						&operator,
+						&owner,
+						payback_value,
+					)?;
+				}
+			}
+			Self::do_adjust_loan(&owner, currency_id, collateral_adjustment, debit_adjustment)?;
+
+			Self::deposit_event(Event::LoanAdjustedOnBehalf {
+				owner,
+				operator,
+				collateral_type: currency_id,
+				collateral_adjustment,
+				debit_adjustment,
+			});
+			Ok(())
+		}
+
+		/// Adjust the loans of several collaterals atomically, all adjustments
+		/// fail if any of them fails.
+		///
+		/// - `adjustments`: list of `(currency_id, collateral_adjustment, debit_adjustment)`, the
+		///   adjustments are applied in order, same as `adjust_loan`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan().saturating_mul(adjustments.len() as u64))]
+		pub fn adjust_loans(origin: OriginFor<T>, adjustments: Vec<(CurrencyId, Amount, Amount)>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				adjustments.len() <= T::CollateralCurrencyIds::get().len(),
+				Error::<T>::TooManyAdjustments
+			);
+			for (currency_id, collateral_adjustment, debit_adjustment) in adjustments {
+				Self::do_adjust_loan(&who, currency_id, collateral_adjustment, debit_adjustment)?;
+			}
+			Ok(())
+		}
+
+		/// Adjust the debit of caller's loan to reach the target collateral ratio at the current
+		/// feed price, the collateral is unchanged.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `target_ratio`: the target collateral ratio, higher than the current ratio means to
+		///   payback stablecoin, lower means to issue stablecoin.
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan())]
+		pub fn adjust_to_ratio(origin: OriginFor<T>, currency_id: CurrencyId, target_ratio: Ratio) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!target_ratio.is_zero(), Error::<T>::InvalidTargetRatio);
+
+			let debit_adjustment = Self::debit_adjustment_to_ratio(&who, currency_id, target_ratio)?;
+			Self::do_adjust_loan(&who, currency_id, Zero::zero(), debit_adjustment)
+		}
+
+		/// Adjust the loan of `owner` under `currency_id` by the permit signed off-chain by
+		/// `owner`, the collateral and stablecoin are provided by or sent to `owner`, caller
+		/// only relays the permit and pays the transaction fee.
+		///
+		/// - `owner`: the signer of permit.
+		/// - `currency_id`: collateral currency id.
+		/// - `collateral_adjustment`: signed amount, positive means to deposit collateral currency
+		///   into CDP, negative means withdraw collateral currency from CDP.
+		/// - `debit_adjustment`: signed amount, positive means to issue some amount of stablecoin,
+		///   negative means to payback some amount of stablecoin to CDP.
+		/// - `deadline`: the last block number at which the permit is valid.
+		/// - `signature`: the signature of `owner` over the payload built by `permit_payload`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan_by_permit())]
+		pub fn adjust_loan_by_permit(
+			origin: OriginFor<T>,
+			owner: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyId,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+			deadline: BlockNumberFor<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= deadline,
+				Error::<T>::PermitExpired
+			);
+
+			let nonce = Self::permit_nonces(&owner);
+			let payload = Self::permit_payload(
+				&owner,
+				currency_id,
+				collateral_adjustment,
+				debit_adjustment,
+				nonce,
+				deadline,
+			);
+			ensure!(
+				signature.verify(&payload[..], &owner),
+				Error::<T>::InvalidPermitSignature
+			);
+			PermitNonces::<T>::insert(&owner, nonce.saturating_add(1));
+
+			Self::do_adjust_loan(&owner, currency_id, collateral_adjustment, debit_adjustment)?;
+
+			Self::deposit_event(Event::LoanAdjustedByPermit {
+				owner,
+				relayer,
+				collateral_type: currency_id,
+				collateral_adjustment,
+				debit_adjustment,
+			});
+			Ok(())
+		}
+
+		/// Adjust the collateral of caller's CDP which is pledged from the balance locked by
+		/// vesting schedules, the pledged collateral is kept in caller's account under the lock
+		/// and counted after a haircut by risk checks.
+		///
+		/// - `collateral_adjustment`: signed amount, positive means to pledge the vesting balance
+		///   as collateral, negative means to release the vested collateral.
+		#[pallet::call_index(17)]
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_vested_collateral())]
+		pub fn adjust_vested_collateral(origin: OriginFor<T>, collateral_adjustment: Amount) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<module_cdp_engine::Pallet<T>>::adjust_vested_collateral(&who, collateral_adjustment)?;
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
 	/// Check if `from` has the authorization of `to` under `currency_id`
 	fn check_authorization(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		Self::check_permission(from, to, currency_id, |permission| {
+			permission == AuthorizationPermission::FullManage
+		})
+	}
+
+	/// Check if `from` has the authorization of `to` under `currency_id`,
+	/// and the authorization is not expired and its permission is allowed by
+	/// `is_allowed`.
+	fn check_permission(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		currency_id: CurrencyId,
+		is_allowed: impl FnOnce(AuthorizationPermission) -> bool,
+	) -> DispatchResult {
+		if from == to {
+			return Ok(());
+		}
 		ensure!(
-			from == to || Authorization::<T>::contains_key(from, (currency_id, to)),
+			Authorization::<T>::contains_key(from, (currency_id, to)),
 			Error::<T>::NoPermission
 		);
+		if let Some(scope) = Self::authorization_scopes(from, (currency_id, to)) {
+			if let Some(expiry) = scope.expiry {
+				ensure!(
+					frame_system::Pallet::<T>::block_number() <= expiry,
+					Error::<T>::AuthorizationExpired
+				);
+			}
+			ensure!(is_allowed(scope.permission), Error::<T>::NoPermission);
+		}
 		Ok(())
 	}
 
@@ -390,20 +861,217 @@ impl<T: Config> Pallet<T> {
 		collateral_adjustment: Amount,
 		debit_adjustment: Amount,
 	) -> DispatchResult {
-		// not allowed to adjust the debit after system shutdown
+		// not allowed to adjust the debit after the collateral is shutdown
 		if !debit_adjustment.is_zero() {
-			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
 		}
 		<module_cdp_engine::Pallet<T>>::adjust_position(who, currency_id, collateral_adjustment, debit_adjustment)?;
 		Ok(())
 	}
 
+	/// Get the health information of the CDP, return None if the collateral type
+	/// is invalid or the feed price is unavailable. The required collateral ratio
+	/// fallbacks to the liquidation ratio if it's not set.
+	pub fn position_info(who: &T::AccountId, currency_id: CurrencyId) -> Option<PositionInfo> {
+		let Position { collateral, debit } = <module_loans::Pallet<T>>::positions(currency_id, who);
+		// the vested collateral is counted after the haircut
+		let risk_collateral =
+			collateral.saturating_sub(<module_loans::Pallet<T>>::collateral_haircut(currency_id, who));
+		let feed_price = <T as module_cdp_engine::Config>::PriceSource::get_relative_price(
+			currency_id,
+			<module_cdp_engine::Pallet<T>>::stable_currency_of(currency_id),
+		)?;
+		let required_ratio = match <module_cdp_engine::Pallet<T>>::required_collateral_ratio(currency_id).ok()? {
+			Some(required_ratio) => required_ratio,
+			None => <module_cdp_engine::Pallet<T>>::get_liquidation_ratio(currency_id).ok()?,
+		};
+		let maximum_total_debit_value = <module_cdp_engine::Pallet<T>>::maximum_total_debit_value(currency_id).ok()?;
+
+		let debit_value = <module_cdp_engine::Pallet<T>>::convert_to_debit_value(currency_id, debit);
+		let collateral_value = feed_price.saturating_mul_int(risk_collateral);
+		let collateral_ratio =
+			<module_cdp_engine::Pallet<T>>::calculate_collateral_ratio(currency_id, risk_collateral, debit, feed_price);
+
+		// the max debit value is rounded down and the required collateral is rounded up
+		let max_debit_value = required_ratio
+			.reciprocal()
+			.map(|n| n.saturating_mul_int(collateral_value))
+			.unwrap_or_default();
+		let total_debit_value = <module_cdp_engine::Pallet<T>>::convert_to_debit_value(
+			currency_id,
+			<module_loans::Pallet<T>>::total_positions(currency_id).debit,
+		);
+		let max_borrowable = max_debit_value
+			.saturating_sub(debit_value)
+			.min(maximum_total_debit_value.saturating_sub(total_debit_value));
+
+		let required_collateral_value = required_ratio.saturating_mul_int(debit_value);
+		let required_collateral = Price::checked_from_integer(required_collateral_value)
+			.and_then(|n| n.checked_div(&feed_price))
+			.map(|n| n.ceil().into_inner() / Price::accuracy())
+			.unwrap_or(Balance::MAX)
+			.saturating_sub(risk_collateral);
+
+		Some(PositionInfo {
+			collateral,
+			debit_value,
+			collateral_ratio,
+			max_borrowable,
+			required_collateral,
+		})
+	}
+
+	/// Get the interest the CDP will owe in `secs` at the current interest
+	/// rate, return None if the collateral type is invalid.
+	pub fn projected_interest(who: &T::AccountId, currency_id: CurrencyId, secs: u64) -> Option<Balance> {
+		let Position { debit, .. } = <module_loans::Pallet<T>>::positions(currency_id, who);
+		<module_cdp_engine::Pallet<T>>::projected_interest(currency_id, debit, secs).ok()
+	}
+
+	/// Get the health information of the whole CDP system at the current feed
+	/// price, including the utilization of each collateral type.
+	pub fn system_health() -> SystemHealth {
+		let stable_currency_id = <T as module_cdp_engine::Config>::GetStableCurrencyId::get();
+		let mut collateral_types: Vec<CurrencyId> = module_cdp_engine::CollateralParams::<T>::iter_keys().collect();
+		collateral_types.sort();
+
+		let mut total_collateral_value: Balance = Zero::zero();
+		let mut total_debit_value: Balance = Zero::zero();
+		let collaterals = collateral_types
+			.into_iter()
+			.map(|currency_id| {
+				let Position { collateral, debit } = <module_loans::Pallet<T>>::total_positions(currency_id);
+				let debit_value = <module_cdp_engine::Pallet<T>>::convert_to_debit_value(currency_id, debit);
+				let collateral_stable_currency_id = <module_cdp_engine::Pallet<T>>::stable_currency_of(currency_id);
+				let collateral_ratio = <T as module_cdp_engine::Config>::PriceSource::get_relative_price(
+					currency_id,
+					collateral_stable_currency_id,
+				)
+				.map(|feed_price| {
+					// the system totals are valued in the default stable currency only
+					if collateral_stable_currency_id == stable_currency_id {
+						total_collateral_value =
+							total_collateral_value.saturating_add(feed_price.saturating_mul_int(collateral));
+						total_debit_value = total_debit_value.saturating_add(debit_value);
+					}
+					<module_cdp_engine::Pallet<T>>::calculate_collateral_ratio(
+						currency_id,
+						collateral,
+						debit,
+						feed_price,
+					)
+				});
+				let utilization = <module_cdp_engine::Pallet<T>>::maximum_total_debit_value(currency_id)
+					.ok()
+					.and_then(|maximum_total_debit_value| {
+						Ratio::checked_from_rational(debit_value, maximum_total_debit_value)
+					})
+					.unwrap_or_default();
+
+				CollateralHealth {
+					currency_id,
+					collateral,
+					debit_value,
+					collateral_ratio,
+					utilization,
+				}
+			})
+			.collect();
+
+		SystemHealth {
+			collateral_value: total_collateral_value,
+			debit_value: total_debit_value,
+			collateral_ratio: Ratio::checked_from_rational(total_collateral_value, total_debit_value)
+				.unwrap_or_else(Ratio::max_value),
+			surplus_pool: <T as module_cdp_engine::Config>::CDPTreasury::get_surplus_pool(stable_currency_id),
+			debit_pool: <T as module_cdp_engine::Config>::CDPTreasury::get_debit_pool(stable_currency_id),
+			stable_total_issuance: <T as module_cdp_engine::Config>::Currency::total_issuance(stable_currency_id),
+			collaterals,
+		}
+	}
+
+	/// The payload of loan permit to be signed by `owner`, it's bound to the
+	/// genesis hash of chain to prevent replay on other chains.
+	pub fn permit_payload(
+		owner: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+		nonce: u32,
+		deadline: BlockNumberFor<T>,
+	) -> Vec<u8> {
+		(
+			PERMIT_PREFIX,
+			frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero()),
+			owner,
+			currency_id,
+			collateral_adjustment,
+			debit_adjustment,
+			nonce,
+			deadline,
+		)
+			.encode()
+	}
+
+	/// Calculate the debit adjustment of the loan to reach the target collateral ratio, the
+	/// target debit is rounded down so the result ratio is not below the target.
+	fn debit_adjustment_to_ratio(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		target_ratio: Ratio,
+	) -> Result<Amount, DispatchError> {
+		let Position { collateral, debit } = <module_loans::Pallet<T>>::positions(currency_id, who);
+		let feed_price = <T as module_cdp_engine::Config>::PriceSource::get_relative_price(
+			currency_id,
+			<module_cdp_engine::Pallet<T>>::stable_currency_of(currency_id),
+		)
+		.ok_or(module_cdp_engine::Error::<T>::InvalidFeedPrice)?;
+
+		// the vested collateral is counted after the haircut
+		let collateral_value = feed_price.saturating_mul_int(
+			collateral.saturating_sub(<module_loans::Pallet<T>>::collateral_haircut(currency_id, who)),
+		);
+		let target_debit_value = target_ratio
+			.reciprocal()
+			.map(|n| n.saturating_mul_int(collateral_value))
+			.ok_or(ArithmeticError::Overflow)?;
+		let target_debit =
+			<module_cdp_engine::Pallet<T>>::try_convert_to_debit_balance(currency_id, target_debit_value)
+				.ok_or(module_cdp_engine::Error::<T>::ConvertDebitBalanceFailed)?;
+
+		if target_debit >= debit {
+			Ok(<module_loans::Pallet<T>>::amount_try_from_balance(
+				target_debit - debit,
+			)?)
+		} else {
+			Ok(<module_loans::Pallet<T>>::amount_try_from_balance(debit - target_debit)?.saturating_neg())
+		}
+	}
+
+	fn do_transfer_loan(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		ensure!(
+			!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+			Error::<T>::AlreadyShutdown
+		);
+		ensure!(from != to, Error::<T>::CannotTransferToSelf);
+		let Position { collateral, debit } = <module_loans::Pallet<T>>::positions(currency_id, from);
+		ensure!(!collateral.is_zero() || !debit.is_zero(), Error::<T>::NoLoanToTransfer);
+		// the merged position is checked against the required collateral ratio by loans
+		<module_loans::Pallet<T>>::transfer_loan(from, to, currency_id)
+	}
+
 	fn do_close_loan_by_dex(
 		who: T::AccountId,
 		currency_id: CurrencyId,
 		max_collateral_amount: Balance,
 	) -> DispatchResult {
-		ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+		ensure!(
+			!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+			Error::<T>::AlreadyShutdown
+		);
 		<module_cdp_engine::Pallet<T>>::close_cdp_has_debit_by_dex(who, currency_id, max_collateral_amount)?;
 		Ok(())
 	}
@@ -447,7 +1115,8 @@ impl<T: Config> HonzonManager<T::AccountId, CurrencyId, Amount, Balance> for Pal
 
 	fn get_current_collateral_ratio(who: &T::AccountId, currency_id: CurrencyId) -> Option<Ratio> {
 		let Position { collateral, debit } = <module_loans::Pallet<T>>::positions(currency_id, who);
-		let stable_currency_id = T::GetStableCurrencyId::get();
+		let collateral = collateral.saturating_sub(<module_loans::Pallet<T>>::collateral_haircut(currency_id, who));
+		let stable_currency_id = <module_cdp_engine::Pallet<T>>::stable_currency_of(currency_id);
 
 		T::PriceSource::get_relative_price(currency_id, stable_currency_id).map(|price| {
 			<module_cdp_engine::Pallet<T>>::calculate_collateral_ratio(currency_id, collateral, debit, price)
@@ -32,7 +32,7 @@ use module_support::{
 	mocks::MockStableAsset, AuctionManager, ExchangeRate, FractionalRate, Price, PriceProvider, Rate, Ratio,
 	SpecificJointsSwap,
 };
-use orml_traits::parameter_type_with_key;
+use orml_traits::{parameter_type_with_key, DataProvider};
 use primitives::{Balance, Moment, ReserveIdentifier, TokenSymbol};
 use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
@@ -55,6 +55,7 @@ pub const BOB: AccountId = AccountId32::new([2u8; 32]);
 pub const CAROL: AccountId = AccountId32::new([3u8; 32]);
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const KUSD: CurrencyId = CurrencyId::Token(TokenSymbol::KUSD);
 pub const BTC: CurrencyId = CurrencyId::ForeignAsset(255);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 
@@ -98,7 +99,7 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = ();
-	type MaxLocks = ();
+	type MaxLocks = ConstU32<2>;
 	type MaxReserves = ();
 	type ReserveIdentifier = ReserveIdentifier;
 	type DustRemovalWhitelist = Nothing;
@@ -135,6 +136,34 @@ impl orml_currencies::Config for Runtime {
 
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub const VestedCollateralCurrencyId: CurrencyId = BTC;
+	pub VestedCollateralHaircut: Ratio = Ratio::saturating_from_rational(20, 100);
+}
+
+thread_local! {
+	static VESTING_BALANCES: RefCell<Vec<(AccountId, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct MockVestingBalance;
+impl MockVestingBalance {
+	pub fn set(who: AccountId, amount: Balance) {
+		VESTING_BALANCES.with(|v| {
+			let mut balances = v.borrow_mut();
+			balances.retain(|(account, _)| *account != who);
+			balances.push((who, amount));
+		})
+	}
+}
+impl module_support::VestingBalance<AccountId, Balance> for MockVestingBalance {
+	fn vesting_balance(who: &AccountId) -> Balance {
+		VESTING_BALANCES.with(|v| {
+			v.borrow()
+				.iter()
+				.find(|(account, _)| account == who)
+				.map(|(_, amount)| *amount)
+				.unwrap_or_default()
+		})
+	}
 }
 
 impl module_loans::Config for Runtime {
@@ -144,6 +173,9 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type VestedCollateralCurrencyId = VestedCollateralCurrencyId;
+	type VestingBalance = MockVestingBalance;
+	type VestedCollateralHaircut = VestedCollateralHaircut;
 }
 
 pub struct MockPriceSource;
@@ -187,17 +219,26 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static SHUTDOWN_COLLATERALS: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
 }
 
 pub fn mock_shutdown() {
 	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
 }
 
+pub fn mock_collateral_shutdown(currency_id: CurrencyId) {
+	SHUTDOWN_COLLATERALS.with(|v| v.borrow_mut().push(currency_id))
+}
+
 pub struct MockEmergencyShutdown;
 impl EmergencyShutdown for MockEmergencyShutdown {
 	fn is_shutdown() -> bool {
 		IS_SHUTDOWN.with(|v| *v.borrow_mut())
 	}
+
+	fn is_collateral_shutdown(currency_id: CurrencyId) -> bool {
+		Self::is_shutdown() || SHUTDOWN_COLLATERALS.with(|v| v.borrow().contains(&currency_id))
+	}
 }
 
 ord_parameter_types! {
@@ -205,7 +246,9 @@ ord_parameter_types! {
 }
 
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(20, 100);
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD, KUSD];
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account_truncating();
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
@@ -215,15 +258,28 @@ parameter_types! {
 
 impl module_cdp_treasury::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
 	type Swap = SpecificJointsSwap<(), AlternativeSwapPathJointList>;
+	type PriceSource = MockPriceSource;
+	type TradingPathLimit = ConstU32<4>;
 	type MaxAuctionsCount = ConstU32<10_000>;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = CDPEngineModule;
+	type MaxCollateralTypes = ConstU32<10>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = ();
 	type TreasuryAccount = TreasuryAccount;
+	type IncentivesRewardsSource = TreasuryAccount;
+	type MetricsEraLength = ConstU64<100>;
+	type MaxMetricsEras = ConstU32<10>;
+	type ParamsUpdateDelay = ConstU64<0>;
 	type WeightInfo = ();
 	type StableAsset = MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber>;
 }
@@ -255,13 +311,23 @@ parameter_types! {
 	pub DefaultDebitExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(1, 10);
 	pub DefaultLiquidationPenalty: FractionalRate = FractionalRate::try_from(Rate::saturating_from_rational(10, 100)).unwrap();
 	pub MaxSwapSlippageCompareToOracle: Ratio = Ratio::saturating_from_rational(50, 100);
+	pub KeeperTipRatio: Rate = Rate::saturating_from_rational(10, 100);
 	pub MaxLiquidationContractSlippage: Ratio = Ratio::saturating_from_rational(80, 100);
 	pub const CDPEnginePalletId: PalletId = PalletId(*b"aca/cdpe");
 }
 
+pub struct MockStableMarketPriceSource;
+impl DataProvider<CurrencyId, Price> for MockStableMarketPriceSource {
+	fn get(_currency_id: &CurrencyId) -> Option<Price> {
+		None
+	}
+}
+
 impl module_cdp_engine::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PriceSource = MockPriceSource;
+	type StableMarketPriceSource = MockStableMarketPriceSource;
+	type PriceStaleness = ();
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
@@ -271,8 +337,14 @@ impl module_cdp_engine::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageCompareToOracle;
+	type KeeperTipRatio = KeeperTipRatio;
+	type MaxKeeperTip = ConstU128<10>;
+	type MaxLiquidationBatchSize = ConstU32<10>;
+	type MaxDebitExchangeRateHistory = ConstU32<10>;
+	type DebitExchangeRateSampleInterval = ConstU64<0>;
 	type UnsignedPriority = ConstU64<1048576>; // 1 << 20
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type KeeperRewards = ();
 	type UnixTime = Timestamp;
 	type Currency = Currencies;
 	type DEX = ();
@@ -293,6 +365,8 @@ impl Config for Runtime {
 	type Currency = PalletBalances;
 	type DepositPerAuthorization = ConstU128<100>;
 	type CollateralCurrencyIds = CollateralCurrencyIds<Runtime>;
+	type OffchainSignature = sp_runtime::MultiSignature;
+	type OffchainPublic = sp_runtime::MultiSigner;
 	type WeightInfo = ();
 }
 
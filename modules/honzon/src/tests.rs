@@ -25,6 +25,7 @@ use frame_support::{assert_noop, assert_ok};
 use mock::{RuntimeEvent, *};
 use module_support::{Rate, Ratio};
 use orml_traits::{Change, MultiCurrency};
+use sp_core::Pair;
 use sp_runtime::FixedPointNumber;
 
 #[test]
@@ -103,6 +104,155 @@ fn unauthorize_all_should_work() {
 	});
 }
 
+#[test]
+fn authorize_with_scope_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let scope = AuthorizationScope {
+			permission: AuthorizationPermission::RepayOnly,
+			expiry: Some(10),
+		};
+		assert_noop!(
+			HonzonModule::authorize_with_scope(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				BOB,
+				AuthorizationPermission::RepayOnly,
+				Some(1)
+			),
+			Error::<Runtime>::InvalidExpiry
+		);
+		assert_ok!(HonzonModule::authorize_with_scope(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			BOB,
+			AuthorizationPermission::RepayOnly,
+			Some(10)
+		));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 100);
+		System::assert_has_event(RuntimeEvent::HonzonModule(crate::Event::Authorization {
+			authorizer: ALICE,
+			authorizee: BOB,
+			collateral_type: BTC,
+		}));
+		System::assert_last_event(RuntimeEvent::HonzonModule(crate::Event::AuthorizationScopeUpdated {
+			authorizer: ALICE,
+			authorizee: BOB,
+			collateral_type: BTC,
+			scope,
+		}));
+		assert_eq!(HonzonModule::authorization_scopes(ALICE, (BTC, BOB)), Some(scope));
+		assert_noop!(
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC),
+			Error::<Runtime>::NoPermission
+		);
+
+		// update the scope of existing authorization without extra reserve
+		assert_ok!(HonzonModule::authorize_with_scope(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			BOB,
+			AuthorizationPermission::FullManage,
+			Some(10)
+		));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 100);
+		assert_ok!(HonzonModule::check_authorization(&ALICE, &BOB, BTC));
+
+		System::set_block_number(11);
+		assert_noop!(
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC),
+			Error::<Runtime>::AuthorizationExpired
+		);
+
+		assert_ok!(HonzonModule::unauthorize(RuntimeOrigin::signed(ALICE), BTC, BOB));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+		assert_eq!(HonzonModule::authorization_scopes(ALICE, (BTC, BOB)), None);
+	});
+}
+
+#[test]
+fn adjust_loan_on_behalf_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 100, 50));
+		assert_noop!(
+			HonzonModule::adjust_loan_on_behalf(RuntimeOrigin::signed(BOB), BTC, ALICE, 10, 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(HonzonModule::authorize_with_scope(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			BOB,
+			AuthorizationPermission::AddCollateralOnly,
+			Some(10)
+		));
+		assert_ok!(HonzonModule::adjust_loan_on_behalf(
+			RuntimeOrigin::signed(BOB),
+			BTC,
+			ALICE,
+			10,
+			0
+		));
+		System::assert_last_event(RuntimeEvent::HonzonModule(crate::Event::LoanAdjustedOnBehalf {
+			owner: ALICE,
+			operator: BOB,
+			collateral_type: BTC,
+			collateral_adjustment: 10,
+			debit_adjustment: 0,
+		}));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 110);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(BTC, &BOB), 990);
+		assert_noop!(
+			HonzonModule::adjust_loan_on_behalf(RuntimeOrigin::signed(BOB), BTC, ALICE, 0, -10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_on_behalf(RuntimeOrigin::signed(BOB), BTC, ALICE, -10, 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			HonzonModule::transfer_loan_from(RuntimeOrigin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(HonzonModule::authorize_with_scope(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			BOB,
+			AuthorizationPermission::RepayOnly,
+			Some(10)
+		));
+		assert_ok!(Currencies::deposit(AUSD, &BOB, 10));
+		assert_ok!(HonzonModule::adjust_loan_on_behalf(
+			RuntimeOrigin::signed(BOB),
+			BTC,
+			ALICE,
+			0,
+			-10
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 40);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 5);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 9);
+
+		System::set_block_number(11);
+		assert_noop!(
+			HonzonModule::adjust_loan_on_behalf(RuntimeOrigin::signed(BOB), BTC, ALICE, 0, -10),
+			Error::<Runtime>::AuthorizationExpired
+		);
+	});
+}
+
 #[test]
 fn transfer_loan_from_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -123,6 +273,105 @@ fn transfer_loan_from_should_work() {
 	});
 }
 
+#[test]
+fn transfer_loan_to_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(BOB), BTC, 100, 50));
+		assert_noop!(
+			HonzonModule::transfer_loan_to(RuntimeOrigin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::NoPermission,
+		);
+		assert_ok!(HonzonModule::authorize(RuntimeOrigin::signed(ALICE), BTC, BOB));
+		assert_noop!(
+			HonzonModule::transfer_loan_to(RuntimeOrigin::signed(CAROL), BTC, ALICE),
+			Error::<Runtime>::NoPermission,
+		);
+		assert_ok!(HonzonModule::authorize(RuntimeOrigin::signed(ALICE), BTC, CAROL));
+		assert_noop!(
+			HonzonModule::transfer_loan_to(RuntimeOrigin::signed(CAROL), BTC, ALICE),
+			Error::<Runtime>::NoLoanToTransfer,
+		);
+
+		assert_ok!(HonzonModule::transfer_loan_to(RuntimeOrigin::signed(BOB), BTC, ALICE));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(BTC, BOB).collateral, 0);
+		assert_eq!(LoansModule::positions(BTC, BOB).debit, 0);
+	});
+}
+
+#[test]
+fn merge_loans_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		<PalletBalances as frame_support::traits::Currency<_>>::make_free_balance_be(&BOB, 1000);
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 100, 50));
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(BOB), BTC, 100, 100));
+
+		assert_noop!(
+			HonzonModule::merge_loans(RuntimeOrigin::signed(CAROL), BTC, ALICE, BOB),
+			Error::<Runtime>::NoPermission,
+		);
+		assert_ok!(HonzonModule::authorize(RuntimeOrigin::signed(ALICE), BTC, CAROL));
+		assert_noop!(
+			HonzonModule::merge_loans(RuntimeOrigin::signed(CAROL), BTC, ALICE, BOB),
+			Error::<Runtime>::NoPermission,
+		);
+		assert_ok!(HonzonModule::authorize(RuntimeOrigin::signed(BOB), BTC, CAROL));
+		assert_noop!(
+			HonzonModule::merge_loans(RuntimeOrigin::signed(CAROL), BTC, ALICE, ALICE),
+			Error::<Runtime>::CannotTransferToSelf,
+		);
+
+		// the merged position must be above the required collateral ratio
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(14, 1))),
+			Change::NoChange,
+		));
+		assert_noop!(
+			HonzonModule::merge_loans(RuntimeOrigin::signed(CAROL), BTC, ALICE, BOB),
+			module_cdp_engine::Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NoChange,
+		));
+
+		assert_ok!(HonzonModule::merge_loans(RuntimeOrigin::signed(CAROL), BTC, ALICE, BOB));
+		assert_eq!(LoansModule::positions(BTC, BOB).collateral, 200);
+		assert_eq!(LoansModule::positions(BTC, BOB).debit, 150);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+	});
+}
+
 #[test]
 fn transfer_unauthorization_loans_should_not_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -151,6 +400,38 @@ fn adjust_loan_should_work() {
 	});
 }
 
+#[test]
+fn adjust_vested_collateral_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			HonzonModule::adjust_vested_collateral(RuntimeOrigin::signed(ALICE), 100),
+			module_cdp_engine::Error::<Runtime>::InvalidCollateralType
+		);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			HonzonModule::adjust_vested_collateral(RuntimeOrigin::signed(ALICE), 100),
+			module_loans::Error::<Runtime>::ExceedVestingBalance
+		);
+		MockVestingBalance::set(ALICE, 100);
+		assert_ok!(HonzonModule::adjust_vested_collateral(
+			RuntimeOrigin::signed(ALICE),
+			100
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(LoansModule::vested_collaterals(ALICE), 100);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 1000);
+		assert_eq!(Tokens::accounts(ALICE, BTC).frozen, 100);
+	});
+}
+
 #[test]
 fn adjust_loan_by_debit_value_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -203,6 +484,49 @@ fn on_emergency_shutdown_should_work() {
 	});
 }
 
+#[test]
+fn on_collateral_shutdown_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 100, 50));
+
+		mock_collateral_shutdown(BTC);
+		assert_noop!(
+			HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 0, 10),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_noop!(
+			HonzonModule::transfer_loan_from(RuntimeOrigin::signed(ALICE), BTC, BOB),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_noop!(
+			HonzonModule::transfer_debit(RuntimeOrigin::signed(ALICE), BTC, DOT, 10),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+
+		// other collateral types are not affected
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), DOT, 100, 50));
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 50);
+	});
+}
+
 #[test]
 fn close_loan_has_debit_by_dex_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -302,6 +626,39 @@ fn transfer_debit_works() {
 	});
 }
 
+#[test]
+fn transfer_debit_between_different_stable_currencies() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		for currency_id in [BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				RuntimeOrigin::signed(ALICE),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(CDPEngineModule::set_collateral_stable_currency(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			KUSD
+		));
+
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 100, 500));
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), DOT, 100, 500));
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(Currencies::free_balance(KUSD, &ALICE), 50);
+
+		assert_noop!(
+			HonzonModule::transfer_debit(RuntimeOrigin::signed(ALICE), BTC, DOT, 50),
+			Error::<Runtime>::StableCurrencyMismatch
+		);
+	});
+}
+
 #[test]
 fn transfer_debit_no_ausd() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -341,3 +698,279 @@ fn transfer_debit_no_ausd() {
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 0);
 	});
 }
+
+#[test]
+fn adjust_loans_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		for currency_id in [BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				RuntimeOrigin::signed(ALICE),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+
+		assert_ok!(HonzonModule::adjust_loans(
+			RuntimeOrigin::signed(ALICE),
+			vec![(BTC, 100, 50), (DOT, 200, 100)]
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(DOT, ALICE).collateral, 200);
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 100);
+
+		// all adjustments are reverted if any of them fails
+		assert_noop!(
+			HonzonModule::adjust_loans(RuntimeOrigin::signed(ALICE), vec![(BTC, -50, -50), (DOT, -200, 0)]),
+			module_cdp_engine::Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+
+		assert_noop!(
+			HonzonModule::adjust_loans(
+				RuntimeOrigin::signed(ALICE),
+				vec![(BTC, 10, 0), (DOT, 10, 0), (BTC, 10, 0)]
+			),
+			Error::<Runtime>::TooManyAdjustments
+		);
+
+		// rebalance the debit from DOT to BTC
+		assert_ok!(HonzonModule::adjust_loans(
+			RuntimeOrigin::signed(ALICE),
+			vec![(DOT, 0, -50), (BTC, 0, 5)]
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 55);
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 50);
+	});
+}
+
+#[test]
+fn adjust_to_ratio_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 1000, 1000));
+		assert_eq!(
+			HonzonModule::get_current_collateral_ratio(&ALICE, BTC),
+			Some(Ratio::saturating_from_integer(10))
+		);
+
+		assert_noop!(
+			HonzonModule::adjust_to_ratio(RuntimeOrigin::signed(ALICE), BTC, Ratio::zero()),
+			Error::<Runtime>::InvalidTargetRatio
+		);
+		assert_noop!(
+			HonzonModule::adjust_to_ratio(RuntimeOrigin::signed(ALICE), BTC, Ratio::saturating_from_rational(3, 2)),
+			module_cdp_engine::Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+
+		// issue stablecoin
+		assert_ok!(HonzonModule::adjust_to_ratio(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Ratio::saturating_from_integer(2)
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 1000);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 5000);
+		assert_eq!(
+			HonzonModule::get_current_collateral_ratio(&ALICE, BTC),
+			Some(Ratio::saturating_from_integer(2))
+		);
+
+		// payback stablecoin
+		assert_ok!(HonzonModule::adjust_to_ratio(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Ratio::saturating_from_integer(5)
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 1000);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 2000);
+		assert_eq!(
+			HonzonModule::get_current_collateral_ratio(&ALICE, BTC),
+			Some(Ratio::saturating_from_integer(5))
+		);
+	});
+}
+
+#[test]
+fn adjust_loan_by_permit_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+
+		let pair = sp_core::sr25519::Pair::from_seed(&[9u8; 32]);
+		let owner: AccountId = sp_runtime::MultiSigner::from(pair.public()).into_account();
+		assert_ok!(Tokens::deposit(BTC, &owner, 1000));
+		let sign = |nonce: u32, deadline: BlockNumber| -> sp_runtime::MultiSignature {
+			pair.sign(&HonzonModule::permit_payload(&owner, BTC, 100, 50, nonce, deadline))
+				.into()
+		};
+
+		assert_noop!(
+			HonzonModule::adjust_loan_by_permit(RuntimeOrigin::signed(BOB), owner.clone(), BTC, 100, 50, 0, sign(0, 0)),
+			Error::<Runtime>::PermitExpired
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_permit(RuntimeOrigin::signed(BOB), owner.clone(), BTC, 100, 60, 1, sign(0, 1)),
+			Error::<Runtime>::InvalidPermitSignature
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_permit(RuntimeOrigin::signed(BOB), owner.clone(), BTC, 100, 50, 1, sign(1, 1)),
+			Error::<Runtime>::InvalidPermitSignature
+		);
+
+		assert_ok!(HonzonModule::adjust_loan_by_permit(
+			RuntimeOrigin::signed(BOB),
+			owner.clone(),
+			BTC,
+			100,
+			50,
+			1,
+			sign(0, 1)
+		));
+		System::assert_last_event(RuntimeEvent::HonzonModule(crate::Event::LoanAdjustedByPermit {
+			owner: owner.clone(),
+			relayer: BOB,
+			collateral_type: BTC,
+			collateral_adjustment: 100,
+			debit_adjustment: 50,
+		}));
+		assert_eq!(LoansModule::positions(BTC, &owner).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, &owner).debit, 50);
+		assert_eq!(Currencies::free_balance(BTC, &owner), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &owner), 5);
+		assert_eq!(HonzonModule::permit_nonces(&owner), 1);
+
+		// the permit cannot be replayed
+		assert_noop!(
+			HonzonModule::adjust_loan_by_permit(RuntimeOrigin::signed(BOB), owner.clone(), BTC, 100, 50, 1, sign(0, 1)),
+			Error::<Runtime>::InvalidPermitSignature
+		);
+	});
+}
+
+#[test]
+fn position_info_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(HonzonModule::position_info(&ALICE, DOT), None);
+
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 1000, 1000));
+		assert_eq!(
+			HonzonModule::position_info(&ALICE, BTC),
+			Some(PositionInfo {
+				collateral: 1000,
+				debit_value: 100,
+				collateral_ratio: Ratio::saturating_from_integer(10),
+				max_borrowable: 455,
+				required_collateral: 0,
+			})
+		);
+
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 0, 4500));
+		assert_eq!(
+			HonzonModule::position_info(&ALICE, BTC),
+			Some(PositionInfo {
+				collateral: 1000,
+				debit_value: 550,
+				collateral_ratio: Ratio::saturating_from_rational(1000, 550),
+				max_borrowable: 5,
+				required_collateral: 0,
+			})
+		);
+
+		// below the required collateral ratio
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_integer(2))),
+			Change::NoChange,
+		));
+		assert_eq!(
+			HonzonModule::position_info(&ALICE, BTC),
+			Some(PositionInfo {
+				collateral: 1000,
+				debit_value: 550,
+				collateral_ratio: Ratio::saturating_from_rational(1000, 550),
+				max_borrowable: 0,
+				required_collateral: 100,
+			})
+		);
+	});
+}
+
+#[test]
+fn system_health_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		for (currency_id, maximum_total_debit_value) in [(BTC, 10000), (DOT, 5000)] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				RuntimeOrigin::signed(ALICE),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(maximum_total_debit_value),
+			));
+		}
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 1000, 1000));
+		assert_ok!(HonzonModule::adjust_loan(RuntimeOrigin::signed(ALICE), DOT, 500, 2000));
+
+		assert_eq!(
+			HonzonModule::system_health(),
+			SystemHealth {
+				collateral_value: 1500,
+				debit_value: 300,
+				collateral_ratio: Ratio::saturating_from_integer(5),
+				surplus_pool: 0,
+				debit_pool: 0,
+				stable_total_issuance: 300,
+				collaterals: vec![
+					CollateralHealth {
+						currency_id: DOT,
+						collateral: 500,
+						debit_value: 200,
+						collateral_ratio: Some(Ratio::saturating_from_rational(5, 2)),
+						utilization: Ratio::saturating_from_rational(1, 25),
+					},
+					CollateralHealth {
+						currency_id: BTC,
+						collateral: 1000,
+						debit_value: 100,
+						collateral_ratio: Some(Ratio::saturating_from_integer(10)),
+						utilization: Ratio::saturating_from_rational(1, 100),
+					},
+				],
+			}
+		);
+	});
+}
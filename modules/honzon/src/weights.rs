@@ -57,6 +57,10 @@ pub trait WeightInfo {
 	fn shrink_position_debit() -> Weight;
 	fn transfer_debit() -> Weight;
 	fn precompile_get_current_collateral_ratio() -> Weight;
+	fn authorize_with_scope() -> Weight;
+	fn adjust_loan_on_behalf() -> Weight;
+	fn adjust_loan_by_permit() -> Weight;
+	fn adjust_vested_collateral() -> Weight;
 }
 
 /// Weights for module_honzon using the Acala node and recommended hardware.
@@ -226,6 +230,28 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 		Weight::from_parts(44_244_000, 0)
 			.saturating_add(T::DbWeight::get().reads(11 as u64))
 	}
+	fn authorize_with_scope() -> Weight {
+		Weight::from_parts(48_215_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	fn adjust_loan_on_behalf() -> Weight {
+		Weight::from_parts(176_320_000, 0)
+			.saturating_add(T::DbWeight::get().reads(19 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
+	// Storage: Honzon PermitNonces (r:1 w:1)
+	// Storage: System BlockHash (r:1 w:0)
+	fn adjust_loan_by_permit() -> Weight {
+		Weight::from_parts(206_540_000, 0)
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	fn adjust_vested_collateral() -> Weight {
+		Weight::from_parts(96_412_000, 0)
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -282,4 +308,24 @@ impl WeightInfo for () {
 		Weight::from_parts(44_244_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(11 as u64))
 	}
+	fn authorize_with_scope() -> Weight {
+		Weight::from_parts(48_215_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn adjust_loan_on_behalf() -> Weight {
+		Weight::from_parts(176_320_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(19 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
+	}
+	fn adjust_loan_by_permit() -> Weight {
+		Weight::from_parts(206_540_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(18 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	fn adjust_vested_collateral() -> Weight {
+		Weight::from_parts(96_412_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(13 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
 }
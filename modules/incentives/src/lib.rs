@@ -29,11 +29,24 @@
 //! Pool types:
 //! 1. Loans: record the shares and rewards for users of Loans(Honzon protocol).
 //! 2. Dex: record the shares and rewards for DEX makers who staking LP token.
+//! 3. Earning: record the shares and rewards for users who bond native token in earning module.
+//! 4. LoansDebit: record the shares and rewards for users who hold debit in Loans(Honzon
+//! protocol), to bootstrap the supply of stable currency.
 //!
 //! Rewards accumulation:
 //! 1. Incentives: periodicly(AccumulatePeriod), accumulate fixed amount according to Incentive.
 //! Rewards come from RewardsSource, please transfer enough tokens to RewardsSource before
 //! start incentive plan.
+//!
+//! Rewards vesting:
+//! If `RewardVestingPeriod` is set, claimed rewards are not paid out immediately, but unlock
+//! linearly over the period and can be claimed by `claim_vested`.
+//!
+//! Keeper rewards:
+//! Accounts which successfully liquidate unsafe CDPs, settle collateral auctions or trigger the
+//! settlement of CDP treasury are paid a fixed bonus of native token per action from
+//! KeeperRewardSource. The payouts are capped by the total allowance approved by governance and
+//! the budget of each era(KeeperRewardEraPeriod).
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -41,12 +54,14 @@
 
 use frame_support::{pallet_prelude::*, transactional, PalletId};
 use frame_system::pallet_prelude::*;
-use module_support::{DEXIncentives, EmergencyShutdown, FractionalRate, IncentivesManager, PoolId, Rate};
+use module_support::{
+	DEXIncentives, EmergencyShutdown, FractionalRate, IncentivesManager, KeeperAction, KeeperRewards, PoolId, Rate,
+};
 use orml_traits::{Happened, MultiCurrency, RewardHandler};
-use primitives::{Amount, Balance, CurrencyId};
+use primitives::{Balance, CurrencyId, Position};
 use sp_runtime::{
-	traits::{AccountIdConversion, UniqueSaturatedInto, Zero},
-	DispatchResult, FixedPointNumber,
+	traits::{AccountIdConversion, Saturating, UniqueSaturatedInto, Zero},
+	DispatchResult, FixedPointNumber, Perquintill,
 };
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
@@ -57,6 +72,33 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The vesting schedule of claimed rewards.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VestingReward<BlockNumber> {
+	/// Total rewards under vesting.
+	pub total: Balance,
+	/// Rewards which have been paid out.
+	pub claimed: Balance,
+	/// The block number vesting starts at.
+	pub start: BlockNumber,
+	/// The count of blocks over which rewards unlock linearly.
+	pub period: BlockNumber,
+}
+
+impl<BlockNumber: Saturating + UniqueSaturatedInto<u64> + Copy> VestingReward<BlockNumber> {
+	/// Rewards which are unlocked at `now` but not paid out yet.
+	pub fn claimable(&self, now: BlockNumber) -> Balance {
+		let elapsed: u64 = now.saturating_sub(self.start).unique_saturated_into();
+		let period: u64 = self.period.unique_saturated_into();
+		let unlocked = if elapsed >= period {
+			self.total
+		} else {
+			Perquintill::from_rational(elapsed, period).mul_floor(self.total)
+		};
+		unlocked.saturating_sub(self.claimed)
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -80,6 +122,15 @@ pub mod module {
 		#[pallet::constant]
 		type RewardsSource: Get<Self::AccountId>;
 
+		/// The source account for keeper rewards.
+		#[pallet::constant]
+		type KeeperRewardSource: Get<Self::AccountId>;
+
+		/// The period of keeper reward era, the keeper rewards paid within an
+		/// era are capped by `KeeperRewardEraBudget`.
+		#[pallet::constant]
+		type KeeperRewardEraPeriod: Get<BlockNumberFor<Self>>;
+
 		/// The origin which may update incentive related params
 		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
@@ -107,6 +158,8 @@ pub mod module {
 		InvalidPoolId,
 		/// Invalid rate
 		InvalidRate,
+		/// Invalid vesting period
+		InvalidVestingPeriod,
 	}
 
 	#[pallet::event]
@@ -142,6 +195,30 @@ pub mod module {
 		ClaimRewardDeductionRateUpdated { pool: PoolId, deduction_rate: Rate },
 		/// Payout deduction currency updated.
 		ClaimRewardDeductionCurrencyUpdated { pool: PoolId, currency: Option<CurrencyId> },
+		/// Reward vesting period updated.
+		RewardVestingPeriodUpdated { period: Option<BlockNumberFor<T>> },
+		/// Claimed rewards are put into vesting.
+		RewardsVesting {
+			who: T::AccountId,
+			reward_currency_id: CurrencyId,
+			amount: Balance,
+		},
+		/// Claim vested rewards.
+		ClaimVestedRewards {
+			who: T::AccountId,
+			reward_currency_id: CurrencyId,
+			amount: Balance,
+		},
+		/// Keeper reward amount updated.
+		KeeperRewardAmountUpdated { action: KeeperAction, amount: Balance },
+		/// Keeper reward budget updated.
+		KeeperRewardBudgetUpdated { allowance: Balance, era_budget: Balance },
+		/// Paid reward to the keeper.
+		KeeperRewarded {
+			keeper: T::AccountId,
+			action: KeeperAction,
+			amount: Balance,
+		},
 	}
 
 	/// Mapping from pool to its fixed incentive amounts of multi currencies per period.
@@ -179,6 +256,58 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// The period over which claimed rewards unlock linearly, `None` means
+	/// claimed rewards are paid out immediately.
+	///
+	/// RewardVestingPeriod: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn reward_vesting_period)]
+	pub type RewardVestingPeriod<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	/// The vesting schedules of claimed rewards.
+	///
+	/// VestingRewards: double_map AccountId, RewardCurrencyId => VestingReward
+	#[pallet::storage]
+	#[pallet::getter(fn vesting_rewards)]
+	pub type VestingRewards<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyId,
+		VestingReward<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The reward amount of native token paid to keeper for each action.
+	///
+	/// KeeperRewardAmounts: map KeeperAction => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn keeper_reward_amounts)]
+	pub type KeeperRewardAmounts<T: Config> = StorageMap<_, Twox64Concat, KeeperAction, Balance, ValueQuery>;
+
+	/// The remaining allowance of keeper rewards can be paid from
+	/// KeeperRewardSource.
+	///
+	/// KeeperRewardAllowance: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn keeper_reward_allowance)]
+	pub type KeeperRewardAllowance<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The max keeper rewards can be paid within an era.
+	///
+	/// KeeperRewardEraBudget: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn keeper_reward_era_budget)]
+	pub type KeeperRewardEraBudget<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The keeper rewards paid within the era.
+	///
+	/// KeeperRewardEraSpent: (EraIndex, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn keeper_reward_era_spent)]
+	pub type KeeperRewardEraSpent<T: Config> = StorageValue<_, (BlockNumberFor<T>, Balance), ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -194,8 +323,8 @@ pub mod module {
 				for (pool_id, pool_info) in orml_rewards::PoolInfos::<T>::iter() {
 					if !pool_info.total_shares.is_zero() {
 						match pool_id {
-							// do not accumulate incentives for PoolId::Loans after shutdown
-							PoolId::Loans(_) if shutdown => {
+							// do not accumulate incentives for PoolId::Loans and PoolId::LoansDebit after shutdown
+							PoolId::Loans(_) | PoolId::LoansDebit(_) if shutdown => {
 								log::debug!(
 									target: "incentives",
 									"on_initialize: skip accumulate incentives for pool {:?} after shutdown",
@@ -365,6 +494,95 @@ pub mod module {
 			});
 			Ok(())
 		}
+
+		/// Update the period over which claimed rewards unlock linearly, `None`
+		/// means claimed rewards are paid out immediately.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `period`: the count of blocks.
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::update_claim_reward_deduction_currency())]
+		pub fn update_reward_vesting_period(origin: OriginFor<T>, period: Option<BlockNumberFor<T>>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(period.map_or(true, |p| !p.is_zero()), Error::<T>::InvalidVestingPeriod);
+			RewardVestingPeriod::<T>::set(period);
+			Self::deposit_event(Event::RewardVestingPeriodUpdated { period });
+			Ok(())
+		}
+
+		/// Claim all unlocked rewards under vesting.
+		///
+		/// The dispatch origin of this call must be `Signed` by the transactor.
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim_rewards())]
+		pub fn claim_vested(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			for (currency_id, mut vesting) in VestingRewards::<T>::iter_prefix(&who).collect::<Vec<_>>() {
+				let amount = vesting.claimable(now);
+				if !amount.is_zero() {
+					T::Currency::transfer(currency_id, &Self::account_id(), &who, amount)?;
+					vesting.claimed = vesting.claimed.saturating_add(amount);
+					Self::deposit_event(Event::ClaimVestedRewards {
+						who: who.clone(),
+						reward_currency_id: currency_id,
+						amount,
+					});
+				}
+
+				if vesting.claimed >= vesting.total {
+					VestingRewards::<T>::remove(&who, currency_id);
+				} else {
+					VestingRewards::<T>::insert(&who, currency_id, vesting);
+				}
+			}
+			Ok(())
+		}
+
+		/// Update the reward amount paid to keeper for specific actions
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `updates`: Vec<(KeeperAction, RewardAmount)>
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::update_claim_reward_deduction_rates(updates.len() as u32))]
+		pub fn update_keeper_reward_amounts(
+			origin: OriginFor<T>,
+			updates: Vec<(KeeperAction, Balance)>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (action, amount) in updates {
+				if amount.is_zero() {
+					KeeperRewardAmounts::<T>::remove(action);
+				} else {
+					KeeperRewardAmounts::<T>::insert(action, amount);
+				}
+				Self::deposit_event(Event::KeeperRewardAmountUpdated { action, amount });
+			}
+			Ok(())
+		}
+
+		/// Update the budget of keeper rewards
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `allowance`: the total keeper rewards can be paid from KeeperRewardSource.
+		/// - `era_budget`: the max keeper rewards can be paid within an era.
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::update_claim_reward_deduction_currency())]
+		pub fn update_keeper_reward_budget(
+			origin: OriginFor<T>,
+			#[pallet::compact] allowance: Balance,
+			#[pallet::compact] era_budget: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			KeeperRewardAllowance::<T>::put(allowance);
+			KeeperRewardEraBudget::<T>::put(era_budget);
+			Self::deposit_event(Event::KeeperRewardBudgetUpdated { allowance, era_budget });
+			Ok(())
+		}
 	}
 }
 
@@ -502,7 +720,58 @@ impl<T: Config> Pallet<T> {
 		if !reaccumulate_amount.is_zero() {
 			<orml_rewards::Pallet<T>>::accumulate_reward(&pool_id, reward_currency_id, reaccumulate_amount)?;
 		}
-		T::Currency::transfer(reward_currency_id, &Self::account_id(), who, payout_amount)?;
+		match Self::reward_vesting_period() {
+			Some(period) => Self::vest_reward(who, reward_currency_id, payout_amount, period)?,
+			None => T::Currency::transfer(reward_currency_id, &Self::account_id(), who, payout_amount)?,
+		}
+		Ok(())
+	}
+
+	/// Put the claimed reward into the vesting schedule. The unlocked part of the
+	/// previous schedule is paid out, and the locked part vests again together with
+	/// the new reward from now on.
+	fn vest_reward(
+		who: &T::AccountId,
+		reward_currency_id: CurrencyId,
+		amount: Balance,
+		period: BlockNumberFor<T>,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		let locked = match Self::vesting_rewards(who, reward_currency_id) {
+			Some(vesting) => {
+				let claimable = vesting.claimable(now);
+				if !claimable.is_zero() {
+					T::Currency::transfer(reward_currency_id, &Self::account_id(), who, claimable)?;
+					Self::deposit_event(Event::ClaimVestedRewards {
+						who: who.clone(),
+						reward_currency_id,
+						amount: claimable,
+					});
+				}
+				vesting.total.saturating_sub(vesting.claimed).saturating_sub(claimable)
+			}
+			None => Zero::zero(),
+		};
+
+		VestingRewards::<T>::insert(
+			who,
+			reward_currency_id,
+			VestingReward {
+				total: locked.saturating_add(amount),
+				claimed: Zero::zero(),
+				start: now,
+				period,
+			},
+		);
+		Self::deposit_event(Event::RewardsVesting {
+			who: who.clone(),
+			reward_currency_id,
+			amount,
+		});
 		Ok(())
 	}
 }
@@ -541,6 +810,50 @@ impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 	}
 }
 
+impl<T: Config> KeeperRewards<T::AccountId> for Pallet<T> {
+	/// Pay the keeper the reward of the action from KeeperRewardSource, the
+	/// reward is capped by the remaining allowance and the budget of current
+	/// era. Failing to pay the reward does not revert the action.
+	fn reward_keeper(keeper: &T::AccountId, action: KeeperAction) {
+		let reward = Self::keeper_reward_amounts(action);
+		if reward.is_zero() {
+			return;
+		}
+
+		let era = <frame_system::Pallet<T>>::block_number() / T::KeeperRewardEraPeriod::get();
+		let (spent_era, spent) = Self::keeper_reward_era_spent();
+		let spent = if spent_era == era { spent } else { Zero::zero() };
+		let amount = reward
+			.min(Self::keeper_reward_era_budget().saturating_sub(spent))
+			.min(Self::keeper_reward_allowance());
+		if amount.is_zero() {
+			return;
+		}
+
+		match T::Currency::transfer(
+			T::NativeCurrencyId::get(),
+			&T::KeeperRewardSource::get(),
+			keeper,
+			amount,
+		) {
+			Ok(_) => {
+				KeeperRewardAllowance::<T>::mutate(|allowance| *allowance = allowance.saturating_sub(amount));
+				KeeperRewardEraSpent::<T>::put((era, spent.saturating_add(amount)));
+				Self::deposit_event(Event::KeeperRewarded {
+					keeper: keeper.clone(),
+					action,
+					amount,
+				});
+			}
+			Err(e) => log::warn!(
+				target: "incentives",
+				"reward_keeper: failed to pay reward {:?} to keeper {:?}: {:?}",
+				amount, keeper, e
+			),
+		}
+	}
+}
+
 impl<T: Config> IncentivesManager<T::AccountId, Balance, CurrencyId, PoolId> for Pallet<T> {
 	fn get_incentive_reward_amount(pool_id: PoolId, currency_id: CurrencyId) -> Balance {
 		IncentiveRewardAmounts::<T>::get(pool_id, currency_id)
@@ -574,16 +887,33 @@ impl<T: Config> IncentivesManager<T::AccountId, Balance, CurrencyId, PoolId> for
 }
 
 pub struct OnUpdateLoan<T>(sp_std::marker::PhantomData<T>);
-impl<T: Config> Happened<(T::AccountId, CurrencyId, Amount, Balance)> for OnUpdateLoan<T> {
-	fn happened(info: &(T::AccountId, CurrencyId, Amount, Balance)) {
-		let (who, currency_id, adjustment, _previous_amount) = info;
-		let adjustment_abs = TryInto::<Balance>::try_into(adjustment.saturating_abs()).unwrap_or_default();
+impl<T: Config> Happened<(T::AccountId, CurrencyId, Position, Position)> for OnUpdateLoan<T> {
+	fn happened(info: &(T::AccountId, CurrencyId, Position, Position)) {
+		let (who, currency_id, previous_position, new_position) = info;
+
+		Self::update_share(
+			who,
+			&PoolId::Loans(*currency_id),
+			previous_position.collateral,
+			new_position.collateral,
+		);
+		// the debit amount is proportional to the debit value under the same collateral type
+		Self::update_share(
+			who,
+			&PoolId::LoansDebit(*currency_id),
+			previous_position.debit,
+			new_position.debit,
+		);
+	}
+}
 
-		if adjustment.is_positive() {
-			<orml_rewards::Pallet<T>>::add_share(who, &PoolId::Loans(*currency_id), adjustment_abs);
-		} else {
-			<orml_rewards::Pallet<T>>::remove_share(who, &PoolId::Loans(*currency_id), adjustment_abs);
-		};
+impl<T: Config> OnUpdateLoan<T> {
+	fn update_share(who: &T::AccountId, pool_id: &PoolId, previous_share: Balance, new_share: Balance) {
+		if new_share > previous_share {
+			<orml_rewards::Pallet<T>>::add_share(who, pool_id, new_share - previous_share);
+		} else if new_share < previous_share {
+			<orml_rewards::Pallet<T>>::remove_share(who, pool_id, previous_share - new_share);
+		}
 	}
 }
 
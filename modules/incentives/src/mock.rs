@@ -29,7 +29,7 @@ use frame_support::{
 use frame_system::EnsureSignedBy;
 pub use module_support::{Price, Ratio, SwapLimit};
 use orml_traits::parameter_type_with_key;
-use primitives::{DexShare, TokenSymbol};
+use primitives::{Amount, DexShare, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
 use sp_std::cell::RefCell;
@@ -55,6 +55,7 @@ ord_parameter_types! {
 	pub const BOB: AccountId = AccountId::from([2u8; 32]);
 	pub const VAULT: AccountId = IncentivesModule::account_id();
 	pub const RewardsSource: AccountId = AccountId::from([3u8; 32]);
+	pub const KeeperRewardSource: AccountId = AccountId::from([4u8; 32]);
 	pub const ROOT: AccountId = AccountId32::new([255u8; 32]);
 }
 
@@ -144,6 +145,8 @@ impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardsSource = RewardsSource;
 	type AccumulatePeriod = ConstU64<10>;
+	type KeeperRewardSource = KeeperRewardSource;
+	type KeeperRewardEraPeriod = ConstU64<100>;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type UpdateOrigin = EnsureSignedBy<ROOT, AccountId>;
 	type Currency = TokensModule;
@@ -343,7 +343,18 @@ fn on_update_loan_works() {
 			Default::default(),
 		);
 
-		OnUpdateLoan::<Runtime>::happened(&(ALICE::get(), BTC, 100, 0));
+		OnUpdateLoan::<Runtime>::happened(&(
+			ALICE::get(),
+			BTC,
+			Position {
+				collateral: 0,
+				debit: 0,
+			},
+			Position {
+				collateral: 100,
+				debit: 0,
+			},
+		));
 		assert_eq!(
 			RewardsModule::pool_infos(PoolId::Loans(BTC)),
 			PoolInfo {
@@ -356,7 +367,18 @@ fn on_update_loan_works() {
 			(100, Default::default())
 		);
 
-		OnUpdateLoan::<Runtime>::happened(&(ALICE::get(), BTC, 100, 100));
+		OnUpdateLoan::<Runtime>::happened(&(
+			ALICE::get(),
+			BTC,
+			Position {
+				collateral: 100,
+				debit: 0,
+			},
+			Position {
+				collateral: 200,
+				debit: 0,
+			},
+		));
 		assert_eq!(
 			RewardsModule::pool_infos(PoolId::Loans(BTC)),
 			PoolInfo {
@@ -369,7 +391,18 @@ fn on_update_loan_works() {
 			(200, Default::default())
 		);
 
-		OnUpdateLoan::<Runtime>::happened(&(BOB::get(), BTC, 600, 0));
+		OnUpdateLoan::<Runtime>::happened(&(
+			BOB::get(),
+			BTC,
+			Position {
+				collateral: 0,
+				debit: 0,
+			},
+			Position {
+				collateral: 600,
+				debit: 0,
+			},
+		));
 		assert_eq!(
 			RewardsModule::pool_infos(PoolId::Loans(BTC)),
 			PoolInfo {
@@ -382,7 +415,18 @@ fn on_update_loan_works() {
 			(600, Default::default())
 		);
 
-		OnUpdateLoan::<Runtime>::happened(&(ALICE::get(), BTC, -50, 200));
+		OnUpdateLoan::<Runtime>::happened(&(
+			ALICE::get(),
+			BTC,
+			Position {
+				collateral: 200,
+				debit: 0,
+			},
+			Position {
+				collateral: 150,
+				debit: 0,
+			},
+		));
 		assert_eq!(
 			RewardsModule::pool_infos(PoolId::Loans(BTC)),
 			PoolInfo {
@@ -395,7 +439,18 @@ fn on_update_loan_works() {
 			(150, Default::default())
 		);
 
-		OnUpdateLoan::<Runtime>::happened(&(BOB::get(), BTC, -600, 600));
+		OnUpdateLoan::<Runtime>::happened(&(
+			BOB::get(),
+			BTC,
+			Position {
+				collateral: 600,
+				debit: 0,
+			},
+			Position {
+				collateral: 0,
+				debit: 0,
+			},
+		));
 		assert_eq!(
 			RewardsModule::pool_infos(PoolId::Loans(BTC)),
 			PoolInfo {
@@ -410,6 +465,75 @@ fn on_update_loan_works() {
 	});
 }
 
+#[test]
+fn on_update_loan_updates_debit_shares() {
+	ExtBuilder::default().build().execute_with(|| {
+		OnUpdateLoan::<Runtime>::happened(&(
+			ALICE::get(),
+			BTC,
+			Position {
+				collateral: 0,
+				debit: 0,
+			},
+			Position {
+				collateral: 100,
+				debit: 300,
+			},
+		));
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(PoolId::Loans(BTC), ALICE::get()),
+			(100, Default::default())
+		);
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(PoolId::LoansDebit(BTC), ALICE::get()),
+			(300, Default::default())
+		);
+
+		// only debit changes
+		OnUpdateLoan::<Runtime>::happened(&(
+			ALICE::get(),
+			BTC,
+			Position {
+				collateral: 100,
+				debit: 300,
+			},
+			Position {
+				collateral: 100,
+				debit: 200,
+			},
+		));
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(PoolId::Loans(BTC), ALICE::get()),
+			(100, Default::default())
+		);
+		assert_eq!(
+			RewardsModule::pool_infos(PoolId::LoansDebit(BTC)),
+			PoolInfo {
+				total_shares: 200,
+				..Default::default()
+			}
+		);
+
+		OnUpdateLoan::<Runtime>::happened(&(
+			ALICE::get(),
+			BTC,
+			Position {
+				collateral: 100,
+				debit: 200,
+			},
+			Position {
+				collateral: 0,
+				debit: 0,
+			},
+		));
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(PoolId::LoansDebit(BTC), ALICE::get()),
+			Default::default(),
+		);
+		assert_eq!(RewardsModule::pool_infos(PoolId::LoansDebit(BTC)), PoolInfo::default());
+	});
+}
+
 #[test]
 fn payout_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1334,3 +1458,201 @@ fn claim_reward_deduction_currency_works() {
 		assert_eq!(TokensModule::free_balance(AUSD, &ALICE::get()), 1900);
 	});
 }
+
+#[test]
+fn update_reward_vesting_period_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IncentivesModule::update_reward_vesting_period(RuntimeOrigin::signed(ALICE::get()), Some(10)),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_reward_vesting_period(RuntimeOrigin::signed(ROOT::get()), Some(0)),
+			Error::<Runtime>::InvalidVestingPeriod
+		);
+		assert_ok!(IncentivesModule::update_reward_vesting_period(
+			RuntimeOrigin::signed(ROOT::get()),
+			Some(10)
+		));
+		System::assert_last_event(RuntimeEvent::IncentivesModule(
+			crate::Event::RewardVestingPeriodUpdated { period: Some(10) },
+		));
+		assert_eq!(IncentivesModule::reward_vesting_period(), Some(10));
+
+		assert_ok!(IncentivesModule::update_reward_vesting_period(
+			RuntimeOrigin::signed(ROOT::get()),
+			None
+		));
+		assert_eq!(IncentivesModule::reward_vesting_period(), None);
+	});
+}
+
+#[test]
+fn claim_vested_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_reward_vesting_period(
+			RuntimeOrigin::signed(ROOT::get()),
+			Some(10)
+		));
+		assert_ok!(TokensModule::deposit(DOT, &VAULT::get(), 30));
+		RewardsModule::add_share(&ALICE::get(), &PoolId::Loans(BTC), 100);
+		RewardsModule::add_share(&BOB::get(), &PoolId::Loans(BTC), 200);
+		assert_ok!(RewardsModule::accumulate_reward(&PoolId::Loans(BTC), DOT, 30));
+
+		// claimed rewards are put into vesting
+		assert_ok!(IncentivesModule::claim_rewards(
+			RuntimeOrigin::signed(ALICE::get()),
+			PoolId::Loans(BTC)
+		));
+		System::assert_last_event(RuntimeEvent::IncentivesModule(crate::Event::RewardsVesting {
+			who: ALICE::get(),
+			reward_currency_id: DOT,
+			amount: 10,
+		}));
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE::get()), 0);
+		assert_eq!(TokensModule::free_balance(DOT, &VAULT::get()), 30);
+		assert_eq!(
+			IncentivesModule::vesting_rewards(ALICE::get(), DOT),
+			Some(VestingReward {
+				total: 10,
+				claimed: 0,
+				start: 1,
+				period: 10,
+			})
+		);
+
+		// half of the rewards are unlocked
+		System::set_block_number(6);
+		assert_ok!(IncentivesModule::claim_vested(RuntimeOrigin::signed(ALICE::get())));
+		System::assert_last_event(RuntimeEvent::IncentivesModule(crate::Event::ClaimVestedRewards {
+			who: ALICE::get(),
+			reward_currency_id: DOT,
+			amount: 5,
+		}));
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE::get()), 5);
+
+		// new claimed rewards vest again together with the locked rewards
+		System::set_block_number(8);
+		assert_ok!(TokensModule::deposit(DOT, &VAULT::get(), 30));
+		assert_ok!(RewardsModule::accumulate_reward(&PoolId::Loans(BTC), DOT, 30));
+		assert_ok!(IncentivesModule::claim_rewards(
+			RuntimeOrigin::signed(ALICE::get()),
+			PoolId::Loans(BTC)
+		));
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE::get()), 7);
+		assert_eq!(
+			IncentivesModule::vesting_rewards(ALICE::get(), DOT),
+			Some(VestingReward {
+				total: 13,
+				claimed: 0,
+				start: 8,
+				period: 10,
+			})
+		);
+
+		// all rewards are unlocked
+		System::set_block_number(18);
+		assert_ok!(IncentivesModule::claim_vested(RuntimeOrigin::signed(ALICE::get())));
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE::get()), 20);
+		assert_eq!(IncentivesModule::vesting_rewards(ALICE::get(), DOT), None);
+	});
+}
+
+#[test]
+fn update_keeper_reward_params_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IncentivesModule::update_keeper_reward_amounts(
+				RuntimeOrigin::signed(ALICE::get()),
+				vec![(KeeperAction::Liquidation, 10)]
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_keeper_reward_budget(RuntimeOrigin::signed(ALICE::get()), 100, 10),
+			BadOrigin
+		);
+
+		assert_ok!(IncentivesModule::update_keeper_reward_amounts(
+			RuntimeOrigin::signed(ROOT::get()),
+			vec![(KeeperAction::Liquidation, 10), (KeeperAction::AuctionSettlement, 5)]
+		));
+		System::assert_has_event(RuntimeEvent::IncentivesModule(
+			crate::Event::KeeperRewardAmountUpdated {
+				action: KeeperAction::Liquidation,
+				amount: 10,
+			},
+		));
+		assert_eq!(IncentivesModule::keeper_reward_amounts(KeeperAction::Liquidation), 10);
+		assert_eq!(
+			IncentivesModule::keeper_reward_amounts(KeeperAction::AuctionSettlement),
+			5
+		);
+
+		assert_ok!(IncentivesModule::update_keeper_reward_amounts(
+			RuntimeOrigin::signed(ROOT::get()),
+			vec![(KeeperAction::AuctionSettlement, 0)]
+		));
+		assert!(!KeeperRewardAmounts::<Runtime>::contains_key(
+			KeeperAction::AuctionSettlement
+		));
+
+		assert_ok!(IncentivesModule::update_keeper_reward_budget(
+			RuntimeOrigin::signed(ROOT::get()),
+			100,
+			10
+		));
+		System::assert_last_event(RuntimeEvent::IncentivesModule(
+			crate::Event::KeeperRewardBudgetUpdated {
+				allowance: 100,
+				era_budget: 10,
+			},
+		));
+		assert_eq!(IncentivesModule::keeper_reward_allowance(), 100);
+		assert_eq!(IncentivesModule::keeper_reward_era_budget(), 10);
+	});
+}
+
+#[test]
+fn reward_keeper_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(ACA, &KeeperRewardSource::get(), 100));
+		assert_ok!(IncentivesModule::update_keeper_reward_amounts(
+			RuntimeOrigin::signed(ROOT::get()),
+			vec![(KeeperAction::Liquidation, 10)]
+		));
+		assert_ok!(IncentivesModule::update_keeper_reward_budget(
+			RuntimeOrigin::signed(ROOT::get()),
+			25,
+			15
+		));
+
+		IncentivesModule::reward_keeper(&ALICE::get(), KeeperAction::Liquidation);
+		System::assert_last_event(RuntimeEvent::IncentivesModule(crate::Event::KeeperRewarded {
+			keeper: ALICE::get(),
+			action: KeeperAction::Liquidation,
+			amount: 10,
+		}));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE::get()), 10);
+
+		// capped by era budget
+		IncentivesModule::reward_keeper(&ALICE::get(), KeeperAction::Liquidation);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE::get()), 15);
+		assert_eq!(IncentivesModule::keeper_reward_era_spent(), (0, 15));
+		IncentivesModule::reward_keeper(&ALICE::get(), KeeperAction::Liquidation);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE::get()), 15);
+
+		// action without reward
+		IncentivesModule::reward_keeper(&ALICE::get(), KeeperAction::AuctionSettlement);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE::get()), 15);
+
+		// era budget is reset in the next era, capped by allowance
+		System::set_block_number(100);
+		IncentivesModule::reward_keeper(&BOB::get(), KeeperAction::Liquidation);
+		assert_eq!(TokensModule::free_balance(ACA, &BOB::get()), 10);
+		assert_eq!(IncentivesModule::keeper_reward_allowance(), 0);
+		IncentivesModule::reward_keeper(&BOB::get(), KeeperAction::Liquidation);
+		assert_eq!(TokensModule::free_balance(ACA, &BOB::get()), 10);
+		assert_eq!(TokensModule::free_balance(ACA, &KeeperRewardSource::get()), 75);
+	});
+}
@@ -0,0 +1,314 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Limit Order Module
+//!
+//! ## Overview
+//!
+//! Users place limit orders by reserving the supply amount and specifying the minimum price
+//! (target amount per unit of supply amount) they accept. Anyone can act as a keeper and execute
+//! an order against the DEX, in whole or in part, once the price condition is met. The owner can
+//! cancel the order at any time to unreserve the remaining supply amount.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use module_support::{ExchangeRate, Swap, SwapLimit};
+use orml_traits::{GetByKey, MultiReservableCurrency};
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{traits::Zero, ArithmeticError, FixedPointNumber, RuntimeDebug};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type OrderId = u64;
+
+/// Limit order placed by `owner`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct LimitOrder<AccountId> {
+	/// The owner of the order.
+	pub owner: AccountId,
+	/// The currency to sell.
+	pub supply_currency_id: CurrencyId,
+	/// The currency to buy.
+	pub target_currency_id: CurrencyId,
+	/// The reserved supply amount which has not been filled yet.
+	pub remaining_supply_amount: Balance,
+	/// The minimum target amount per unit of supply amount.
+	pub limit_price: ExchangeRate,
+	/// The target amount received by filled parts of the order.
+	pub filled_target_amount: Balance,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// Swap to execute limit orders.
+		type Swap: Swap<Self::AccountId, Balance, CurrencyId>;
+
+		/// The minimum supply amount of a limit order for the supply currency.
+		type MinimumOrderAmount: GetByKey<CurrencyId, Balance>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The supply and target currency are the same
+		InvalidCurrencyId,
+		/// The supply amount is below the minimum order amount
+		OrderAmountTooSmall,
+		/// The limit price is zero
+		InvalidLimitPrice,
+		/// The order does not exist
+		OrderNotFound,
+		/// Only the owner can cancel the order
+		NoPermission,
+		/// The fill amount is zero
+		InvalidFillAmount,
+		/// The swap price is lower than the limit price
+		PriceConditionNotMet,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A limit order was placed.
+		OrderPlaced {
+			order_id: OrderId,
+			owner: T::AccountId,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+			limit_price: ExchangeRate,
+		},
+		/// A limit order was executed by keeper, `remaining_supply_amount` is zero when the order
+		/// is completely filled.
+		OrderExecuted {
+			order_id: OrderId,
+			keeper: T::AccountId,
+			supply_amount: Balance,
+			target_amount: Balance,
+			remaining_supply_amount: Balance,
+		},
+		/// A limit order was cancelled and the remaining supply amount was unreserved.
+		OrderCancelled {
+			order_id: OrderId,
+			owner: T::AccountId,
+			remaining_supply_amount: Balance,
+		},
+	}
+
+	/// The next limit order id.
+	///
+	/// NextOrderId: OrderId
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// The limit orders which have not been completely filled or cancelled.
+	///
+	/// Orders: map OrderId => Option<LimitOrder>
+	#[pallet::storage]
+	#[pallet::getter(fn orders)]
+	pub type Orders<T: Config> = StorageMap<_, Twox64Concat, OrderId, LimitOrder<T::AccountId>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Place a limit order, the supply amount will be reserved until the order is filled or
+		/// cancelled.
+		///
+		/// - `supply_currency_id`: the currency to sell.
+		/// - `target_currency_id`: the currency to buy.
+		/// - `supply_amount`: the amount of supply currency to sell.
+		/// - `limit_price`: the minimum target amount per unit of supply amount.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			#[pallet::compact] supply_amount: Balance,
+			limit_price: ExchangeRate,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_place_order(&who, supply_currency_id, target_currency_id, supply_amount, limit_price)?;
+			Ok(())
+		}
+
+		/// Cancel a limit order and unreserve the remaining supply amount.
+		///
+		/// The dispatch origin of this call must be the owner of the order.
+		///
+		/// - `order_id`: the order to cancel.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_cancel_order(&who, order_id)
+		}
+
+		/// Execute a limit order against the DEX if the price condition is met. Anyone can execute
+		/// orders as keeper.
+		///
+		/// - `order_id`: the order to execute.
+		/// - `supply_amount`: the maximum supply amount to fill, capped by the remaining supply
+		///   amount of the order.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::execute_order())]
+		pub fn execute_order(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			#[pallet::compact] supply_amount: Balance,
+		) -> DispatchResult {
+			let keeper = ensure_signed(origin)?;
+			Self::do_execute_order(&keeper, order_id, supply_amount)?;
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn do_place_order(
+		who: &T::AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+		limit_price: ExchangeRate,
+	) -> Result<OrderId, DispatchError> {
+		ensure!(supply_currency_id != target_currency_id, Error::<T>::InvalidCurrencyId);
+		ensure!(
+			supply_amount >= T::MinimumOrderAmount::get(&supply_currency_id),
+			Error::<T>::OrderAmountTooSmall
+		);
+		ensure!(!limit_price.is_zero(), Error::<T>::InvalidLimitPrice);
+
+		let order_id = NextOrderId::<T>::try_mutate(|next_order_id| -> Result<OrderId, DispatchError> {
+			let current_id = *next_order_id;
+			*next_order_id = next_order_id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			Ok(current_id)
+		})?;
+
+		T::Currency::reserve(supply_currency_id, who, supply_amount)?;
+		Orders::<T>::insert(
+			order_id,
+			LimitOrder {
+				owner: who.clone(),
+				supply_currency_id,
+				target_currency_id,
+				remaining_supply_amount: supply_amount,
+				limit_price,
+				filled_target_amount: Zero::zero(),
+			},
+		);
+
+		Self::deposit_event(Event::OrderPlaced {
+			order_id,
+			owner: who.clone(),
+			supply_currency_id,
+			target_currency_id,
+			supply_amount,
+			limit_price,
+		});
+		Ok(order_id)
+	}
+
+	fn do_cancel_order(who: &T::AccountId, order_id: OrderId) -> DispatchResult {
+		let order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+		ensure!(order.owner == *who, Error::<T>::NoPermission);
+
+		T::Currency::unreserve(order.supply_currency_id, who, order.remaining_supply_amount);
+		Orders::<T>::remove(order_id);
+
+		Self::deposit_event(Event::OrderCancelled {
+			order_id,
+			owner: order.owner,
+			remaining_supply_amount: order.remaining_supply_amount,
+		});
+		Ok(())
+	}
+
+	fn do_execute_order(
+		keeper: &T::AccountId,
+		order_id: OrderId,
+		supply_amount: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Orders::<T>::try_mutate_exists(order_id, |maybe_order| -> Result<(Balance, Balance), DispatchError> {
+			let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+			let supply_amount = supply_amount.min(order.remaining_supply_amount);
+			ensure!(!supply_amount.is_zero(), Error::<T>::InvalidFillAmount);
+
+			let min_target_amount = order
+				.limit_price
+				.checked_mul_int(supply_amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			let limit = SwapLimit::ExactSupply(supply_amount, min_target_amount);
+			ensure!(
+				T::Swap::get_swap_amount(order.supply_currency_id, order.target_currency_id, limit).is_some(),
+				Error::<T>::PriceConditionNotMet
+			);
+
+			let remaining = T::Currency::unreserve(order.supply_currency_id, &order.owner, supply_amount);
+			ensure!(remaining.is_zero(), ArithmeticError::Underflow);
+			let (actual_supply_amount, actual_target_amount) =
+				T::Swap::swap(&order.owner, order.supply_currency_id, order.target_currency_id, limit)?;
+
+			order.remaining_supply_amount = order
+				.remaining_supply_amount
+				.checked_sub(actual_supply_amount)
+				.ok_or(ArithmeticError::Underflow)?;
+			order.filled_target_amount = order
+				.filled_target_amount
+				.checked_add(actual_target_amount)
+				.ok_or(ArithmeticError::Overflow)?;
+
+			Self::deposit_event(Event::OrderExecuted {
+				order_id,
+				keeper: keeper.clone(),
+				supply_amount: actual_supply_amount,
+				target_amount: actual_target_amount,
+				remaining_supply_amount: order.remaining_supply_amount,
+			});
+
+			if order.remaining_supply_amount.is_zero() {
+				*maybe_order = None;
+			}
+			Ok((actual_supply_amount, actual_target_amount))
+		})
+	}
+}
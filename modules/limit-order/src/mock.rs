@@ -0,0 +1,194 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the limit order module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU32, ConstU64, Everything, Nothing},
+};
+use module_support::AggregatedSwapPath;
+use orml_traits::{parameter_type_with_key, MultiCurrency};
+use primitives::{Amount, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{IdentityLookup, One},
+	BuildStorage,
+};
+
+pub type AccountId = u128;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const KEEPER: AccountId = 10;
+pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+mod limit_order {
+	pub use super::super::*;
+}
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+parameter_type_with_key! {
+	pub MinimumOrderAmount: |_currency_id: CurrencyId| -> Balance {
+		100
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+}
+
+parameter_types! {
+	pub static MockSwapPrice: ExchangeRate = ExchangeRate::one();
+}
+
+/// Swap at `MockSwapPrice` without exchange fee.
+pub struct MockSwap;
+impl Swap<AccountId, Balance, CurrencyId> for MockSwap {
+	fn get_swap_amount(
+		_supply_currency_id: CurrencyId,
+		_target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> Option<(Balance, Balance)> {
+		match limit {
+			SwapLimit::ExactSupply(supply_amount, min_target_amount) => {
+				let target_amount = MockSwapPrice::get().saturating_mul_int(supply_amount);
+				if target_amount >= min_target_amount {
+					Some((supply_amount, target_amount))
+				} else {
+					None
+				}
+			}
+			SwapLimit::ExactTarget(..) => unimplemented!(),
+		}
+	}
+
+	fn swap(
+		who: &AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let (supply_amount, target_amount) = Self::get_swap_amount(supply_currency_id, target_currency_id, limit)
+			.ok_or(DispatchError::Other("Cannot swap"))?;
+		Tokens::withdraw(supply_currency_id, who, supply_amount)?;
+		Tokens::deposit(target_currency_id, who, target_amount)?;
+		Ok((supply_amount, target_amount))
+	}
+
+	fn swap_by_aggregated_path(
+		_who: &AccountId,
+		_swap_path: &[AggregatedSwapPath<CurrencyId>],
+		_limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		unimplemented!()
+	}
+}
+
+impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Tokens;
+	type Swap = MockSwap;
+	type MinimumOrderAmount = MinimumOrderAmount;
+	type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		LimitOrderModule: limit_order,
+		Tokens: orml_tokens,
+	}
+);
+
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: vec![(ALICE, DOT, 1_000_000), (BOB, DOT, 1_000_000)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: self.balances,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,165 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the limit order module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use orml_traits::MultiCurrency;
+use sp_runtime::traits::One;
+
+#[test]
+fn place_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LimitOrderModule::place_order(RuntimeOrigin::signed(ALICE), DOT, DOT, 1_000, ExchangeRate::one()),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(RuntimeOrigin::signed(ALICE), DOT, AUSD, 99, ExchangeRate::one()),
+			Error::<Runtime>::OrderAmountTooSmall
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(RuntimeOrigin::signed(ALICE), DOT, AUSD, 1_000, ExchangeRate::zero()),
+			Error::<Runtime>::InvalidLimitPrice
+		);
+
+		assert_ok!(LimitOrderModule::place_order(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			AUSD,
+			1_000,
+			ExchangeRate::saturating_from_integer(10)
+		));
+		System::assert_last_event(RuntimeEvent::LimitOrderModule(crate::Event::OrderPlaced {
+			order_id: 0,
+			owner: ALICE,
+			supply_currency_id: DOT,
+			target_currency_id: AUSD,
+			supply_amount: 1_000,
+			limit_price: ExchangeRate::saturating_from_integer(10),
+		}));
+		assert_eq!(LimitOrderModule::next_order_id(), 1);
+		assert_eq!(
+			LimitOrderModule::orders(0),
+			Some(LimitOrder {
+				owner: ALICE,
+				supply_currency_id: DOT,
+				target_currency_id: AUSD,
+				remaining_supply_amount: 1_000,
+				limit_price: ExchangeRate::saturating_from_integer(10),
+				filled_target_amount: 0,
+			})
+		);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_000);
+		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 1_000);
+	});
+}
+
+#[test]
+fn cancel_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LimitOrderModule::cancel_order(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Runtime>::OrderNotFound
+		);
+		assert_ok!(LimitOrderModule::place_order(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			AUSD,
+			1_000,
+			ExchangeRate::one()
+		));
+		assert_noop!(
+			LimitOrderModule::cancel_order(RuntimeOrigin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(LimitOrderModule::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+		System::assert_last_event(RuntimeEvent::LimitOrderModule(crate::Event::OrderCancelled {
+			order_id: 0,
+			owner: ALICE,
+			remaining_supply_amount: 1_000,
+		}));
+		assert_eq!(LimitOrderModule::orders(0), None);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000_000);
+		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+	});
+}
+
+#[test]
+fn execute_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LimitOrderModule::execute_order(RuntimeOrigin::signed(KEEPER), 0, 1_000),
+			Error::<Runtime>::OrderNotFound
+		);
+		assert_ok!(LimitOrderModule::place_order(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			AUSD,
+			1_000,
+			ExchangeRate::saturating_from_integer(10)
+		));
+		assert_noop!(
+			LimitOrderModule::execute_order(RuntimeOrigin::signed(KEEPER), 0, 0),
+			Error::<Runtime>::InvalidFillAmount
+		);
+
+		MockSwapPrice::set(ExchangeRate::saturating_from_integer(9));
+		assert_noop!(
+			LimitOrderModule::execute_order(RuntimeOrigin::signed(KEEPER), 0, 1_000),
+			Error::<Runtime>::PriceConditionNotMet
+		);
+
+		// partial fill
+		MockSwapPrice::set(ExchangeRate::saturating_from_integer(11));
+		assert_ok!(LimitOrderModule::execute_order(RuntimeOrigin::signed(KEEPER), 0, 400));
+		System::assert_last_event(RuntimeEvent::LimitOrderModule(crate::Event::OrderExecuted {
+			order_id: 0,
+			keeper: KEEPER,
+			supply_amount: 400,
+			target_amount: 4_400,
+			remaining_supply_amount: 600,
+		}));
+		assert_eq!(
+			LimitOrderModule::orders(0).map(|order| (order.remaining_supply_amount, order.filled_target_amount)),
+			Some((600, 4_400))
+		);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_000);
+		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 600);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 4_400);
+
+		// fill amount is capped by the remaining supply amount
+		assert_ok!(LimitOrderModule::execute_order(RuntimeOrigin::signed(KEEPER), 0, 1_000));
+		System::assert_last_event(RuntimeEvent::LimitOrderModule(crate::Event::OrderExecuted {
+			order_id: 0,
+			keeper: KEEPER,
+			supply_amount: 600,
+			target_amount: 6_600,
+			remaining_supply_amount: 0,
+		}));
+		assert_eq!(LimitOrderModule::orders(0), None);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_000);
+		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 11_000);
+	});
+}
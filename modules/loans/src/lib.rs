@@ -22,25 +22,34 @@
 //!
 //! Loans module manages CDP's collateral assets and the debits backed by these
 //! assets.
+//!
+//! The balance locked by vesting schedules can be pledged as collateral in
+//! place, it's kept in the owner's account under the lock of loans module and
+//! counted after a haircut by risk checks. When the vested collateral is
+//! confiscated, the part still locked by vesting schedules is transferred to
+//! cdp treasury once it's vested.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::collapsible_if)]
 
-use frame_support::{pallet_prelude::*, transactional, PalletId};
-use module_support::{CDPTreasury, RiskManager};
-use orml_traits::{Happened, MultiCurrency, MultiCurrencyExtended};
+use frame_support::{pallet_prelude::*, traits::LockIdentifier, transactional, PalletId};
+use module_support::{CDPTreasury, Ratio, RiskManager, VestingBalance};
+use orml_traits::{Happened, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency};
 use primitives::{Amount, Balance, CurrencyId, Position};
 use sp_runtime::{
 	traits::{AccountIdConversion, Zero},
-	ArithmeticError, DispatchResult,
+	ArithmeticError, DispatchResult, FixedPointNumber,
 };
+use sp_std::prelude::*;
 
 mod mock;
 mod tests;
 
 pub use module::*;
 
+pub const VESTED_COLLATERAL_LOCK_ID: LockIdentifier = *b"aca/vcol";
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -51,12 +60,8 @@ pub mod module {
 
 		/// Currency type for deposit/withdraw collateral assets to/from loans
 		/// module
-		type Currency: MultiCurrencyExtended<
-			Self::AccountId,
-			CurrencyId = CurrencyId,
-			Balance = Balance,
-			Amount = Amount,
-		>;
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance, Amount = Amount>
+			+ MultiLockableCurrency<Self::AccountId>;
 
 		/// Risk manager is used to limit the debit size of CDP
 		type RiskManager: RiskManager<Self::AccountId, CurrencyId, Balance, Balance>;
@@ -69,13 +74,31 @@ pub mod module {
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
-		/// Event handler which calls when update loan.
-		type OnUpdateLoan: Happened<(Self::AccountId, CurrencyId, Amount, Balance)>;
+		/// Event handler which calls when update loan, with the previous and the
+		/// new position.
+		type OnUpdateLoan: Happened<(Self::AccountId, CurrencyId, Position, Position)>;
+
+		/// The collateral type whose balance locked by vesting schedules can be
+		/// pledged as collateral in place.
+		#[pallet::constant]
+		type VestedCollateralCurrencyId: Get<CurrencyId>;
+
+		/// The balances locked by vesting schedules.
+		type VestingBalance: VestingBalance<Self::AccountId, Balance>;
+
+		/// The haircut of vested collateral, risk checks count the vested
+		/// collateral `amount` as `(1 - haircut) * amount`.
+		#[pallet::constant]
+		type VestedCollateralHaircut: Get<Ratio>;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		AmountConvertFailed,
+		/// The vested collateral exceeds the balance locked by vesting schedules.
+		ExceedVestingBalance,
+		/// The CDP with vested collateral can't be transferred.
+		VestedCollateralNotTransferable,
 	}
 
 	#[pallet::event]
@@ -101,6 +124,14 @@ pub mod module {
 			to: T::AccountId,
 			currency_id: CurrencyId,
 		},
+		/// The collateral pledged from the balance locked by vesting schedules
+		/// is adjusted.
+		VestedCollateralAdjusted {
+			owner: T::AccountId,
+			collateral_adjustment: Amount,
+		},
+		/// The confiscated vested collateral is transferred to cdp treasury.
+		VestedCollateralSwept { owner: T::AccountId, amount: Balance },
 	}
 
 	/// The collateralized debit positions, map from
@@ -120,6 +151,24 @@ pub mod module {
 	#[pallet::getter(fn total_positions)]
 	pub type TotalPositions<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Position, ValueQuery>;
 
+	/// The collateral pledged from the balance locked by vesting schedules, it's
+	/// a part of the position of `VestedCollateralCurrencyId` and kept in the
+	/// owner's account under the lock.
+	///
+	/// VestedCollaterals: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn vested_collaterals)]
+	pub type VestedCollaterals<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The confiscated vested collateral which is still kept in the owner's
+	/// account under the lock, it's transferred to cdp treasury once it's
+	/// vested.
+	///
+	/// PendingConfiscations: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn pending_confiscations)]
+	pub type PendingConfiscations<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -132,7 +181,10 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account_truncating()
 	}
 
-	/// confiscate collateral and debit to cdp treasury.
+	/// confiscate collateral and debit to cdp treasury, return the collateral
+	/// amount transferred to cdp treasury. The regular collateral is confiscated
+	/// first, the confiscated vested collateral which is still locked by vesting
+	/// schedules is transferred to cdp treasury once it's vested.
 	///
 	/// Ensured atomic.
 	#[transactional]
@@ -141,17 +193,30 @@ impl<T: Config> Pallet<T> {
 		currency_id: CurrencyId,
 		collateral_confiscate: Balance,
 		debit_decrease: Balance,
-	) -> DispatchResult {
+	) -> Result<Balance, DispatchError> {
 		// convert balance type to amount type
 		let collateral_adjustment = Self::amount_try_from_balance(collateral_confiscate)?;
 		let debit_adjustment = Self::amount_try_from_balance(debit_decrease)?;
+		let (regular_confiscate, vested_confiscate) =
+			Self::split_collateral_decrease(who, currency_id, collateral_confiscate);
 
 		// transfer collateral to cdp treasury
-		T::CDPTreasury::deposit_collateral(&Self::account_id(), currency_id, collateral_confiscate)?;
+		T::CDPTreasury::deposit_collateral(&Self::account_id(), currency_id, regular_confiscate)?;
+		let mut confiscated = regular_confiscate;
+		if !vested_confiscate.is_zero() {
+			Self::unlock_vested_collateral(who, vested_confiscate)?;
+			PendingConfiscations::<T>::mutate(who, |pending| *pending = pending.saturating_add(vested_confiscate));
+			Self::update_vested_collateral_lock(who)?;
+
+			// the failure of sweeping shouldn't block the confiscation, the pending
+			// confiscation is kept to be swept later.
+			let swept = Self::sweep_vested_collateral(who).unwrap_or_default();
+			confiscated = confiscated.saturating_add(swept.min(vested_confiscate));
+		}
 
 		// deposit debit to cdp treasury
 		let bad_debt_value = T::RiskManager::get_debit_value(currency_id, debit_decrease);
-		T::CDPTreasury::on_system_debit(bad_debt_value)?;
+		T::CDPTreasury::on_system_debit(T::RiskManager::get_stable_currency_id(currency_id), bad_debt_value)?;
 
 		// update loan
 		Self::update_loan(
@@ -167,7 +232,7 @@ impl<T: Config> Pallet<T> {
 			confiscated_collateral_amount: collateral_confiscate,
 			deduct_debit_amount: debit_decrease,
 		});
-		Ok(())
+		Ok(confiscated)
 	}
 
 	/// adjust the position.
@@ -180,18 +245,26 @@ impl<T: Config> Pallet<T> {
 		collateral_adjustment: Amount,
 		debit_adjustment: Amount,
 	) -> DispatchResult {
+		let collateral_balance_adjustment = Self::balance_try_from_amount_abs(collateral_adjustment)?;
+		let debit_balance_adjustment = Self::balance_try_from_amount_abs(debit_adjustment)?;
+		// withdraw the regular collateral first, and then unlock the vested collateral in place
+		let (regular_decrease, vested_decrease) =
+			Self::split_collateral_decrease(who, currency_id, collateral_balance_adjustment);
+
 		// mutate collateral and debit
 		// Note: if a new position, will inc consumer
 		Self::update_loan(who, currency_id, collateral_adjustment, debit_adjustment)?;
 
-		let collateral_balance_adjustment = Self::balance_try_from_amount_abs(collateral_adjustment)?;
-		let debit_balance_adjustment = Self::balance_try_from_amount_abs(debit_adjustment)?;
 		let module_account = Self::account_id();
 
 		if collateral_adjustment.is_positive() {
 			T::Currency::transfer(currency_id, who, &module_account, collateral_balance_adjustment)?;
 		} else if collateral_adjustment.is_negative() {
-			T::Currency::transfer(currency_id, &module_account, who, collateral_balance_adjustment)?;
+			T::Currency::transfer(currency_id, &module_account, who, regular_decrease)?;
+			if !vested_decrease.is_zero() {
+				Self::unlock_vested_collateral(who, vested_decrease)?;
+				Self::update_vested_collateral_lock(who)?;
+			}
 		}
 
 		if debit_adjustment.is_positive() {
@@ -200,6 +273,7 @@ impl<T: Config> Pallet<T> {
 
 			// issue debit with collateral backed by cdp treasury
 			T::CDPTreasury::issue_debit(
+				T::RiskManager::get_stable_currency_id(currency_id),
 				who,
 				T::RiskManager::get_debit_value(currency_id, debit_balance_adjustment),
 				true,
@@ -208,6 +282,7 @@ impl<T: Config> Pallet<T> {
 			// repay debit
 			// burn debit by cdp treasury
 			T::CDPTreasury::burn_debit(
+				T::RiskManager::get_stable_currency_id(currency_id),
 				who,
 				T::RiskManager::get_debit_value(currency_id, debit_balance_adjustment),
 			)?;
@@ -217,7 +292,7 @@ impl<T: Config> Pallet<T> {
 		let Position { collateral, debit } = Self::positions(currency_id, who);
 		T::RiskManager::check_position_valid(
 			currency_id,
-			collateral,
+			collateral.saturating_sub(Self::collateral_haircut(currency_id, who)),
 			debit,
 			collateral_adjustment.is_negative() || debit_adjustment.is_positive(),
 		)?;
@@ -225,8 +300,149 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// adjust the collateral pledged from the balance locked by vesting
+	/// schedules, the pledged collateral is kept in the account of `who`
+	/// under the lock.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn adjust_vested_collateral(who: &T::AccountId, collateral_adjustment: Amount) -> DispatchResult {
+		let currency_id = T::VestedCollateralCurrencyId::get();
+		let collateral_balance_adjustment = Self::balance_try_from_amount_abs(collateral_adjustment)?;
+
+		if collateral_adjustment.is_positive() {
+			let vested_collateral = Self::vested_collaterals(who)
+				.checked_add(collateral_balance_adjustment)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				vested_collateral.saturating_add(Self::pending_confiscations(who))
+					<= T::VestingBalance::vesting_balance(who),
+				Error::<T>::ExceedVestingBalance
+			);
+			VestedCollaterals::<T>::insert(who, vested_collateral);
+		} else if collateral_adjustment.is_negative() {
+			Self::unlock_vested_collateral(who, collateral_balance_adjustment)?;
+		}
+		Self::update_vested_collateral_lock(who)?;
+		Self::update_loan(who, currency_id, collateral_adjustment, Zero::zero())?;
+
+		// ensure pass risk check
+		let Position { collateral, debit } = Self::positions(currency_id, who);
+		T::RiskManager::check_position_valid(
+			currency_id,
+			collateral.saturating_sub(Self::collateral_haircut(currency_id, who)),
+			debit,
+			collateral_adjustment.is_negative(),
+		)?;
+
+		Self::deposit_event(Event::VestedCollateralAdjusted {
+			owner: who.clone(),
+			collateral_adjustment,
+		});
+		Ok(())
+	}
+
+	/// release all the vested collateral of the position of `who` under
+	/// `currency_id` in place without risk check, the caller should ensure the
+	/// risk of the remaining position.
+	pub fn release_vested_collateral(who: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		let vested_collateral = Self::vested_collateral_of(currency_id, who);
+		if vested_collateral.is_zero() {
+			return Ok(());
+		}
+
+		let collateral_adjustment = Self::amount_try_from_balance(vested_collateral)?;
+		Self::unlock_vested_collateral(who, vested_collateral)?;
+		Self::update_vested_collateral_lock(who)?;
+		Self::update_loan(who, currency_id, collateral_adjustment.saturating_neg(), Zero::zero())?;
+
+		Self::deposit_event(Event::VestedCollateralAdjusted {
+			owner: who.clone(),
+			collateral_adjustment: collateral_adjustment.saturating_neg(),
+		});
+		Ok(())
+	}
+
+	/// transfer the pending confiscation of `who` which has been vested to cdp
+	/// treasury, return the transferred amount.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn sweep_vested_collateral(who: &T::AccountId) -> Result<Balance, DispatchError> {
+		let currency_id = T::VestedCollateralCurrencyId::get();
+		let pending = Self::pending_confiscations(who);
+		let vested =
+			T::Currency::free_balance(currency_id, who).saturating_sub(T::VestingBalance::vesting_balance(who));
+		let amount = pending.min(vested);
+		if amount.is_zero() {
+			return Ok(Zero::zero());
+		}
+
+		PendingConfiscations::<T>::insert(who, pending.saturating_sub(amount));
+		Self::update_vested_collateral_lock(who)?;
+		T::CDPTreasury::deposit_collateral(who, currency_id, amount)?;
+
+		Self::deposit_event(Event::VestedCollateralSwept {
+			owner: who.clone(),
+			amount,
+		});
+		Ok(amount)
+	}
+
+	/// The vested collateral of the position of `who` under `currency_id`.
+	pub fn vested_collateral_of(currency_id: CurrencyId, who: &T::AccountId) -> Balance {
+		if currency_id == T::VestedCollateralCurrencyId::get() {
+			Self::vested_collaterals(who)
+		} else {
+			Zero::zero()
+		}
+	}
+
+	/// The part of the collateral of the position of `who` under `currency_id`
+	/// which isn't counted by risk checks.
+	pub fn collateral_haircut(currency_id: CurrencyId, who: &T::AccountId) -> Balance {
+		T::VestedCollateralHaircut::get().saturating_mul_int(Self::vested_collateral_of(currency_id, who))
+	}
+
+	/// Split the decrease of collateral into the regular part and the vested
+	/// part, the regular collateral is decreased first.
+	fn split_collateral_decrease(who: &T::AccountId, currency_id: CurrencyId, amount: Balance) -> (Balance, Balance) {
+		let regular_collateral = Self::positions(currency_id, who)
+			.collateral
+			.saturating_sub(Self::vested_collateral_of(currency_id, who));
+		let regular_decrease = amount.min(regular_collateral);
+		(regular_decrease, amount.saturating_sub(regular_decrease))
+	}
+
+	fn unlock_vested_collateral(who: &T::AccountId, amount: Balance) -> DispatchResult {
+		VestedCollaterals::<T>::try_mutate_exists(who, |maybe_vested_collateral| -> DispatchResult {
+			let vested_collateral = maybe_vested_collateral
+				.unwrap_or_default()
+				.checked_sub(amount)
+				.ok_or(ArithmeticError::Underflow)?;
+			*maybe_vested_collateral = Some(vested_collateral).filter(|v| !v.is_zero());
+			Ok(())
+		})
+	}
+
+	fn update_vested_collateral_lock(who: &T::AccountId) -> DispatchResult {
+		let currency_id = T::VestedCollateralCurrencyId::get();
+		let locked = Self::vested_collaterals(who).saturating_add(Self::pending_confiscations(who));
+		if locked.is_zero() {
+			T::Currency::remove_lock(VESTED_COLLATERAL_LOCK_ID, currency_id, who)
+		} else {
+			T::Currency::set_lock(VESTED_COLLATERAL_LOCK_ID, currency_id, who, locked)
+		}
+	}
+
 	/// transfer whole loan of `from` to `to`
 	pub fn transfer_loan(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		// the vested collateral is kept in the account of `from`
+		ensure!(
+			Self::vested_collateral_of(currency_id, from).is_zero(),
+			Error::<T>::VestedCollateralNotTransferable
+		);
+
 		// get `from` position data
 		let Position { collateral, debit } = Self::positions(currency_id, from);
 
@@ -242,7 +458,12 @@ impl<T: Config> Pallet<T> {
 			.expect("existing debit balance cannot overflow; qed");
 
 		// check new position
-		T::RiskManager::check_position_valid(currency_id, new_to_collateral_balance, new_to_debit_balance, true)?;
+		T::RiskManager::check_position_valid(
+			currency_id,
+			new_to_collateral_balance.saturating_sub(Self::collateral_haircut(currency_id, to)),
+			new_to_debit_balance,
+			true,
+		)?;
 
 		// balance -> amount
 		let collateral_adjustment = Self::amount_try_from_balance(collateral)?;
@@ -304,13 +525,15 @@ impl<T: Config> Pallet<T> {
 				}
 			}
 
+			let previous_position = p;
+			p.collateral = new_collateral;
+			p.debit = new_debit;
+
 			// use the collateral amount as the shares for Loans incentives
 			// NOTE: but for KSM loans in Karura, the debit amount was used before,
 			// and the data will been messed up, before migration or calibration,
 			// it is forbidden to turn on incentives for pool LoansIncentive(KSM).
-			T::OnUpdateLoan::happened(&(who.clone(), currency_id, collateral_adjustment, p.collateral));
-			p.collateral = new_collateral;
-			p.debit = new_debit;
+			T::OnUpdateLoan::happened(&(who.clone(), currency_id, previous_position, p));
 
 			if p.collateral.is_zero() && p.debit.is_zero() {
 				// decrease account ref if zero position
@@ -373,4 +596,30 @@ impl<T: Config> Pallet<T> {
 	pub fn balance_try_from_amount_abs(a: Amount) -> Result<Balance, Error<T>> {
 		TryInto::<Balance>::try_into(a.saturating_abs()).map_err(|_| Error::<T>::AmountConvertFailed)
 	}
+
+	/// Get at most `limit` positions under `currency_id`, starting after the
+	/// raw storage key `start_key`, or from the beginning if it's `None`.
+	/// Positions are walked in the stable order of their storage keys, and the
+	/// returned key is used to fetch the next page, it's `None` if all positions
+	/// have been walked.
+	pub fn positions_page(
+		currency_id: CurrencyId,
+		start_key: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<(T::AccountId, Position)>, Option<Vec<u8>>) {
+		let mut iterator = match start_key {
+			Some(key) => Positions::<T>::iter_prefix_from(currency_id, key),
+			None => Positions::<T>::iter_prefix(currency_id),
+		};
+
+		let mut positions = vec![];
+		for _ in 0..limit {
+			match iterator.next() {
+				Some(item) => positions.push(item),
+				None => return (positions, None),
+			}
+		}
+
+		(positions, Some(iterator.last_raw_key().to_vec()))
+	}
 }
@@ -27,13 +27,16 @@ use frame_support::{
 	PalletId,
 };
 use frame_system::EnsureSignedBy;
-use module_support::{mocks::MockStableAsset, AuctionManager, RiskManager, SpecificJointsSwap};
+use module_support::{
+	mocks::MockStableAsset, AuctionManager, Price, PriceProvider, Ratio, RiskManager, SpecificJointsSwap,
+	VestingBalance,
+};
 use orml_traits::parameter_type_with_key;
 use primitives::TokenSymbol;
 use sp_core::H256;
 use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup},
-	BuildStorage,
+	BuildStorage, FixedPointNumber,
 };
 use sp_std::cell::RefCell;
 use std::collections::HashMap;
@@ -93,7 +96,7 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = ();
-	type MaxLocks = ();
+	type MaxLocks = ConstU32<2>;
 	type MaxReserves = ();
 	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Nothing;
@@ -128,6 +131,13 @@ impl orml_currencies::Config for Runtime {
 }
 pub type AdaptedBasicCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, PalletBalances, Amount, BlockNumber>;
 
+pub struct MockPriceSource;
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+}
+
 pub struct MockAuctionManager;
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type CurrencyId = CurrencyId;
@@ -161,7 +171,9 @@ ord_parameter_types! {
 }
 
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(20, 100);
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD];
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account_truncating();
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![];
@@ -169,15 +181,28 @@ parameter_types! {
 
 impl module_cdp_treasury::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
 	type Swap = SpecificJointsSwap<(), AlternativeSwapPathJointList>;
+	type PriceSource = MockPriceSource;
+	type TradingPathLimit = ConstU32<4>;
 	type MaxAuctionsCount = ConstU32<10_000>;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = MockRiskManager;
+	type MaxCollateralTypes = ConstU32<10>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = ();
 	type TreasuryAccount = TreasuryAccount;
+	type IncentivesRewardsSource = TreasuryAccount;
+	type MetricsEraLength = ConstU64<100>;
+	type MaxMetricsEras = ConstU32<10>;
+	type ParamsUpdateDelay = ConstU64<0>;
 	type WeightInfo = ();
 	type StableAsset = MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber>;
 }
@@ -185,6 +210,10 @@ impl module_cdp_treasury::Config for Runtime {
 // mock risk manager
 pub struct MockRiskManager;
 impl RiskManager<AccountId, CurrencyId, Balance, Balance> for MockRiskManager {
+	fn get_stable_currency_id(_currency_id: CurrencyId) -> CurrencyId {
+		GetStableCurrencyId::get()
+	}
+
 	fn get_debit_value(_currency_id: CurrencyId, debit_balance: Balance) -> Balance {
 		debit_balance / Balance::from(2u64)
 	}
@@ -221,18 +250,26 @@ impl RiskManager<AccountId, CurrencyId, Balance, Balance> for MockRiskManager {
 
 thread_local! {
 	pub static DOT_SHARES: RefCell<HashMap<AccountId, Balance>> = RefCell::new(HashMap::new());
+	pub static VESTING_BALANCES: RefCell<HashMap<AccountId, Balance>> = RefCell::new(HashMap::new());
+}
+
+pub struct MockVestingBalance;
+impl MockVestingBalance {
+	pub fn set(who: AccountId, amount: Balance) {
+		VESTING_BALANCES.with(|v| v.borrow_mut().insert(who, amount));
+	}
+}
+impl VestingBalance<AccountId, Balance> for MockVestingBalance {
+	fn vesting_balance(who: &AccountId) -> Balance {
+		VESTING_BALANCES.with(|v| *v.borrow().get(who).unwrap_or(&0))
+	}
 }
 
 pub struct MockOnUpdateLoan;
-impl Happened<(AccountId, CurrencyId, Amount, Balance)> for MockOnUpdateLoan {
-	fn happened(info: &(AccountId, CurrencyId, Amount, Balance)) {
-		let (who, currency_id, adjustment, previous_amount) = info;
-		let adjustment_abs = TryInto::<Balance>::try_into(adjustment.saturating_abs()).unwrap_or_default();
-		let new_share_amount = if adjustment.is_positive() {
-			previous_amount.saturating_add(adjustment_abs)
-		} else {
-			previous_amount.saturating_sub(adjustment_abs)
-		};
+impl Happened<(AccountId, CurrencyId, Position, Position)> for MockOnUpdateLoan {
+	fn happened(info: &(AccountId, CurrencyId, Position, Position)) {
+		let (who, currency_id, _previous_position, new_position) = info;
+		let new_share_amount = new_position.collateral;
 
 		if *currency_id == DOT {
 			DOT_SHARES.with(|v| {
@@ -246,6 +283,8 @@ impl Happened<(AccountId, CurrencyId, Amount, Balance)> for MockOnUpdateLoan {
 
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub const VestedCollateralCurrencyId: CurrencyId = BTC;
+	pub VestedCollateralHaircut: Ratio = Ratio::saturating_from_rational(20, 100);
 }
 
 impl Config for Runtime {
@@ -255,6 +294,9 @@ impl Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = MockOnUpdateLoan;
+	type VestedCollateralCurrencyId = VestedCollateralCurrencyId;
+	type VestingBalance = MockVestingBalance;
+	type VestedCollateralHaircut = VestedCollateralHaircut;
 }
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
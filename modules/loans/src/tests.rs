@@ -162,6 +162,65 @@ fn update_loan_should_work() {
 	});
 }
 
+#[test]
+fn positions_page_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(LoansModule::positions_page(BTC, None, 10), (vec![], None));
+
+		assert_ok!(LoansModule::update_loan(&ALICE, BTC, 100, 50));
+		assert_ok!(LoansModule::update_loan(&BOB, BTC, 200, 100));
+		assert_ok!(LoansModule::update_loan(&3, BTC, 300, 150));
+		assert_ok!(LoansModule::update_loan(&ALICE, DOT, 100, 50));
+
+		let (first_page, next_key) = LoansModule::positions_page(BTC, None, 2);
+		assert_eq!(first_page.len(), 2);
+		assert!(next_key.is_some());
+
+		let (second_page, next_key) = LoansModule::positions_page(BTC, next_key, 2);
+		assert_eq!(second_page.len(), 1);
+		assert_eq!(next_key, None);
+
+		let mut positions = [first_page, second_page].concat();
+		positions.sort_by_key(|(who, _)| *who);
+		assert_eq!(
+			positions,
+			vec![
+				(
+					ALICE,
+					Position {
+						collateral: 100,
+						debit: 50
+					}
+				),
+				(
+					BOB,
+					Position {
+						collateral: 200,
+						debit: 100
+					}
+				),
+				(
+					3,
+					Position {
+						collateral: 300,
+						debit: 150
+					}
+				),
+			]
+		);
+
+		// the order is stable
+		assert_eq!(
+			LoansModule::positions_page(BTC, None, 3).0,
+			[
+				LoansModule::positions_page(BTC, None, 2).0,
+				LoansModule::positions_page(BTC, LoansModule::positions_page(BTC, None, 2).1, 2).0
+			]
+			.concat()
+		);
+	});
+}
+
 #[test]
 fn transfer_loan_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -234,3 +293,91 @@ fn loan_updated_updated_when_adjust_collateral() {
 		assert_eq!(DOT_SHARES.with(|v| *v.borrow().get(&BOB).unwrap_or(&0)), 200);
 	});
 }
+
+#[test]
+fn adjust_vested_collateral_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		MockVestingBalance::set(ALICE, 600);
+
+		assert_noop!(
+			LoansModule::adjust_vested_collateral(&ALICE, 700),
+			Error::<Runtime>::ExceedVestingBalance
+		);
+
+		// the vested collateral is kept in place under the lock
+		assert_ok!(LoansModule::adjust_vested_collateral(&ALICE, 400));
+		System::assert_last_event(RuntimeEvent::LoansModule(crate::Event::VestedCollateralAdjusted {
+			owner: ALICE,
+			collateral_adjustment: 400,
+		}));
+		assert_eq!(LoansModule::vested_collaterals(&ALICE), 400);
+		assert_eq!(LoansModule::positions(BTC, &ALICE).collateral, 400);
+		assert_eq!(LoansModule::total_positions(BTC).collateral, 400);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(BTC, &LoansModule::account_id()), 0);
+		assert_eq!(Tokens::accounts(ALICE, BTC).frozen, 400);
+		assert_eq!(LoansModule::collateral_haircut(BTC, &ALICE), 80);
+		assert_eq!(LoansModule::collateral_haircut(DOT, &ALICE), 0);
+
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, 200, 0));
+		assert_eq!(LoansModule::positions(BTC, &ALICE).collateral, 600);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 800);
+		assert_eq!(Currencies::free_balance(BTC, &LoansModule::account_id()), 200);
+
+		// withdraw the regular collateral first, and then unlock the vested collateral
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, -300, 0));
+		assert_eq!(LoansModule::positions(BTC, &ALICE).collateral, 300);
+		assert_eq!(LoansModule::vested_collaterals(&ALICE), 300);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(BTC, &LoansModule::account_id()), 0);
+		assert_eq!(Tokens::accounts(ALICE, BTC).frozen, 300);
+
+		// the loan with vested collateral can't be transferred
+		assert_noop!(
+			LoansModule::transfer_loan(&ALICE, &BOB, BTC),
+			Error::<Runtime>::VestedCollateralNotTransferable
+		);
+
+		assert_ok!(LoansModule::adjust_vested_collateral(&ALICE, -300));
+		assert_eq!(LoansModule::vested_collaterals(&ALICE), 0);
+		assert!(!<Positions<Runtime>>::contains_key(BTC, &ALICE));
+		assert_eq!(Tokens::accounts(ALICE, BTC).frozen, 0);
+	});
+}
+
+#[test]
+fn confiscate_vested_collateral_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		MockVestingBalance::set(ALICE, 600);
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, 200, 100));
+		assert_ok!(LoansModule::adjust_vested_collateral(&ALICE, 500));
+		assert_eq!(LoansModule::positions(BTC, &ALICE).collateral, 700);
+
+		// the regular collateral is confiscated first, the vested collateral which is
+		// still locked by vesting schedules is pending
+		assert_ok!(LoansModule::confiscate_collateral_and_debit(&ALICE, BTC, 500, 100), 400);
+		assert_eq!(CDPTreasuryModule::get_total_collaterals(BTC), 400);
+		assert_eq!(LoansModule::positions(BTC, &ALICE).collateral, 200);
+		assert_eq!(LoansModule::positions(BTC, &ALICE).debit, 0);
+		assert_eq!(LoansModule::vested_collaterals(&ALICE), 200);
+		assert_eq!(LoansModule::pending_confiscations(&ALICE), 100);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 600);
+		assert_eq!(Tokens::accounts(ALICE, BTC).frozen, 300);
+
+		// nothing to sweep before it's vested
+		assert_ok!(LoansModule::sweep_vested_collateral(&ALICE), 0);
+
+		MockVestingBalance::set(ALICE, 0);
+		assert_ok!(LoansModule::sweep_vested_collateral(&ALICE), 100);
+		System::assert_last_event(RuntimeEvent::LoansModule(crate::Event::VestedCollateralSwept {
+			owner: ALICE,
+			amount: 100,
+		}));
+		assert_eq!(CDPTreasuryModule::get_total_collaterals(BTC), 500);
+		assert_eq!(LoansModule::pending_confiscations(&ALICE), 0);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 500);
+		assert_eq!(Tokens::accounts(ALICE, BTC).frozen, 200);
+	});
+}
@@ -0,0 +1,33 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use sp_runtime::codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait PricesApi<CurrencyId, PriceInfo> where
+		CurrencyId: Codec,
+		PriceInfo: Codec,
+	{
+		/// Get the price of specific currency with the metadata about the feed quality, including
+		/// the feed timestamp, the count of sources and whether it's locked, stale or quarantined.
+		fn get_price_info(currency_id: CurrencyId) -> PriceInfo;
+	}
+}
@@ -25,21 +25,32 @@
 //!   - specify a fixed price for stable currency
 //!   - feed price in USD or related price bewteen two currencies
 //!   - lock/unlock the price data get from oracle
+//!   - validate the price from oracle by the average price of DEX, and fallback to it when oracle
+//!     price is unavailable
+//!   - combine the prices fed by a set of oracle operators with median, which requires a minimum
+//!     quorum of operators
+//!   - treat the oracle price older than the max stale period as unavailable, and automatically
+//!     lock the last good price until the feed is fresh again
+//!   - quarantine the oracle price which changes more than the max price change, until it's
+//!     confirmed by the next feed or accepted by governance
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::pallet_prelude::*;
+use frame_support::{pallet_prelude::*, traits::UnixTime};
 use frame_system::pallet_prelude::*;
-use module_support::{DEXManager, Erc20InfoMapping, ExchangeRateProvider, LockablePrice, Price, PriceProvider, Rate};
+use module_support::{
+	DEXManager, DEXPriceProvider, Erc20InfoMapping, ExchangeRateProvider, LockablePrice, OperatorDataProvider, Price,
+	PriceFeedStaleness, PriceProvider, Rate, Ratio,
+};
 use orml_traits::{DataFeeder, DataProvider, GetByKey, MultiCurrency};
-use primitives::{Balance, CurrencyId, Lease};
+use primitives::{Balance, CurrencyId, Lease, Moment};
 use sp_core::U256;
 use sp_runtime::{
 	traits::{BlockNumberProvider, CheckedMul, One, Saturating, UniqueSaturatedInto},
 	FixedPointNumber,
 };
-use sp_std::marker::PhantomData;
+use sp_std::{marker::PhantomData, vec::Vec};
 
 mod mock;
 mod tests;
@@ -48,6 +59,24 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The price of currency with the metadata to assess the quality of feed.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo)]
+pub struct PriceInfo {
+	/// The price for 1 basic unit used by system, the locked price takes
+	/// priority over the real-time price.
+	pub price: Option<Price>,
+	/// The timestamp of the latest oracle feed.
+	pub timestamp: Option<Moment>,
+	/// The count of oracle sources that have fed price.
+	pub source_count: u32,
+	/// Whether the price is locked.
+	pub is_locked: bool,
+	/// Whether the oracle price is stale.
+	pub is_stale: bool,
+	/// Whether the oracle price is quarantined by the max price change.
+	pub is_quarantined: bool,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -106,6 +135,22 @@ pub mod module {
 		/// equal to the price of another.
 		type PricingPegged: GetByKey<CurrencyId, Option<CurrencyId>>;
 
+		/// The time-weighted average price source of DEX
+		type DEXPriceSource: DEXPriceProvider<CurrencyId>;
+
+		/// The data source of the prices fed by specific oracle operator.
+		type OperatorSource: OperatorDataProvider<Self::AccountId, CurrencyId, Price>;
+
+		/// The max number of oracle operators for a currency.
+		#[pallet::constant]
+		type MaxOperators: Get<u32>;
+
+		/// The timestamp of the latest oracle feed of currency.
+		type FeedTimestamp: GetByKey<CurrencyId, Option<Moment>>;
+
+		/// Time used for checking the staleness of oracle feed.
+		type UnixTime: UnixTime;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -116,6 +161,14 @@ pub mod module {
 		AccessPriceFailed,
 		/// There's no locked price
 		NoLockedPrice,
+		/// The quorum is zero or exceeds the number of operators
+		InvalidQuorum,
+		/// The operator is duplicated
+		DuplicatedOperator,
+		/// The max stale period is zero
+		InvalidStalePeriod,
+		/// There's no quarantined price
+		NoQuarantinedPrice,
 	}
 
 	#[pallet::event]
@@ -128,6 +181,38 @@ pub mod module {
 		},
 		/// Unlock price.
 		UnlockPrice { currency_id: CurrencyId },
+		/// The max deviation of oracle price from DEX price updated.
+		DEXPriceMaxDeviationUpdated {
+			currency_id: CurrencyId,
+			max_deviation: Option<Ratio>,
+		},
+		/// The oracle operators of currency updated.
+		PriceOperatorsUpdated {
+			currency_id: CurrencyId,
+			operators: Vec<T::AccountId>,
+			quorum: u32,
+		},
+		/// The max stale period of oracle price updated.
+		MaxStalePeriodUpdated {
+			currency_id: CurrencyId,
+			max_stale_period: Option<Moment>,
+		},
+		/// The oracle price is stale, the last good price is locked.
+		StalePriceLocked {
+			currency_id: CurrencyId,
+			locked_price: Price,
+		},
+		/// The oracle price is fresh again, the price locked for stale is unlocked.
+		StalePriceUnlocked { currency_id: CurrencyId },
+		/// The max price change of oracle price per update updated.
+		MaxPriceChangeUpdated {
+			currency_id: CurrencyId,
+			max_price_change: Option<Ratio>,
+		},
+		/// The oracle price changes too much and is quarantined.
+		PriceQuarantined { currency_id: CurrencyId, price: Price },
+		/// The quarantined price is confirmed or accepted by governance.
+		QuarantinedPriceAccepted { currency_id: CurrencyId, price: Price },
 	}
 
 	/// Mapping from currency id to it's locked price
@@ -137,11 +222,75 @@ pub mod module {
 	#[pallet::getter(fn locked_price)]
 	pub type LockedPrice<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
 
+	/// Mapping from currency id to the max deviation of oracle price from DEX
+	/// average price, if it's set, the oracle price exceeding it is rejected
+	/// and DEX average price is used when oracle price is unavailable.
+	///
+	/// map CurrencyId => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn dex_price_max_deviation)]
+	pub type DEXPriceMaxDeviation<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Ratio, OptionQuery>;
+
+	/// Mapping from currency id to the oracle operators and the quorum, if it's
+	/// set, the oracle price is the median of prices fed by these operators.
+	///
+	/// PriceOperators: map CurrencyId => Option<(Operators, Quorum)>
+	#[pallet::storage]
+	#[pallet::getter(fn price_operators)]
+	pub type PriceOperators<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, (BoundedVec<T::AccountId, T::MaxOperators>, u32), OptionQuery>;
+
+	/// Mapping from currency id to the max stale period of oracle price in
+	/// milliseconds, if it's set, the oracle price older than it is unavailable.
+	///
+	/// MaxStalePeriod: map CurrencyId => Option<Moment>
+	#[pallet::storage]
+	#[pallet::getter(fn max_stale_period)]
+	pub type MaxStalePeriod<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Moment, OptionQuery>;
+
+	/// Mapping from currency id to whether the locked price is locked
+	/// automatically because of stale oracle price.
+	///
+	/// StalePriceLocked: map CurrencyId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn stale_price_locked)]
+	pub type StalePriceLocked<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
+	/// Mapping from currency id to the max price change of oracle price per
+	/// update, if it's set, the oracle price changes more than it is
+	/// quarantined.
+	///
+	/// MaxPriceChange: map CurrencyId => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn max_price_change)]
+	pub type MaxPriceChange<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Ratio, OptionQuery>;
+
+	/// Mapping from currency id to the last accepted oracle price.
+	///
+	/// AcceptedPrice: map CurrencyId => Option<Price>
+	#[pallet::storage]
+	#[pallet::getter(fn accepted_price)]
+	pub type AcceptedPrice<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
+	/// Mapping from currency id to the quarantined oracle price.
+	///
+	/// QuarantinedPrice: map CurrencyId => Option<Price>
+	#[pallet::storage]
+	#[pallet::getter(fn quarantined_price)]
+	pub type QuarantinedPrice<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Check the price changes of oracle, then lock the last good price of
+		/// currencies whose oracle price is stale and unlock them once the oracle
+		/// price is fresh again.
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			Self::check_price_changes().saturating_add(Self::lock_stale_prices())
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -170,15 +319,219 @@ pub mod module {
 			<Pallet<T> as LockablePrice<CurrencyId>>::unlock_price(currency_id)?;
 			Ok(())
 		}
+
+		/// Set the max deviation of oracle price from DEX average price.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `max_deviation`: the max deviation, `None` means not to use DEX price.
+		#[pallet::call_index(2)]
+		#[pallet::weight((T::WeightInfo::unlock_price(), DispatchClass::Operational))]
+		pub fn set_dex_price_max_deviation(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_deviation: Option<Ratio>,
+		) -> DispatchResult {
+			T::LockOrigin::ensure_origin(origin)?;
+			DEXPriceMaxDeviation::<T>::set(currency_id, max_deviation);
+			Self::deposit_event(Event::DEXPriceMaxDeviationUpdated {
+				currency_id,
+				max_deviation,
+			});
+			Ok(())
+		}
+
+		/// Set the oracle operators and the quorum for the price of currency.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `operators`: the oracle operators, empty means to use the price of `Source`.
+		/// - `quorum`: the min number of operators that have fed price.
+		#[pallet::call_index(3)]
+		#[pallet::weight((T::WeightInfo::unlock_price(), DispatchClass::Operational))]
+		pub fn set_price_operators(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			operators: BoundedVec<T::AccountId, T::MaxOperators>,
+			quorum: u32,
+		) -> DispatchResult {
+			T::LockOrigin::ensure_origin(origin)?;
+
+			if operators.is_empty() {
+				PriceOperators::<T>::remove(currency_id);
+			} else {
+				ensure!(
+					quorum > 0 && quorum as usize <= operators.len(),
+					Error::<T>::InvalidQuorum
+				);
+				for (i, operator) in operators.iter().enumerate() {
+					ensure!(!operators[..i].contains(operator), Error::<T>::DuplicatedOperator);
+				}
+				PriceOperators::<T>::insert(currency_id, (operators.clone(), quorum));
+			}
+
+			Self::deposit_event(Event::PriceOperatorsUpdated {
+				currency_id,
+				operators: operators.into_inner(),
+				quorum,
+			});
+			Ok(())
+		}
+
+		/// Set the max stale period of oracle price.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `max_stale_period`: the max stale period in milliseconds, `None` means never stale.
+		#[pallet::call_index(4)]
+		#[pallet::weight((T::WeightInfo::unlock_price(), DispatchClass::Operational))]
+		pub fn set_max_stale_period(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_stale_period: Option<Moment>,
+		) -> DispatchResult {
+			T::LockOrigin::ensure_origin(origin)?;
+			ensure!(max_stale_period != Some(0), Error::<T>::InvalidStalePeriod);
+			MaxStalePeriod::<T>::set(currency_id, max_stale_period);
+			Self::deposit_event(Event::MaxStalePeriodUpdated {
+				currency_id,
+				max_stale_period,
+			});
+			Ok(())
+		}
+
+		/// Set the max price change of oracle price per update.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `max_price_change`: the max price change, `None` means no limit.
+		#[pallet::call_index(5)]
+		#[pallet::weight((T::WeightInfo::unlock_price(), DispatchClass::Operational))]
+		pub fn set_max_price_change(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_price_change: Option<Ratio>,
+		) -> DispatchResult {
+			T::LockOrigin::ensure_origin(origin)?;
+			MaxPriceChange::<T>::set(currency_id, max_price_change);
+			if max_price_change.is_none() {
+				AcceptedPrice::<T>::remove(currency_id);
+				QuarantinedPrice::<T>::remove(currency_id);
+			}
+			Self::deposit_event(Event::MaxPriceChangeUpdated {
+				currency_id,
+				max_price_change,
+			});
+			Ok(())
+		}
+
+		/// Accept the quarantined price as the oracle price.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::call_index(6)]
+		#[pallet::weight((T::WeightInfo::unlock_price(), DispatchClass::Operational))]
+		pub fn accept_quarantined_price(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::LockOrigin::ensure_origin(origin)?;
+			let price = QuarantinedPrice::<T>::take(currency_id).ok_or(Error::<T>::NoQuarantinedPrice)?;
+			AcceptedPrice::<T>::insert(currency_id, price);
+			Self::deposit_event(Event::QuarantinedPriceAccepted { currency_id, price });
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Accept the oracle price if its change is within the max price change, or
+	/// it's confirmed by the quarantined price, otherwise quarantine it.
+	fn check_price_changes() -> Weight {
+		let mut weight = Weight::zero();
+		for (currency_id, max_price_change) in MaxPriceChange::<T>::iter() {
+			weight = weight.saturating_add(T::DbWeight::get().reads(5));
+			let price = match Self::access_fresh_oracle_price(currency_id) {
+				Some(price) => price,
+				None => continue,
+			};
+
+			match Self::accepted_price(currency_id) {
+				Some(accepted_price) if !is_within_change(accepted_price, price, max_price_change) => {
+					let confirmed = Self::quarantined_price(currency_id).map_or(false, |quarantined_price| {
+						is_within_change(quarantined_price, price, max_price_change)
+					});
+					if confirmed {
+						weight = weight.saturating_add(T::DbWeight::get().writes(2));
+						AcceptedPrice::<T>::insert(currency_id, price);
+						QuarantinedPrice::<T>::remove(currency_id);
+						Self::deposit_event(Event::QuarantinedPriceAccepted { currency_id, price });
+					} else if Self::quarantined_price(currency_id) != Some(price) {
+						weight = weight.saturating_add(T::DbWeight::get().writes(1));
+						QuarantinedPrice::<T>::insert(currency_id, price);
+						Self::deposit_event(Event::PriceQuarantined { currency_id, price });
+					}
+				}
+				maybe_accepted_price => {
+					if maybe_accepted_price != Some(price) {
+						weight = weight.saturating_add(T::DbWeight::get().writes(1));
+						AcceptedPrice::<T>::insert(currency_id, price);
+					}
+					if QuarantinedPrice::<T>::contains_key(currency_id) {
+						weight = weight.saturating_add(T::DbWeight::get().writes(1));
+						QuarantinedPrice::<T>::remove(currency_id);
+					}
+				}
+			}
+		}
+		weight
+	}
+
+	/// Lock the last good price of currencies whose oracle price is stale, and
+	/// unlock them once the oracle price is fresh again.
+	fn lock_stale_prices() -> Weight {
+		let mut weight = Weight::zero();
+		for currency_id in MaxStalePeriod::<T>::iter_keys() {
+			weight = weight.saturating_add(T::DbWeight::get().reads(4));
+			let stale_price_locked = Self::stale_price_locked(currency_id);
+
+			if Self::is_stale(currency_id) {
+				// do not override the price locked by `LockOrigin`
+				if stale_price_locked || Self::locked_price(currency_id).is_some() {
+					continue;
+				}
+				if let Some(locked_price) = Self::do_access_price(currency_id, false) {
+					weight = weight.saturating_add(T::WeightInfo::lock_price());
+					LockedPrice::<T>::insert(currency_id, locked_price);
+					StalePriceLocked::<T>::insert(currency_id, true);
+					Self::deposit_event(Event::StalePriceLocked {
+						currency_id,
+						locked_price,
+					});
+				}
+			} else if stale_price_locked {
+				weight = weight.saturating_add(T::WeightInfo::unlock_price());
+				LockedPrice::<T>::remove(currency_id);
+				StalePriceLocked::<T>::remove(currency_id);
+				Self::deposit_event(Event::StalePriceUnlocked { currency_id });
+			}
+		}
+		weight
+	}
+
 	/// access the exchange rate of specific currency to USD,
 	/// it always access the real-time price directly.
 	///
 	/// Note: this returns the price for 1 basic unit
 	fn access_price(currency_id: CurrencyId) -> Option<Price> {
+		Self::do_access_price(currency_id, true)
+	}
+
+	/// access the exchange rate of specific currency to USD, the stale oracle
+	/// price is unavailable if `check_stale` is true.
+	fn do_access_price(currency_id: CurrencyId, check_stale: bool) -> Option<Price> {
 		// if it's configured pegged to another currency id
 		let currency_id = if let Some(pegged_currency_id) = T::PricingPegged::get(&currency_id) {
 			pegged_currency_id
@@ -191,7 +544,7 @@ impl<T: Config> Pallet<T> {
 			Some(T::StableCurrencyFixedPrice::get())
 		} else if currency_id == T::GetLiquidCurrencyId::get() {
 			// directly return real-time the multiple of the price of StakingCurrencyId and the exchange rate
-			return Self::access_price(T::GetStakingCurrencyId::get())
+			return Self::do_access_price(T::GetStakingCurrencyId::get(), check_stale)
 				.and_then(|n| n.checked_mul(&T::LiquidStakingExchangeRateProvider::get_exchange_rate()));
 		} else if let CurrencyId::LiquidCrowdloan(lease) = currency_id {
 			// Note: For LiquidCrowdloan, The reliable market price may not be available in the initial stage,
@@ -206,14 +559,18 @@ impl<T: Config> Pallet<T> {
 				.reciprocal()
 				.expect("shouldn't fail");
 
-			return Self::access_price(T::GetStakingCurrencyId::get()).and_then(|n| n.checked_mul(&discount_rate));
+			return Self::do_access_price(T::GetStakingCurrencyId::get(), check_stale)
+				.and_then(|n| n.checked_mul(&discount_rate));
 		} else if let CurrencyId::DexShare(dex_share_0, dex_share_1) = currency_id {
 			let token_0: CurrencyId = dex_share_0.into();
 			let token_1: CurrencyId = dex_share_1.into();
 
 			// directly return the fair price
 			return {
-				if let (Some(price_0), Some(price_1)) = (Self::access_price(token_0), Self::access_price(token_1)) {
+				if let (Some(price_0), Some(price_1)) = (
+					Self::do_access_price(token_0, check_stale),
+					Self::do_access_price(token_1, check_stale),
+				) {
 					let (pool_0, pool_1) = T::DEX::get_liquidity_pool(token_0, token_1);
 					let total_shares = T::Currency::total_issuance(currency_id);
 					lp_token_fair_price(total_shares, pool_0, pool_1, price_0, price_1)
@@ -223,16 +580,136 @@ impl<T: Config> Pallet<T> {
 			};
 		} else {
 			// get real-time price from oracle
-			T::Source::get(&currency_id)
+			if check_stale && Self::is_stale(currency_id) {
+				None
+			} else {
+				Self::access_oracle_price(currency_id).map(|price| Self::check_price_change(currency_id, price))
+			}
 		};
 
 		let maybe_adjustment_multiplier = 10u128.checked_pow(T::Erc20InfoMapping::decimals(currency_id)?.into());
 
-		if let (Some(price), Some(adjustment_multiplier)) = (maybe_price, maybe_adjustment_multiplier) {
+		let maybe_price = match (maybe_price, maybe_adjustment_multiplier) {
 			// return the price for 1 basic unit
-			Price::checked_from_rational(price.into_inner(), adjustment_multiplier)
+			(Some(price), Some(adjustment_multiplier)) => {
+				Price::checked_from_rational(price.into_inner(), adjustment_multiplier)
+			}
+			_ => None,
+		};
+
+		if currency_id == T::GetStableCurrencyId::get() {
+			maybe_price
 		} else {
+			Self::check_with_dex_price(currency_id, maybe_price)
+		}
+	}
+
+	/// Get the price from oracle, if the operators are configured, it's the median
+	/// of the prices fed by operators and requires at least quorum prices.
+	fn access_oracle_price(currency_id: CurrencyId) -> Option<Price> {
+		let (operators, quorum) = match Self::price_operators(currency_id) {
+			Some(config) => config,
+			None => return T::Source::get(&currency_id),
+		};
+
+		let mut values: Vec<Price> = operators
+			.iter()
+			.filter_map(|operator| T::OperatorSource::get_operator_value(operator, &currency_id))
+			.collect();
+		if values.is_empty() || (values.len() as u32) < quorum {
+			return None;
+		}
+
+		values.sort_unstable();
+		Some(values[values.len() / 2])
+	}
+
+	/// Get the price of currency with the metadata to assess the quality of feed.
+	pub fn price_info(currency_id: CurrencyId) -> PriceInfo {
+		let locked_price = Self::locked_price(currency_id);
+		let source_count = match Self::price_operators(currency_id) {
+			Some((operators, _)) => operators
+				.iter()
+				.filter(|operator| T::OperatorSource::get_operator_value(operator, &currency_id).is_some())
+				.count() as u32,
+			None => T::Source::get(&currency_id).is_some() as u32,
+		};
+
+		PriceInfo {
+			price: locked_price.or_else(|| Self::access_price(currency_id)),
+			timestamp: T::FeedTimestamp::get(&currency_id),
+			source_count,
+			is_locked: locked_price.is_some(),
+			is_stale: Self::is_stale(currency_id),
+			is_quarantined: Self::quarantined_price(currency_id).is_some(),
+		}
+	}
+
+	/// Get the oracle price if it's not stale.
+	fn access_fresh_oracle_price(currency_id: CurrencyId) -> Option<Price> {
+		if Self::is_stale(currency_id) {
 			None
+		} else {
+			Self::access_oracle_price(currency_id)
+		}
+	}
+
+	/// Return the last accepted price if the oracle price changes more than the
+	/// max price change.
+	fn check_price_change(currency_id: CurrencyId, oracle_price: Price) -> Price {
+		match (Self::max_price_change(currency_id), Self::accepted_price(currency_id)) {
+			(Some(max_price_change), Some(accepted_price))
+				if !is_within_change(accepted_price, oracle_price, max_price_change) =>
+			{
+				accepted_price
+			}
+			_ => oracle_price,
+		}
+	}
+
+	/// Check the oracle price of 1 basic unit with the DEX average price if the
+	/// max deviation is set, return the DEX price if oracle price is unavailable.
+	fn check_with_dex_price(currency_id: CurrencyId, maybe_oracle_price: Option<Price>) -> Option<Price> {
+		let max_deviation = match Self::dex_price_max_deviation(currency_id) {
+			Some(max_deviation) => max_deviation,
+			None => return maybe_oracle_price,
+		};
+
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let maybe_dex_price = T::DEXPriceSource::get_relative_price(currency_id, stable_currency_id)
+			.zip(Self::access_price(stable_currency_id))
+			.and_then(|(relative_price, stable_price)| relative_price.checked_mul(&stable_price));
+
+		match (maybe_oracle_price, maybe_dex_price) {
+			(Some(oracle_price), Some(dex_price)) => {
+				let deviation = oracle_price.max(dex_price).saturating_sub(oracle_price.min(dex_price));
+				if deviation <= dex_price.saturating_mul(max_deviation) {
+					Some(oracle_price)
+				} else {
+					None
+				}
+			}
+			(None, maybe_dex_price) => maybe_dex_price,
+			(maybe_oracle_price, None) => maybe_oracle_price,
+		}
+	}
+}
+
+impl<T: Config> PriceFeedStaleness<CurrencyId> for Pallet<T> {
+	/// The oracle price is stale if the latest feed is older than the max stale
+	/// period, or there's no feed at all.
+	fn is_stale(currency_id: CurrencyId) -> bool {
+		let max_stale_period = match Self::max_stale_period(currency_id) {
+			Some(max_stale_period) => max_stale_period,
+			None => return false,
+		};
+
+		match T::FeedTimestamp::get(&currency_id) {
+			Some(timestamp) => {
+				let now: Moment = T::UnixTime::now().as_millis().unique_saturated_into();
+				timestamp.saturating_add(max_stale_period) < now
+			}
+			None => true,
 		}
 	}
 }
@@ -242,6 +719,7 @@ impl<T: Config> LockablePrice<CurrencyId> for Pallet<T> {
 	fn lock_price(currency_id: CurrencyId) -> DispatchResult {
 		let price = Self::access_price(currency_id).ok_or(Error::<T>::AccessPriceFailed)?;
 		LockedPrice::<T>::insert(currency_id, price);
+		StalePriceLocked::<T>::remove(currency_id);
 		Pallet::<T>::deposit_event(Event::LockPrice {
 			currency_id,
 			locked_price: price,
@@ -252,6 +730,7 @@ impl<T: Config> LockablePrice<CurrencyId> for Pallet<T> {
 	/// Unlock the locked price
 	fn unlock_price(currency_id: CurrencyId) -> DispatchResult {
 		let _ = LockedPrice::<T>::take(currency_id).ok_or(Error::<T>::NoLockedPrice)?;
+		StalePriceLocked::<T>::remove(currency_id);
 		Pallet::<T>::deposit_event(Event::UnlockPrice { currency_id });
 		Ok(())
 	}
@@ -282,6 +761,12 @@ impl<T: Config> PriceProvider<CurrencyId> for LockedPriceProvider<T> {
 	}
 }
 
+/// Check whether the change from `reference` to `price` is within `max_change` of `reference`.
+fn is_within_change(reference: Price, price: Price, max_change: Ratio) -> bool {
+	let change = reference.max(price).saturating_sub(reference.min(price));
+	change <= reference.saturating_mul(max_change)
+}
+
 /// The fair price is determined by the external feed price and the size of the liquidity pool:
 /// https://blog.alphafinance.io/fair-lp-token-pricing/
 /// fair_price = (pool_0 * pool_1)^0.5 * (price_0 * price_1)^0.5 / total_shares * 2
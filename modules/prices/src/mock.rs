@@ -23,7 +23,7 @@
 use super::*;
 use frame_support::{
 	construct_runtime, ord_parameter_types, parameter_types,
-	traits::{ConstU64, Everything, Nothing},
+	traits::{ConstU32, ConstU64, Everything, Nothing},
 };
 use frame_system::EnsureSignedBy;
 use module_support::{mocks::MockErc20InfoMapping, ExchangeRate, SwapLimit};
@@ -158,6 +158,14 @@ impl DEXManager<AccountId, Balance, CurrencyId> for MockDEX {
 		unimplemented!()
 	}
 
+	fn get_best_route(
+		_: CurrencyId,
+		_: CurrencyId,
+		_: SwapLimit<Balance>,
+	) -> Option<(Vec<CurrencyId>, Balance, Balance)> {
+		unimplemented!()
+	}
+
 	fn swap_with_specific_path(
 		_: &AccountId,
 		_: &[CurrencyId],
@@ -251,6 +259,44 @@ parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::one();
 	pub static MockRelayBlockNumberProvider: BlockNumber = 0;
 	pub RewardRatePerRelaychainBlock: Rate = Rate::saturating_from_rational(1, 1000);
+	pub static MockDEXPrice: Option<ExchangeRate> = None;
+	pub static MockOperatorPrices: Vec<(AccountId, CurrencyId, Price)> = vec![];
+	pub static MockNow: Moment = 0;
+	pub static MockFeedTimestamps: Vec<(CurrencyId, Moment)> = vec![];
+}
+
+pub struct MockDEXPriceSource;
+impl DEXPriceProvider<CurrencyId> for MockDEXPriceSource {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<ExchangeRate> {
+		MockDEXPrice::get()
+	}
+}
+
+pub struct MockOperatorSource;
+impl OperatorDataProvider<AccountId, CurrencyId, Price> for MockOperatorSource {
+	fn get_operator_value(operator: &AccountId, key: &CurrencyId) -> Option<Price> {
+		MockOperatorPrices::get()
+			.into_iter()
+			.find(|(who, currency_id, _)| who == operator && currency_id == key)
+			.map(|(_, _, price)| price)
+	}
+}
+
+pub struct MockFeedTimestamp;
+impl GetByKey<CurrencyId, Option<Moment>> for MockFeedTimestamp {
+	fn get(key: &CurrencyId) -> Option<Moment> {
+		MockFeedTimestamps::get()
+			.into_iter()
+			.find(|(currency_id, _)| currency_id == key)
+			.map(|(_, timestamp)| timestamp)
+	}
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(MockNow::get())
+	}
 }
 
 impl Config for Runtime {
@@ -269,6 +315,11 @@ impl Config for Runtime {
 	type RelayChainBlockNumber = MockRelayBlockNumberProvider;
 	type RewardRatePerRelaychainBlock = RewardRatePerRelaychainBlock;
 	type PricingPegged = PricingPegged;
+	type DEXPriceSource = MockDEXPriceSource;
+	type OperatorSource = MockOperatorSource;
+	type MaxOperators = ConstU32<3>;
+	type FeedTimestamp = MockFeedTimestamp;
+	type UnixTime = MockUnixTime;
 	type WeightInfo = ();
 }
 
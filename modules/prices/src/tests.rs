@@ -23,6 +23,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{RuntimeEvent, *};
+use module_support::ExchangeRate;
 use sp_runtime::{
 	traits::{BadOrigin, Bounded},
 	FixedPointNumber,
@@ -557,3 +558,461 @@ fn price_providers_work() {
 		assert_eq!(LockedPriceProvider::<Runtime>::get_relative_price(TAI, KSM), None);
 	});
 }
+
+#[test]
+fn set_dex_price_max_deviation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PricesModule::set_dex_price_max_deviation(
+				RuntimeOrigin::signed(2),
+				DOT,
+				Some(Ratio::saturating_from_rational(1, 10))
+			),
+			BadOrigin
+		);
+		assert_ok!(PricesModule::set_dex_price_max_deviation(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(1, 10))
+		));
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::DEXPriceMaxDeviationUpdated {
+			currency_id: DOT,
+			max_deviation: Some(Ratio::saturating_from_rational(1, 10)),
+		}));
+		assert_eq!(
+			PricesModule::dex_price_max_deviation(DOT),
+			Some(Ratio::saturating_from_rational(1, 10))
+		);
+
+		assert_ok!(PricesModule::set_dex_price_max_deviation(
+			RuntimeOrigin::signed(1),
+			DOT,
+			None
+		));
+		assert_eq!(PricesModule::dex_price_max_deviation(DOT), None);
+	});
+}
+
+#[test]
+fn access_price_with_dex_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// 100 USD, right shift the decimal point (18-10) places
+		let dot_price = Price::saturating_from_integer(10000000000u128);
+
+		// not checked with DEX price if max deviation is not set
+		MockDEXPrice::set(Some(ExchangeRate::saturating_from_integer(20000)));
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+
+		assert_ok!(PricesModule::set_dex_price_max_deviation(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(1, 10))
+		));
+		assert_ok!(PricesModule::set_dex_price_max_deviation(
+			RuntimeOrigin::signed(1),
+			KSM,
+			Some(Ratio::saturating_from_rational(1, 10))
+		));
+
+		// reject the oracle price deviated too much from DEX price
+		assert_eq!(PricesModule::access_price(DOT), None);
+
+		// oracle price is within the max deviation
+		MockDEXPrice::set(Some(ExchangeRate::saturating_from_integer(10500)));
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+
+		// use oracle price if DEX price is unavailable
+		MockDEXPrice::set(None);
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+		assert_eq!(PricesModule::access_price(KSM), None);
+
+		// fallback to DEX price if oracle price is unavailable
+		MockDEXPrice::set(Some(ExchangeRate::saturating_from_integer(200)));
+		assert_eq!(
+			PricesModule::access_price(KSM),
+			Some(Price::saturating_from_integer(200000000u128))
+		);
+	});
+}
+
+#[test]
+fn set_price_operators_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PricesModule::set_price_operators(RuntimeOrigin::signed(2), DOT, vec![1, 2, 3].try_into().unwrap(), 2),
+			BadOrigin
+		);
+		assert_noop!(
+			PricesModule::set_price_operators(RuntimeOrigin::signed(1), DOT, vec![1, 2, 3].try_into().unwrap(), 0),
+			Error::<Runtime>::InvalidQuorum
+		);
+		assert_noop!(
+			PricesModule::set_price_operators(RuntimeOrigin::signed(1), DOT, vec![1, 2, 3].try_into().unwrap(), 4),
+			Error::<Runtime>::InvalidQuorum
+		);
+		assert_noop!(
+			PricesModule::set_price_operators(RuntimeOrigin::signed(1), DOT, vec![1, 2, 1].try_into().unwrap(), 2),
+			Error::<Runtime>::DuplicatedOperator
+		);
+
+		assert_ok!(PricesModule::set_price_operators(
+			RuntimeOrigin::signed(1),
+			DOT,
+			vec![1, 2, 3].try_into().unwrap(),
+			2
+		));
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::PriceOperatorsUpdated {
+			currency_id: DOT,
+			operators: vec![1, 2, 3],
+			quorum: 2,
+		}));
+		assert_eq!(
+			PricesModule::price_operators(DOT),
+			Some((vec![1, 2, 3].try_into().unwrap(), 2))
+		);
+
+		assert_ok!(PricesModule::set_price_operators(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Default::default(),
+			0
+		));
+		assert_eq!(PricesModule::price_operators(DOT), None);
+	});
+}
+
+#[test]
+fn access_price_with_price_operators_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// 100 USD, right shift the decimal point (18-10) places
+		let dot_price = Price::saturating_from_integer(10000000000u128);
+
+		assert_ok!(PricesModule::set_price_operators(
+			RuntimeOrigin::signed(1),
+			DOT,
+			vec![1, 2, 3].try_into().unwrap(),
+			2
+		));
+
+		// quorum is not reached
+		MockOperatorPrices::set(vec![(1, DOT, Price::saturating_from_integer(90))]);
+		assert_eq!(PricesModule::access_price(DOT), None);
+
+		// median of the prices fed by operators
+		MockOperatorPrices::set(vec![
+			(1, DOT, Price::saturating_from_integer(90)),
+			(2, DOT, Price::saturating_from_integer(120)),
+			(3, DOT, Price::saturating_from_integer(100)),
+			(4, DOT, Price::saturating_from_integer(1000)),
+		]);
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+
+		MockOperatorPrices::set(vec![
+			(1, DOT, Price::saturating_from_integer(90)),
+			(3, DOT, Price::saturating_from_integer(100)),
+		]);
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+
+		// use the price of Source after operators are removed
+		assert_ok!(PricesModule::set_price_operators(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Default::default(),
+			0
+		));
+		MockOperatorPrices::set(vec![]);
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+	});
+}
+
+#[test]
+fn set_max_stale_period_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PricesModule::set_max_stale_period(RuntimeOrigin::signed(2), DOT, Some(1000)),
+			BadOrigin
+		);
+		assert_noop!(
+			PricesModule::set_max_stale_period(RuntimeOrigin::signed(1), DOT, Some(0)),
+			Error::<Runtime>::InvalidStalePeriod
+		);
+
+		assert_ok!(PricesModule::set_max_stale_period(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(1000)
+		));
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::MaxStalePeriodUpdated {
+			currency_id: DOT,
+			max_stale_period: Some(1000),
+		}));
+		assert_eq!(PricesModule::max_stale_period(DOT), Some(1000));
+
+		assert_ok!(PricesModule::set_max_stale_period(RuntimeOrigin::signed(1), DOT, None));
+		assert_eq!(PricesModule::max_stale_period(DOT), None);
+	});
+}
+
+#[test]
+fn stale_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// 100 USD, right shift the decimal point (18-10) places
+		let dot_price = Price::saturating_from_integer(10000000000u128);
+		assert!(!PricesModule::is_stale(DOT));
+
+		assert_ok!(PricesModule::set_max_stale_period(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(1000)
+		));
+
+		// stale if there's no feed
+		assert!(PricesModule::is_stale(DOT));
+		assert_eq!(PricesModule::access_price(DOT), None);
+
+		MockFeedTimestamps::set(vec![(DOT, 0)]);
+		MockNow::set(1000);
+		assert!(!PricesModule::is_stale(DOT));
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+
+		// the feed is older than the max stale period
+		MockNow::set(1001);
+		assert!(PricesModule::is_stale(DOT));
+		assert_eq!(PricesModule::access_price(DOT), None);
+		assert_eq!(PricesModule::access_price(LDOT), None);
+		assert!(!PricesModule::is_stale(LDOT));
+	});
+}
+
+#[test]
+fn lock_and_unlock_stale_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// 100 USD, right shift the decimal point (18-10) places
+		let dot_price = Price::saturating_from_integer(10000000000u128);
+		assert_ok!(PricesModule::set_max_stale_period(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(1000)
+		));
+		MockFeedTimestamps::set(vec![(DOT, 0)]);
+		MockNow::set(1000);
+
+		PricesModule::on_initialize(1);
+		assert_eq!(PricesModule::locked_price(DOT), None);
+
+		// lock the last good price when the feed is stale
+		MockNow::set(1001);
+		PricesModule::on_initialize(2);
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::StalePriceLocked {
+			currency_id: DOT,
+			locked_price: dot_price,
+		}));
+		assert_eq!(PricesModule::locked_price(DOT), Some(dot_price));
+		assert!(PricesModule::stale_price_locked(DOT));
+		assert_eq!(RealTimePriceProvider::<Runtime>::get_price(DOT), None);
+		assert_eq!(PriorityLockedPriceProvider::<Runtime>::get_price(DOT), Some(dot_price));
+
+		// unlock when the feed is fresh again
+		MockFeedTimestamps::set(vec![(DOT, 1001)]);
+		PricesModule::on_initialize(3);
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::StalePriceUnlocked {
+			currency_id: DOT,
+		}));
+		assert_eq!(PricesModule::locked_price(DOT), None);
+		assert!(!PricesModule::stale_price_locked(DOT));
+
+		// do not override the price locked by LockOrigin
+		assert_ok!(PricesModule::lock_price(RuntimeOrigin::signed(1), DOT));
+		MockNow::set(2002);
+		PricesModule::on_initialize(4);
+		assert!(!PricesModule::stale_price_locked(DOT));
+		assert_eq!(PricesModule::locked_price(DOT), Some(dot_price));
+	});
+}
+
+#[test]
+fn set_max_price_change_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PricesModule::set_max_price_change(
+				RuntimeOrigin::signed(2),
+				DOT,
+				Some(Ratio::saturating_from_rational(15, 100))
+			),
+			BadOrigin
+		);
+		assert_ok!(PricesModule::set_max_price_change(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(15, 100))
+		));
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::MaxPriceChangeUpdated {
+			currency_id: DOT,
+			max_price_change: Some(Ratio::saturating_from_rational(15, 100)),
+		}));
+		assert_eq!(
+			PricesModule::max_price_change(DOT),
+			Some(Ratio::saturating_from_rational(15, 100))
+		);
+
+		PricesModule::on_initialize(1);
+		assert_eq!(
+			PricesModule::accepted_price(DOT),
+			Some(Price::saturating_from_integer(100))
+		);
+
+		// clear the accepted price when removing the limit
+		assert_ok!(PricesModule::set_max_price_change(RuntimeOrigin::signed(1), DOT, None));
+		assert_eq!(PricesModule::max_price_change(DOT), None);
+		assert_eq!(PricesModule::accepted_price(DOT), None);
+	});
+}
+
+#[test]
+fn quarantined_price_confirmed_by_next_feed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// 100 USD, right shift the decimal point (18-10) places
+		let dot_price = Price::saturating_from_integer(10000000000u128);
+		assert_ok!(PricesModule::set_max_price_change(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(15, 100))
+		));
+		PricesModule::on_initialize(1);
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+
+		// the price of DOT drops to 10 USD, use the last accepted price
+		mock_oracle_update();
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+
+		PricesModule::on_initialize(2);
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::PriceQuarantined {
+			currency_id: DOT,
+			price: Price::saturating_from_integer(10),
+		}));
+		assert_eq!(
+			PricesModule::quarantined_price(DOT),
+			Some(Price::saturating_from_integer(10))
+		);
+		assert_eq!(PricesModule::access_price(DOT), Some(dot_price));
+
+		// confirmed by the next feed
+		PricesModule::on_initialize(3);
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::QuarantinedPriceAccepted {
+			currency_id: DOT,
+			price: Price::saturating_from_integer(10),
+		}));
+		assert_eq!(PricesModule::quarantined_price(DOT), None);
+		assert_eq!(
+			PricesModule::access_price(DOT),
+			Some(Price::saturating_from_integer(1000000000u128))
+		);
+	});
+}
+
+#[test]
+fn accept_quarantined_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PricesModule::set_max_price_change(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(15, 100))
+		));
+		PricesModule::on_initialize(1);
+		assert_noop!(
+			PricesModule::accept_quarantined_price(RuntimeOrigin::signed(1), DOT),
+			Error::<Runtime>::NoQuarantinedPrice
+		);
+
+		mock_oracle_update();
+		PricesModule::on_initialize(2);
+		assert_noop!(
+			PricesModule::accept_quarantined_price(RuntimeOrigin::signed(2), DOT),
+			BadOrigin
+		);
+		assert_ok!(PricesModule::accept_quarantined_price(RuntimeOrigin::signed(1), DOT));
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::QuarantinedPriceAccepted {
+			currency_id: DOT,
+			price: Price::saturating_from_integer(10),
+		}));
+		assert_eq!(PricesModule::quarantined_price(DOT), None);
+		assert_eq!(
+			PricesModule::accepted_price(DOT),
+			Some(Price::saturating_from_integer(10))
+		);
+		assert_eq!(
+			PricesModule::access_price(DOT),
+			Some(Price::saturating_from_integer(1000000000u128))
+		);
+	});
+}
+
+#[test]
+fn price_info_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// 100 USD, right shift the decimal point (18-10) places
+		let dot_price = Price::saturating_from_integer(10000000000u128);
+		MockFeedTimestamps::set(vec![(DOT, 0)]);
+		assert_eq!(
+			PricesModule::price_info(DOT),
+			PriceInfo {
+				price: Some(dot_price),
+				timestamp: Some(0),
+				source_count: 1,
+				is_locked: false,
+				is_stale: false,
+				is_quarantined: false,
+			}
+		);
+
+		assert_ok!(PricesModule::set_price_operators(
+			RuntimeOrigin::signed(1),
+			DOT,
+			vec![1, 2, 3].try_into().unwrap(),
+			2
+		));
+		MockOperatorPrices::set(vec![
+			(1, DOT, Price::saturating_from_integer(100)),
+			(2, DOT, Price::saturating_from_integer(100)),
+		]);
+		assert_ok!(PricesModule::set_max_stale_period(
+			RuntimeOrigin::signed(1),
+			DOT,
+			Some(1000)
+		));
+		assert_ok!(PricesModule::lock_price(RuntimeOrigin::signed(1), DOT));
+		MockNow::set(1001);
+		assert_eq!(
+			PricesModule::price_info(DOT),
+			PriceInfo {
+				price: Some(dot_price),
+				timestamp: Some(0),
+				source_count: 2,
+				is_locked: true,
+				is_stale: true,
+				is_quarantined: false,
+			}
+		);
+
+		assert_ok!(PricesModule::unlock_price(RuntimeOrigin::signed(1), DOT));
+		assert_eq!(
+			PricesModule::price_info(DOT),
+			PriceInfo {
+				price: None,
+				timestamp: Some(0),
+				source_count: 2,
+				is_locked: false,
+				is_stale: true,
+				is_quarantined: false,
+			}
+		);
+	});
+}
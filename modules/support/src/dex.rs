@@ -55,6 +55,15 @@ pub trait DEXManager<AccountId, Balance, CurrencyId> {
 		alternative_path_joint_list: Vec<Vec<CurrencyId>>,
 	) -> Option<(Vec<CurrencyId>, Balance, Balance)>;
 
+	/// Return the best price route among the direct route and the two-hop
+	/// routes through the currencies which have enabled trading pair with
+	/// `supply_currency_id`.
+	fn get_best_route(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> Option<(Vec<CurrencyId>, Balance, Balance)>;
+
 	fn swap_with_specific_path(
 		who: &AccountId,
 		path: &[CurrencyId],
@@ -213,6 +222,14 @@ where
 		Some(Default::default())
 	}
 
+	fn get_best_route(
+		_supply_currency_id: CurrencyId,
+		_target_currency_id: CurrencyId,
+		_limit: SwapLimit<Balance>,
+	) -> Option<(Vec<CurrencyId>, Balance, Balance)> {
+		Some(Default::default())
+	}
+
 	fn swap_with_specific_path(
 		_who: &AccountId,
 		_path: &[CurrencyId],
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use parity_scale_codec::FullCodec;
-use primitives::Position;
+use primitives::{CurrencyId, Position};
 use sp_core::U256;
 use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::{
@@ -29,6 +29,9 @@ use sp_std::{
 use crate::{dex::*, ExchangeRate, Ratio};
 
 pub trait RiskManager<AccountId, CurrencyId, Balance, DebitBalance> {
+	/// get the stable currency issued against specific collateral type
+	fn get_stable_currency_id(currency_id: CurrencyId) -> CurrencyId;
+
 	fn get_debit_value(currency_id: CurrencyId, debit_balance: DebitBalance) -> Balance;
 
 	fn check_position_valid(
@@ -41,10 +44,24 @@ pub trait RiskManager<AccountId, CurrencyId, Balance, DebitBalance> {
 	fn check_debit_cap(currency_id: CurrencyId, total_debit_balance: DebitBalance) -> DispatchResult;
 }
 
+/// A risk manager that accepts all positions, and issues the stable currency
+/// of `GetStableCurrencyId` against all collateral types.
+#[cfg(feature = "std")]
+pub struct DefaultRiskManager<GetStableCurrencyId>(sp_std::marker::PhantomData<GetStableCurrencyId>);
+
 #[cfg(feature = "std")]
-impl<AccountId, CurrencyId, Balance: Default, DebitBalance> RiskManager<AccountId, CurrencyId, Balance, DebitBalance>
-	for ()
+impl<
+		AccountId,
+		CurrencyId,
+		Balance: Default,
+		DebitBalance,
+		GetStableCurrencyId: frame_support::traits::Get<CurrencyId>,
+	> RiskManager<AccountId, CurrencyId, Balance, DebitBalance> for DefaultRiskManager<GetStableCurrencyId>
 {
+	fn get_stable_currency_id(_currency_id: CurrencyId) -> CurrencyId {
+		GetStableCurrencyId::get()
+	}
+
 	fn get_debit_value(_currency_id: CurrencyId, _debit_balance: DebitBalance) -> Balance {
 		Default::default()
 	}
@@ -84,37 +101,64 @@ pub trait CDPTreasury<AccountId> {
 	type Balance;
 	type CurrencyId;
 
-	/// get surplus amount of cdp treasury
-	fn get_surplus_pool() -> Self::Balance;
+	/// whether the stable currency can be issued by cdp treasury
+	fn is_stable_currency(stable_currency_id: Self::CurrencyId) -> bool;
+
+	/// get the stable currency issued against specific collateral type, which
+	/// the collateral of cdp treasury is sold for
+	fn get_stable_currency_id(currency_id: Self::CurrencyId) -> Self::CurrencyId;
 
-	/// get debit amount of cdp treasury
-	fn get_debit_pool() -> Self::Balance;
+	/// get surplus amount of specific stable currency of cdp treasury
+	fn get_surplus_pool(stable_currency_id: Self::CurrencyId) -> Self::Balance;
 
-	/// get collateral assets amount of cdp treasury
+	/// get debit amount of specific stable currency of cdp treasury
+	fn get_debit_pool(stable_currency_id: Self::CurrencyId) -> Self::Balance;
+
+	/// get collateral assets amount of cdp treasury, including the collateral
+	/// deployed into DEX liquidity
 	fn get_total_collaterals(id: Self::CurrencyId) -> Self::Balance;
 
-	/// calculate the proportion of specific debit amount for the whole system
-	fn get_debit_proportion(amount: Self::Balance) -> Ratio;
+	/// calculate the proportion of specific debit amount of stable currency
+	/// for the whole system
+	fn get_debit_proportion(stable_currency_id: Self::CurrencyId, amount: Self::Balance) -> Ratio;
 
-	/// issue debit for cdp treasury
-	fn on_system_debit(amount: Self::Balance) -> DispatchResult;
+	/// issue debit of stable currency for cdp treasury
+	fn on_system_debit(stable_currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult;
 
 	/// issue surplus(stable currency) for cdp treasury
-	fn on_system_surplus(amount: Self::Balance) -> DispatchResult;
+	fn on_system_surplus(stable_currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult;
+
+	/// issue the accrued stability fee(stable currency), split between the
+	/// surplus pool and the rewards source of incentives
+	fn on_stability_fee(stable_currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult;
+
+	/// record the liquidation penalty of specific collateral type assessed on
+	/// liquidation, it's unrealized until the collateral is sold
+	fn on_liquidation_penalty(currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult;
 
-	/// issue debit to `who`
+	/// issue debit(stable currency) to `who`
 	/// if backed flag is true, means the debit to issue is backed on some
 	/// assets, otherwise will increase same amount of debit to system debit.
-	fn issue_debit(who: &AccountId, debit: Self::Balance, backed: bool) -> DispatchResult;
+	fn issue_debit(
+		stable_currency_id: Self::CurrencyId,
+		who: &AccountId,
+		debit: Self::Balance,
+		backed: bool,
+	) -> DispatchResult;
 
 	/// burn debit(stable currency) of `who`
-	fn burn_debit(who: &AccountId, debit: Self::Balance) -> DispatchResult;
+	fn burn_debit(stable_currency_id: Self::CurrencyId, who: &AccountId, debit: Self::Balance) -> DispatchResult;
 
 	/// deposit surplus(stable currency) to cdp treasury by `from`
-	fn deposit_surplus(from: &AccountId, surplus: Self::Balance) -> DispatchResult;
+	fn deposit_surplus(
+		stable_currency_id: Self::CurrencyId,
+		from: &AccountId,
+		surplus: Self::Balance,
+	) -> DispatchResult;
 
 	/// withdraw surplus(stable currency) from cdp treasury to `to`
-	fn withdraw_surplus(to: &AccountId, surplus: Self::Balance) -> DispatchResult;
+	fn withdraw_surplus(stable_currency_id: Self::CurrencyId, to: &AccountId, surplus: Self::Balance)
+		-> DispatchResult;
 
 	/// deposit collateral assets to cdp treasury by `who`
 	fn deposit_collateral(from: &AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult;
@@ -130,6 +174,9 @@ pub trait CDPTreasuryExtended<AccountId>: CDPTreasury<AccountId> {
 		collateral_in_auction: bool,
 	) -> sp_std::result::Result<(Self::Balance, Self::Balance), DispatchError>;
 
+	/// create collateral auctions for `amount` collateral of `currency_id`,
+	/// return the count of created lots, or error if collateral is not enough
+	/// or any lot cannot be created
 	fn create_collateral_auctions(
 		currency_id: Self::CurrencyId,
 		amount: Self::Balance,
@@ -144,10 +191,33 @@ pub trait CDPTreasuryExtended<AccountId>: CDPTreasury<AccountId> {
 	) -> sp_std::result::Result<(Self::Balance, Self::Balance), DispatchError>;
 
 	fn max_auction() -> u32;
+
+	/// set the expected size of per lot collateral auction of `currency_id`,
+	/// subject to the params update delay of the treasury
+	fn set_expected_collateral_auction_size(currency_id: Self::CurrencyId, size: Self::Balance) -> DispatchResult;
 }
 
 pub trait EmergencyShutdown {
 	fn is_shutdown() -> bool;
+
+	/// Whether the CDPs of `currency_id` are shutdown, either by the global
+	/// emergency shutdown or by the shutdown of this collateral only.
+	fn is_collateral_shutdown(_currency_id: CurrencyId) -> bool {
+		Self::is_shutdown()
+	}
+}
+
+/// The balances locked by vesting schedules, which can be pledged as CDP
+/// collateral in place.
+pub trait VestingBalance<AccountId, Balance> {
+	/// The balance of `who` that is still locked by vesting schedules.
+	fn vesting_balance(who: &AccountId) -> Balance;
+}
+
+impl<AccountId, Balance: Default> VestingBalance<AccountId, Balance> for () {
+	fn vesting_balance(_who: &AccountId) -> Balance {
+		Default::default()
+	}
 }
 
 /// Functionality of Honzon Protocol to be exposed to EVM+.
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::Rate;
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::CurrencyId;
 use scale_info::TypeInfo;
 use sp_runtime::{DispatchResult, RuntimeDebug};
@@ -34,6 +34,29 @@ pub enum PoolId {
 
 	/// Rewards and shares pool for earning module
 	Earning(CurrencyId),
+
+	/// Rewards and shares pool for users who hold debit in CDP(CollateralCurrencyId)
+	LoansDebit(CurrencyId),
+}
+
+/// The keeper actions rewarded by the keeper reward program
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum KeeperAction {
+	/// Liquidate an unsafe CDP
+	Liquidation,
+	/// Settle a collateral auction
+	AuctionSettlement,
+	/// Trigger the settlement of the CDP treasury surplus or debit
+	TreasurySettlement,
+}
+
+pub trait KeeperRewards<AccountId> {
+	/// Reward the keeper who successfully performed the action
+	fn reward_keeper(keeper: &AccountId, action: KeeperAction);
+}
+
+impl<AccountId> KeeperRewards<AccountId> for () {
+	fn reward_keeper(_: &AccountId, _: KeeperAction) {}
 }
 
 pub trait IncentivesManager<AccountId, Balance, CurrencyId, PoolId> {
@@ -104,6 +104,34 @@ pub trait DEXPriceProvider<CurrencyId> {
 	fn get_relative_price(base: CurrencyId, quote: CurrencyId) -> Option<ExchangeRate>;
 }
 
+impl<CurrencyId> DEXPriceProvider<CurrencyId> for () {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<ExchangeRate> {
+		None
+	}
+}
+
+/// Provide the value fed by specific oracle operator.
+pub trait OperatorDataProvider<AccountId, Key, Value> {
+	fn get_operator_value(operator: &AccountId, key: &Key) -> Option<Value>;
+}
+
+impl<AccountId, Key, Value> OperatorDataProvider<AccountId, Key, Value> for () {
+	fn get_operator_value(_operator: &AccountId, _key: &Key) -> Option<Value> {
+		None
+	}
+}
+
+/// Check whether the price feed of currency is stale.
+pub trait PriceFeedStaleness<CurrencyId> {
+	fn is_stale(currency_id: CurrencyId) -> bool;
+}
+
+impl<CurrencyId> PriceFeedStaleness<CurrencyId> for () {
+	fn is_stale(_currency_id: CurrencyId) -> bool {
+		false
+	}
+}
+
 pub trait LockablePrice<CurrencyId> {
 	fn lock_price(currency_id: CurrencyId) -> DispatchResult;
 	fn unlock_price(currency_id: CurrencyId) -> DispatchResult;
@@ -32,8 +32,8 @@ use frame_support::{
 	dispatch::{DispatchInfo, DispatchResult, GetDispatchInfo, Pays, PostDispatchInfo},
 	pallet_prelude::*,
 	traits::{
-		Currency, ExistenceRequirement, Imbalance, IsSubType, NamedReservableCurrency, OnUnbalanced, SameOrOther,
-		WithdrawReasons,
+		Contains, Currency, ExistenceRequirement, Imbalance, IsSubType, NamedReservableCurrency, OnUnbalanced,
+		SameOrOther, WithdrawReasons,
 	},
 	transactional,
 	weights::WeightToFee,
@@ -54,7 +54,7 @@ use sp_runtime::{
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	FixedPointNumber, FixedPointOperand, Percent, Perquintill,
+	FixedPointNumber, FixedPointOperand, Percent, Permill, Perquintill,
 };
 use sp_std::prelude::*;
 use xcm::v3::prelude::MultiLocation;
@@ -268,6 +268,7 @@ pub mod module {
 
 	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::TransactionPayment;
 	pub const DEPOSIT_ID: ReserveIdentifier = ReserveIdentifier::TransactionPaymentDeposit;
+	pub const FREE_TX_DEPOSIT_ID: ReserveIdentifier = ReserveIdentifier::TransactionPaymentFreeTx;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -387,6 +388,23 @@ pub mod module {
 
 		/// The origin which change swap balance threshold or enable charge fee pool.
 		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// CDP treasury account which receives the configured share of transaction fees and tips,
+		/// the share is swapped to stable currency and deposited into the surplus pool.
+		#[pallet::constant]
+		type CDPTreasuryAccount: Get<Self::AccountId>;
+
+		/// Stable currency id, the share of transaction fees and tips routed to CDP treasury is
+		/// swapped to it.
+		#[pallet::constant]
+		type StableCurrencyId: Get<CurrencyId>;
+
+		/// The calls whose fee can be waived by the free transaction quota.
+		type FreeTxCallFilter: Contains<CallOf<Self>>;
+
+		/// The period in blocks to refresh the free transaction quota of accounts.
+		#[pallet::constant]
+		type FreeTxQuotaPeriod: Get<BlockNumberFor<Self>>;
 	}
 
 	#[pallet::type_value]
@@ -408,6 +426,8 @@ pub mod module {
 		DexNotAvailable,
 		/// Charge fee pool is already exist
 		ChargeFeePoolAlreadyExisted,
+		/// The oracle price of the supply token is not available
+		InvalidPrice,
 	}
 
 	#[pallet::event]
@@ -445,6 +465,22 @@ pub mod module {
 			actual_tip: PalletBalanceOf<T>,
 			actual_surplus: PalletBalanceOf<T>,
 		},
+		/// The share of transaction fees and tips routed to CDP treasury is updated.
+		CDPTreasuryFeeRatiosUpdated { fee_ratio: Permill, tip_ratio: Permill },
+		/// Transaction fees and tips are routed to CDP treasury.
+		FeeRoutedToCDPTreasury { amount: PalletBalanceOf<T> },
+		/// The routed transaction fees and tips are swapped to stable currency and deposited into
+		/// the surplus pool of CDP treasury.
+		FeeDepositedToCDPTreasury {
+			native_amount: PalletBalanceOf<T>,
+			stable_amount: Balance,
+		},
+		/// The free transaction quota is updated.
+		FreeTxQuotaUpdated { quota: u32, min_balance: Balance },
+		/// The free transaction deposit is reserved.
+		FreeTxDepositReserved { who: T::AccountId, amount: Balance },
+		/// The free transaction deposit is unreserved.
+		FreeTxDepositUnreserved { who: T::AccountId, amount: Balance },
 	}
 
 	/// The next fee multiplier.
@@ -494,14 +530,62 @@ pub mod module {
 	#[pallet::getter(fn swap_balance_threshold)]
 	pub type SwapBalanceThreshold<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
 
+	/// The share of transaction fees routed to CDP treasury.
+	///
+	/// FeeToCDPTreasuryRatio: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn fee_to_cdp_treasury_ratio)]
+	pub type FeeToCDPTreasuryRatio<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The share of transaction tips routed to CDP treasury.
+	///
+	/// TipToCDPTreasuryRatio: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn tip_to_cdp_treasury_ratio)]
+	pub type TipToCDPTreasuryRatio<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The number of fee-less transactions per account in each `FreeTxQuotaPeriod`.
+	/// Zero means the free transaction quota is disabled.
+	///
+	/// FreeTxQuota: u32
+	#[pallet::storage]
+	#[pallet::getter(fn free_tx_quota)]
+	pub type FreeTxQuota<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The minimum free transaction deposit required for an account to use the free transaction
+	/// quota, which prevents sybil accounts from draining it. The deposit must be reserved for
+	/// at least one `FreeTxQuotaPeriod`, so the same funds can not be moved across accounts to
+	/// use the quota of each.
+	///
+	/// FreeTxMinBalance: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn free_tx_min_balance)]
+	pub type FreeTxMinBalance<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The used free transaction quota of accounts, recorded with the period index.
+	///
+	/// FreeTxUsage: map AccountId => Option<(BlockNumber, u32)>
+	#[pallet::storage]
+	#[pallet::getter(fn free_tx_usage)]
+	pub type FreeTxUsage<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (BlockNumberFor<T>, u32), OptionQuery>;
+
+	/// The block number at which the free transaction deposit of accounts is last reserved.
+	///
+	/// FreeTxDepositReservedAt: map AccountId => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn free_tx_deposit_reserved_at)]
+	pub type FreeTxDepositReservedAt<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		/// `on_initialize` to return the weight used in `on_finalize`.
+		/// `on_initialize` to deposit the routed fees into CDP treasury, and return the weight
+		/// used in `on_initialize` and `on_finalize`.
 		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
-			<T as Config>::WeightInfo::on_finalize()
+			Self::deposit_fee_to_cdp_treasury().saturating_add(<T as Config>::WeightInfo::on_finalize())
 		}
 
 		fn on_finalize(_: BlockNumberFor<T>) {
@@ -656,6 +740,60 @@ pub mod module {
 			ensure_signed(origin.clone())?;
 			call.dispatch(origin)
 		}
+
+		/// Update the share of transaction fees and tips routed to CDP treasury.
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_cdp_treasury_fee_ratios())]
+		pub fn set_cdp_treasury_fee_ratios(
+			origin: OriginFor<T>,
+			fee_ratio: Permill,
+			tip_ratio: Permill,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			FeeToCDPTreasuryRatio::<T>::put(fee_ratio);
+			TipToCDPTreasuryRatio::<T>::put(tip_ratio);
+			Self::deposit_event(Event::<T>::CDPTreasuryFeeRatiosUpdated { fee_ratio, tip_ratio });
+			Ok(())
+		}
+
+		/// Update the free transaction quota and the minimum balance required to use it.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_free_tx_quota())]
+		pub fn set_free_tx_quota(
+			origin: OriginFor<T>,
+			quota: u32,
+			#[pallet::compact] min_balance: Balance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			FreeTxQuota::<T>::put(quota);
+			FreeTxMinBalance::<T>::put(min_balance);
+			Self::deposit_event(Event::<T>::FreeTxQuotaUpdated { quota, min_balance });
+			Ok(())
+		}
+
+		/// Reserve `amount` of native asset as the free transaction deposit. The age of the
+		/// deposit is reset, the free transaction quota can be used after one
+		/// `FreeTxQuotaPeriod`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::reserve_free_tx_deposit())]
+		pub fn reserve_free_tx_deposit(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			T::Currency::reserve_named(&FREE_TX_DEPOSIT_ID, &who, amount)?;
+			FreeTxDepositReservedAt::<T>::insert(&who, frame_system::Pallet::<T>::block_number());
+			Self::deposit_event(Event::<T>::FreeTxDepositReserved { who, amount });
+			Ok(())
+		}
+
+		/// Unreserve all the free transaction deposit.
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::unreserve_free_tx_deposit())]
+		pub fn unreserve_free_tx_deposit(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let amount = T::Currency::unreserve_all_named(&FREE_TX_DEPOSIT_ID, &who);
+			FreeTxDepositReservedAt::<T>::remove(&who);
+			Self::deposit_event(Event::<T>::FreeTxDepositUnreserved { who, amount });
+			Ok(())
+		}
 	}
 }
 
@@ -863,10 +1001,12 @@ where
 				);
 				let fee = Self::check_native_is_not_enough(who, fee, reason).map_or_else(|| fee, |amount| amount);
 				let custom_fee_surplus = T::CustomFeeSurplus::get().mul_ceil(fee);
+				let target_amount = fee.saturating_add(custom_fee_surplus);
+				let max_supply_amount = Self::max_supply_limit(fee_swap_path.first(), target_amount)?;
 				T::Swap::swap_by_path(
 					who,
 					fee_swap_path,
-					SwapLimit::ExactTarget(Balance::MAX, fee.saturating_add(custom_fee_surplus)),
+					SwapLimit::ExactTarget(max_supply_amount, target_amount),
 				)
 				.map(|_| (who.clone(), custom_fee_surplus))
 			}
@@ -909,11 +1049,13 @@ where
 					Self::swap_from_pool_or_dex(who, fee_amount, *currency_id).map(|_| (who.clone(), fee_surplus))
 				} else {
 					// `supply_currency_id` not in charge fee pool, direct swap.
+					let target_amount = fee.saturating_add(custom_fee_surplus);
+					let max_supply_amount = Self::max_supply_limit(Some(currency_id), target_amount)?;
 					T::Swap::swap(
 						who,
 						*currency_id,
 						T::NativeCurrencyId::get(),
-						SwapLimit::ExactTarget(Balance::MAX, fee.saturating_add(custom_fee_surplus)),
+						SwapLimit::ExactTarget(max_supply_amount, target_amount),
 					)
 					.map(|_| (who.clone(), custom_fee_surplus))
 				}
@@ -951,8 +1093,11 @@ where
 
 			// alter native fee swap path, swap from dex: O(1)
 			if let Some(path) = AlternativeFeeSwapPath::<T>::get(who) {
-				if T::Swap::swap_by_path(who, &path, SwapLimit::ExactTarget(Balance::MAX, fee_amount)).is_ok() {
-					return Ok(fee_surplus);
+				if let Ok(max_supply_amount) = Self::max_supply_limit(path.first(), fee_amount) {
+					if T::Swap::swap_by_path(who, &path, SwapLimit::ExactTarget(max_supply_amount, fee_amount)).is_ok()
+					{
+						return Ok(fee_surplus);
+					}
 				}
 			}
 
@@ -980,6 +1125,139 @@ where
 		}
 	}
 
+	/// Use one free transaction of `who` for `call` in the current period if available.
+	/// Return true if the fee of `call` is waived.
+	///
+	/// `who` must have reserved at least `FreeTxMinBalance` as the free transaction deposit
+	/// for one `FreeTxQuotaPeriod`.
+	fn try_use_free_tx_quota(who: &T::AccountId, call: &CallOf<T>) -> bool {
+		let quota = Self::free_tx_quota();
+		if quota.is_zero()
+			|| !T::FreeTxCallFilter::contains(call)
+			|| T::Currency::reserved_balance_named(&FREE_TX_DEPOSIT_ID, who) < Self::free_tx_min_balance()
+		{
+			return false;
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let quota_period = T::FreeTxQuotaPeriod::get().max(One::one());
+		if Self::free_tx_deposit_reserved_at(who)
+			.map_or(true, |reserved_at| now < reserved_at.saturating_add(quota_period))
+		{
+			return false;
+		}
+
+		let period = now / quota_period;
+		FreeTxUsage::<T>::mutate(who, |usage| {
+			let used = match usage {
+				Some((usage_period, used)) if *usage_period == period => *used,
+				_ => 0,
+			};
+			if used >= quota {
+				return false;
+			}
+
+			*usage = Some((period, used.saturating_add(1)));
+			true
+		})
+	}
+
+	/// The account keeps the fees routed to CDP treasury until they are swapped to stable
+	/// currency.
+	pub fn cdp_treasury_fee_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"cdpt")
+	}
+
+	/// Route `ratio` of `imbalance` to CDP treasury, and return the remainder. The routed fee is
+	/// kept by `cdp_treasury_fee_account_id` and deposited into CDP treasury in `on_initialize`.
+	fn route_to_cdp_treasury(imbalance: NegativeImbalanceOf<T>, ratio: Permill) -> NegativeImbalanceOf<T> {
+		let amount = ratio.mul_floor(imbalance.peek());
+		if amount.is_zero() {
+			return imbalance;
+		}
+
+		// the amount below the existential deposit can't create the account, don't route it
+		let account = Self::cdp_treasury_fee_account_id();
+		if amount < T::Currency::minimum_balance() && T::Currency::total_balance(&account).is_zero() {
+			return imbalance;
+		}
+
+		let (routed, remainder) = imbalance.split(amount);
+		T::Currency::resolve_creating(&account, routed);
+		Self::deposit_event(Event::<T>::FeeRoutedToCDPTreasury { amount });
+		remainder
+	}
+
+	/// Swap the fees routed to CDP treasury to stable currency, and deposit it into the surplus
+	/// pool of CDP treasury. The fees remain to be deposited in next block if the swap fails.
+	/// Return the weight used.
+	fn deposit_fee_to_cdp_treasury() -> Weight {
+		let account = Self::cdp_treasury_fee_account_id();
+		let native_amount = T::Currency::free_balance(&account).saturating_sub(T::Currency::minimum_balance());
+		if native_amount.is_zero() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		match Self::swap_fee_to_stable(&account, native_amount) {
+			Ok(stable_amount) => Self::deposit_event(Event::<T>::FeeDepositedToCDPTreasury {
+				native_amount,
+				stable_amount,
+			}),
+			Err(e) => {
+				log::debug!(
+					target: "transaction-payment",
+					"deposit_fee_to_cdp_treasury: Attempt to swap fee {:?} failed: {:?}, remain it to next block",
+					native_amount, e
+				);
+			}
+		}
+		<T as Config>::WeightInfo::deposit_fee_to_cdp_treasury()
+	}
+
+	/// Swap `native_amount` native currency of `account` to stable currency by the oracle bounded
+	/// limit, and transfer it to CDP treasury.
+	#[transactional]
+	fn swap_fee_to_stable(account: &T::AccountId, native_amount: Balance) -> Result<Balance, DispatchError> {
+		let native_currency_id = T::NativeCurrencyId::get();
+		let stable_currency_id = T::StableCurrencyId::get();
+		let price = T::PriceSource::get_relative_price(native_currency_id, stable_currency_id)
+			.ok_or(Error::<T>::InvalidPrice)?;
+		let min_target_amount = Ratio::one()
+			.saturating_sub(T::MaxSwapSlippageCompareToOracle::get())
+			.saturating_mul_int(price.saturating_mul_int(native_amount));
+
+		let (_, stable_amount) = T::Swap::swap(
+			account,
+			native_currency_id,
+			stable_currency_id,
+			SwapLimit::ExactSupply(native_amount, min_target_amount),
+		)?;
+		T::MultiCurrency::transfer(
+			stable_currency_id,
+			account,
+			&T::CDPTreasuryAccount::get(),
+			stable_amount,
+		)?;
+		Ok(stable_amount)
+	}
+
+	/// The max amount of `supply_currency_id` allowed to swap for `target_amount` native asset,
+	/// which is limited by `MaxSwapSlippageCompareToOracle` against the oracle price. If the
+	/// oracle price is unavailable, the swap is not allowed.
+	fn max_supply_limit(
+		supply_currency_id: Option<&CurrencyId>,
+		target_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		let price = supply_currency_id
+			.and_then(|currency_id| T::PriceSource::get_relative_price(T::NativeCurrencyId::get(), *currency_id))
+			.ok_or(Error::<T>::InvalidPrice)?;
+		Ok(Ratio::one()
+			.saturating_sub(T::MaxSwapSlippageCompareToOracle::get())
+			.reciprocal()
+			.unwrap_or_else(<Ratio as sp_runtime::traits::Bounded>::max_value)
+			.saturating_mul_int(price.saturating_mul_int(target_amount)))
+	}
+
 	/// swap user's given asset with native asset. prior exchange from charge fee pool, if native
 	/// asset balance of charge fee pool is not enough, swap from dex.
 	#[transactional]
@@ -1220,6 +1498,11 @@ where
 			return Ok((fee, None, 0, who.clone()));
 		}
 
+		// waive the fee if `who` still has free transaction quota for `call`.
+		if tip.is_zero() && Pallet::<T>::try_use_free_tx_quota(who, call) {
+			return Ok((Zero::zero(), None, 0, who.clone()));
+		}
+
 		let reason = if tip.is_zero() {
 			WithdrawReasons::TRANSACTION_PAYMENT
 		} else {
@@ -1413,6 +1696,8 @@ where
 				Err(_) => payed,
 			};
 			let (tip, fee) = actual_payment.split(actual_tip);
+			let fee = Pallet::<T>::route_to_cdp_treasury(fee, Pallet::<T>::fee_to_cdp_treasury_ratio());
+			let tip = Pallet::<T>::route_to_cdp_treasury(tip, Pallet::<T>::tip_to_cdp_treasury_ratio());
 
 			// distribute fee
 			<T as Config>::OnTransactionPayment::on_unbalanceds(Some(fee).into_iter().chain(Some(tip)));
@@ -1490,6 +1775,8 @@ where
 		};
 
 		// distribute fee
+		let actual_payment =
+			Pallet::<T>::route_to_cdp_treasury(actual_payment, Pallet::<T>::fee_to_cdp_treasury_ratio());
 		<T as Config>::OnTransactionPayment::on_unbalanced(actual_payment);
 
 		Ok(())
@@ -1515,6 +1802,8 @@ where
 		.map_err(|_| InvalidTransaction::Payment)?;
 
 		// distribute fee
+		let actual_payment =
+			Pallet::<T>::route_to_cdp_treasury(actual_payment, Pallet::<T>::fee_to_cdp_treasury_ratio());
 		<T as Config>::OnTransactionPayment::on_unbalanced(actual_payment);
 		Ok(())
 	}
@@ -193,6 +193,8 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<Zero, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = KaruraTreasuryAccount;
+	type RuntimeCall = RuntimeCall;
 }
 
 impl module_aggregated_dex::Config for Runtime {
@@ -259,6 +261,8 @@ parameter_types! {
 	pub const TransactionPaymentPalletId: PalletId = PalletId(*b"aca/fees");
 	pub const TreasuryPalletId: PalletId = PalletId(*b"aca/trsy");
 	pub KaruraTreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+	pub CDPTreasuryAccount: AccountId = PalletId(*b"aca/cdpt").into_account_truncating();
+	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
 		vec![AUSD],
 	];
@@ -302,6 +306,10 @@ impl Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type CDPTreasuryAccount = CDPTreasuryAccount;
+	type StableCurrencyId = GetStableCurrencyId;
+	type FreeTxCallFilter = Everything;
+	type FreeTxQuotaPeriod = ConstU64<100>;
 }
 
 thread_local! {
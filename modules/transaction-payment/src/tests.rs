@@ -21,7 +21,9 @@
 #![cfg(test)]
 
 use super::*;
-use crate::mock::{AlternativeFeeSurplus, AusdFeeSwapPath, CustomFeeSurplus, DotFeeSwapPath, PalletBalances};
+use crate::mock::{
+	AlternativeFeeSurplus, AusdFeeSwapPath, CDPTreasuryAccount, CustomFeeSurplus, DotFeeSwapPath, PalletBalances,
+};
 use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::{DispatchClass, DispatchInfo, Pays},
@@ -38,7 +40,7 @@ use primitives::currency::*;
 use sp_io::TestExternalities;
 use sp_runtime::{
 	testing::TestXt,
-	traits::{One, UniqueSaturatedInto},
+	traits::{BadOrigin, One, UniqueSaturatedInto},
 };
 use xcm::v3::prelude::*;
 
@@ -288,6 +290,269 @@ fn charges_fee_when_locked_transfer_not_enough() {
 	});
 }
 
+#[test]
+fn set_cdp_treasury_fee_ratios_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			TransactionPayment::set_cdp_treasury_fee_ratios(
+				RuntimeOrigin::signed(BOB),
+				Permill::from_percent(20),
+				Permill::from_percent(40)
+			),
+			BadOrigin
+		);
+
+		assert_ok!(TransactionPayment::set_cdp_treasury_fee_ratios(
+			RuntimeOrigin::signed(ALICE),
+			Permill::from_percent(20),
+			Permill::from_percent(40)
+		));
+		System::assert_last_event(crate::mock::RuntimeEvent::TransactionPayment(
+			crate::Event::CDPTreasuryFeeRatiosUpdated {
+				fee_ratio: Permill::from_percent(20),
+				tip_ratio: Permill::from_percent(40),
+			},
+		));
+		assert_eq!(
+			TransactionPayment::fee_to_cdp_treasury_ratio(),
+			Permill::from_percent(20)
+		);
+		assert_eq!(
+			TransactionPayment::tip_to_cdp_treasury_ratio(),
+			Permill::from_percent(40)
+		);
+	});
+}
+
+#[test]
+fn post_dispatch_routes_fee_and_tip_to_cdp_treasury() {
+	builder_with_dex_and_fee_pool(false).execute_with(|| {
+		assert_ok!(TransactionPayment::set_cdp_treasury_fee_ratios(
+			RuntimeOrigin::signed(ALICE),
+			Permill::from_percent(20),
+			Permill::from_percent(40)
+		));
+
+		let fee = 23 * 2 + 1000;
+		let refund = 200;
+		let tip: Balance = 10;
+		let pre = ChargeTransactionPayment::<Runtime>::from(tip)
+			.pre_dispatch(&CHARLIE, &CALL, &INFO, 23)
+			.unwrap();
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			Some(pre),
+			&INFO,
+			&POST_INFO,
+			23,
+			&Ok(())
+		));
+
+		// fee = 846 * 20% = 169, tip = 10 * 40% = 4
+		assert_eq!(
+			Currencies::free_balance(ACA, &TransactionPayment::cdp_treasury_fee_account_id()),
+			169 + 4
+		);
+		assert_eq!(FEE_UNBALANCED_AMOUNT.with(|a| *a.borrow()), fee - refund - 169);
+		assert_eq!(TIP_UNBALANCED_AMOUNT.with(|a| *a.borrow()), tip - 4);
+		System::assert_has_event(crate::mock::RuntimeEvent::TransactionPayment(
+			crate::Event::FeeRoutedToCDPTreasury { amount: 169 },
+		));
+		System::assert_has_event(crate::mock::RuntimeEvent::TransactionPayment(
+			crate::Event::FeeRoutedToCDPTreasury { amount: 4 },
+		));
+	});
+}
+
+#[test]
+fn route_to_cdp_treasury_below_existential_deposit_is_not_routed() {
+	builder_with_dex_and_fee_pool(false).execute_with(|| {
+		assert_ok!(TransactionPayment::set_cdp_treasury_fee_ratios(
+			RuntimeOrigin::signed(ALICE),
+			Permill::zero(),
+			Permill::from_percent(40)
+		));
+
+		let tip: Balance = 10;
+		let pre = ChargeTransactionPayment::<Runtime>::from(tip)
+			.pre_dispatch(&CHARLIE, &CALL, &INFO, 23)
+			.unwrap();
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			Some(pre),
+			&INFO,
+			&POST_INFO,
+			23,
+			&Ok(())
+		));
+
+		// tip = 10 * 40% = 4, it's below the existential deposit
+		assert_eq!(
+			Currencies::free_balance(ACA, &TransactionPayment::cdp_treasury_fee_account_id()),
+			0
+		);
+		assert_eq!(TIP_UNBALANCED_AMOUNT.with(|a| *a.borrow()), tip);
+	});
+}
+
+#[test]
+fn on_initialize_deposits_routed_fee_to_cdp_treasury_surplus() {
+	builder_with_dex_and_fee_pool(true).execute_with(|| {
+		let fee_account = TransactionPayment::cdp_treasury_fee_account_id();
+		assert_ok!(Currencies::update_balance(
+			RuntimeOrigin::root(),
+			fee_account.clone(),
+			ACA,
+			2010,
+		));
+		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryAccount::get()), 0);
+
+		// the routed fee remains if there's no oracle price
+		MockPriceSource::set_relative_price(None);
+		TransactionPayment::on_initialize(2);
+		assert_eq!(Currencies::free_balance(ACA, &fee_account), 2010);
+
+		// swap the routed fee except the existential deposit to stable, and deposit it into the surplus pool
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 10)));
+		let (native_pool, stable_pool) = DEXModule::get_liquidity_pool(ACA, AUSD);
+		let stable_amount = stable_pool * 2000 / (native_pool + 2000);
+		TransactionPayment::on_initialize(3);
+		System::assert_last_event(crate::mock::RuntimeEvent::TransactionPayment(
+			crate::Event::FeeDepositedToCDPTreasury {
+				native_amount: 2000,
+				stable_amount,
+			},
+		));
+		assert_eq!(Currencies::free_balance(ACA, &fee_account), 10);
+		assert_eq!(Currencies::free_balance(AUSD, &fee_account), 0);
+		assert_eq!(
+			Currencies::free_balance(AUSD, &CDPTreasuryAccount::get()),
+			stable_amount
+		);
+
+		// nothing to deposit
+		TransactionPayment::on_initialize(4);
+		assert_eq!(
+			Currencies::free_balance(AUSD, &CDPTreasuryAccount::get()),
+			stable_amount
+		);
+	});
+}
+
+#[test]
+fn set_free_tx_quota_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			TransactionPayment::set_free_tx_quota(RuntimeOrigin::signed(BOB), 2, 1000),
+			BadOrigin
+		);
+
+		assert_ok!(TransactionPayment::set_free_tx_quota(
+			RuntimeOrigin::signed(ALICE),
+			2,
+			1000
+		));
+		System::assert_last_event(crate::mock::RuntimeEvent::TransactionPayment(
+			crate::Event::FreeTxQuotaUpdated {
+				quota: 2,
+				min_balance: 1000,
+			},
+		));
+		assert_eq!(TransactionPayment::free_tx_quota(), 2);
+		assert_eq!(TransactionPayment::free_tx_min_balance(), 1000);
+	});
+}
+
+#[test]
+fn free_tx_quota_waives_fee() {
+	builder_with_dex_and_fee_pool(false).execute_with(|| {
+		let fee = 23 * 2 + 1000;
+		assert_ok!(TransactionPayment::set_free_tx_quota(
+			RuntimeOrigin::signed(ALICE),
+			2,
+			1000
+		));
+
+		assert_ok!(TransactionPayment::reserve_free_tx_deposit(
+			RuntimeOrigin::signed(ALICE),
+			1000
+		));
+		System::assert_last_event(crate::mock::RuntimeEvent::TransactionPayment(
+			crate::Event::FreeTxDepositReserved {
+				who: ALICE,
+				amount: 1000,
+			},
+		));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 1000);
+		assert_eq!(TransactionPayment::free_tx_deposit_reserved_at(&ALICE), Some(0));
+
+		// the deposit is not reserved for a whole period
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).pre_dispatch(&ALICE, &CALL, &INFO, 23));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 1000 - fee);
+		assert_eq!(TransactionPayment::free_tx_usage(&ALICE), None);
+
+		// the first two txs in the period are fee-less
+		System::set_block_number(100);
+		for _ in 0..2 {
+			let pre = ChargeTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(&ALICE, &CALL, &INFO, 23)
+				.unwrap();
+			assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+				Some(pre),
+				&INFO,
+				&POST_INFO,
+				23,
+				&Ok(())
+			));
+		}
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 1000 - fee);
+		assert_eq!(TransactionPayment::free_tx_usage(&ALICE), Some((1, 2)));
+
+		// quota is used up
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).pre_dispatch(&ALICE, &CALL, &INFO, 23));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 1000 - fee * 2);
+
+		// tx with tip is not fee-less
+		System::set_block_number(200);
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(5).pre_dispatch(&ALICE, &CALL, &INFO, 23));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 1000 - fee * 3 - 5);
+
+		// quota is refreshed in the new period
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).pre_dispatch(&ALICE, &CALL, &INFO, 23));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 1000 - fee * 3 - 5);
+		assert_eq!(TransactionPayment::free_tx_usage(&ALICE), Some((2, 1)));
+
+		// a new deposit can not use the quota until it is reserved for a whole period
+		assert_eq!(Currencies::free_balance(ACA, &CHARLIE), 100000);
+		assert_ok!(TransactionPayment::reserve_free_tx_deposit(
+			RuntimeOrigin::signed(CHARLIE),
+			1000
+		));
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).pre_dispatch(&CHARLIE, &CALL, &INFO, 23));
+		assert_eq!(Currencies::free_balance(ACA, &CHARLIE), 100000 - 1000 - fee);
+		assert_eq!(TransactionPayment::free_tx_usage(&CHARLIE), None);
+
+		// the quota can not be used after the deposit is unreserved
+		assert_ok!(TransactionPayment::unreserve_free_tx_deposit(RuntimeOrigin::signed(
+			ALICE
+		)));
+		System::assert_last_event(crate::mock::RuntimeEvent::TransactionPayment(
+			crate::Event::FreeTxDepositUnreserved {
+				who: ALICE,
+				amount: 1000,
+			},
+		));
+		assert_eq!(TransactionPayment::free_tx_deposit_reserved_at(&ALICE), None);
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).pre_dispatch(&ALICE, &CALL, &INFO, 23));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee * 4 - 5);
+
+		// account without deposit can not use the quota
+		assert_noop!(
+			ChargeTransactionPayment::<Runtime>::from(0).validate(&BOB, &CALL, &INFO, 23),
+			TransactionValidityError::Invalid(InvalidTransaction::Payment)
+		);
+		assert_eq!(TransactionPayment::free_tx_usage(&BOB), None);
+	});
+}
+
 #[test]
 fn pre_post_dispatch_and_refund_native_is_enough() {
 	builder_with_dex_and_fee_pool(false).execute_with(|| {
@@ -797,6 +1062,46 @@ fn charges_fee_when_validate_with_fee_call_use_swap(with_fee_call: <Runtime as C
 	});
 }
 
+#[test]
+fn charges_fee_with_fee_call_failed_by_oracle_slippage_limit() {
+	builder_with_dex_and_fee_pool(true).execute_with(|| {
+		assert_ok!(Currencies::update_balance(RuntimeOrigin::root(), BOB, LDOT, 1000));
+		assert_eq!(
+			DEXModule::get_swap_amount(&vec![LDOT, ACA], SwapLimit::ExactTarget(Balance::MAX, 315)),
+			Some((46, 315))
+		);
+
+		// max supply = 315 * 0.01 / (1 - 0.5) = 6 LDOT, less than 46 LDOT required by dex.
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 100)));
+		for with_fee_call in [with_fee_currency_call(LDOT), with_fee_path_call(vec![LDOT, ACA])] {
+			assert_noop!(
+				ChargeTransactionPayment::<Runtime>::from(0).validate(&BOB, &with_fee_call, &INFO2, 50),
+				TransactionValidityError::Invalid(InvalidTransaction::Payment)
+			);
+		}
+
+		// no oracle price, the swap is not allowed.
+		MockPriceSource::set_relative_price(None);
+		for with_fee_call in [with_fee_currency_call(LDOT), with_fee_path_call(vec![LDOT, ACA])] {
+			assert_noop!(
+				ChargeTransactionPayment::<Runtime>::from(0).validate(&BOB, &with_fee_call, &INFO2, 50),
+				TransactionValidityError::Invalid(InvalidTransaction::Payment)
+			);
+		}
+
+		// the swap is within the slippage limit of oracle price.
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 10)));
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).validate(
+			&BOB,
+			&with_fee_path_call(vec![LDOT, ACA]),
+			&INFO2,
+			50
+		));
+		assert_eq!(1000 - 46, Currencies::free_balance(LDOT, &BOB));
+		assert_eq!(10, Currencies::free_balance(ACA, &BOB));
+	});
+}
+
 #[test]
 fn charges_fee_when_validate_with_fee_currency_call_use_pool() {
 	// Enable dex with Alice, and initialize tx charge fee pool
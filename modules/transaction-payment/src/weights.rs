@@ -53,6 +53,11 @@ pub trait WeightInfo {
 	fn with_fee_path() -> Weight;
 	fn with_fee_aggregated_path() -> Weight;
 	fn with_fee_currency() -> Weight;
+	fn set_cdp_treasury_fee_ratios() -> Weight;
+	fn set_free_tx_quota() -> Weight;
+	fn reserve_free_tx_deposit() -> Weight;
+	fn unreserve_free_tx_deposit() -> Weight;
+	fn deposit_fee_to_cdp_treasury() -> Weight;
 }
 
 /// Weights for module_transaction_payment using the Acala node and recommended hardware.
@@ -108,6 +113,44 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: TransactionPayment FeeToCDPTreasuryRatio (r:0 w:1)
+	// Storage: TransactionPayment TipToCDPTreasuryRatio (r:0 w:1)
+	fn set_cdp_treasury_fee_ratios() -> Weight {
+		Weight::from_parts(9_112_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: TransactionPayment FreeTxQuota (r:0 w:1)
+	// Storage: TransactionPayment FreeTxMinBalance (r:0 w:1)
+	fn set_free_tx_quota() -> Weight {
+		Weight::from_parts(9_034_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TransactionPayment FreeTxDepositReservedAt (r:0 w:1)
+	fn reserve_free_tx_deposit() -> Weight {
+		Weight::from_parts(27_418_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TransactionPayment FreeTxDepositReservedAt (r:0 w:1)
+	fn unreserve_free_tx_deposit() -> Weight {
+		Weight::from_parts(26_872_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: AcalaOracle Values (r:2 w:0)
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:3)
+	fn deposit_fee_to_cdp_treasury() -> Weight {
+		Weight::from_parts(98_514_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -142,4 +185,27 @@ impl WeightInfo for () {
 		Weight::from_parts(193_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 	}
+	fn set_cdp_treasury_fee_ratios() -> Weight {
+		Weight::from_parts(9_112_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_free_tx_quota() -> Weight {
+		Weight::from_parts(9_034_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn reserve_free_tx_deposit() -> Weight {
+		Weight::from_parts(27_418_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn unreserve_free_tx_deposit() -> Weight {
+		Weight::from_parts(26_872_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn deposit_fee_to_cdp_treasury() -> Weight {
+		Weight::from_parts(98_514_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
 }
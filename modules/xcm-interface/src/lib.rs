@@ -23,6 +23,13 @@
 //! send the messages out to the intended destination.
 //!
 //! This module hides away XCM layer from native modules via the use of traits.
+//!
+//! The outbound Homa messages (bond extra, unbond and withdraw unbonded) are tracked until they
+//! are confirmed by `UpdateOrigin`. The messages which failed to send are retried in following
+//! blocks up to `MaxSendAttempts`, and the messages which are not confirmed within
+//! `MessageTimeout` are dropped. Both failures are surfaced as events, so that Homa ledgers can be
+//! reconciled with the relaychain. At most `MaxMessagesPerBlock` pending messages are processed
+//! per block, the processing continues from where it stopped in the following blocks.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -33,14 +40,19 @@ use module_support::{relaychain::CallBuilder, CrowdloanVaultXcm, HomaSubAccountX
 use orml_traits::XcmTransfer;
 use primitives::{Balance, CurrencyId, EraIndex};
 use scale_info::TypeInfo;
-use sp_runtime::traits::Convert;
+use sp_runtime::{
+	traits::{Convert, Saturating},
+	ArithmeticError,
+};
 use sp_std::{convert::From, prelude::*, vec, vec::Vec};
 use xcm::{prelude::*, v3::Weight as XcmWeight};
 
 mod mocks;
 mod tests;
+pub mod weights;
 
 pub use module::*;
+pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod module {
@@ -60,6 +72,22 @@ pub mod module {
 		ProxyReserveTransferAssets,
 	}
 
+	/// The outbound Homa XCM message which is waiting for confirmation.
+	#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+	pub struct PendingXcmMessage<BlockNumber> {
+		/// The Homa operation of the message.
+		pub operation: XcmInterfaceOperation,
+		/// The index of sub account on relaychain.
+		pub sub_account_index: u16,
+		/// The staking currency amount of the operation.
+		pub amount: Balance,
+		/// The block number the message was sent at, `None` means the message failed to send
+		/// and waits to retry.
+		pub sent_at: Option<BlockNumber>,
+		/// The count of attempts to send the message.
+		pub attempts: u32,
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_xcm::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -95,12 +123,32 @@ pub mod module {
 
 		/// Convert AccountId to MultiLocation to build XCM message.
 		type AccountIdToMultiLocation: Convert<Self::AccountId, MultiLocation>;
+
+		/// The max attempts to send a Homa XCM message.
+		#[pallet::constant]
+		type MaxSendAttempts: Get<u32>;
+
+		/// The count of blocks the sent Homa XCM message is dropped after if not confirmed.
+		#[pallet::constant]
+		type MessageTimeout: Get<BlockNumberFor<Self>>;
+
+		/// The max count of pending Homa XCM messages processed in one block, and confirmed by
+		/// one call.
+		#[pallet::constant]
+		type MaxMessagesPerBlock: Get<u32>;
+
+		/// Weight information for the extrinsics and hooks in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// The xcm operation have failed
 		XcmFailed,
+		/// The pending xcm message does not exist
+		MessageNotFound,
+		/// Too many xcm messages to confirm
+		TooManyMessages,
 	}
 
 	#[pallet::event]
@@ -116,6 +164,31 @@ pub mod module {
 			xcm_operation: XcmInterfaceOperation,
 			new_xcm_dest_weight: Balance,
 		},
+		/// Homa xcm message has been sent to relaychain.
+		XcmMessageSent {
+			message_id: u64,
+			xcm_operation: XcmInterfaceOperation,
+			sub_account_index: u16,
+			amount: Balance,
+		},
+		/// Homa xcm message failed to send, it will be retried.
+		XcmMessageSendFailed { message_id: u64, attempts: u32 },
+		/// Homa xcm message has been confirmed.
+		XcmMessageConfirmed { message_id: u64 },
+		/// Homa xcm message has been dropped after failed to send MaxSendAttempts times.
+		XcmMessageDropped {
+			message_id: u64,
+			xcm_operation: XcmInterfaceOperation,
+			sub_account_index: u16,
+			amount: Balance,
+		},
+		/// Homa xcm message has been dropped as not confirmed within MessageTimeout.
+		XcmMessageTimedOut {
+			message_id: u64,
+			xcm_operation: XcmInterfaceOperation,
+			sub_account_index: u16,
+			amount: Balance,
+		},
 	}
 
 	/// The dest weight limit and fee for execution XCM msg sended by XcmInterface. Must be
@@ -127,12 +200,102 @@ pub mod module {
 	pub type XcmDestWeightAndFee<T: Config> =
 		StorageMap<_, Twox64Concat, XcmInterfaceOperation, (XcmWeight, Balance), ValueQuery>;
 
+	/// The id of next Homa xcm message.
+	///
+	/// NextMessageId: u64
+	#[pallet::storage]
+	#[pallet::getter(fn next_message_id)]
+	pub type NextMessageId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The outbound Homa xcm messages waiting for confirmation or retry.
+	///
+	/// PendingXcmMessages: map: MessageId => Option<PendingXcmMessage>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_xcm_messages)]
+	pub type PendingXcmMessages<T: Config> =
+		StorageMap<_, Twox64Concat, u64, PendingXcmMessage<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The id of the last pending xcm message processed by `on_initialize`, the processing of
+	/// next block continues after it. `None` means to start from the first pending message.
+	///
+	/// ProcessingCursor: Option<MessageId>
+	#[pallet::storage]
+	#[pallet::getter(fn processing_cursor)]
+	pub type ProcessingCursor<T: Config> = StorageValue<_, u64, OptionQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let max_messages = T::MaxMessagesPerBlock::get() as usize;
+			let messages: Vec<_> = match Self::processing_cursor() {
+				Some(cursor) => PendingXcmMessages::<T>::iter_from(PendingXcmMessages::<T>::hashed_key_for(cursor))
+					.take(max_messages)
+					.collect(),
+				None => PendingXcmMessages::<T>::iter().take(max_messages).collect(),
+			};
+			let count = messages.len() as u32;
+
+			// continue after the last processed message in next block if the limit is reached,
+			// otherwise start over from the first pending message.
+			let cursor = if messages.len() == max_messages {
+				messages.last().map(|(message_id, _)| *message_id)
+			} else {
+				None
+			};
+			ProcessingCursor::<T>::set(cursor);
+
+			for (message_id, mut message) in messages {
+				match message.sent_at {
+					Some(sent_at) => {
+						if now.saturating_sub(sent_at) >= T::MessageTimeout::get() {
+							PendingXcmMessages::<T>::remove(message_id);
+							Self::deposit_event(Event::<T>::XcmMessageTimedOut {
+								message_id,
+								xcm_operation: message.operation,
+								sub_account_index: message.sub_account_index,
+								amount: message.amount,
+							});
+						}
+					}
+					None => {
+						message.attempts = message.attempts.saturating_add(1);
+						if Self::send_homa_message(&message.operation, message.sub_account_index, message.amount)
+							.is_ok()
+						{
+							message.sent_at = Some(now);
+							Self::deposit_event(Event::<T>::XcmMessageSent {
+								message_id,
+								xcm_operation: message.operation.clone(),
+								sub_account_index: message.sub_account_index,
+								amount: message.amount,
+							});
+							PendingXcmMessages::<T>::insert(message_id, message);
+						} else if message.attempts >= T::MaxSendAttempts::get() {
+							PendingXcmMessages::<T>::remove(message_id);
+							Self::deposit_event(Event::<T>::XcmMessageDropped {
+								message_id,
+								xcm_operation: message.operation,
+								sub_account_index: message.sub_account_index,
+								amount: message.amount,
+							});
+						} else {
+							Self::deposit_event(Event::<T>::XcmMessageSendFailed {
+								message_id,
+								attempts: message.attempts,
+							});
+							PendingXcmMessages::<T>::insert(message_id, message);
+						}
+					}
+				}
+			}
+
+			T::WeightInfo::on_initialize(count)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -169,9 +332,150 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Confirm the Homa xcm messages have been executed on relaychain.
+		///
+		/// Parameters:
+		/// - `message_ids`: the ids of pending xcm messages.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::confirm_xcm_messages(message_ids.len() as u32))]
+		pub fn confirm_xcm_messages(origin: OriginFor<T>, message_ids: Vec<u64>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				message_ids.len() <= T::MaxMessagesPerBlock::get() as usize,
+				Error::<T>::TooManyMessages
+			);
+
+			for message_id in message_ids {
+				ensure!(
+					PendingXcmMessages::<T>::take(message_id).is_some(),
+					Error::<T>::MessageNotFound
+				);
+				Self::deposit_event(Event::<T>::XcmMessageConfirmed { message_id });
+			}
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Build the XCM message of the Homa operation for sub account, `None` for the operations
+		/// which are not sent to relaychain by the sub account.
+		pub fn build_homa_message(
+			operation: &XcmInterfaceOperation,
+			sub_account_index: u16,
+			amount: Balance,
+		) -> Option<Xcm<()>> {
+			let (xcm_dest_weight, xcm_fee) = Self::xcm_dest_weight_and_fee(operation);
+			match operation {
+				XcmInterfaceOperation::HomaWithdrawUnbonded => {
+					// TODO: config xcm_dest_weight and fee for withdraw_unbonded and transfer seperately.
+					// Temperarily use double fee.
+					Some(T::RelayChainCallBuilder::finalize_multiple_calls_into_xcm_message(
+						vec![
+							(
+								T::RelayChainCallBuilder::utility_as_derivative_call(
+									T::RelayChainCallBuilder::staking_withdraw_unbonded(
+										T::RelayChainUnbondingSlashingSpans::get(),
+									),
+									sub_account_index,
+								),
+								xcm_dest_weight,
+							),
+							(
+								T::RelayChainCallBuilder::utility_as_derivative_call(
+									T::RelayChainCallBuilder::balances_transfer_keep_alive(
+										T::ParachainAccount::get(),
+										amount,
+									),
+									sub_account_index,
+								),
+								xcm_dest_weight,
+							),
+						],
+						xcm_fee.saturating_mul(2),
+					))
+				}
+				XcmInterfaceOperation::HomaBondExtra => Some(T::RelayChainCallBuilder::finalize_call_into_xcm_message(
+					T::RelayChainCallBuilder::utility_as_derivative_call(
+						T::RelayChainCallBuilder::staking_bond_extra(amount),
+						sub_account_index,
+					),
+					xcm_fee,
+					xcm_dest_weight,
+				)),
+				XcmInterfaceOperation::HomaUnbond => Some(T::RelayChainCallBuilder::finalize_call_into_xcm_message(
+					T::RelayChainCallBuilder::utility_as_derivative_call(
+						T::RelayChainCallBuilder::staking_unbond(amount),
+						sub_account_index,
+					),
+					xcm_fee,
+					xcm_dest_weight,
+				)),
+				_ => None,
+			}
+		}
+
+		fn send_homa_message(
+			operation: &XcmInterfaceOperation,
+			sub_account_index: u16,
+			amount: Balance,
+		) -> DispatchResult {
+			let xcm_message =
+				Self::build_homa_message(operation, sub_account_index, amount).ok_or(Error::<T>::XcmFailed)?;
+			let result = pallet_xcm::Pallet::<T>::send_xcm(Here, Parent, xcm_message);
+			log::debug!(
+				target: "xcm-interface",
+				"subaccount {:?} send XCM {:?} {:?}, result: {:?}",
+				sub_account_index, operation, amount, result
+			);
+
+			ensure!(result.is_ok(), Error::<T>::XcmFailed);
+			Ok(())
+		}
+
+		/// Send the Homa xcm message and track it until confirmed. The message which failed to
+		/// send is queued to retry.
+		fn send_and_track_homa_message(
+			operation: XcmInterfaceOperation,
+			sub_account_index: u16,
+			amount: Balance,
+		) -> DispatchResult {
+			let message_id = NextMessageId::<T>::try_mutate(|id| -> Result<u64, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+				Ok(current_id)
+			})?;
+
+			let sent_at = if Self::send_homa_message(&operation, sub_account_index, amount).is_ok() {
+				Self::deposit_event(Event::<T>::XcmMessageSent {
+					message_id,
+					xcm_operation: operation.clone(),
+					sub_account_index,
+					amount,
+				});
+				Some(<frame_system::Pallet<T>>::block_number())
+			} else {
+				Self::deposit_event(Event::<T>::XcmMessageSendFailed {
+					message_id,
+					attempts: 1,
+				});
+				None
+			};
+
+			PendingXcmMessages::<T>::insert(
+				message_id,
+				PendingXcmMessage {
+					operation,
+					sub_account_index,
+					amount,
+					sent_at,
+					attempts: 1,
+				},
+			);
+			Ok(())
+		}
+
 		pub fn build_transfer_to_liquid_crowdloan_module_account(
 			vault: T::AccountId,
 			recipient: T::AccountId,
@@ -222,87 +526,21 @@ pub mod module {
 		}
 
 		/// Send XCM message to the relaychain for sub account to withdraw_unbonded staking currency
-		/// and send it back.
+		/// and send it back. The message is tracked until confirmed.
 		fn withdraw_unbonded_from_sub_account(sub_account_index: u16, amount: Balance) -> DispatchResult {
-			let (xcm_dest_weight, xcm_fee) = Self::xcm_dest_weight_and_fee(XcmInterfaceOperation::HomaWithdrawUnbonded);
-
-			// TODO: config xcm_dest_weight and fee for withdraw_unbonded and transfer seperately.
-			// Temperarily use double fee.
-			let xcm_message = T::RelayChainCallBuilder::finalize_multiple_calls_into_xcm_message(
-				vec![
-					(
-						T::RelayChainCallBuilder::utility_as_derivative_call(
-							T::RelayChainCallBuilder::staking_withdraw_unbonded(
-								T::RelayChainUnbondingSlashingSpans::get(),
-							),
-							sub_account_index,
-						),
-						xcm_dest_weight,
-					),
-					(
-						T::RelayChainCallBuilder::utility_as_derivative_call(
-							T::RelayChainCallBuilder::balances_transfer_keep_alive(T::ParachainAccount::get(), amount),
-							sub_account_index,
-						),
-						xcm_dest_weight,
-					),
-				],
-				xcm_fee.saturating_mul(2),
-			);
-
-			let result = pallet_xcm::Pallet::<T>::send_xcm(Here, Parent, xcm_message);
-			log::debug!(
-				target: "xcm-interface",
-				"subaccount {:?} send XCM to withdraw unbonded {:?}, result: {:?}",
-				sub_account_index, amount, result
-			);
-
-			ensure!(result.is_ok(), Error::<T>::XcmFailed);
-			Ok(())
+			Self::send_and_track_homa_message(XcmInterfaceOperation::HomaWithdrawUnbonded, sub_account_index, amount)
 		}
 
-		/// Send XCM message to the relaychain for sub account to bond extra.
+		/// Send XCM message to the relaychain for sub account to bond extra. The message is
+		/// tracked until confirmed.
 		fn bond_extra_on_sub_account(sub_account_index: u16, amount: Balance) -> DispatchResult {
-			let (xcm_dest_weight, xcm_fee) = Self::xcm_dest_weight_and_fee(XcmInterfaceOperation::HomaBondExtra);
-			let xcm_message = T::RelayChainCallBuilder::finalize_call_into_xcm_message(
-				T::RelayChainCallBuilder::utility_as_derivative_call(
-					T::RelayChainCallBuilder::staking_bond_extra(amount),
-					sub_account_index,
-				),
-				xcm_fee,
-				xcm_dest_weight,
-			);
-			let result = pallet_xcm::Pallet::<T>::send_xcm(Here, Parent, xcm_message);
-			log::debug!(
-				target: "xcm-interface",
-				"subaccount {:?} send XCM to bond {:?}, result: {:?}",
-				sub_account_index, amount, result,
-			);
-
-			ensure!(result.is_ok(), Error::<T>::XcmFailed);
-			Ok(())
+			Self::send_and_track_homa_message(XcmInterfaceOperation::HomaBondExtra, sub_account_index, amount)
 		}
 
-		/// Send XCM message to the relaychain for sub account to unbond.
+		/// Send XCM message to the relaychain for sub account to unbond. The message is tracked
+		/// until confirmed.
 		fn unbond_on_sub_account(sub_account_index: u16, amount: Balance) -> DispatchResult {
-			let (xcm_dest_weight, xcm_fee) = Self::xcm_dest_weight_and_fee(XcmInterfaceOperation::HomaUnbond);
-			let xcm_message = T::RelayChainCallBuilder::finalize_call_into_xcm_message(
-				T::RelayChainCallBuilder::utility_as_derivative_call(
-					T::RelayChainCallBuilder::staking_unbond(amount),
-					sub_account_index,
-				),
-				xcm_fee,
-				xcm_dest_weight,
-			);
-			let result = pallet_xcm::Pallet::<T>::send_xcm(Here, Parent, xcm_message);
-			log::debug!(
-				target: "xcm-interface",
-				"subaccount {:?} send XCM to unbond {:?}, result: {:?}",
-				sub_account_index, amount, result
-			);
-
-			ensure!(result.is_ok(), Error::<T>::XcmFailed);
-			Ok(())
+			Self::send_and_track_homa_message(XcmInterfaceOperation::HomaUnbond, sub_account_index, amount)
 		}
 
 		/// The fee of cross-chain transfer is deducted from the recipient.
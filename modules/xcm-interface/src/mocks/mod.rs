@@ -240,6 +240,10 @@ macro_rules! impl_mock {
 			type XcmTransfer = MockXcmTransfer;
 			type SelfLocation = SelfLocation;
 			type AccountIdToMultiLocation = AccountIdToMultiLocation;
+			type MaxSendAttempts = ConstU32<3>;
+			type MessageTimeout = ConstU64<10>;
+			type MaxMessagesPerBlock = ConstU32<2>;
+			type WeightInfo = ();
 		}
 
 		construct_runtime!(
@@ -20,8 +20,11 @@
 
 #![cfg(test)]
 
+use super::*;
 use crate::mocks::{kusama, polkadot, ExtBuilder, ALICE, BOB};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use insta::assert_debug_snapshot;
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn build_transfer_to_liquid_crowdloan_module_account_polkadot() {
@@ -40,3 +43,141 @@ fn build_transfer_to_liquid_crowdloan_module_account_kusama() {
 		assert_debug_snapshot!(xcm);
 	});
 }
+
+#[test]
+fn failed_homa_message_is_retried_and_dropped() {
+	ExtBuilder::default().build::<polkadot::Runtime>().execute_with(|| {
+		// the mock XcmRouter is unable to send message
+		assert_ok!(polkadot::XcmInterface::bond_extra_on_sub_account(0, 1_000));
+		polkadot::System::assert_last_event(polkadot::RuntimeEvent::XcmInterface(
+			crate::Event::XcmMessageSendFailed {
+				message_id: 0,
+				attempts: 1,
+			},
+		));
+		assert_eq!(polkadot::XcmInterface::next_message_id(), 1);
+		assert_eq!(
+			polkadot::XcmInterface::pending_xcm_messages(0),
+			Some(PendingXcmMessage {
+				operation: XcmInterfaceOperation::HomaBondExtra,
+				sub_account_index: 0,
+				amount: 1_000,
+				sent_at: None,
+				attempts: 1,
+			})
+		);
+
+		polkadot::XcmInterface::on_initialize(2);
+		polkadot::System::assert_last_event(polkadot::RuntimeEvent::XcmInterface(
+			crate::Event::XcmMessageSendFailed {
+				message_id: 0,
+				attempts: 2,
+			},
+		));
+		assert_eq!(
+			polkadot::XcmInterface::pending_xcm_messages(0).map(|message| message.attempts),
+			Some(2)
+		);
+
+		polkadot::XcmInterface::on_initialize(3);
+		polkadot::System::assert_last_event(polkadot::RuntimeEvent::XcmInterface(crate::Event::XcmMessageDropped {
+			message_id: 0,
+			xcm_operation: XcmInterfaceOperation::HomaBondExtra,
+			sub_account_index: 0,
+			amount: 1_000,
+		}));
+		assert_eq!(polkadot::XcmInterface::pending_xcm_messages(0), None);
+	});
+}
+
+#[test]
+fn sent_homa_message_times_out() {
+	ExtBuilder::default().build::<polkadot::Runtime>().execute_with(|| {
+		PendingXcmMessages::<polkadot::Runtime>::insert(
+			0,
+			PendingXcmMessage {
+				operation: XcmInterfaceOperation::HomaUnbond,
+				sub_account_index: 1,
+				amount: 1_000,
+				sent_at: Some(1),
+				attempts: 1,
+			},
+		);
+
+		polkadot::XcmInterface::on_initialize(10);
+		assert!(polkadot::XcmInterface::pending_xcm_messages(0).is_some());
+
+		polkadot::XcmInterface::on_initialize(11);
+		polkadot::System::assert_last_event(polkadot::RuntimeEvent::XcmInterface(crate::Event::XcmMessageTimedOut {
+			message_id: 0,
+			xcm_operation: XcmInterfaceOperation::HomaUnbond,
+			sub_account_index: 1,
+			amount: 1_000,
+		}));
+		assert_eq!(polkadot::XcmInterface::pending_xcm_messages(0), None);
+	});
+}
+
+#[test]
+fn confirm_xcm_messages_works() {
+	ExtBuilder::default().build::<polkadot::Runtime>().execute_with(|| {
+		assert_ok!(polkadot::XcmInterface::unbond_on_sub_account(0, 1_000));
+		assert!(polkadot::XcmInterface::pending_xcm_messages(0).is_some());
+
+		assert_noop!(
+			polkadot::XcmInterface::confirm_xcm_messages(polkadot::RuntimeOrigin::signed(BOB), vec![0]),
+			BadOrigin
+		);
+		assert_noop!(
+			polkadot::XcmInterface::confirm_xcm_messages(polkadot::RuntimeOrigin::signed(ALICE), vec![1]),
+			Error::<polkadot::Runtime>::MessageNotFound
+		);
+
+		assert_ok!(polkadot::XcmInterface::confirm_xcm_messages(
+			polkadot::RuntimeOrigin::signed(ALICE),
+			vec![0]
+		));
+		polkadot::System::assert_last_event(polkadot::RuntimeEvent::XcmInterface(
+			crate::Event::XcmMessageConfirmed { message_id: 0 },
+		));
+		assert_eq!(polkadot::XcmInterface::pending_xcm_messages(0), None);
+	});
+}
+
+#[test]
+fn pending_homa_messages_processed_per_block_are_limited() {
+	ExtBuilder::default().build::<polkadot::Runtime>().execute_with(|| {
+		for message_id in 0..3 {
+			PendingXcmMessages::<polkadot::Runtime>::insert(
+				message_id,
+				PendingXcmMessage {
+					operation: XcmInterfaceOperation::HomaUnbond,
+					sub_account_index: 1,
+					amount: 1_000,
+					sent_at: Some(1),
+					attempts: 1,
+				},
+			);
+		}
+
+		// only MaxMessagesPerBlock messages are processed
+		polkadot::XcmInterface::on_initialize(11);
+		assert_eq!(PendingXcmMessages::<polkadot::Runtime>::iter().count(), 1);
+		assert!(polkadot::XcmInterface::processing_cursor().is_some());
+
+		// the processing continues after the cursor
+		polkadot::XcmInterface::on_initialize(12);
+		assert_eq!(PendingXcmMessages::<polkadot::Runtime>::iter().count(), 0);
+		assert_eq!(polkadot::XcmInterface::processing_cursor(), None);
+	});
+}
+
+#[test]
+fn confirm_too_many_xcm_messages_fails() {
+	ExtBuilder::default().build::<polkadot::Runtime>().execute_with(|| {
+		assert_noop!(
+			polkadot::XcmInterface::confirm_xcm_messages(polkadot::RuntimeOrigin::signed(ALICE), vec![0, 1, 2]),
+			Error::<polkadot::Runtime>::TooManyMessages
+		);
+	});
+}
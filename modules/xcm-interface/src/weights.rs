@@ -0,0 +1,75 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for module_xcm_interface
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for module_xcm_interface.
+pub trait WeightInfo {
+	fn on_initialize(c: u32, ) -> Weight;
+	fn confirm_xcm_messages(c: u32, ) -> Weight;
+}
+
+/// Weights for module_xcm_interface using the Acala node and recommended hardware.
+pub struct AcalaWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
+	// Storage: XcmInterface ProcessingCursor (r:1 w:1)
+	// Storage: XcmInterface PendingXcmMessages (r:1 w:1)
+	// Storage: XcmInterface XcmDestWeightAndFee (r:1 w:0)
+	fn on_initialize(c: u32, ) -> Weight {
+		Weight::from_parts(6_120_000, 0)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_parts(38_450_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+	// Storage: XcmInterface PendingXcmMessages (r:1 w:1)
+	fn confirm_xcm_messages(c: u32, ) -> Weight {
+		Weight::from_parts(5_210_000, 0)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(4_870_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn on_initialize(c: u32, ) -> Weight {
+		Weight::from_parts(6_120_000, 0)
+			.saturating_add(Weight::from_parts(38_450_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+	fn confirm_xcm_messages(c: u32, ) -> Weight {
+		Weight::from_parts(5_210_000, 0)
+			.saturating_add(Weight::from_parts(4_870_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+}
@@ -18,7 +18,9 @@
 
 //! Acala Client abstractions.
 
-use primitives::{AccountId, Balance, Block, BlockNumber, Hash, Header, Nonce};
+use acala_rpc::auction_manager::CollateralAuctionDetail;
+use module_cdp_treasury::TreasuryMetrics;
+use primitives::{AccountId, AuctionId, Balance, Block, BlockNumber, CurrencyId, Hash, Header, Nonce};
 use sc_client_api::{Backend as BackendT, BlockchainEvents, KeysIter, PairsIter};
 use sp_api::{CallApiAt, NumberFor, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
@@ -39,6 +41,13 @@ pub trait RuntimeApiCollection:
 	+ sp_block_builder::BlockBuilder<Block>
 	+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
 	+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
+	+ module_auction_manager_rpc_runtime_api::AuctionManagerApi<
+		Block,
+		AccountId,
+		AuctionId,
+		CollateralAuctionDetail,
+		Balance,
+	> + module_cdp_treasury_rpc_runtime_api::CdpTreasuryApi<Block, CurrencyId, Balance, TreasuryMetrics>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
@@ -52,6 +61,13 @@ impl<Api> RuntimeApiCollection for Api where
 		+ sp_block_builder::BlockBuilder<Block>
 		+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
 		+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
+		+ module_auction_manager_rpc_runtime_api::AuctionManagerApi<
+			Block,
+			AccountId,
+			AuctionId,
+			CollateralAuctionDetail,
+			Balance,
+		> + module_cdp_treasury_rpc_runtime_api::CdpTreasuryApi<Block, CurrencyId, Balance, TreasuryMetrics>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
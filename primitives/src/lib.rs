@@ -189,6 +189,7 @@ pub enum ReserveIdentifier {
 	Nft,
 	TransactionPayment,
 	TransactionPaymentDeposit,
+	TransactionPaymentFreeTx,
 
 	// always the last, indicate number of variants
 	Count,
@@ -0,0 +1,121 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the auction manager module.
+
+use crate::runtime_error_into_rpc_err;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+pub use module_auction_manager_rpc_runtime_api::AuctionManagerApi as AuctionManagerRuntimeApi;
+use primitives::{AccountId, AuctionId, Balance, Block, BlockNumber, Hash};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use std::sync::Arc;
+
+/// The details of collateral auction returned by the RPC.
+pub type CollateralAuctionDetail = module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>;
+
+/// Auction manager RPC methods.
+#[rpc(server)]
+pub trait AuctionManagerApi {
+	/// Get the details of active collateral auction.
+	#[method(name = "auctionManager_getCollateralAuction")]
+	fn get_collateral_auction(
+		&self,
+		auction_id: AuctionId,
+		at: Option<Hash>,
+	) -> RpcResult<Option<CollateralAuctionDetail>>;
+
+	/// Get the details of all active collateral auctions.
+	#[method(name = "auctionManager_getActiveCollateralAuctions")]
+	fn get_active_collateral_auctions(&self, at: Option<Hash>) -> RpcResult<Vec<CollateralAuctionDetail>>;
+
+	/// Get the details of active collateral auctions which the account is the last bidder or the
+	/// refund recipient of.
+	#[method(name = "auctionManager_getCollateralAuctionsOf")]
+	fn get_collateral_auctions_of(&self, who: AccountId, at: Option<Hash>) -> RpcResult<Vec<CollateralAuctionDetail>>;
+
+	/// Get the bidder and the payment locked in the escrow account of collateral auction.
+	#[method(name = "auctionManager_getEscrowedBid")]
+	fn get_escrowed_bid(&self, auction_id: AuctionId, at: Option<Hash>) -> RpcResult<Option<(AccountId, Balance)>>;
+
+	/// Get the total bid payment locked in the escrow account.
+	#[method(name = "auctionManager_getTotalEscrowed")]
+	fn get_total_escrowed(&self, at: Option<Hash>) -> RpcResult<Balance>;
+}
+
+/// Provides RPC methods to query the collateral auctions.
+pub struct AuctionManager<C> {
+	client: Arc<C>,
+}
+
+impl<C> AuctionManager<C> {
+	/// Create new `AuctionManager` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> AuctionManagerApiServer for AuctionManager<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AuctionManagerRuntimeApi<Block, AccountId, AuctionId, CollateralAuctionDetail, Balance>,
+{
+	fn get_collateral_auction(
+		&self,
+		auction_id: AuctionId,
+		at: Option<Hash>,
+	) -> RpcResult<Option<CollateralAuctionDetail>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_collateral_auction(at, auction_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_active_collateral_auctions(&self, at: Option<Hash>) -> RpcResult<Vec<CollateralAuctionDetail>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_active_collateral_auctions(at)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_collateral_auctions_of(&self, who: AccountId, at: Option<Hash>) -> RpcResult<Vec<CollateralAuctionDetail>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_collateral_auctions_of(at, who)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_escrowed_bid(&self, auction_id: AuctionId, at: Option<Hash>) -> RpcResult<Option<(AccountId, Balance)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_escrowed_bid(at, auction_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_total_escrowed(&self, at: Option<Hash>) -> RpcResult<Balance> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_total_escrowed(at)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,135 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the cdp treasury module.
+
+use crate::runtime_error_into_rpc_err;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use module_cdp_treasury::TreasuryMetrics;
+pub use module_cdp_treasury_rpc_runtime_api::CdpTreasuryApi as CdpTreasuryRuntimeApi;
+use primitives::{Balance, Block, CurrencyId, Hash};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use std::sync::Arc;
+
+/// Cdp treasury RPC methods.
+#[rpc(server)]
+pub trait CdpTreasuryApi {
+	/// Get the surplus pool of cdp treasury.
+	#[method(name = "cdpTreasury_getSurplusPool")]
+	fn get_surplus_pool(&self, at: Option<Hash>) -> RpcResult<Balance>;
+
+	/// Get the debit pool of cdp treasury.
+	#[method(name = "cdpTreasury_getDebitPool")]
+	fn get_debit_pool(&self, at: Option<Hash>) -> RpcResult<Balance>;
+
+	/// Get the total collateral amount of specific collateral type held by cdp treasury.
+	#[method(name = "cdpTreasury_getTotalCollaterals")]
+	fn get_total_collaterals(&self, currency_id: CurrencyId, at: Option<Hash>) -> RpcResult<Balance>;
+
+	/// Get the count of collateral auctions will be created if auction all the collateral of
+	/// specific collateral type not in auction.
+	#[method(name = "cdpTreasury_getProjectedCollateralAuctionsCount")]
+	fn get_projected_collateral_auctions_count(&self, currency_id: CurrencyId, at: Option<Hash>) -> RpcResult<u32>;
+
+	/// Get the cumulative accounting metrics of cdp treasury.
+	#[method(name = "cdpTreasury_getCumulativeMetrics")]
+	fn get_cumulative_metrics(&self, at: Option<Hash>) -> RpcResult<TreasuryMetrics>;
+
+	/// Get the accounting metrics of specific era.
+	#[method(name = "cdpTreasury_getEraMetrics")]
+	fn get_era_metrics(&self, era: u32, at: Option<Hash>) -> RpcResult<Option<TreasuryMetrics>>;
+
+	/// Get the total amount of specific collateral type liquidated by cdp treasury.
+	#[method(name = "cdpTreasury_getTotalCollateralLiquidated")]
+	fn get_total_collateral_liquidated(&self, currency_id: CurrencyId, at: Option<Hash>) -> RpcResult<Balance>;
+}
+
+/// Provides RPC methods to query the cdp treasury.
+pub struct CdpTreasury<C> {
+	client: Arc<C>,
+}
+
+impl<C> CdpTreasury<C> {
+	/// Create new `CdpTreasury` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> CdpTreasuryApiServer for CdpTreasury<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: CdpTreasuryRuntimeApi<Block, CurrencyId, Balance, TreasuryMetrics>,
+{
+	fn get_surplus_pool(&self, at: Option<Hash>) -> RpcResult<Balance> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_surplus_pool(at)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_debit_pool(&self, at: Option<Hash>) -> RpcResult<Balance> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_debit_pool(at)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_total_collaterals(&self, currency_id: CurrencyId, at: Option<Hash>) -> RpcResult<Balance> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_total_collaterals(at, currency_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_projected_collateral_auctions_count(&self, currency_id: CurrencyId, at: Option<Hash>) -> RpcResult<u32> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_projected_collateral_auctions_count(at, currency_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_cumulative_metrics(&self, at: Option<Hash>) -> RpcResult<TreasuryMetrics> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_cumulative_metrics(at)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_era_metrics(&self, era: u32, at: Option<Hash>) -> RpcResult<Option<TreasuryMetrics>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_era_metrics(at, era)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_total_collateral_liquidated(&self, currency_id: CurrencyId, at: Option<Hash>) -> RpcResult<Balance> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.get_total_collateral_liquidated(at, currency_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
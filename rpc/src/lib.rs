@@ -20,7 +20,11 @@
 
 #![warn(missing_docs)]
 
-use primitives::{AccountId, Balance, Block, Hash, Nonce};
+use jsonrpsee::{
+	core::Error as JsonRpseeError,
+	types::error::{CallError, ErrorObject},
+};
+use primitives::{AccountId, AuctionId, Balance, Block, CurrencyId, Hash, Nonce};
 pub use sc_rpc::SubscriptionTaskExecutor;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -35,9 +39,29 @@ pub use sc_rpc_api::{dev::DevApiServer, DenyUnsafe};
 use sc_transaction_pool_api::TransactionPool;
 use substrate_frame_rpc_system::{System, SystemApiServer};
 
+/// acala rpc
+pub mod auction_manager;
+pub mod cdp_treasury;
+
+use auction_manager::{AuctionManager, AuctionManagerApiServer};
+use cdp_treasury::{CdpTreasury, CdpTreasuryApiServer};
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
 
+/// Error code of runtime api call failure.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Converts a runtime api call error into an RPC error.
+pub(crate) fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
 	/// The client instance to use.
@@ -58,6 +82,14 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: cdp_treasury::CdpTreasuryRuntimeApi<Block, CurrencyId, Balance, module_cdp_treasury::TreasuryMetrics>,
+	C::Api: auction_manager::AuctionManagerRuntimeApi<
+		Block,
+		AccountId,
+		AuctionId,
+		auction_manager::CollateralAuctionDetail,
+		Balance,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
@@ -71,6 +103,8 @@ where
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(CdpTreasury::new(client.clone()).into_rpc())?;
+	module.merge(AuctionManager::new(client.clone()).into_rpc())?;
 
 	module.merge(Dev::new(client, deny_unsafe).into_rpc())?;
 
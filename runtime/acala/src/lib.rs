@@ -38,8 +38,8 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdConversion, AccountIdLookup, BadOrigin, BlakeTwo256, Block as BlockT, Bounded, Convert,
-		IdentityLookup, SaturatedConversion, StaticLookup,
+		AccountIdConversion, AccountIdLookup, BadOrigin, BlakeTwo256, Block as BlockT, BlockNumberProvider, Bounded,
+		Convert, IdentityLookup, SaturatedConversion, StaticLookup,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, ArithmeticError, DispatchResult, FixedPointNumber, Perbill, Percent, Permill, Perquintill,
@@ -161,7 +161,9 @@ parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
+	pub const AuctionManagerPalletId: PalletId = PalletId(*b"aca/aumg");
 	pub const CDPEnginePalletId: PalletId = PalletId(*b"aca/cdpe");
+	pub const EmergencyShutdownPalletId: PalletId = PalletId(*b"aca/emsd");
 	pub const HomaPalletId: PalletId = PalletId(*b"aca/homa");
 	pub const HonzonTreasuryPalletId: PalletId = PalletId(*b"aca/hztr");
 	pub const HomaTreasuryPalletId: PalletId = PalletId(*b"aca/hmtr");
@@ -184,6 +186,8 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		LoansPalletId::get().into_account_truncating(),
 		CDPEnginePalletId::get().into_account_truncating(),
 		CDPTreasuryPalletId::get().into_account_truncating(),
+		EmergencyShutdownPalletId::get().into_account_truncating(),
+		AuctionManagerPalletId::get().into_sub_account_truncating(b"escrow"),
 		CollatorPotId::get().into_account_truncating(),
 		DEXPalletId::get().into_account_truncating(),
 		HomaPalletId::get().into_account_truncating(),
@@ -882,12 +886,18 @@ impl module_prices::Config for Runtime {
 	type RelayChainBlockNumber = RelaychainDataProvider<Runtime>;
 	type RewardRatePerRelaychainBlock = RewardRatePerRelaychainBlock;
 	type PricingPegged = PricingPegged;
+	type DEXPriceSource = module_dex_oracle::AverageDEXPriceProvider<Runtime>;
+	type OperatorSource = runtime_common::OracleOperatorPrices<Runtime, AcalaDataProvider, ExpiresIn>;
+	type MaxOperators = ConstU32<50>;
+	type FeedTimestamp = runtime_common::OracleFeedTimestamp<Runtime, AcalaDataProvider>;
+	type UnixTime = Timestamp;
 	type WeightInfo = weights::module_prices::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = ACA;
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD];
 	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
 	pub Erc20HoldingAccount: H160 = primitives::evm::ERC20_HOLDING_ACCOUNT;
@@ -952,6 +962,18 @@ impl orml_vesting::Config for Runtime {
 	type BlockNumberProvider = RelaychainDataProvider<Runtime>;
 }
 
+/// The amount of native token still locked by the vesting schedules of an account.
+pub struct NativeVestingBalance;
+impl module_support::VestingBalance<AccountId, Balance> for NativeVestingBalance {
+	fn vesting_balance(who: &AccountId) -> Balance {
+		let now = <Runtime as orml_vesting::Config>::BlockNumberProvider::current_block_number();
+		orml_vesting::VestingSchedules::<Runtime>::get(who)
+			.iter()
+			.map(|schedule| schedule.locked_amount(now))
+			.fold(0, Balance::saturating_add)
+	}
+}
+
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * RuntimeBlockWeights::get().max_block;
 }
@@ -1001,14 +1023,21 @@ impl module_auction_manager::Config for Runtime {
 	type MinimumIncrementSize = MinimumIncrementSize;
 	type AuctionTimeToClose = AuctionTimeToClose;
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
-	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CdpTreasury;
 	type PriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type Swap = AcalaSwap;
+	type KeeperRewards = Incentives;
+	type PalletId = AuctionManagerPalletId;
 	type WeightInfo = weights::module_auction_manager::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub VestedCollateralHaircut: Ratio = Ratio::saturating_from_rational(30, 100);
+}
+
 impl module_loans::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Currencies;
@@ -1016,6 +1045,9 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = module_incentives::OnUpdateLoan<Runtime>;
+	type VestedCollateralCurrencyId = GetNativeCurrencyId;
+	type VestingBalance = NativeVestingBalance;
+	type VestedCollateralHaircut = VestedCollateralHaircut;
 }
 
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
@@ -1084,12 +1116,19 @@ parameter_types! {
 		.expect("Rate is in range; qed");
 	pub MinimumDebitValue: Balance = 50 * dollar(AUSD);
 	pub MaxSwapSlippageCompareToOracle: Ratio = Ratio::saturating_from_rational(10, 100);
+	pub KeeperTipRatio: Rate = Rate::saturating_from_rational(10, 100);
+	pub MaxKeeperTip: Balance = 100 * dollar(AUSD);
 	pub MaxLiquidationContractSlippage: Ratio = Ratio::saturating_from_rational(15, 100);
+	// sample hourly and keep the debit exchange rates of 30 days
+	pub const MaxDebitExchangeRateHistory: u32 = 720;
+	pub const DebitExchangeRateSampleInterval: u64 = 3600;
 }
 
 impl module_cdp_engine::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
+	type StableMarketPriceSource = AggregatedDataProvider;
+	type PriceStaleness = Prices;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
@@ -1100,8 +1139,14 @@ impl module_cdp_engine::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
 	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageCompareToOracle;
+	type KeeperTipRatio = KeeperTipRatio;
+	type MaxKeeperTip = MaxKeeperTip;
+	type MaxLiquidationBatchSize = ConstU32<10>;
+	type MaxDebitExchangeRateHistory = MaxDebitExchangeRateHistory;
+	type DebitExchangeRateSampleInterval = DebitExchangeRateSampleInterval;
 	type UnsignedPriority = runtime_common::CdpEngineUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type KeeperRewards = Incentives;
 	type UnixTime = Timestamp;
 	type Currency = Currencies;
 	type DEX = Dex;
@@ -1124,6 +1169,8 @@ impl module_honzon::Config for Runtime {
 	type Currency = Balances;
 	type DepositPerAuthorization = DepositPerAuthorization;
 	type CollateralCurrencyIds = CollateralCurrencyIds<Runtime>;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as sp_runtime::traits::Verify>::Signer;
 	type WeightInfo = weights::module_honzon::WeightInfo<Runtime>;
 }
 
@@ -1134,11 +1181,15 @@ impl module_emergency_shutdown::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRoot<AccountId>;
+	type RefundsPerBlock = ConstU32<50>;
+	type GetStableCurrencyId = GetStableCurrencyId;
+	type PalletId = EmergencyShutdownPalletId;
 	type WeightInfo = weights::module_emergency_shutdown::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (3, 1000);	// 0.3%
+	pub DEXProtocolFeeReceiver: AccountId = CDPTreasuryPalletId::get().into_account_truncating();
 	pub const ExtendedProvisioningBlocks: BlockNumber = 2 * DAYS;
 	pub const TradingPathLimit: u32 = 4;
 }
@@ -1155,6 +1206,8 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
 	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = DEXProtocolFeeReceiver;
+	type RuntimeCall = RuntimeCall;
 }
 
 impl module_aggregated_dex::Config for Runtime {
@@ -1182,6 +1235,7 @@ impl module_dex_oracle::Config for Runtime {
 }
 
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(10, 100);
 	pub HonzonTreasuryAccount: AccountId = HonzonTreasuryPalletId::get().into_account_truncating();
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
 		vec![LCDOT],
@@ -1193,15 +1247,28 @@ parameter_types! {
 
 impl module_cdp_treasury::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = AuctionManager;
 	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
 	type DEX = Dex;
 	type Swap = AcalaSwap;
+	type PriceSource = module_prices::RealTimePriceProvider<Runtime>;
+	type TradingPathLimit = TradingPathLimit;
 	type MaxAuctionsCount = ConstU32<50>;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = CdpEngine;
+	type MaxCollateralTypes = ConstU32<32>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = Incentives;
 	type TreasuryAccount = HonzonTreasuryAccount;
+	type IncentivesRewardsSource = UnreleasedNativeVaultAccountId;
+	type MetricsEraLength = ConstU32<DAYS>;
+	type MaxMetricsEras = ConstU32<30>;
+	type ParamsUpdateDelay = ConstU32<DAYS>;
 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
 	type StableAsset = RebasedStableAsset;
 }
@@ -1235,6 +1302,17 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 	}
 }
 
+parameter_types! {
+	pub const FreeTxQuotaPeriod: BlockNumber = 7 * DAYS;
+}
+
+pub struct FreeTxCallFilter;
+impl Contains<RuntimeCall> for FreeTxCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::Honzon(_) | RuntimeCall::Dex(_))
+	}
+}
+
 impl module_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -1260,6 +1338,10 @@ impl module_transaction_payment::Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type CDPTreasuryAccount = DEXProtocolFeeReceiver;
+	type StableCurrencyId = GetStableCurrencyId;
+	type FreeTxCallFilter = FreeTxCallFilter;
+	type FreeTxQuotaPeriod = FreeTxQuotaPeriod;
 }
 
 impl module_evm_accounts::Config for Runtime {
@@ -1290,6 +1372,7 @@ impl orml_rewards::Config for Runtime {
 
 parameter_types! {
 	pub const AccumulatePeriod: BlockNumber = MINUTES;
+	pub const KeeperRewardEraPeriod: BlockNumber = DAYS;
 }
 
 impl module_incentives::Config for Runtime {
@@ -1297,6 +1380,8 @@ impl module_incentives::Config for Runtime {
 	type RewardsSource = UnreleasedNativeVaultAccountId;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type AccumulatePeriod = AccumulatePeriod;
+	type KeeperRewardSource = AcalaTreasuryAccount;
+	type KeeperRewardEraPeriod = KeeperRewardEraPeriod;
 	type UpdateOrigin = EnsureRootOrThreeFourthsGeneralCouncil;
 	type Currency = Currencies;
 	type EmergencyShutdown = EmergencyShutdown;
@@ -1360,6 +1445,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 					c,
 					RuntimeCall::Dex(module_dex::Call::swap_with_exact_supply { .. })
 						| RuntimeCall::Dex(module_dex::Call::swap_with_exact_target { .. })
+						| RuntimeCall::Dex(module_dex::Call::swap_with_exact_supply_before_deadline { .. })
+						| RuntimeCall::Dex(module_dex::Call::swap_with_exact_target_before_deadline { .. })
 						| RuntimeCall::AggregatedDex(module_aggregated_dex::Call::swap_with_exact_supply { .. })
 						| RuntimeCall::AggregatedDex(module_aggregated_dex::Call::swap_with_exact_target { .. })
 				)
@@ -1368,6 +1455,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				matches!(
 					c,
 					RuntimeCall::Honzon(module_honzon::Call::adjust_loan { .. })
+						| RuntimeCall::Honzon(module_honzon::Call::adjust_loans { .. })
+						| RuntimeCall::Honzon(module_honzon::Call::adjust_to_ratio { .. })
 						| RuntimeCall::Honzon(module_honzon::Call::close_loan_has_debit_by_dex { .. })
 						| RuntimeCall::Honzon(module_honzon::Call::adjust_loan_by_debit_value { .. })
 						| RuntimeCall::Honzon(module_honzon::Call::transfer_debit { .. })
@@ -1378,6 +1467,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 					c,
 					RuntimeCall::Dex(module_dex::Call::add_liquidity { .. })
 						| RuntimeCall::Dex(module_dex::Call::remove_liquidity { .. })
+						| RuntimeCall::Dex(module_dex::Call::add_liquidity_with_single_currency { .. })
+						| RuntimeCall::Dex(module_dex::Call::remove_liquidity_to_single_currency { .. })
 				)
 			}
 			ProxyType::StableAssetLiquidity | ProxyType::StableAssetSwap => false,
@@ -1569,6 +1660,7 @@ impl Convert<u16, MultiLocation> for SubAccountIndexMultiLocationConvertor {
 
 parameter_types! {
 	pub ParachainAccount: AccountId = ParachainInfo::get().into_account_truncating();
+	pub const XcmMessageTimeout: BlockNumber = 2 * HOURS;
 }
 
 impl module_xcm_interface::Config for Runtime {
@@ -1582,6 +1674,10 @@ impl module_xcm_interface::Config for Runtime {
 	type XcmTransfer = XTokens;
 	type SelfLocation = xcm_config::SelfLocation;
 	type AccountIdToMultiLocation = runtime_common::xcm_config::AccountIdToMultiLocation;
+	type MaxSendAttempts = ConstU32<3>;
+	type MessageTimeout = XcmMessageTimeout;
+	type MaxMessagesPerBlock = ConstU32<20>;
+	type WeightInfo = ();
 }
 
 impl orml_unknown_tokens::Config for Runtime {
@@ -1889,8 +1985,18 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
-pub type Executive =
-	frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem, ()>;
+pub type Executive = frame_executive::Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPalletsWithSystem,
+	(
+		module_cdp_treasury::InitializeModuleAccounts<Runtime>,
+		module_cdp_treasury::MigrateDebitPool<Runtime>,
+		module_auction_manager::MigrateBidsToEscrow<Runtime>,
+	),
+>;
 
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
@@ -2079,6 +2185,127 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl module_auction_manager_rpc_runtime_api::AuctionManagerApi<
+		Block,
+		AccountId,
+		AuctionId,
+		module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>,
+		Balance,
+	> for Runtime {
+		fn get_collateral_auction(
+			auction_id: AuctionId,
+		) -> Option<module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>> {
+			AuctionManager::get_collateral_auction_detail(auction_id)
+		}
+
+		fn get_active_collateral_auctions() -> Vec<module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>> {
+			AuctionManager::get_active_collateral_auctions()
+		}
+
+		fn get_collateral_auctions_of(
+			who: AccountId,
+		) -> Vec<module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>> {
+			AuctionManager::get_collateral_auctions_of(&who)
+		}
+
+		fn get_escrowed_bid(auction_id: AuctionId) -> Option<(AccountId, Balance)> {
+			AuctionManager::escrowed_bids(auction_id)
+		}
+
+		fn get_total_escrowed() -> Balance {
+			AuctionManager::total_escrowed()
+		}
+	}
+
+	impl module_honzon_rpc_runtime_api::HonzonApi<
+		Block,
+		AccountId,
+		CurrencyId,
+		module_honzon::PositionInfo,
+		module_honzon::SystemHealth,
+		Balance,
+		ExchangeRate,
+	> for Runtime {
+		fn get_position_info(who: AccountId, currency_id: CurrencyId) -> Option<module_honzon::PositionInfo> {
+			Honzon::position_info(&who, currency_id)
+		}
+
+		fn get_system_health() -> module_honzon::SystemHealth {
+			Honzon::system_health()
+		}
+
+		fn get_projected_interest(who: AccountId, currency_id: CurrencyId, blocks: u32) -> Option<Balance> {
+			Honzon::projected_interest(&who, currency_id, u64::from(blocks).saturating_mul(SECS_PER_BLOCK))
+		}
+
+		fn get_debit_exchange_rate_history(currency_id: CurrencyId) -> Vec<(u64, ExchangeRate)> {
+			CdpEngine::debit_exchange_rate_history(currency_id).into_inner()
+		}
+	}
+
+	impl module_prices_rpc_runtime_api::PricesApi<
+		Block,
+		CurrencyId,
+		module_prices::PriceInfo,
+	> for Runtime {
+		fn get_price_info(currency_id: CurrencyId) -> module_prices::PriceInfo {
+			Prices::price_info(currency_id)
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexApi<
+		Block,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_swap_target_amount(path: Vec<CurrencyId>, supply_amount: Balance) -> Option<Balance> {
+			Dex::get_swap_target_amount(&path, supply_amount)
+		}
+
+		fn get_swap_supply_amount(path: Vec<CurrencyId>, target_amount: Balance) -> Option<Balance> {
+			Dex::get_swap_supply_amount(&path, target_amount)
+		}
+
+		fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+			Dex::get_liquidity(currency_id_a, currency_id_b)
+		}
+	}
+
+	impl module_cdp_treasury_rpc_runtime_api::CdpTreasuryApi<
+		Block,
+		CurrencyId,
+		Balance,
+		module_cdp_treasury::TreasuryMetrics,
+	> for Runtime {
+		fn get_surplus_pool() -> Balance {
+			CdpTreasury::surplus_pool()
+		}
+
+		fn get_debit_pool() -> Balance {
+			CdpTreasury::debit_pool()
+		}
+
+		fn get_total_collaterals(currency_id: CurrencyId) -> Balance {
+			CdpTreasury::total_claimable_collaterals(currency_id)
+		}
+
+		fn get_projected_collateral_auctions_count(currency_id: CurrencyId) -> u32 {
+			CdpTreasury::get_projected_collateral_auctions_count(currency_id)
+		}
+
+		fn get_cumulative_metrics() -> module_cdp_treasury::TreasuryMetrics {
+			CdpTreasury::cumulative_metrics()
+		}
+
+		fn get_era_metrics(era: u32) -> Option<module_cdp_treasury::TreasuryMetrics> {
+			CdpTreasury::era_metrics(era)
+		}
+
+		fn get_total_collateral_liquidated(currency_id: CurrencyId) -> Balance {
+			CdpTreasury::total_collateral_liquidated(currency_id)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn block_limits() -> BlockLimits {
 			BlockLimits {
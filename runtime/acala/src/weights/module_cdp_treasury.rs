@@ -123,6 +123,43 @@ impl<T: frame_system::Config> module_cdp_treasury::WeightInfo for WeightInfo<T>
 		Weight::from_parts(16_390_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	// Storage: `CdpTreasury::ExpectedCollateralAuctionValue` (r:0 w:1)
+	// Proof: `CdpTreasury::ExpectedCollateralAuctionValue` (`max_values`: None, `max_size`: Some(67), added: 2542, mode: `MaxEncodedLen`)
+	fn set_expected_collateral_auction_value() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `997`
+		//  Estimated: `0`
+		// Minimum execution time: 15_972 nanoseconds.
+		Weight::from_parts(16_390_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: `CdpTreasury::ExpectedStableAuctionValue` (r:0 w:1)
+	// Proof: `CdpTreasury::ExpectedStableAuctionValue` (`max_values`: None, `max_size`: Some(67), added: 2542, mode: `MaxEncodedLen`)
+	fn set_expected_stable_auction_value() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `997`
+		//  Estimated: `0`
+		// Minimum execution time: 15_972 nanoseconds.
+		Weight::from_parts(16_390_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: `CdpTreasury::ScheduledParamUpdateBlocks` (r:32 w:32)
+	// Proof: `CdpTreasury::ScheduledParamUpdateBlocks` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
+	// Storage: `CdpTreasury::ScheduledParamUpdates` (r:0 w:64)
+	// Proof: `CdpTreasury::ScheduledParamUpdates` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 32]`.
+	fn set_collateral_auction_params(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `965`
+		//  Estimated: `1489 + n * (2546 ±0)`
+		// Minimum execution time: 21_457 nanoseconds.
+		Weight::from_parts(5_372_118, 1489)
+			// Standard Error: 6_204
+			.saturating_add(Weight::from_parts(16_482_310, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2546).saturating_mul(n.into()))
+	}
 	// Storage: `Tokens::Accounts` (r:2 w:2)
 	// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(147), added: 2622, mode: `MaxEncodedLen`)
 	// Storage: `System::Account` (r:2 w:1)
@@ -136,4 +173,114 @@ impl<T: frame_system::Config> module_cdp_treasury::WeightInfo for WeightInfo<T>
 			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	fn trigger_settlement() -> Weight {
+		Weight::from_parts(160_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	fn flash_loan() -> Weight {
+		Weight::from_parts(43000000, 0)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	fn repay_debit_pool() -> Weight {
+		Weight::from_parts(36000000, 0)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn deploy_liquidity() -> Weight {
+		Weight::from_parts(98000000, 0)
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
+	fn recall_liquidity() -> Weight {
+		Weight::from_parts(86000000, 0)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	fn set_debit_offset_buffer() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_surplus_buffer_size() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_dex_settlement_cap() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_max_swap_slippage() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_collateral_swap_path() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn transfer_surplus() -> Weight {
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn set_surplus_distribution() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_stability_reserve_target() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_collateral_dust_threshold() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn sweep_dust_collateral() -> Weight {
+		Weight::from_parts(180_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	fn set_surplus_settlement_size() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_debit_settlement_premium() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_settlement_params() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn set_flash_loan_fee_rate() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_max_liquidity_deployment_ratio() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_stability_fee_incentives_ratio() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_debit_pool_interest_rate() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_issuance_limits() -> Weight {
+		Weight::from_parts(26_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn set_collateral_swap_budget() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn on_finalize() -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
 }
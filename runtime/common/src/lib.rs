@@ -25,23 +25,27 @@ use cumulus_pallet_parachain_system::CheckAssociatedRelayNumber;
 use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
-	traits::{Contains, EitherOfDiverse, Get},
+	traits::{Contains, EitherOfDiverse, Get, Time},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, WEIGHT_REF_TIME_PER_SECOND},
 		Weight,
 	},
 };
 use frame_system::{limits, EnsureRoot};
-use orml_traits::{currency::MutationHooks, GetByKey};
+use orml_traits::{currency::MutationHooks, DataProviderExtended, GetByKey};
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use polkadot_parachain_primitives::primitives::RelayChainBlockNumber;
 use primitives::{
 	evm::{is_system_contract, CHAIN_ID_ACALA_TESTNET, CHAIN_ID_KARURA_TESTNET, CHAIN_ID_MANDALA},
-	Balance, CurrencyId,
+	Balance, CurrencyId, Moment,
 };
 use scale_info::TypeInfo;
 use sp_core::{Bytes, H160};
-use sp_runtime::{traits::Convert, transaction_validity::TransactionPriority, Perbill, RuntimeDebug};
+use sp_runtime::{
+	traits::{Convert, Saturating},
+	transaction_validity::TransactionPriority,
+	Perbill, RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*};
 use static_assertions::const_assert;
 
@@ -127,6 +131,36 @@ impl<NativeCurrencyId: Get<CurrencyId>, NativeED: Get<Balance>, OtherEDs: GetByK
 	}
 }
 
+/// The unexpired price fed by specific operator of oracle.
+pub struct OracleOperatorPrices<T, I, ExpiresIn>(PhantomData<(T, I, ExpiresIn)>);
+impl<T, I, ExpiresIn> module_support::OperatorDataProvider<T::AccountId, CurrencyId, Price>
+	for OracleOperatorPrices<T, I, ExpiresIn>
+where
+	T: orml_oracle::Config<I, OracleKey = CurrencyId, OracleValue = Price>,
+	I: 'static,
+	ExpiresIn: Get<<T::Time as Time>::Moment>,
+{
+	fn get_operator_value(operator: &T::AccountId, key: &CurrencyId) -> Option<Price> {
+		orml_oracle::RawValues::<T, I>::get(operator, key)
+			.filter(|raw| raw.timestamp.saturating_add(ExpiresIn::get()) > T::Time::now())
+			.map(|raw| raw.value)
+	}
+}
+
+/// The timestamp of the latest combined price of oracle.
+pub struct OracleFeedTimestamp<T, I>(PhantomData<(T, I)>);
+impl<T, I> GetByKey<CurrencyId, Option<Moment>> for OracleFeedTimestamp<T, I>
+where
+	T: orml_oracle::Config<I, OracleKey = CurrencyId>,
+	T::Time: Time<Moment = Moment>,
+	I: 'static,
+{
+	fn get(key: &CurrencyId) -> Option<Moment> {
+		let combined: Option<orml_oracle::TimestampedValueOf<T, I>> = orml_oracle::Pallet::<T, I>::get_no_op(key);
+		combined.map(|value| value.timestamp)
+	}
+}
+
 /// Convert weight to gas
 pub struct WeightToGas;
 impl Convert<Weight, u64> for WeightToGas {
@@ -256,7 +256,9 @@ impl orml_nft::Config for Test {
 
 parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD];
 	pub MaxSwapSlippageCompareToOracle: Ratio = Ratio::one();
+	pub KeeperTipRatio: Rate = Rate::saturating_from_rational(10, 100);
 	pub const TreasuryPalletId: PalletId = PalletId(*b"aca/trsy");
 	pub const TransactionPaymentPalletId: PalletId = PalletId(*b"aca/fees");
 	pub KaruraTreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
@@ -290,6 +292,10 @@ impl module_transaction_payment::Config for Test {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type CDPTreasuryAccount = CDPTreasuryAccount;
+	type StableCurrencyId = GetStableCurrencyId;
+	type FreeTxCallFilter = Nothing;
+	type FreeTxQuotaPeriod = ConstU32<100>;
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -392,10 +398,13 @@ impl module_dex::Config for Test {
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU32<0>;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = CDPTreasuryAccount;
+	type RuntimeCall = RuntimeCall;
 }
 
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub VestedCollateralHaircut: Ratio = Ratio::saturating_from_rational(20, 100);
 }
 
 impl module_loans::Config for Test {
@@ -405,6 +414,9 @@ impl module_loans::Config for Test {
 	type CDPTreasury = CDPTreasury;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type VestedCollateralCurrencyId = GetNativeCurrencyId;
+	type VestingBalance = ();
+	type VestedCollateralHaircut = VestedCollateralHaircut;
 }
 
 pub struct MockPriceSource;
@@ -435,6 +447,8 @@ parameter_types! {
 impl module_cdp_engine::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type PriceSource = MockPriceSource;
+	type StableMarketPriceSource = Oracle;
+	type PriceStaleness = ();
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
@@ -444,8 +458,14 @@ impl module_cdp_engine::Config for Test {
 	type CDPTreasury = CDPTreasury;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageCompareToOracle;
+	type KeeperTipRatio = KeeperTipRatio;
+	type MaxKeeperTip = ConstU128<10>;
+	type MaxLiquidationBatchSize = ConstU32<10>;
+	type MaxDebitExchangeRateHistory = ConstU32<10>;
+	type DebitExchangeRateSampleInterval = ConstU64<0>;
 	type UnsignedPriority = ConstU64<1048576>; // 1 << 20
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type KeeperRewards = ();
 	type UnixTime = Timestamp;
 	type Currency = Currencies;
 	type DEX = DexModule;
@@ -495,6 +515,7 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 }
 
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(20, 100);
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub CDPTreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account_truncating();
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
@@ -504,17 +525,30 @@ parameter_types! {
 
 impl module_cdp_treasury::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DexModule;
 	type MaxAuctionsCount = ConstU32<10_000>;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = CDPEngine;
+	type MaxCollateralTypes = ConstU32<10>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = ();
 	type TreasuryAccount = CDPTreasuryAccount;
+	type IncentivesRewardsSource = RewardsSource;
+	type MetricsEraLength = ConstU32<100>;
+	type MaxMetricsEras = ConstU32<10>;
+	type ParamsUpdateDelay = ConstU32<0>;
 	type WeightInfo = ();
 	type StableAsset = MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber>;
 	type Swap = SpecificJointsSwap<DexModule, AlternativeSwapPathJointList>;
+	type PriceSource = MockPriceSource;
+	type TradingPathLimit = TradingPathLimit;
 }
 
 impl module_honzon::Config for Test {
@@ -522,6 +556,8 @@ impl module_honzon::Config for Test {
 	type Currency = Balances;
 	type DepositPerAuthorization = ConstU128<100>;
 	type CollateralCurrencyIds = CollateralCurrencyIds<Test>;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as sp_runtime::traits::Verify>::Signer;
 	type WeightInfo = ();
 }
 
@@ -643,6 +679,12 @@ parameter_type_with_key! {
 	};
 }
 
+parameter_type_with_key! {
+	pub PriceFeedTimestamp: |_currency_id: CurrencyId| -> Option<Moment> {
+		None
+	};
+}
+
 parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::saturating_from_rational(1, 1);
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
@@ -671,6 +713,11 @@ impl module_prices::Config for Test {
 	type RelayChainBlockNumber = MockRelayBlockNumberProvider;
 	type RewardRatePerRelaychainBlock = RewardRatePerRelaychainBlock;
 	type PricingPegged = PricingPegged;
+	type DEXPriceSource = ();
+	type OperatorSource = ();
+	type MaxOperators = ConstU32<10>;
+	type FeedTimestamp = PriceFeedTimestamp;
+	type UnixTime = Timestamp;
 	type WeightInfo = ();
 }
 
@@ -756,6 +803,8 @@ impl module_incentives::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardsSource = RewardsSource;
 	type AccumulatePeriod = ConstU32<10>;
+	type KeeperRewardSource = RewardsSource;
+	type KeeperRewardEraPeriod = ConstU32<100>;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type Currency = Tokens;
@@ -175,6 +175,7 @@ fn emergency_shutdown_and_cdp_treasury() {
 				module_emergency_shutdown::Error::<Runtime>::CanNotRefund,
 			);
 			assert_ok!(EmergencyShutdown::emergency_shutdown(RuntimeOrigin::root()));
+			assert_ok!(EmergencyShutdown::fix_final_prices(RuntimeOrigin::root()));
 			assert_ok!(EmergencyShutdown::open_collateral_refund(RuntimeOrigin::root()));
 			assert_ok!(EmergencyShutdown::refund_collaterals(
 				RuntimeOrigin::signed(AccountId::from(ALICE)),
@@ -531,13 +532,24 @@ fn test_cdp_engine_module() {
 				RELAY_CHAIN_CURRENCY
 			));
 
+			let confiscated_collateral_amount = CdpTreasury::total_collaterals(RELAY_CHAIN_CURRENCY);
 			let settle_cdp_in_debit_event = RuntimeEvent::CdpEngine(module_cdp_engine::Event::SettleCDPInDebit {
 				collateral_type: RELAY_CHAIN_CURRENCY,
 				owner: AccountId::from(ALICE),
+				confiscated_collateral_amount,
+				refunded_collateral_amount: 200 * dollar(RELAY_CHAIN_CURRENCY) - confiscated_collateral_amount,
 			});
 			System::assert_has_event(settle_cdp_in_debit_event);
 
 			assert_eq!(Loans::positions(RELAY_CHAIN_CURRENCY, AccountId::from(ALICE)).debit, 0);
+			assert_eq!(
+				Loans::positions(RELAY_CHAIN_CURRENCY, AccountId::from(ALICE)).collateral,
+				0
+			);
+			assert_eq!(
+				Currencies::free_balance(RELAY_CHAIN_CURRENCY, &AccountId::from(ALICE)),
+				2000 * dollar(RELAY_CHAIN_CURRENCY) - confiscated_collateral_amount
+			);
 			assert_eq!(CdpTreasury::debit_pool(), 50 * dollar(USD_CURRENCY));
 
 			// DOT is 10 decimal places where as ksm is 12 decimals. Hence the difference in collaterals.
@@ -612,13 +624,13 @@ fn cdp_treasury_handles_honzon_surplus_correctly() {
 				50 * dollar(USD_CURRENCY)
 			);
 			assert_eq!(Currencies::free_balance(USD_CURRENCY, &CdpTreasury::account_id()), 0);
-			assert_eq!(CdpTreasury::get_surplus_pool(), 0);
-			assert_eq!(CdpTreasury::get_debit_pool(), 0);
+			assert_eq!(CdpTreasury::get_surplus_pool(USD_CURRENCY), 0);
+			assert_eq!(CdpTreasury::get_debit_pool(USD_CURRENCY), 0);
 			run_to_block(2);
 
 			// Empty treasury recieves stablecoins into surplus pool from loan
-			assert_eq!(CdpTreasury::get_surplus_pool(), 270716741782);
-			assert_eq!(CdpTreasury::get_debit_pool(), 0);
+			assert_eq!(CdpTreasury::get_surplus_pool(USD_CURRENCY), 270716741782);
+			assert_eq!(CdpTreasury::get_debit_pool(USD_CURRENCY), 0);
 			// Honzon generated cdp treasury surplus can be transfered
 			assert_eq!(Currencies::free_balance(USD_CURRENCY, &AccountId::from(BOB)), 0);
 			assert_eq!(
@@ -634,7 +646,7 @@ fn cdp_treasury_handles_honzon_surplus_correctly() {
 				RuntimeOrigin::signed(CdpTreasury::account_id()),
 				sp_runtime::MultiAddress::Id(AccountId::from(BOB)),
 				USD_CURRENCY,
-				CdpTreasury::get_surplus_pool() - 1
+				CdpTreasury::get_surplus_pool(USD_CURRENCY) - 1
 			));
 			assert_eq!(
 				Currencies::free_balance(USD_CURRENCY, &AccountId::from(BOB)),
@@ -659,7 +671,7 @@ fn cdp_treasury_handles_honzon_surplus_correctly() {
 				5 * dollar(RELAY_CHAIN_CURRENCY),
 			));
 			// Just over 50 dollar(USD_CURRENCY), due to interest on loan
-			assert_eq!(CdpTreasury::get_debit_pool(), 50331074791608);
+			assert_eq!(CdpTreasury::get_debit_pool(USD_CURRENCY), 50331074791608);
 			assert_eq!(Loans::total_positions(RELAY_CHAIN_CURRENCY).debit, 0);
 			run_to_block(4);
 			// Debt exchange rate doesn't update due to no debit positions
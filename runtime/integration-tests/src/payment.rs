@@ -20,6 +20,7 @@ use crate::setup::*;
 use crate::stable_asset::enable_stable_asset;
 use frame_support::{
 	dispatch::{DispatchClass, DispatchInfo, Pays, PostDispatchInfo},
+	traits::Hooks,
 	weights::Weight,
 };
 use module_support::AggregatedSwapPath;
@@ -438,6 +439,12 @@ fn with_fee_call_works(
 			// enable USD as charge fee pool token.
 			assert_ok!(init_charge_fee_pool(USD_CURRENCY));
 
+			// the direct swap of fee requires the oracle price to limit the supply amount.
+			set_oracle_price(vec![
+				(NATIVE_CURRENCY, Price::saturating_from_rational(1, 1)),
+				(RELAY_CHAIN_CURRENCY, Price::saturating_from_rational(1, 1)),
+			]);
+
 			// un-wrapped call use dex swap only `AlternativeFeeSwapPath` is set, otherwise use fee pool.
 			// user don't have USD(which use fee pool), and also don't have native token, then failed.
 			assert_noop!(
@@ -509,3 +516,32 @@ fn with_fee_call_works(
 			}));
 		});
 }
+
+#[test]
+fn routed_transaction_fee_is_deposited_into_cdp_treasury_surplus() {
+	ExtBuilder::default().build().execute_with(|| {
+		// USD - ACA
+		assert_ok!(add_liquidity(
+			USD_CURRENCY,
+			NATIVE_CURRENCY,
+			1000 * dollar(USD_CURRENCY),
+			1000 * dollar(NATIVE_CURRENCY)
+		));
+		set_oracle_price(vec![(NATIVE_CURRENCY, Price::saturating_from_rational(1, 1))]);
+
+		let fee_account = TransactionPayment::cdp_treasury_fee_account_id();
+		let ed = NativeTokenExistentialDeposit::get();
+		assert_ok!(Currencies::update_balance(
+			RuntimeOrigin::root(),
+			MultiAddress::Id(fee_account.clone()),
+			NATIVE_CURRENCY,
+			(10 * dollar(NATIVE_CURRENCY) + ed).unique_saturated_into(),
+		));
+		let surplus_pool = CdpTreasury::surplus_pool();
+
+		// the routed fee is swapped to stable and deposited into the surplus pool
+		TransactionPayment::on_initialize(1);
+		assert_eq!(Currencies::free_balance(NATIVE_CURRENCY, &fee_account), ed);
+		assert!(CdpTreasury::surplus_pool() > surplus_pool + 9 * dollar(USD_CURRENCY));
+	});
+}
@@ -627,7 +627,9 @@ fn three_usd_pool_works() {
 					TransactionValidityError::Invalid(InvalidTransaction::Payment)
 				);
 			}
-			// USD_CURRENCY to NATIVE_CURRENCY is valid, because it exist in dex swap.
+			// USD_CURRENCY to NATIVE_CURRENCY is valid, because it exist in dex swap and the oracle price
+			// of NATIVE_CURRENCY is available.
+			set_oracle_price(vec![(NATIVE_CURRENCY, Price::saturating_from_rational(10, 1))]);
 			assert_ok!(
 				<module_transaction_payment::ChargeTransactionPayment::<Runtime>>::from(0).validate(
 					&AccountId::from(BOB),
@@ -267,4 +267,46 @@ impl<T: frame_system::Config> module_cdp_engine::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	fn adjust_stability_fees(c: u32, ) -> Weight {
+		Weight::from_parts(6000000, 0)
+			.saturating_add(Weight::from_parts(14000000, 0).saturating_mul(c))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: `Loans::PendingConfiscations` (r:1 w:1)
+	// Proof: `Loans::PendingConfiscations` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	// Storage: `Vesting::VestingSchedules` (r:1 w:0)
+	// Proof: `Vesting::VestingSchedules` (`max_values`: None, `max_size`: Some(2850), added: 5325, mode: `MaxEncodedLen`)
+	// Storage: `Loans::VestedCollaterals` (r:1 w:0)
+	// Proof: `Loans::VestedCollaterals` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	// Storage: `Balances::Locks` (r:1 w:1)
+	// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	// Storage: `System::Account` (r:2 w:2)
+	// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn sweep_vested_collateral() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1803`
+		//  Estimated: `6315`
+		// Minimum execution time: 57_693 nanoseconds.
+		Weight::from_parts(58_480_000, 6315)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: `CdpEngine::CollateralParams` (r:1 w:0)
+	// Proof: `CdpEngine::CollateralParams` (`max_values`: None, `max_size`: Some(135), added: 2610, mode: `MaxEncodedLen`)
+	// Storage: `Loans::TotalPositions` (r:1 w:0)
+	// Proof: `Loans::TotalPositions` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	// Storage: `Tokens::Accounts` (r:1 w:0)
+	// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(147), added: 2622, mode: `MaxEncodedLen`)
+	// Storage: `CdpEngine::CollateralStableCurrency` (r:0 w:1)
+	// Proof: `CdpEngine::CollateralStableCurrency` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	fn set_collateral_stable_currency() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1480`
+		//  Estimated: `3612`
+		// Minimum execution time: 28_061 nanoseconds.
+		Weight::from_parts(28_795_000, 3612)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -355,4 +355,24 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 		Weight::from_parts(43_216_000, 7960)
 			.saturating_add(T::DbWeight::get().reads(11))
 	}
+	fn authorize_with_scope() -> Weight {
+		Weight::from_parts(48_215_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	fn adjust_loan_on_behalf() -> Weight {
+		Weight::from_parts(176_320_000, 0)
+			.saturating_add(T::DbWeight::get().reads(19))
+			.saturating_add(T::DbWeight::get().writes(11))
+	}
+	fn adjust_loan_by_permit() -> Weight {
+		Weight::from_parts(206_540_000, 0)
+			.saturating_add(T::DbWeight::get().reads(18))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	fn adjust_vested_collateral() -> Weight {
+		Weight::from_parts(96_412_000, 0)
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
 }
@@ -142,4 +142,63 @@ impl<T: frame_system::Config> module_transaction_payment::WeightInfo for WeightI
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	// Storage: `TransactionPayment::FeeToCDPTreasuryRatio` (r:0 w:1)
+	// Proof: `TransactionPayment::FeeToCDPTreasuryRatio` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	// Storage: `TransactionPayment::TipToCDPTreasuryRatio` (r:0 w:1)
+	// Proof: `TransactionPayment::TipToCDPTreasuryRatio` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn set_cdp_treasury_fee_ratios() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `666`
+		//  Estimated: `0`
+		// Minimum execution time: 8_926 nanoseconds.
+		Weight::from_parts(9_318_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: `TransactionPayment::FreeTxQuota` (r:0 w:1)
+	// Proof: `TransactionPayment::FreeTxQuota` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	// Storage: `TransactionPayment::FreeTxMinBalance` (r:0 w:1)
+	// Proof: `TransactionPayment::FreeTxMinBalance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn set_free_tx_quota() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `666`
+		//  Estimated: `0`
+		// Minimum execution time: 8_871 nanoseconds.
+		Weight::from_parts(9_204_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: `Balances::Reserves` (r:1 w:1)
+	// Proof: `Balances::Reserves` (`max_values`: None, `max_size`: Some(168), added: 2643, mode: `MaxEncodedLen`)
+	// Storage: `System::Account` (r:1 w:1)
+	// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	// Storage: `TransactionPayment::FreeTxDepositReservedAt` (r:0 w:1)
+	// Proof: `TransactionPayment::FreeTxDepositReservedAt` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	fn reserve_free_tx_deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1342`
+		//  Estimated: `3633`
+		// Minimum execution time: 31_405 nanoseconds.
+		Weight::from_parts(32_117_000, 3633)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: `Balances::Reserves` (r:1 w:1)
+	// Proof: `Balances::Reserves` (`max_values`: None, `max_size`: Some(168), added: 2643, mode: `MaxEncodedLen`)
+	// Storage: `System::Account` (r:1 w:1)
+	// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	// Storage: `TransactionPayment::FreeTxDepositReservedAt` (r:0 w:1)
+	// Proof: `TransactionPayment::FreeTxDepositReservedAt` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	fn unreserve_free_tx_deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1438`
+		//  Estimated: `3633`
+		// Minimum execution time: 30_862 nanoseconds.
+		Weight::from_parts(31_590_000, 3633)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn deposit_fee_to_cdp_treasury() -> Weight {
+		Weight::from_parts(98_514_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
 }
@@ -0,0 +1,102 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::utils::{dollar, set_balance, STABLECOIN};
+use crate::{AccountId, Airdrop, AirdropMaxProofLength, Runtime, System, TreasuryAccount};
+use module_airdrop::hash_pair;
+use sp_core::H256;
+use sp_runtime::traits::Get;
+use sp_std::prelude::*;
+
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_system::RawOrigin;
+
+use orml_benchmarking::runtime_benchmarks;
+
+/// Create an airdrop with the claim of `who`, returns the proof of max length.
+fn create_airdrop(who: &AccountId) -> Result<Vec<H256>, sp_runtime::DispatchError> {
+	let proof = (0..AirdropMaxProofLength::get())
+		.map(|i| H256::repeat_byte(i as u8))
+		.collect::<Vec<_>>();
+	let root = proof
+		.iter()
+		.fold(Airdrop::leaf_hash(who, 100 * dollar(STABLECOIN)), |node, sibling| {
+			hash_pair(node, *sibling)
+		});
+	set_balance(STABLECOIN, &TreasuryAccount::get(), 10_000 * dollar(STABLECOIN));
+	Airdrop::create_airdrop(
+		RawOrigin::Root.into(),
+		STABLECOIN,
+		root,
+		1_000 * dollar(STABLECOIN),
+		100,
+	)?;
+	Ok(proof)
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_airdrop }
+
+	create_airdrop {
+		set_balance(STABLECOIN, &TreasuryAccount::get(), 10_000 * dollar(STABLECOIN));
+	}: _(RawOrigin::Root, STABLECOIN, H256::zero(), 1_000 * dollar(STABLECOIN), 100)
+	verify {
+		assert!(Airdrop::airdrops(0).is_some());
+	}
+
+	claim {
+		let caller: AccountId = whitelisted_caller();
+		let proof = create_airdrop(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, 100 * dollar(STABLECOIN), proof.try_into().unwrap())
+	verify {
+		assert!(Airdrop::claimed(0, &caller));
+	}
+
+	close_airdrop {
+		let caller: AccountId = whitelisted_caller();
+		create_airdrop(&caller)?;
+		System::set_block_number(100);
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(Airdrop::airdrops(0).is_none());
+	}
+
+	clear_claimed {
+		let n in 1 .. 1_000;
+
+		let caller: AccountId = whitelisted_caller();
+		create_airdrop(&caller)?;
+		for i in 0 .. n {
+			module_airdrop::Claimed::<Runtime>::insert(0, account::<AccountId>("claimer", i, 0), true);
+		}
+		System::set_block_number(100);
+		Airdrop::close_airdrop(RawOrigin::Signed(caller.clone()).into(), 0)?;
+	}: _(RawOrigin::Signed(caller), 0, n)
+	verify {
+		assert!(Airdrop::closed_airdrops(0).is_none());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
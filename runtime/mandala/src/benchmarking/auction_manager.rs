@@ -18,10 +18,11 @@
 
 use crate::{AccountId, AuctionId, AuctionManager, CdpTreasury, Currencies, EmergencyShutdown, Price, Runtime};
 
-use super::utils::{dollar, feed_price, STABLECOIN, STAKING};
+use super::utils::{dollar, feed_price, inject_liquidity, LIQUID, STABLECOIN, STAKING};
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
-use module_support::{AuctionManager as AuctionManagerTrait, CDPTreasury};
+use module_auction_manager::DutchAuctionParams;
+use module_support::{AuctionManager as AuctionManagerTrait, CDPTreasury, Rate, Ratio};
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
 use sp_runtime::FixedPointNumber;
@@ -56,6 +57,78 @@ runtime_benchmarks! {
 		// shutdown
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
 	}: cancel(RawOrigin::None, auction_id)
+
+	force_cancel {
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+
+		// set balance
+		Currencies::deposit(STABLECOIN, &bidder, 80 * dollar(STABLECOIN))?;
+		Currencies::deposit(STAKING, &funder, dollar(STAKING))?;
+		CdpTreasury::deposit_collateral(&funder, STAKING, dollar(STAKING))?;
+
+		// feed price
+		feed_price(vec![(STAKING, Price::saturating_from_integer(120))])?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, STAKING, dollar(STAKING), 100 * dollar(STABLECOIN))?;
+		let auction_id: AuctionId = Default::default();
+
+		// bid collateral auction
+		AuctionManager::collateral_auction_bid_handler(1, auction_id, (bidder, 80 * dollar(STABLECOIN)), None)?;
+	}: _(RawOrigin::Root, auction_id)
+	verify {
+		assert!(AuctionManager::collateral_auctions(auction_id).is_none());
+	}
+
+	set_dutch_auction_params {
+		let params = DutchAuctionParams {
+			start_premium: Ratio::saturating_from_rational(12, 10),
+			decay_per_block: Rate::saturating_from_rational(1, 100),
+			floor_ratio: Ratio::saturating_from_rational(8, 10),
+		};
+	}: _(RawOrigin::Root, STAKING, Some(params))
+
+	take_dutch_auction {
+		let taker: AccountId = account("taker", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+
+		// set balance
+		Currencies::deposit(STABLECOIN, &taker, 200 * dollar(STABLECOIN))?;
+		Currencies::deposit(STAKING, &funder, dollar(STAKING))?;
+		CdpTreasury::deposit_collateral(&funder, STAKING, dollar(STAKING))?;
+
+		// feed price
+		feed_price(vec![(STAKING, Price::saturating_from_integer(120))])?;
+
+		// create Dutch collateral auction
+		AuctionManager::set_dutch_auction_params(RawOrigin::Root.into(), STAKING, Some(DutchAuctionParams {
+			start_premium: Ratio::saturating_from_rational(12, 10),
+			decay_per_block: Rate::saturating_from_rational(1, 100),
+			floor_ratio: Ratio::saturating_from_rational(8, 10),
+		}))?;
+		AuctionManager::new_collateral_auction(&funder, STAKING, dollar(STAKING), 100 * dollar(STABLECOIN))?;
+		let auction_id: AuctionId = Default::default();
+	}: _(RawOrigin::Signed(taker), auction_id, 200 * dollar(STABLECOIN))
+
+	swap_and_bid {
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let maker: AccountId = account("maker", 0, SEED);
+
+		// set balance and liquidity
+		Currencies::deposit(LIQUID, &bidder, 1_000 * dollar(LIQUID))?;
+		Currencies::deposit(STAKING, &funder, dollar(STAKING))?;
+		CdpTreasury::deposit_collateral(&funder, STAKING, dollar(STAKING))?;
+		inject_liquidity(maker, LIQUID, STABLECOIN, 10_000 * dollar(LIQUID), 10_000 * dollar(STABLECOIN), false)?;
+
+		// feed price
+		feed_price(vec![(STAKING, Price::saturating_from_integer(120))])?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, STAKING, dollar(STAKING), 100 * dollar(STABLECOIN))?;
+		let auction_id: AuctionId = Default::default();
+	}: _(RawOrigin::Signed(bidder), auction_id, LIQUID, 1_000 * dollar(LIQUID), 80 * dollar(STABLECOIN))
 }
 
 #[cfg(test)]
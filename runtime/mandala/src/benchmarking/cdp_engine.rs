@@ -29,7 +29,7 @@ use super::{
 		STAKING,
 	},
 };
-use frame_benchmarking::account;
+use frame_benchmarking::{account, whitelisted_caller};
 use frame_support::traits::{Get, OnInitialize};
 use frame_system::RawOrigin;
 use module_support::DEXManager;
@@ -147,7 +147,7 @@ runtime_benchmarks! {
 
 		let auction_size = collateral_amount / b as u128;
 		// adjust auction size so we hit MaxAuctionCount
-		CdpTreasury::set_expected_collateral_auction_size(RawOrigin::Root.into(), STAKING, auction_size)?;
+		module_cdp_treasury::ExpectedCollateralAuctionSize::<Runtime>::insert(STAKING, auction_size);
 		// adjust position
 		CdpEngine::adjust_position(&owner, STAKING, collateral_amount.try_into().unwrap(), min_debit_amount)?;
 
@@ -260,6 +260,30 @@ runtime_benchmarks! {
 	deregister_liquidation_contract {
 		CdpEngine::register_liquidation_contract(RawOrigin::Root.into(), H160::default())?;
 	}: _(RawOrigin::Root, H160::default())
+
+	sweep_vested_collateral {
+		let caller: AccountId = whitelisted_caller();
+		let owner: AccountId = account("owner", 0, SEED);
+		let owner_lookup: Address = AccountIdLookup::unlookup(owner.clone());
+
+		set_balance(NATIVE, &owner, 100 * dollar(NATIVE));
+		module_loans::PendingConfiscations::<Runtime>::insert(&owner, 10 * dollar(NATIVE));
+	}: _(RawOrigin::Signed(caller), owner_lookup)
+	verify {
+		assert_eq!(module_loans::PendingConfiscations::<Runtime>::get(&owner), 0);
+	}
+
+	set_collateral_stable_currency {
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			STAKING,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(100 * dollar(STABLECOIN)),
+		)?;
+	}: _(RawOrigin::Root, STAKING, STABLECOIN)
 }
 
 #[cfg(test)]
@@ -16,15 +16,24 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{AccountId, CdpTreasury, Currencies, Runtime};
+use crate::{AccountId, Balance, CdpTreasury, Currencies, CurrencyId, Runtime, RuntimeCall, System};
 
-use super::utils::{dollar, initialize_swap_pools, set_balance, STABLECOIN, STAKING};
+use super::utils::{
+	dollar, feed_price, initialize_swap_pools, inject_liquidity, lookup_of_account, set_balance, NATIVE, STABLECOIN,
+	STAKING,
+};
 use frame_benchmarking::whitelisted_caller;
-use frame_support::traits::Get;
+use frame_support::{
+	traits::{Get, Hooks},
+	BoundedVec,
+};
 use frame_system::RawOrigin;
-use module_support::{CDPTreasury, SwapLimit};
+use module_cdp_treasury::{SettlementKind, SurplusSettlementSizePolicy};
+use module_support::{CDPTreasury, Price, Rate, Ratio, SwapLimit};
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
+use sp_runtime::{FixedPointNumber, Permill};
+use sp_std::prelude::*;
 
 runtime_benchmarks! {
 	{ Runtime, module_cdp_treasury }
@@ -33,7 +42,7 @@ runtime_benchmarks! {
 		let b in 1 .. <Runtime as module_cdp_treasury::Config>::MaxAuctionsCount::get();
 
 		let auction_size = (1_000 * dollar(STAKING)) / b as u128;
-		CdpTreasury::set_expected_collateral_auction_size(RawOrigin::Root.into(), STAKING, auction_size)?;
+		module_cdp_treasury::ExpectedCollateralAuctionSize::<Runtime>::insert(STAKING, auction_size);
 
 		Currencies::deposit(STAKING, &CdpTreasury::account_id(), 10_000 * dollar(STAKING))?;
 	}: _(RawOrigin::Root, STAKING, 1_000 * dollar(STAKING), 1_000 * dollar(STABLECOIN), true)
@@ -50,9 +59,152 @@ runtime_benchmarks! {
 	set_expected_collateral_auction_size {
 	}: _(RawOrigin::Root, STAKING, 200 * dollar(STAKING))
 
+	set_expected_collateral_auction_value {
+	}: _(RawOrigin::Root, STAKING, 2_000 * dollar(STABLECOIN))
+
+	set_expected_stable_auction_value {
+	}: _(RawOrigin::Root, STABLECOIN, 2_000 * dollar(STABLECOIN))
+
+	set_collateral_auction_params {
+		let n in 1 .. <Runtime as module_cdp_treasury::Config>::MaxCollateralTypes::get();
+
+		let updates: Vec<(CurrencyId, Balance)> = (0..n)
+			.map(|i| (CurrencyId::ForeignAsset(i as u16), 200 * dollar(STAKING)))
+			.collect();
+		let updates = BoundedVec::try_from(updates).unwrap();
+		// replace the scheduled updates
+		CdpTreasury::set_collateral_auction_params(RawOrigin::Root.into(), updates.clone())?;
+	}: _(RawOrigin::Root, updates)
+
 	extract_surplus_to_treasury {
-		CdpTreasury::on_system_surplus(1_000 * dollar(STABLECOIN))?;
+		CdpTreasury::on_system_surplus(STABLECOIN, 1_000 * dollar(STABLECOIN))?;
 	}: _(RawOrigin::Root, 200 * dollar(STABLECOIN))
+
+	trigger_settlement {
+		let caller: AccountId = whitelisted_caller();
+		inject_liquidity(caller.clone(), STABLECOIN, NATIVE, 10_000 * dollar(STABLECOIN), 10_000 * dollar(NATIVE), false)?;
+		feed_price(vec![(NATIVE, Price::one())])?;
+
+		CdpTreasury::set_dex_settlement_cap(RawOrigin::Root.into(), 100 * dollar(STABLECOIN))?;
+		CdpTreasury::on_system_surplus(STABLECOIN, 1_000 * dollar(STABLECOIN))?;
+	}: _(RawOrigin::Signed(caller))
+
+	flash_loan {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(STABLECOIN, &caller, 10 * dollar(STABLECOIN));
+		CdpTreasury::set_flash_loan_fee_rate(RawOrigin::Root.into(), Some(Rate::saturating_from_rational(1, 1000)))?;
+		CdpTreasury::on_system_surplus(STABLECOIN, 1_000 * dollar(STABLECOIN))?;
+		let call = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+	}: _(RawOrigin::Signed(caller), STABLECOIN, 1_000 * dollar(STABLECOIN), call)
+
+	repay_debit_pool {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(STABLECOIN, &caller, 1_000 * dollar(STABLECOIN));
+		CdpTreasury::on_system_debit(STABLECOIN, 1_000 * dollar(STABLECOIN))?;
+	}: _(RawOrigin::Signed(caller), 1_000 * dollar(STABLECOIN))
+
+	deploy_liquidity {
+		let caller: AccountId = whitelisted_caller();
+		inject_liquidity(caller.clone(), STABLECOIN, STAKING, 10_000 * dollar(STABLECOIN), 1_000 * dollar(STAKING), false)?;
+
+		CdpTreasury::set_max_liquidity_deployment_ratio(RawOrigin::Root.into(), Permill::one())?;
+		Currencies::deposit(STAKING, &CdpTreasury::account_id(), 100 * dollar(STAKING))?;
+		CdpTreasury::on_system_surplus(STABLECOIN, 1_000 * dollar(STABLECOIN))?;
+	}: _(RawOrigin::Root, STAKING, 100 * dollar(STAKING), 1_000 * dollar(STABLECOIN), 0)
+
+	recall_liquidity {
+		let caller: AccountId = whitelisted_caller();
+		inject_liquidity(caller.clone(), STABLECOIN, STAKING, 10_000 * dollar(STABLECOIN), 1_000 * dollar(STAKING), false)?;
+
+		CdpTreasury::set_max_liquidity_deployment_ratio(RawOrigin::Root.into(), Permill::one())?;
+		Currencies::deposit(STAKING, &CdpTreasury::account_id(), 100 * dollar(STAKING))?;
+		CdpTreasury::on_system_surplus(STABLECOIN, 1_000 * dollar(STABLECOIN))?;
+		CdpTreasury::deploy_liquidity(RawOrigin::Root.into(), STAKING, 100 * dollar(STAKING), 1_000 * dollar(STABLECOIN), 0)?;
+		let share = CdpTreasury::deployed_liquidity_shares(STAKING);
+	}: _(RawOrigin::Root, STAKING, share, 0, 0)
+
+	set_debit_offset_buffer {
+	}: _(RawOrigin::Root, 100 * dollar(STABLECOIN))
+
+	set_surplus_buffer_size {
+	}: _(RawOrigin::Root, 100 * dollar(STABLECOIN))
+
+	set_dex_settlement_cap {
+	}: _(RawOrigin::Root, 100 * dollar(STABLECOIN))
+
+	set_max_swap_slippage {
+	}: _(RawOrigin::Root, STAKING, Some(Ratio::saturating_from_rational(1, 10)))
+
+	set_collateral_swap_path {
+	}: _(RawOrigin::Root, STAKING, Some(vec![STAKING, NATIVE, STABLECOIN]))
+
+	transfer_surplus {
+		let beneficiary: AccountId = whitelisted_caller();
+		CdpTreasury::on_system_surplus(STABLECOIN, 1_000 * dollar(STABLECOIN))?;
+	}: _(RawOrigin::Root, lookup_of_account(beneficiary), 200 * dollar(STABLECOIN))
+
+	set_surplus_distribution {
+	}: _(RawOrigin::Root, Permill::from_percent(20), Permill::from_percent(30))
+
+	set_stability_reserve_target {
+	}: _(RawOrigin::Root, 100 * dollar(STABLECOIN))
+
+	set_collateral_dust_threshold {
+	}: _(RawOrigin::Root, STAKING, dollar(STAKING))
+
+	sweep_dust_collateral {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(STABLECOIN, &caller, 1000 * dollar(STABLECOIN));
+		set_balance(STAKING, &caller, 1000 * dollar(STAKING));
+		initialize_swap_pools(caller.clone())?;
+
+		feed_price(vec![(STAKING, Price::one())])?;
+
+		CdpTreasury::deposit_collateral(&caller, STAKING, dollar(STAKING))?;
+		CdpTreasury::set_collateral_dust_threshold(RawOrigin::Root.into(), STAKING, dollar(STAKING))?;
+	}: _(RawOrigin::Signed(caller), STAKING)
+
+	set_surplus_settlement_size {
+	}: _(RawOrigin::Root, Some(SurplusSettlementSizePolicy {
+		proportion: Permill::from_percent(10),
+		min_size: dollar(STABLECOIN),
+		max_size: 100 * dollar(STABLECOIN),
+	}))
+
+	set_debit_settlement_premium {
+	}: _(RawOrigin::Root, Some(Ratio::saturating_from_rational(1, 100)))
+
+	set_settlement_params {
+	}: _(RawOrigin::Root, SettlementKind::Surplus, Some(100 * dollar(STABLECOIN)), 10)
+
+	set_flash_loan_fee_rate {
+	}: _(RawOrigin::Root, Some(Rate::saturating_from_rational(1, 1000)))
+
+	set_max_liquidity_deployment_ratio {
+	}: _(RawOrigin::Root, Permill::from_percent(50))
+
+	set_stability_fee_incentives_ratio {
+	}: _(RawOrigin::Root, Permill::from_percent(50))
+
+	set_debit_pool_interest_rate {
+	}: _(RawOrigin::Root, Some(Rate::saturating_from_rational(1, 1_000_000)))
+
+	set_issuance_limits {
+	}: _(RawOrigin::Root, Some(1_000_000 * dollar(STABLECOIN)), Some(100_000_000 * dollar(STABLECOIN)))
+
+	set_collateral_swap_budget {
+	}: _(RawOrigin::Root, STAKING, Some(1_000 * dollar(STAKING)), Some(10_000 * dollar(STAKING)))
+
+	on_finalize {
+		CdpTreasury::set_debit_pool_interest_rate(
+			RawOrigin::Root.into(),
+			Some(Rate::saturating_from_rational(1, 1_000_000)),
+		)?;
+		CdpTreasury::on_system_debit(STABLECOIN, 1_000 * dollar(STABLECOIN))?;
+		CdpTreasury::on_system_surplus(STABLECOIN, 100 * dollar(STABLECOIN))?;
+	}: {
+		CdpTreasury::on_finalize(System::block_number());
+	}
 }
 
 #[cfg(test)]
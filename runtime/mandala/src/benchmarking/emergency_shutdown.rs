@@ -47,6 +47,7 @@ runtime_benchmarks! {
 
 	open_collateral_refund {
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
+		EmergencyShutdown::fix_final_prices(RawOrigin::Root.into())?;
 	}: _(RawOrigin::Root)
 
 	refund_collaterals {
@@ -67,10 +68,11 @@ runtime_benchmarks! {
 		}
 		feed_price(values)?;
 
-		CdpTreasury::issue_debit(&caller, 1_000 * dollar(STABLECOIN), true)?;
-		CdpTreasury::issue_debit(&funder, 1_000 * dollar(STABLECOIN), true)?;
+		CdpTreasury::issue_debit(STABLECOIN, &caller, 1_000 * dollar(STABLECOIN), true)?;
+		CdpTreasury::issue_debit(STABLECOIN, &funder, 1_000 * dollar(STABLECOIN), true)?;
 
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
+		EmergencyShutdown::fix_final_prices(RawOrigin::Root.into())?;
 		EmergencyShutdown::open_collateral_refund(RawOrigin::Root.into())?;
 	}: _(RawOrigin::Signed(caller),  1_000 * dollar(STABLECOIN))
 }
@@ -18,7 +18,7 @@
 
 use crate::{
 	AccountId, Amount, CdpEngine, CurrencyId, DepositPerAuthorization, ExistentialDeposits, Honzon,
-	NativeTokenExistentialDeposit, Price, Rate, Ratio, Runtime,
+	NativeTokenExistentialDeposit, Price, Rate, Ratio, Runtime, Signature, Vesting,
 };
 
 use super::{
@@ -29,9 +29,12 @@ use super::{
 };
 use frame_benchmarking::{account, whitelisted_caller};
 use frame_system::RawOrigin;
+use module_honzon::AuthorizationPermission;
 use module_support::HonzonManager;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::{Change, GetByKey};
+use orml_vesting::VestingSchedule;
+use sp_io::hashing::{blake2_256, keccak_256};
 use sp_runtime::{
 	traits::{AccountIdLookup, One, StaticLookup, UniqueSaturatedInto},
 	FixedPointNumber,
@@ -40,6 +43,24 @@ use sp_std::prelude::*;
 
 const SEED: u32 = 0;
 
+fn owner() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&keccak_256(b"Owner")).unwrap()
+}
+
+fn owner_account_id() -> AccountId {
+	let public = libsecp256k1::PublicKey::from_secret_key(&owner());
+	AccountId::from(blake2_256(&public.serialize_compressed()))
+}
+
+fn sign_permit(payload: &[u8]) -> Signature {
+	let message = libsecp256k1::Message::parse(&blake2_256(payload));
+	let (signature, recovery_id) = libsecp256k1::sign(&message, &owner());
+	let mut raw = [0u8; 65];
+	raw[0..64].copy_from_slice(&signature.serialize()[..]);
+	raw[64] = recovery_id.serialize();
+	sp_core::ecdsa::Signature::from_raw(raw).into()
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_honzon }
 
@@ -341,6 +362,176 @@ runtime_benchmarks! {
 	}: {
 		Honzon::get_current_collateral_ratio(&sender, LIQUID);
 	}
+
+	authorize_with_scope {
+		let caller: AccountId = whitelisted_caller();
+		let to: AccountId = account("to", 0, SEED);
+		let to_lookup = AccountIdLookup::unlookup(to);
+
+		// set balance
+		set_balance(NATIVE, &caller, DepositPerAuthorization::get() + NativeTokenExistentialDeposit::get());
+	}: _(RawOrigin::Signed(caller), STAKING, to_lookup, AuthorizationPermission::FullManage, Some(100u32.into()))
+
+	// `adjust_loan_on_behalf`, worst case:
+	// operator deposits collateral and pays back debit for owner
+	adjust_loan_on_behalf {
+		let currency_id: CurrencyId = get_benchmarking_collateral_currency_ids()[0];
+		let owner: AccountId = account("owner", 0, SEED);
+		let owner_lookup = AccountIdLookup::unlookup(owner.clone());
+		let operator: AccountId = whitelisted_caller();
+		let operator_lookup = AccountIdLookup::unlookup(operator.clone());
+
+		let debit_value = 100 * dollar(STABLECOIN);
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(debit_value);
+		let debit_amount: Amount = debit_amount.unique_saturated_into();
+		let collateral_value = 10 * debit_value;
+		let collateral_amount = Price::saturating_from_rational(dollar(currency_id), dollar(STABLECOIN)).saturating_mul_int(collateral_value);
+
+		// set balance
+		set_balance(currency_id, &owner, collateral_amount * 2);
+		set_balance(currency_id, &operator, collateral_amount * 2);
+		set_balance(STABLECOIN, &operator, debit_value);
+		set_balance(NATIVE, &owner, DepositPerAuthorization::get() + NativeTokenExistentialDeposit::get());
+
+		// feed price
+		feed_price(vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(debit_value * 100),
+		)?;
+
+		// initialize owner's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(owner.clone()).into(),
+			currency_id,
+			collateral_amount.try_into().unwrap(),
+			debit_amount,
+		)?;
+
+		// authorize operator
+		Honzon::authorize_with_scope(
+			RawOrigin::Signed(owner).into(),
+			currency_id,
+			operator_lookup,
+			AuthorizationPermission::FullManage,
+			None,
+		)?;
+	}: _(RawOrigin::Signed(operator), currency_id, owner_lookup, collateral_amount.try_into().unwrap(), -debit_amount / 2)
+
+	// `adjust_loan_by_permit`, worst case:
+	// relayer submits the permit of owner to deposit collateral and pay back debit
+	adjust_loan_by_permit {
+		let currency_id: CurrencyId = get_benchmarking_collateral_currency_ids()[0];
+		let owner = owner_account_id();
+		let owner_lookup = AccountIdLookup::unlookup(owner.clone());
+		let relayer: AccountId = whitelisted_caller();
+
+		let debit_value = 100 * dollar(STABLECOIN);
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(debit_value);
+		let debit_amount: Amount = debit_amount.unique_saturated_into();
+		let collateral_value = 10 * debit_value;
+		let collateral_amount = Price::saturating_from_rational(dollar(currency_id), dollar(STABLECOIN)).saturating_mul_int(collateral_value);
+		let collateral_adjustment: Amount = collateral_amount.try_into().unwrap();
+
+		// set balance
+		set_balance(currency_id, &owner, collateral_amount * 2);
+		set_balance(NATIVE, &owner, NativeTokenExistentialDeposit::get());
+
+		// feed price
+		feed_price(vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(debit_value * 100),
+		)?;
+
+		// initialize owner's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(owner.clone()).into(),
+			currency_id,
+			collateral_adjustment,
+			debit_amount,
+		)?;
+
+		// sign the permit by owner
+		let signature = sign_permit(&Honzon::permit_payload(
+			&owner,
+			currency_id,
+			collateral_adjustment,
+			-debit_amount / 2,
+			0,
+			100u32.into(),
+		));
+	}: _(RawOrigin::Signed(relayer), owner_lookup, currency_id, collateral_adjustment, -debit_amount / 2, 100u32.into(), signature)
+	verify {
+		assert_eq!(Honzon::permit_nonces(&owner), 1);
+	}
+
+	// `adjust_vested_collateral`, worst case:
+	// pledge the vesting balance as collateral of the cdp which has debit
+	adjust_vested_collateral {
+		let caller: AccountId = whitelisted_caller();
+		let debit_value = 100 * dollar(STABLECOIN);
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(NATIVE);
+		let debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(debit_value);
+		let debit_amount: Amount = debit_amount.unique_saturated_into();
+		let collateral_value = 10 * debit_value;
+		let collateral_amount = Price::saturating_from_rational(dollar(NATIVE), dollar(STABLECOIN)).saturating_mul_int(collateral_value);
+		let collateral_adjustment: Amount = collateral_amount.try_into().unwrap();
+
+		// set balance and lock the vesting balance
+		set_balance(NATIVE, &caller, collateral_amount * 3);
+		Vesting::update_vesting_schedules(
+			RawOrigin::Root.into(),
+			AccountIdLookup::unlookup(caller.clone()),
+			vec![VestingSchedule {
+				start: 0,
+				period: 1_000,
+				period_count: 1,
+				per_period: collateral_amount,
+			}],
+		)?;
+
+		// feed price
+		feed_price(vec![(NATIVE, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			NATIVE,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(debit_value * 100),
+		)?;
+
+		// initialize caller's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(caller.clone()).into(),
+			NATIVE,
+			collateral_adjustment,
+			debit_amount,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), collateral_adjustment)
+	verify {
+		assert_eq!(module_loans::VestedCollaterals::<Runtime>::get(&caller), collateral_amount);
+	}
 }
 
 #[cfg(test)]
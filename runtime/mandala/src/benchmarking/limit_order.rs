@@ -0,0 +1,75 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2023 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::utils::{dollar, inject_liquidity, set_balance, NATIVE, STABLECOIN};
+use crate::{AccountId, LimitOrder, Runtime, System};
+use module_support::ExchangeRate;
+use sp_runtime::FixedPointNumber;
+
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_system::RawOrigin;
+
+use orml_benchmarking::runtime_benchmarks;
+
+runtime_benchmarks! {
+	{ Runtime, module_limit_order }
+
+	place_order {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(NATIVE, &caller, 10_000 * dollar(NATIVE));
+	}: _(RawOrigin::Signed(caller), NATIVE, STABLECOIN, 100 * dollar(NATIVE), ExchangeRate::saturating_from_rational(1, 2))
+	verify {
+		assert!(LimitOrder::orders(0).is_some());
+	}
+
+	cancel_order {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(NATIVE, &caller, 10_000 * dollar(NATIVE));
+		LimitOrder::place_order(RawOrigin::Signed(caller.clone()).into(), NATIVE, STABLECOIN, 100 * dollar(NATIVE), ExchangeRate::saturating_from_rational(1, 2))?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(LimitOrder::orders(0).is_none());
+	}
+
+	execute_order {
+		let maker: AccountId = account("maker", 0, 0);
+		let caller: AccountId = whitelisted_caller();
+		let keeper: AccountId = account("keeper", 0, 0);
+		inject_liquidity(maker, NATIVE, STABLECOIN, 10_000 * dollar(NATIVE), 10_000 * dollar(STABLECOIN), false)?;
+		set_balance(NATIVE, &caller, 10_000 * dollar(NATIVE));
+		LimitOrder::place_order(RawOrigin::Signed(caller.clone()).into(), NATIVE, STABLECOIN, 100 * dollar(NATIVE), ExchangeRate::saturating_from_rational(1, 2))?;
+	}: _(RawOrigin::Signed(keeper), 0, 50 * dollar(NATIVE))
+	verify {
+		System::assert_has_event(module_limit_order::Event::OrderExecuted {
+			order_id: 0,
+			keeper: account("keeper", 0, 0),
+			supply_amount: 50 * dollar(NATIVE),
+			target_amount: LimitOrder::orders(0).unwrap().filled_target_amount,
+			remaining_supply_amount: 50 * dollar(NATIVE),
+		}.into());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -27,6 +27,7 @@ pub mod utils;
 
 // module benchmarking
 pub mod aggregated_dex;
+pub mod airdrop;
 pub mod asset_registry;
 pub mod auction_manager;
 pub mod cdp_engine;
@@ -43,6 +44,7 @@ pub mod homa;
 pub mod honzon;
 pub mod idle_scheduler;
 pub mod incentives;
+pub mod limit_order;
 pub mod liquid_crowdloan;
 pub mod nominees_election;
 pub mod nutsfinance_stable_asset;
@@ -28,7 +28,10 @@ use module_support::{AggregatedSwapPath, DEXManager, Ratio, SwapLimit};
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
 use primitives::currency::AssetMetadata;
-use sp_runtime::traits::{AccountIdConversion, One};
+use sp_runtime::{
+	traits::{AccountIdConversion, One},
+	Permill,
+};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -228,6 +231,29 @@ runtime_benchmarks! {
 	}: {
 		TransactionPayment::on_finalize(System::block_number());
 	}
+
+	set_cdp_treasury_fee_ratios {
+	}: _(RawOrigin::Root, Permill::from_percent(10), Permill::from_percent(20))
+
+	set_free_tx_quota {
+	}: _(RawOrigin::Root, 10, dollar(NATIVE))
+
+	reserve_free_tx_deposit {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(NATIVE, &caller, 100 * dollar(NATIVE));
+	}: _(RawOrigin::Signed(caller.clone()), 10 * dollar(NATIVE))
+	verify {
+		assert_eq!(TransactionPayment::free_tx_deposit_reserved_at(&caller), Some(System::block_number()));
+	}
+
+	unreserve_free_tx_deposit {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(NATIVE, &caller, 100 * dollar(NATIVE));
+		TransactionPayment::reserve_free_tx_deposit(RawOrigin::Signed(caller.clone()).into(), 10 * dollar(NATIVE))?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(TransactionPayment::free_tx_deposit_reserved_at(&caller), None);
+	}
 }
 
 #[cfg(test)]
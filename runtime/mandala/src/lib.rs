@@ -76,8 +76,8 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdConversion, BadOrigin, BlakeTwo256, Block as BlockT, Bounded, Convert, IdentityLookup,
-		SaturatedConversion, StaticLookup,
+		AccountIdConversion, BadOrigin, BlakeTwo256, Block as BlockT, BlockNumberProvider, Bounded, Convert,
+		IdentityLookup, SaturatedConversion, StaticLookup,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, ArithmeticError, DispatchResult, FixedPointNumber, RuntimeDebug,
@@ -164,7 +164,9 @@ parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
+	pub const AuctionManagerPalletId: PalletId = PalletId(*b"aca/aumg");
 	pub const CDPEnginePalletId: PalletId = PalletId(*b"aca/cdpe");
+	pub const EmergencyShutdownPalletId: PalletId = PalletId(*b"aca/emsd");
 	pub const HonzonTreasuryPalletId: PalletId = PalletId(*b"aca/hztr");
 	pub const HomaPalletId: PalletId = PalletId(*b"aca/homa");
 	pub const HomaTreasuryPalletId: PalletId = PalletId(*b"aca/hmtr");
@@ -180,6 +182,7 @@ parameter_types! {
 	// because transaction payment pallet will ensure the accounts always have enough ED.
 	pub const TransactionPaymentPalletId: PalletId = PalletId(*b"aca/fees");
 	pub const LiquidCrowdloanPalletId: PalletId = PalletId(*b"aca/lqcl");
+	pub const AirdropPalletId: PalletId = PalletId(*b"aca/aird");
 	// Ecosystem modules
 	pub const StableAssetPalletId: PalletId = PalletId(*b"nuts/sta");
 	// lock identifier for earning module
@@ -193,6 +196,8 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		LoansPalletId::get().into_account_truncating(),
 		DEXPalletId::get().into_account_truncating(),
 		CDPTreasuryPalletId::get().into_account_truncating(),
+		EmergencyShutdownPalletId::get().into_account_truncating(),
+		AuctionManagerPalletId::get().into_sub_account_truncating(b"escrow"),
 		HonzonTreasuryPalletId::get().into_account_truncating(),
 		HomaTreasuryPalletId::get().into_account_truncating(),
 		IncentivesPalletId::get().into_account_truncating(),
@@ -200,6 +205,7 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		CollatorPotId::get().into_account_truncating(),
 		UnreleasedNativeVaultAccountId::get(),
 		StableAssetPalletId::get().into_account_truncating(),
+		AirdropPalletId::get().into_account_truncating(),
 	]
 }
 
@@ -936,12 +942,18 @@ impl module_prices::Config for Runtime {
 	type RelayChainBlockNumber = RelaychainDataProvider<Runtime>;
 	type RewardRatePerRelaychainBlock = RewardRatePerRelaychainBlock;
 	type PricingPegged = PricingPegged;
+	type DEXPriceSource = module_dex_oracle::AverageDEXPriceProvider<Runtime>;
+	type OperatorSource = runtime_common::OracleOperatorPrices<Runtime, AcalaDataProvider, ExpiresIn>;
+	type MaxOperators = ConstU32<50>;
+	type FeedTimestamp = runtime_common::OracleFeedTimestamp<Runtime, AcalaDataProvider>;
+	type UnixTime = Timestamp;
 	type WeightInfo = weights::module_prices::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = ACA;
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![AUSD];
 	pub Erc20HoldingAccount: H160 = primitives::evm::ERC20_HOLDING_ACCOUNT;
 }
 
@@ -995,6 +1007,18 @@ impl orml_vesting::Config for Runtime {
 	type BlockNumberProvider = RelaychainDataProvider<Runtime>;
 }
 
+/// The amount of native token still locked by the vesting schedules of an account.
+pub struct NativeVestingBalance;
+impl module_support::VestingBalance<AccountId, Balance> for NativeVestingBalance {
+	fn vesting_balance(who: &AccountId) -> Balance {
+		let now = <Runtime as orml_vesting::Config>::BlockNumberProvider::current_block_number();
+		orml_vesting::VestingSchedules::<Runtime>::get(who)
+			.iter()
+			.map(|schedule| schedule.locked_amount(now))
+			.fold(0, Balance::saturating_add)
+	}
+}
+
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * RuntimeBlockWeights::get().max_block;
 }
@@ -1044,14 +1068,21 @@ impl module_auction_manager::Config for Runtime {
 	type MinimumIncrementSize = MinimumIncrementSize;
 	type AuctionTimeToClose = AuctionTimeToClose;
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
-	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CdpTreasury;
 	type PriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type Swap = AcalaSwap;
+	type KeeperRewards = Incentives;
+	type PalletId = AuctionManagerPalletId;
 	type WeightInfo = weights::module_auction_manager::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub VestedCollateralHaircut: Ratio = Ratio::saturating_from_rational(30, 100);
+}
+
 impl module_loans::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Currencies;
@@ -1059,6 +1090,9 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = module_incentives::OnUpdateLoan<Runtime>;
+	type VestedCollateralCurrencyId = GetNativeCurrencyId;
+	type VestingBalance = NativeVestingBalance;
+	type VestedCollateralHaircut = VestedCollateralHaircut;
 }
 
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
@@ -1128,12 +1162,19 @@ parameter_types! {
 	.expect("Rate is in range; qed");
 	pub MinimumDebitValue: Balance = dollar(AUSD);
 	pub MaxSwapSlippageCompareToOracle: Ratio = Ratio::saturating_from_rational(10, 100);
+	pub KeeperTipRatio: Rate = Rate::saturating_from_rational(10, 100);
+	pub MaxKeeperTip: Balance = 100 * dollar(AUSD);
 	pub MaxLiquidationContractSlippage: Ratio = Ratio::saturating_from_rational(15, 100);
+	// sample hourly and keep the debit exchange rates of 30 days
+	pub const MaxDebitExchangeRateHistory: u32 = 720;
+	pub const DebitExchangeRateSampleInterval: u64 = 3600;
 }
 
 impl module_cdp_engine::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
+	type StableMarketPriceSource = AggregatedDataProvider;
+	type PriceStaleness = Prices;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
@@ -1144,8 +1185,14 @@ impl module_cdp_engine::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
 	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageCompareToOracle;
+	type KeeperTipRatio = KeeperTipRatio;
+	type MaxKeeperTip = MaxKeeperTip;
+	type MaxLiquidationBatchSize = ConstU32<10>;
+	type MaxDebitExchangeRateHistory = MaxDebitExchangeRateHistory;
+	type DebitExchangeRateSampleInterval = DebitExchangeRateSampleInterval;
 	type UnsignedPriority = runtime_common::CdpEngineUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type KeeperRewards = Incentives;
 	type UnixTime = Timestamp;
 	type Currency = Currencies;
 	type DEX = Dex;
@@ -1168,6 +1215,8 @@ impl module_honzon::Config for Runtime {
 	type Currency = Balances;
 	type DepositPerAuthorization = DepositPerAuthorization;
 	type CollateralCurrencyIds = CollateralCurrencyIds<Runtime>;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as sp_runtime::traits::Verify>::Signer;
 	type WeightInfo = weights::module_honzon::WeightInfo<Runtime>;
 }
 
@@ -1178,11 +1227,15 @@ impl module_emergency_shutdown::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRootOrHalfGeneralCouncil;
+	type RefundsPerBlock = ConstU32<50>;
+	type GetStableCurrencyId = GetStableCurrencyId;
+	type PalletId = EmergencyShutdownPalletId;
 	type WeightInfo = weights::module_emergency_shutdown::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 1000);	// 0.1%
+	pub DEXProtocolFeeReceiver: AccountId = CDPTreasuryPalletId::get().into_account_truncating();
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::from_currency_ids(AUSD, ACA).unwrap(),
 		TradingPair::from_currency_ids(AUSD, DOT).unwrap(),
@@ -1210,6 +1263,8 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
 	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
 	type OnLiquidityPoolUpdated = ();
+	type ProtocolFeeReceiver = DEXProtocolFeeReceiver;
+	type RuntimeCall = RuntimeCall;
 }
 
 impl module_aggregated_dex::Config for Runtime {
@@ -1236,21 +1291,49 @@ impl module_dex_oracle::Config for Runtime {
 	type WeightInfo = weights::module_dex_oracle::WeightInfo<Runtime>;
 }
 
+parameter_type_with_key! {
+	pub LimitOrderMinimumAmount: |currency_id: CurrencyId| -> Balance {
+		ExistentialDeposits::get(currency_id).saturating_mul(100)
+	};
+}
+
+impl module_limit_order::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Currencies;
+	type Swap = AcalaSwap;
+	type MinimumOrderAmount = LimitOrderMinimumAmount;
+	type WeightInfo = weights::module_limit_order::WeightInfo<Runtime>;
+}
+
 parameter_types! {
+	pub DefaultMaxSwapSlippage: Ratio = Ratio::saturating_from_rational(10, 100);
 	pub HonzonTreasuryAccount: AccountId = HonzonTreasuryPalletId::get().into_account_truncating();
 }
 
 impl module_cdp_treasury::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type StableCurrencyIds = StableCurrencyIds;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = AuctionManager;
 	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
 	type DEX = Dex;
 	type Swap = AcalaSwap;
+	type PriceSource = module_prices::RealTimePriceProvider<Runtime>;
+	type TradingPathLimit = TradingPathLimit;
 	type MaxAuctionsCount = ConstU32<50>;
+	type DefaultMaxSwapSlippage = DefaultMaxSwapSlippage;
+	type RiskManager = CdpEngine;
+	type MaxCollateralTypes = ConstU32<32>;
 	type PalletId = CDPTreasuryPalletId;
+	type KeeperRewards = Incentives;
 	type TreasuryAccount = HonzonTreasuryAccount;
+	type IncentivesRewardsSource = UnreleasedNativeVaultAccountId;
+	type MetricsEraLength = ConstU32<DAYS>;
+	type MaxMetricsEras = ConstU32<30>;
+	type ParamsUpdateDelay = ConstU32<HOURS>;
 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
 	type StableAsset = RebasedStableAsset;
 }
@@ -1288,6 +1371,17 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 	}
 }
 
+parameter_types! {
+	pub const FreeTxQuotaPeriod: BlockNumber = 7 * DAYS;
+}
+
+pub struct FreeTxCallFilter;
+impl Contains<RuntimeCall> for FreeTxCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::Honzon(_) | RuntimeCall::Dex(_))
+	}
+}
+
 impl module_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -1314,6 +1408,10 @@ impl module_transaction_payment::Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type CDPTreasuryAccount = DEXProtocolFeeReceiver;
+	type StableCurrencyId = GetStableCurrencyId;
+	type FreeTxCallFilter = FreeTxCallFilter;
+	type FreeTxQuotaPeriod = FreeTxQuotaPeriod;
 }
 
 impl module_earning::Config for Runtime {
@@ -1358,6 +1456,7 @@ impl orml_rewards::Config for Runtime {
 
 parameter_types! {
 	pub const AccumulatePeriod: BlockNumber = MINUTES;
+	pub const KeeperRewardEraPeriod: BlockNumber = DAYS;
 }
 
 impl module_incentives::Config for Runtime {
@@ -1365,6 +1464,8 @@ impl module_incentives::Config for Runtime {
 	type RewardsSource = UnreleasedNativeVaultAccountId;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type AccumulatePeriod = AccumulatePeriod;
+	type KeeperRewardSource = TreasuryAccount;
+	type KeeperRewardEraPeriod = KeeperRewardEraPeriod;
 	type UpdateOrigin = EnsureRootOrThreeFourthsGeneralCouncil;
 	type Currency = Currencies;
 	type EmergencyShutdown = EmergencyShutdown;
@@ -1417,6 +1518,7 @@ impl module_homa::Config for Runtime {
 
 parameter_types! {
 	pub ParachainAccount: AccountId = ParachainInfo::get().into_account_truncating();
+	pub const XcmMessageTimeout: BlockNumber = 2 * HOURS;
 }
 
 pub struct SubAccountIndexMultiLocationConvertor;
@@ -1437,6 +1539,10 @@ impl module_xcm_interface::Config for Runtime {
 	type XcmTransfer = XTokens;
 	type SelfLocation = xcm_config::SelfLocation;
 	type AccountIdToMultiLocation = xcm_config::AccountIdToMultiLocation;
+	type MaxSendAttempts = ConstU32<3>;
+	type MessageTimeout = XcmMessageTimeout;
+	type MaxMessagesPerBlock = ConstU32<20>;
+	type WeightInfo = ();
 }
 
 parameter_types! {
@@ -1522,6 +1628,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 					c,
 					RuntimeCall::Dex(module_dex::Call::swap_with_exact_supply { .. })
 						| RuntimeCall::Dex(module_dex::Call::swap_with_exact_target { .. })
+						| RuntimeCall::Dex(module_dex::Call::swap_with_exact_supply_before_deadline { .. })
+						| RuntimeCall::Dex(module_dex::Call::swap_with_exact_target_before_deadline { .. })
 						| RuntimeCall::AggregatedDex(module_aggregated_dex::Call::swap_with_exact_supply { .. })
 						| RuntimeCall::AggregatedDex(module_aggregated_dex::Call::swap_with_exact_target { .. })
 				)
@@ -1530,6 +1638,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				matches!(
 					c,
 					RuntimeCall::Honzon(module_honzon::Call::adjust_loan { .. })
+						| RuntimeCall::Honzon(module_honzon::Call::adjust_loans { .. })
+						| RuntimeCall::Honzon(module_honzon::Call::adjust_to_ratio { .. })
 						| RuntimeCall::Honzon(module_honzon::Call::close_loan_has_debit_by_dex { .. })
 						| RuntimeCall::Honzon(module_honzon::Call::adjust_loan_by_debit_value { .. })
 						| RuntimeCall::Honzon(module_honzon::Call::transfer_debit { .. })
@@ -1540,6 +1650,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 					c,
 					RuntimeCall::Dex(module_dex::Call::add_liquidity { .. })
 						| RuntimeCall::Dex(module_dex::Call::remove_liquidity { .. })
+						| RuntimeCall::Dex(module_dex::Call::add_liquidity_with_single_currency { .. })
+						| RuntimeCall::Dex(module_dex::Call::remove_liquidity_to_single_currency { .. })
 				)
 			}
 			ProxyType::StableAssetSwap => {
@@ -1822,6 +1934,20 @@ impl module_liquid_crowdloan::Config for Runtime {
 	type WeightInfo = weights::module_liquid_crowdloan::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const AirdropMaxProofLength: u32 = 32;
+}
+
+impl module_airdrop::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Currencies;
+	type PalletId = AirdropPalletId;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxProofLength = AirdropMaxProofLength;
+	type GovernanceOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_airdrop::WeightInfo<Runtime>;
+}
+
 define_aggregrated_parameters! {
 	pub RuntimeParameters = {
 		Earning: module_earning::Parameters = 0,
@@ -1974,8 +2100,18 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
-pub type Executive =
-	frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem, ()>;
+pub type Executive = frame_executive::Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPalletsWithSystem,
+	(
+		module_cdp_treasury::InitializeModuleAccounts<Runtime>,
+		module_cdp_treasury::MigrateDebitPool<Runtime>,
+		module_auction_manager::MigrateBidsToEscrow<Runtime>,
+	),
+>;
 
 construct_runtime!(
 	pub enum Runtime {
@@ -2039,6 +2175,7 @@ construct_runtime!(
 		Dex: module_dex = 111,
 		DexOracle: module_dex_oracle = 112,
 		AggregatedDex: module_aggregated_dex = 113,
+		LimitOrder: module_limit_order = 114,
 
 		// Honzon
 		AuctionManager: module_auction_manager = 120,
@@ -2058,6 +2195,7 @@ construct_runtime!(
 		NFT: module_nft = 141,
 		AssetRegistry: module_asset_registry = 142,
 		LiquidCrowdloan: module_liquid_crowdloan = 143,
+		Airdrop: module_airdrop = 144,
 
 		// Parachain
 		ParachainInfo: parachain_info exclude_parts { Call } = 161,
@@ -2135,6 +2273,8 @@ mod benches {
 		[nutsfinance_stable_asset, benchmarking::nutsfinance_stable_asset]
 		[module_idle_scheduler, benchmarking::idle_scheduler]
 		[module_aggregated_dex, benchmarking::aggregated_dex]
+		[module_limit_order, benchmarking::limit_order]
+		[module_airdrop, benchmarking::airdrop]
 	);
 }
 
@@ -2286,6 +2426,127 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_auction_manager_rpc_runtime_api::AuctionManagerApi<
+		Block,
+		AccountId,
+		AuctionId,
+		module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>,
+		Balance,
+	> for Runtime {
+		fn get_collateral_auction(
+			auction_id: AuctionId,
+		) -> Option<module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>> {
+			AuctionManager::get_collateral_auction_detail(auction_id)
+		}
+
+		fn get_active_collateral_auctions() -> Vec<module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>> {
+			AuctionManager::get_active_collateral_auctions()
+		}
+
+		fn get_collateral_auctions_of(
+			who: AccountId,
+		) -> Vec<module_auction_manager::CollateralAuctionDetail<AccountId, BlockNumber>> {
+			AuctionManager::get_collateral_auctions_of(&who)
+		}
+
+		fn get_escrowed_bid(auction_id: AuctionId) -> Option<(AccountId, Balance)> {
+			AuctionManager::escrowed_bids(auction_id)
+		}
+
+		fn get_total_escrowed() -> Balance {
+			AuctionManager::total_escrowed()
+		}
+	}
+
+	impl module_honzon_rpc_runtime_api::HonzonApi<
+		Block,
+		AccountId,
+		CurrencyId,
+		module_honzon::PositionInfo,
+		module_honzon::SystemHealth,
+		Balance,
+		ExchangeRate,
+	> for Runtime {
+		fn get_position_info(who: AccountId, currency_id: CurrencyId) -> Option<module_honzon::PositionInfo> {
+			Honzon::position_info(&who, currency_id)
+		}
+
+		fn get_system_health() -> module_honzon::SystemHealth {
+			Honzon::system_health()
+		}
+
+		fn get_projected_interest(who: AccountId, currency_id: CurrencyId, blocks: u32) -> Option<Balance> {
+			Honzon::projected_interest(&who, currency_id, u64::from(blocks).saturating_mul(SECS_PER_BLOCK))
+		}
+
+		fn get_debit_exchange_rate_history(currency_id: CurrencyId) -> Vec<(u64, ExchangeRate)> {
+			CdpEngine::debit_exchange_rate_history(currency_id).into_inner()
+		}
+	}
+
+	impl module_prices_rpc_runtime_api::PricesApi<
+		Block,
+		CurrencyId,
+		module_prices::PriceInfo,
+	> for Runtime {
+		fn get_price_info(currency_id: CurrencyId) -> module_prices::PriceInfo {
+			Prices::price_info(currency_id)
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexApi<
+		Block,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_swap_target_amount(path: Vec<CurrencyId>, supply_amount: Balance) -> Option<Balance> {
+			Dex::get_swap_target_amount(&path, supply_amount)
+		}
+
+		fn get_swap_supply_amount(path: Vec<CurrencyId>, target_amount: Balance) -> Option<Balance> {
+			Dex::get_swap_supply_amount(&path, target_amount)
+		}
+
+		fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+			Dex::get_liquidity(currency_id_a, currency_id_b)
+		}
+	}
+
+	impl module_cdp_treasury_rpc_runtime_api::CdpTreasuryApi<
+		Block,
+		CurrencyId,
+		Balance,
+		module_cdp_treasury::TreasuryMetrics,
+	> for Runtime {
+		fn get_surplus_pool() -> Balance {
+			CdpTreasury::surplus_pool()
+		}
+
+		fn get_debit_pool() -> Balance {
+			CdpTreasury::debit_pool()
+		}
+
+		fn get_total_collaterals(currency_id: CurrencyId) -> Balance {
+			CdpTreasury::total_claimable_collaterals(currency_id)
+		}
+
+		fn get_projected_collateral_auctions_count(currency_id: CurrencyId) -> u32 {
+			CdpTreasury::get_projected_collateral_auctions_count(currency_id)
+		}
+
+		fn get_cumulative_metrics() -> module_cdp_treasury::TreasuryMetrics {
+			CdpTreasury::cumulative_metrics()
+		}
+
+		fn get_era_metrics(era: u32) -> Option<module_cdp_treasury::TreasuryMetrics> {
+			CdpTreasury::era_metrics(era)
+		}
+
+		fn get_total_collateral_liquidated(currency_id: CurrencyId) -> Balance {
+			CdpTreasury::total_collateral_liquidated(currency_id)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn block_limits() -> BlockLimits {
 			BlockLimits {
@@ -20,6 +20,7 @@
 #![allow(clippy::unnecessary_cast)]
 
 pub mod module_aggregated_dex;
+pub mod module_airdrop;
 pub mod module_asset_registry;
 pub mod module_auction_manager;
 pub mod module_cdp_engine;
@@ -36,6 +37,7 @@ pub mod module_homa;
 pub mod module_honzon;
 pub mod module_idle_scheduler;
 pub mod module_incentives;
+pub mod module_limit_order;
 pub mod module_liquid_crowdloan;
 pub mod module_nft;
 pub mod module_nominees_election;